@@ -0,0 +1,153 @@
+//! Binding and status types.
+//!
+//! Extension-specific fields are flattened into the JSON, so a YOLO binding
+//! still serializes as `{"device_id": .., "image_metric": .., "confidence_threshold": ..}`
+//! and existing `config.json` files and frontends keep working.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// Per-binding settings of one extension (thresholds, drawing, ROI, ...).
+pub trait BindingSettings: Clone + Serialize + DeserializeOwned + Send + Sync {
+    /// Settings for a new binding from the `bind_device` arguments.
+    fn from_args(args: &serde_json::Value) -> Self;
+}
+
+/// A device bound for automatic processing of its image metric
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceBinding<S> {
+    /// Device ID
+    pub device_id: String,
+    /// Device name (for display)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_name: Option<String>,
+    /// Image data source metric, e.g. "image" or "snapshot.data" for a field
+    /// inside the "snapshot" metric
+    #[serde(alias = "metric_name", default = "default_image_metric")]
+    pub image_metric: String,
+    /// Whether events for this device are processed
+    #[serde(default = "default_active")]
+    pub active: bool,
+    /// Extension-specific settings
+    #[serde(flatten)]
+    pub settings: S,
+}
+
+fn default_image_metric() -> String {
+    "image".to_string()
+}
+
+fn default_active() -> bool {
+    true
+}
+
+impl<S> DeviceBinding<S> {
+    /// Active binding without a display name.
+    pub fn new(device_id: impl Into<String>, image_metric: impl Into<String>, settings: S) -> Self {
+        Self {
+            device_id: device_id.into(),
+            device_name: None,
+            image_metric: image_metric.into(),
+            active: true,
+            settings,
+        }
+    }
+
+    /// Whether an event for `metric` carries this binding's image.
+    ///
+    /// Returns the path to follow inside the metric value: `None` when
+    /// `metric` is the image metric itself, `Some("data")` when the binding
+    /// is `"snapshot.data"` and the event is for `"snapshot"`.
+    pub fn match_metric(&self, metric: &str) -> Option<Option<&str>> {
+        if metric == self.image_metric {
+            return Some(None);
+        }
+        match self.image_metric.split_once('.') {
+            Some((top_level, path)) if top_level == metric => Some(Some(path)),
+            _ => None,
+        }
+    }
+}
+
+/// Binding plus runtime statistics, as returned by `get_bindings`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BindingStatus<S, T> {
+    pub binding: DeviceBinding<S>,
+    pub last_inference: Option<i64>,
+    pub total_inferences: u64,
+    pub last_error: Option<String>,
+    /// Last image (data URI) for frontend display
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_image: Option<String>,
+    /// Extension-specific counters and last results
+    #[serde(flatten)]
+    pub state: T,
+}
+
+impl<S, T: Default> BindingStatus<S, T> {
+    pub fn new(binding: DeviceBinding<S>) -> Self {
+        Self {
+            binding,
+            last_inference: None,
+            total_inferences: 0,
+            last_error: None,
+            last_image: None,
+            state: T::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+    struct Settings {
+        #[serde(default)]
+        confidence_threshold: f32,
+    }
+
+    #[derive(Debug, Clone, Default, Serialize, Deserialize)]
+    struct Stats {
+        total_detections: u64,
+    }
+
+    #[test]
+    fn test_flattened_json_layout() {
+        let binding = DeviceBinding::new("cam-01", "image", Settings { confidence_threshold: 0.5 });
+        let value = serde_json::to_value(&binding).unwrap();
+        assert_eq!(value, json!({"device_id": "cam-01", "image_metric": "image", "active": true, "confidence_threshold": 0.5}));
+
+        let mut status: BindingStatus<Settings, Stats> = BindingStatus::new(binding);
+        status.state.total_detections = 3;
+        let value = serde_json::to_value(&status).unwrap();
+        assert_eq!(value["binding"]["device_id"], "cam-01");
+        assert_eq!(value["total_detections"], 3);
+        assert!(value.get("last_image").is_none());
+
+        let parsed: BindingStatus<Settings, Stats> = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed.state.total_detections, 3);
+    }
+
+    #[test]
+    fn test_legacy_metric_name_and_defaults() {
+        let parsed: DeviceBinding<Settings> =
+            serde_json::from_value(json!({"device_id": "cam-01", "metric_name": "frame"})).unwrap();
+        assert_eq!(parsed.image_metric, "frame");
+        assert!(parsed.active);
+        assert_eq!(parsed.settings, Settings::default());
+    }
+
+    #[test]
+    fn test_match_metric() {
+        let binding = DeviceBinding::new("cam-01", "snapshot.data", Settings::default());
+        assert_eq!(binding.match_metric("snapshot.data"), Some(None));
+        assert_eq!(binding.match_metric("snapshot"), Some(Some("data")));
+        assert_eq!(binding.match_metric("image"), None);
+
+        let binding = DeviceBinding::new("cam-01", "image", Settings::default());
+        assert_eq!(binding.match_metric("image"), Some(None));
+        assert_eq!(binding.match_metric("image_small"), None);
+    }
+}
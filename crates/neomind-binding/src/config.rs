@@ -0,0 +1,366 @@
+//! `config.json` persistence.
+//!
+//! The runtime exports `NEOMIND_EXTENSION_DIR` and starts isolated
+//! extensions in that directory, so bindings survive restarts when the
+//! extension's config (including its bindings) is saved there after every
+//! change. Each extension owns the layout of its config struct.
+//!
+//! Persisted files carry a `schema_version` (see [`Versioned`]). Older files
+//! are migrated on load; files that can't be read as the current version are
+//! renamed to `<file>.<unix millis>.bak` instead of being dropped. Writes go
+//! through a temp file and a rename so a crash never leaves half a file.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Key holding the schema version in persisted files
+pub const SCHEMA_VERSION_KEY: &str = "schema_version";
+
+/// A persisted struct with a schema version.
+///
+/// Files written before versioning have no `schema_version` and count as
+/// version 0. Bump `SCHEMA_VERSION` when the layout changes in a way serde
+/// defaults can't absorb, and handle the old version in [`Versioned::migrate`].
+pub trait Versioned: Serialize + DeserializeOwned {
+    /// Version written by this build
+    const SCHEMA_VERSION: u32;
+
+    /// Upgrade `value` from version `from` to `from + 1`.
+    ///
+    /// The default leaves the JSON unchanged, which is right as long as
+    /// versions only add fields with serde defaults.
+    fn migrate(from: u32, value: &mut Value) -> Result<(), String> {
+        let _ = (from, value);
+        Ok(())
+    }
+}
+
+/// Why a persisted file couldn't be decoded.
+#[derive(Debug)]
+pub enum SchemaError {
+    /// Not valid JSON, or doesn't match the struct after migration
+    Json(serde_json::Error),
+    /// Written by a newer build
+    Newer { found: u32, supported: u32 },
+    /// A migration step failed
+    Migration { from: u32, message: String },
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json(e) => write!(f, "{}", e),
+            Self::Newer { found, supported } => {
+                write!(f, "schema version {} is newer than supported version {}", found, supported)
+            }
+            Self::Migration { from, message } => {
+                write!(f, "migration from schema version {} failed: {}", from, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+impl From<serde_json::Error> for SchemaError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Json(e)
+    }
+}
+
+/// Parse a persisted file, migrating it to `C::SCHEMA_VERSION`.
+pub fn decode<C: Versioned>(content: &str) -> Result<C, SchemaError> {
+    let mut value: Value = serde_json::from_str(content)?;
+    let mut version = match value.get(SCHEMA_VERSION_KEY) {
+        Some(v) => v
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| SchemaError::Json(serde::de::Error::custom("invalid schema_version")))?,
+        None => 0,
+    };
+    if version > C::SCHEMA_VERSION {
+        return Err(SchemaError::Newer { found: version, supported: C::SCHEMA_VERSION });
+    }
+    while version < C::SCHEMA_VERSION {
+        C::migrate(version, &mut value).map_err(|message| SchemaError::Migration { from: version, message })?;
+        version += 1;
+    }
+    if let Some(map) = value.as_object_mut() {
+        map.remove(SCHEMA_VERSION_KEY);
+    }
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Serialize `config` with its `schema_version`.
+pub fn encode<C: Versioned>(config: &C) -> serde_json::Result<String> {
+    let mut value = serde_json::to_value(config)?;
+    if let Some(map) = value.as_object_mut() {
+        map.insert(SCHEMA_VERSION_KEY.to_string(), Value::from(C::SCHEMA_VERSION));
+    }
+    serde_json::to_string_pretty(&value)
+}
+
+/// Replace `path` with `contents` via a temp file in the same directory.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    write_atomic_with(path, contents, false)
+}
+
+/// Like [`write_atomic`], readable by the owner only (0600 on Unix).
+pub fn write_atomic_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    write_atomic_with(path, contents, true)
+}
+
+fn write_atomic_with(path: &Path, contents: &[u8], private: bool) -> std::io::Result<()> {
+    let mut tmp_name = path.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".tmp");
+    let tmp = path.with_file_name(tmp_name);
+
+    let result = (|| {
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        if private {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        #[cfg(not(unix))]
+        let _ = private;
+        let mut file = options.open(&tmp)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        std::fs::rename(&tmp, path)
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// Move `path` aside to `<file>.<unix millis>.bak` and return the new path.
+pub fn backup(path: &Path) -> std::io::Result<PathBuf> {
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default();
+    let file_name = path.file_name().unwrap_or_default();
+    let mut backup = path.with_file_name(format!("{}.{}.bak", file_name.to_string_lossy(), millis));
+    let mut n = 1;
+    while backup.exists() {
+        backup = path.with_file_name(format!("{}.{}-{}.bak", file_name.to_string_lossy(), millis, n));
+        n += 1;
+    }
+    std::fs::rename(path, &backup)?;
+    Ok(backup)
+}
+
+/// Back up a file that failed to decode, logging where it went.
+pub fn backup_unreadable(path: &Path, name: &str, error: &dyn fmt::Display) {
+    match backup(path) {
+        Ok(backup) => tracing::warn!(
+            "[{}] Could not load {} ({}), kept it as {}",
+            name,
+            path.display(),
+            error,
+            backup.display()
+        ),
+        Err(e) => tracing::warn!(
+            "[{}] Could not load {} ({}) and failed to back it up: {}",
+            name,
+            path.display(),
+            error,
+            e
+        ),
+    }
+}
+
+/// `$NEOMIND_EXTENSION_DIR/config.json`, `None` outside the extension runner.
+pub fn config_path() -> Option<PathBuf> {
+    std::env::var_os("NEOMIND_EXTENSION_DIR").map(|dir| PathBuf::from(dir).join("config.json"))
+}
+
+/// Load the extension config; `None` when there is none or it cannot be read.
+///
+/// Falls back to `config.json` in the working directory when
+/// `NEOMIND_EXTENSION_DIR` is not set. `name` is the extension's log prefix.
+pub fn load_config<C: Versioned>(name: &str) -> Option<C> {
+    let path = config_path().unwrap_or_else(|| PathBuf::from("config.json"));
+    load_from(&path, name)
+}
+
+/// Save the extension config, logging failures. Returns whether it was written.
+///
+/// Skipped when `NEOMIND_EXTENSION_DIR` is not set (tests, tools).
+pub fn save_config<C: Versioned>(name: &str, config: &C) -> bool {
+    match config_path() {
+        Some(path) => save_to(&path, name, config),
+        None => {
+            tracing::debug!("[{}] NEOMIND_EXTENSION_DIR not set, skipping config persistence", name);
+            false
+        }
+    }
+}
+
+/// Load and migrate `path`. A file that can't be decoded is backed up.
+pub fn load_from<C: Versioned>(path: &Path, name: &str) -> Option<C> {
+    if !path.exists() {
+        tracing::debug!("[{}] No config at {}", name, path.display());
+        return None;
+    }
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            tracing::warn!("[{}] Failed to read config {}: {}", name, path.display(), e);
+            return None;
+        }
+    };
+    match decode(&content) {
+        Ok(config) => {
+            tracing::info!("[{}] Loaded config from {}", name, path.display());
+            Some(config)
+        }
+        Err(e) => {
+            backup_unreadable(path, name, &e);
+            None
+        }
+    }
+}
+
+/// Write `config` to `path` atomically, logging failures.
+pub fn save_to<C: Versioned>(path: &Path, name: &str, config: &C) -> bool {
+    let json = match encode(config) {
+        Ok(json) => json,
+        Err(e) => {
+            tracing::warn!("[{}] Failed to serialize config: {}", name, e);
+            return false;
+        }
+    };
+    match write_atomic(path, json.as_bytes()) {
+        Ok(()) => {
+            tracing::debug!("[{}] Config persisted to {}", name, path.display());
+            true
+        }
+        Err(e) => {
+            tracing::warn!("[{}] Failed to persist config to {}: {}", name, path.display(), e);
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Config {
+        threshold: f32,
+    }
+
+    impl Versioned for Config {
+        const SCHEMA_VERSION: u32 = 1;
+    }
+
+    /// Version 2 renamed `threshold` to `confidence`
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct ConfigV2 {
+        confidence: f32,
+    }
+
+    impl Versioned for ConfigV2 {
+        const SCHEMA_VERSION: u32 = 2;
+
+        fn migrate(from: u32, value: &mut Value) -> Result<(), String> {
+            if from == 1 {
+                let map = value.as_object_mut().ok_or("not an object")?;
+                let threshold = map.remove("threshold").ok_or("missing threshold")?;
+                map.insert("confidence".to_string(), threshold);
+            }
+            Ok(())
+        }
+    }
+
+    fn backups(dir: &Path) -> Vec<PathBuf> {
+        std::fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "bak"))
+            .collect()
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        assert_eq!(load_from::<Config>(&path, "Test"), None);
+
+        assert!(save_to(&path, "Test", &Config { threshold: 0.5 }));
+        let saved: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved, json!({"schema_version": 1, "threshold": 0.5}));
+        assert_eq!(load_from::<Config>(&path, "Test"), Some(Config { threshold: 0.5 }));
+        // Only the config itself, no temp file left behind
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        assert!(!save_to(&dir.path().join("missing/config.json"), "Test", &Config { threshold: 0.5 }));
+    }
+
+    #[test]
+    fn test_migrate() {
+        // Unversioned files are version 0
+        assert_eq!(decode::<Config>(r#"{"threshold": 0.3}"#).unwrap(), Config { threshold: 0.3 });
+        assert_eq!(decode::<ConfigV2>(r#"{"threshold": 0.3}"#).unwrap(), ConfigV2 { confidence: 0.3 });
+        assert_eq!(
+            decode::<ConfigV2>(r#"{"schema_version": 1, "threshold": 0.3}"#).unwrap(),
+            ConfigV2 { confidence: 0.3 }
+        );
+        assert_eq!(
+            decode::<ConfigV2>(r#"{"schema_version": 2, "confidence": 0.3}"#).unwrap(),
+            ConfigV2 { confidence: 0.3 }
+        );
+
+        assert!(matches!(
+            decode::<ConfigV2>(r#"{"schema_version": 1}"#),
+            Err(SchemaError::Migration { from: 1, .. })
+        ));
+        assert!(matches!(
+            decode::<Config>(r#"{"schema_version": 2, "confidence": 0.3}"#),
+            Err(SchemaError::Newer { found: 2, supported: 1 })
+        ));
+        assert!(matches!(decode::<Config>(r#"{"schema_version": "1"}"#), Err(SchemaError::Json(_))));
+    }
+
+    #[test]
+    fn test_unreadable_files_are_backed_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+
+        std::fs::write(&path, "{ not json").unwrap();
+        assert_eq!(load_from::<Config>(&path, "Test"), None);
+        assert!(!path.exists());
+        let kept = backups(dir.path());
+        assert_eq!(kept.len(), 1);
+        assert_eq!(std::fs::read_to_string(&kept[0]).unwrap(), "{ not json");
+
+        // A file from a newer build is kept too, not overwritten by the next save
+        assert!(save_to(&path, "Test", &ConfigV2 { confidence: 0.7 }));
+        assert_eq!(load_from::<Config>(&path, "Test"), None);
+        assert!(save_to(&path, "Test", &Config { threshold: 0.5 }));
+        assert_eq!(backups(dir.path()).len(), 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_atomic_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("faces.json");
+        write_atomic_private(&path, b"{}").unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
@@ -0,0 +1,87 @@
+//! Image payloads in metric values.
+//!
+//! Devices report images as a plain base64 string, a data URL, the
+//! `{"String": ..}` MetricValue wrapper, or an object with the image in an
+//! `image`, `data`, `value` or `base64` field.
+
+use base64::Engine;
+use serde_json::Value;
+
+/// Base64 image data from a metric value, without any data URL prefix.
+///
+/// `nested_path` is a dotted path followed inside `value` first.
+pub fn extract_image(value: &Value, nested_path: Option<&str>) -> Option<String> {
+    let mut target = value;
+    if let Some(path) = nested_path {
+        for part in path.split('.') {
+            target = target.get(part)?;
+        }
+    }
+
+    let raw = match target {
+        Value::String(s) => s.as_str(),
+        _ => target.get("String").and_then(Value::as_str).or_else(|| {
+            ["image", "data", "value", "base64"]
+                .iter()
+                .find_map(|field| target.get(field).and_then(Value::as_str))
+        })?,
+    };
+    Some(strip_data_url(raw).to_string())
+}
+
+/// The base64 part of a `data:image/jpeg;base64,...` URL; other strings unchanged.
+pub fn strip_data_url(s: &str) -> &str {
+    if s.starts_with("data:") {
+        if let Some((_, data)) = s.split_once(',') {
+            return data;
+        }
+    }
+    s
+}
+
+/// Decode a base64 image, with or without a data URL prefix.
+pub fn decode_image(s: &str) -> Result<Vec<u8>, base64::DecodeError> {
+    base64::engine::general_purpose::STANDARD.decode(strip_data_url(s))
+}
+
+/// `data:image/jpeg;base64,` URL for frontend display and image metrics.
+pub fn jpeg_data_url(b64: &str) -> String {
+    format!("data:image/jpeg;base64,{}", b64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_extract_image_formats() {
+        assert_eq!(extract_image(&json!("aGVsbG8="), None).as_deref(), Some("aGVsbG8="));
+        assert_eq!(extract_image(&json!("data:image/jpeg;base64,aGVsbG8="), None).as_deref(), Some("aGVsbG8="));
+        assert_eq!(extract_image(&json!({"String": "aGVsbG8="}), None).as_deref(), Some("aGVsbG8="));
+        assert_eq!(extract_image(&json!({"data": "aGVsbG8="}), None).as_deref(), Some("aGVsbG8="));
+        // Data URLs inside wrappers are stripped as well
+        assert_eq!(
+            extract_image(&json!({"image": "data:image/png;base64,aGVsbG8="}), None).as_deref(),
+            Some("aGVsbG8=")
+        );
+        assert_eq!(extract_image(&json!(42), None), None);
+        assert_eq!(extract_image(&json!({"width": 640}), None), None);
+    }
+
+    #[test]
+    fn test_extract_image_nested_path() {
+        let value = json!({"image": {"data": "aGVsbG8="}, "meta": {"format": "jpeg"}});
+        assert_eq!(extract_image(&value, Some("image.data")).as_deref(), Some("aGVsbG8="));
+        assert_eq!(extract_image(&value, Some("image")).as_deref(), Some("aGVsbG8="));
+        assert_eq!(extract_image(&value, Some("missing.data")), None);
+    }
+
+    #[test]
+    fn test_decode_image() {
+        assert_eq!(decode_image("aGVsbG8=").unwrap(), b"hello");
+        assert_eq!(decode_image("data:image/jpeg;base64,aGVsbG8=").unwrap(), b"hello");
+        assert!(decode_image("not base64!").is_err());
+        assert_eq!(jpeg_data_url("aGVsbG8="), "data:image/jpeg;base64,aGVsbG8=");
+    }
+}
@@ -0,0 +1,26 @@
+//! Device bindings for event-driven extensions.
+//!
+//! yolo-device-inference, ocr-device-inference and face-recognition all work
+//! the same way: `bind_device` records which metric of a device carries an
+//! image, `DeviceMetric` events for bound devices are run through the model,
+//! and the results are written back to the device as virtual metrics.
+//! The common pieces live here:
+//!
+//! - [`binding`] - [`DeviceBinding`] generic over per-extension settings, and
+//!   its runtime [`BindingStatus`]
+//! - [`registry`] - [`BindingRegistry`] with the `bind_device`,
+//!   `unbind_device`, `toggle_binding` and `get_bindings` commands and
+//!   `DeviceMetric` event matching
+//! - [`image`] - base64 image extraction from metric values and data URLs
+//! - [`config`] - `config.json` persistence in `NEOMIND_EXTENSION_DIR`
+//! - [`metrics`] - virtual metric writes through `device_metrics_write`
+
+pub mod binding;
+pub mod config;
+pub mod image;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod metrics;
+pub mod registry;
+
+pub use binding::{BindingSettings, BindingStatus, DeviceBinding};
+pub use registry::{BindingRegistry, ImageEvent, BINDING_COMMANDS};
@@ -0,0 +1,56 @@
+//! Virtual metric writes through the native capability bridge.
+//!
+//! Virtual metrics must start with `transform.`, `virtual.`, `computed.`,
+//! `derived.` or `aggregated.`; by convention the extensions use
+//! `virtual.<extension>.<name>`.
+
+use neomind_extension_sdk::capabilities::CapabilityContext;
+use serde_json::{json, Value};
+
+/// Invoke a host capability from synchronous code such as `handle_event`.
+///
+/// Uses `block_in_place`, which is safe because the extension runner uses a
+/// multi-thread runtime. Successful responses always carry a `result` field.
+pub fn invoke_capability_sync(capability_name: &str, params: &Value) -> Value {
+    tokio::task::block_in_place(|| {
+        let response = CapabilityContext::default().invoke_capability(capability_name, params);
+        if response_succeeded(&response) && response.get("result").is_none() {
+            json!({
+                "success": true,
+                "result": response,
+            })
+        } else {
+            response
+        }
+    })
+}
+
+fn response_succeeded(response: &Value) -> bool {
+    response.get("success").and_then(Value::as_bool).unwrap_or(false)
+}
+
+/// Write one metric value on a device. Failures are logged; returns whether it was written.
+pub fn write_device_metric(device_id: &str, metric: &str, value: Value, timestamp: i64) -> bool {
+    let params = json!({
+        "device_id": device_id,
+        "metric": metric,
+        "value": value,
+        "timestamp": timestamp,
+    });
+    let response = invoke_capability_sync("device_metrics_write", &params);
+    let written = response_succeeded(&response);
+    if !written {
+        let error = response.get("error").and_then(|e| e.as_str()).unwrap_or("unknown error");
+        tracing::warn!("[DeviceMetrics] Failed to write {} for device {}: {}", metric, device_id, error);
+    }
+    written
+}
+
+/// Write several metrics with the same timestamp. Returns how many were written.
+pub fn write_device_metrics(device_id: &str, timestamp: i64, metrics: Vec<(&str, Value)>) -> usize {
+    metrics
+        .into_iter()
+        .map(|(metric, value)| write_device_metric(device_id, metric, value, timestamp))
+        .filter(|written| *written)
+        .count()
+}
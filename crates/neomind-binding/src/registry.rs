@@ -0,0 +1,372 @@
+//! Binding registry with the standard binding commands and event matching.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use neomind_extension_sdk::{ExtensionError, Result};
+use parking_lot::RwLock;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::binding::{BindingSettings, BindingStatus, DeviceBinding};
+use crate::image::{decode_image, extract_image};
+
+/// Commands implemented by [`BindingRegistry::execute_command`]
+pub const BINDING_COMMANDS: [&str; 4] = ["bind_device", "unbind_device", "toggle_binding", "get_bindings"];
+
+/// Image from a `DeviceMetric` event of a bound, active device
+#[derive(Debug, Clone)]
+pub struct ImageEvent<S> {
+    pub binding: DeviceBinding<S>,
+    /// Image as received, base64 without data URL prefix
+    pub image_b64: String,
+    /// Decoded image bytes
+    pub image: Vec<u8>,
+}
+
+/// Device bindings of one extension, keyed by device ID.
+///
+/// `S` is the extension's per-binding settings, `T` the extension-specific
+/// part of each binding's status (counters and last results).
+pub struct BindingRegistry<S, T> {
+    /// Log prefix, e.g. "YoloDeviceInference"
+    name: &'static str,
+    entries: RwLock<HashMap<String, BindingStatus<S, T>>>,
+}
+
+impl<S, T> BindingRegistry<S, T>
+where
+    S: BindingSettings,
+    T: Default + Clone + Serialize,
+{
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.read().is_empty()
+    }
+
+    pub fn contains(&self, device_id: &str) -> bool {
+        self.entries.read().contains_key(device_id)
+    }
+
+    pub fn get(&self, device_id: &str) -> Option<DeviceBinding<S>> {
+        self.entries.read().get(device_id).map(|s| s.binding.clone())
+    }
+
+    /// All bindings sorted by device ID, for persisting.
+    pub fn bindings(&self) -> Vec<DeviceBinding<S>> {
+        self.statuses().into_iter().map(|s| s.binding).collect()
+    }
+
+    /// All binding statuses sorted by device ID.
+    pub fn statuses(&self) -> Vec<BindingStatus<S, T>> {
+        let mut statuses: Vec<_> = self.entries.read().values().cloned().collect();
+        statuses.sort_by(|a, b| a.binding.device_id.cmp(&b.binding.device_id));
+        statuses
+    }
+
+    /// Add or replace a binding. Replacing resets the device's statistics.
+    pub fn bind(&self, binding: DeviceBinding<S>) {
+        tracing::info!("[{}] Device bound: {} (metric: {})", self.name, binding.device_id, binding.image_metric);
+        self.entries.write().insert(binding.device_id.clone(), BindingStatus::new(binding));
+    }
+
+    /// Remove a binding; returns whether the device was bound.
+    pub fn unbind(&self, device_id: &str) -> bool {
+        let removed = self.entries.write().remove(device_id).is_some();
+        if removed {
+            tracing::info!("[{}] Device unbound: {}", self.name, device_id);
+        }
+        removed
+    }
+
+    /// Set (or with `None`, flip) whether a binding is active.
+    /// Returns the new state, `None` when the device is not bound.
+    pub fn set_active(&self, device_id: &str, active: Option<bool>) -> Option<bool> {
+        let mut entries = self.entries.write();
+        let binding = &mut entries.get_mut(device_id)?.binding;
+        binding.active = active.unwrap_or(!binding.active);
+        tracing::info!("[{}] Binding {} active={}", self.name, device_id, binding.active);
+        Some(binding.active)
+    }
+
+    /// Load persisted bindings, keeping the statistics of devices already bound.
+    pub fn restore(&self, bindings: impl IntoIterator<Item = DeviceBinding<S>>) {
+        let mut entries = self.entries.write();
+        for binding in bindings {
+            match entries.get_mut(&binding.device_id) {
+                Some(status) => status.binding = binding,
+                None => {
+                    entries.insert(binding.device_id.clone(), BindingStatus::new(binding));
+                }
+            }
+        }
+        tracing::info!("[{}] Restored bindings, {} total", self.name, entries.len());
+    }
+
+    /// Modify a device's status in place; `None` when the device is not bound.
+    pub fn update<R>(&self, device_id: &str, f: impl FnOnce(&mut BindingStatus<S, T>) -> R) -> Option<R> {
+        self.entries.write().get_mut(device_id).map(f)
+    }
+
+    /// Count a successful inference at `timestamp` and let `f` store its results.
+    pub fn record_inference(&self, device_id: &str, timestamp: i64, f: impl FnOnce(&mut BindingStatus<S, T>)) {
+        self.update(device_id, |status| {
+            status.last_inference = Some(timestamp);
+            status.total_inferences += 1;
+            status.last_error = None;
+            f(status);
+        });
+    }
+
+    /// Remember the last processing error of a device.
+    pub fn record_error(&self, device_id: &str, error: &impl Display) {
+        self.update(device_id, |status| status.last_error = Some(error.to_string()));
+    }
+
+    /// Execute one of [`BINDING_COMMANDS`].
+    ///
+    /// An empty `device_id` is reported as `{"success": false, "error_code": ..}`;
+    /// a missing `device_id`, a missing `active` for `toggle_binding`, an
+    /// unbound device for `toggle_binding` ([`ExtensionError::NotFound`]) and
+    /// unknown commands are errors. Callers persist their config after the
+    /// commands that change bindings.
+    pub fn execute_command(&self, command: &str, args: &Value) -> Result<Value> {
+        if command == "get_bindings" {
+            return Ok(json!({
+                "success": true,
+                "bindings": self.statuses(),
+            }));
+        }
+        if !BINDING_COMMANDS.contains(&command) {
+            return Err(ExtensionError::CommandNotFound(command.to_string()));
+        }
+
+        let device_id = args
+            .get("device_id")
+            .and_then(Value::as_str)
+            .ok_or_else(|| ExtensionError::InvalidArguments("Missing or invalid device_id".to_string()))?;
+        if device_id.is_empty() {
+            return Ok(failure("INVALID_ARGUMENTS", "device_id cannot be empty".to_string()));
+        }
+
+        match command {
+            "bind_device" => {
+                let image_metric = args
+                    .get("image_metric")
+                    .or_else(|| args.get("metric_name"))
+                    .and_then(Value::as_str)
+                    .unwrap_or("image");
+                let mut binding = DeviceBinding::new(device_id, image_metric, S::from_args(args));
+                binding.device_name = args.get("device_name").and_then(Value::as_str).map(String::from);
+                self.bind(binding);
+                Ok(json!({
+                    "success": true,
+                    "device_id": device_id,
+                    "message": format!("Device {} bound successfully", device_id)
+                }))
+            }
+            "unbind_device" => {
+                self.unbind(device_id);
+                Ok(json!({
+                    "success": true,
+                    "device_id": device_id,
+                    "message": format!("Device {} unbound successfully", device_id)
+                }))
+            }
+            _ => {
+                let active = args
+                    .get("active")
+                    .and_then(Value::as_bool)
+                    .ok_or_else(|| ExtensionError::InvalidArguments("Missing active parameter".to_string()))?;
+                match self.set_active(device_id, Some(active)) {
+                    Some(active) => Ok(json!({
+                        "success": true,
+                        "device_id": device_id,
+                        "active": active
+                    })),
+                    None => Err(ExtensionError::NotFound(format!("Device {} not bound", device_id))),
+                }
+            }
+        }
+    }
+
+    /// The image of a `DeviceMetric` event for an active binding.
+    ///
+    /// `None` for other events, unbound or inactive devices, other metrics
+    /// and values without a decodable image.
+    pub fn match_event(&self, event_type: &str, payload: &Value) -> Option<ImageEvent<S>> {
+        if event_type != "DeviceMetric" {
+            return None;
+        }
+        // Events arrive either bare or wrapped in the standardized {"payload": ..} envelope
+        let inner = payload.get("payload").unwrap_or(payload);
+        let device_id = inner.get("device_id").and_then(Value::as_str)?;
+        let metric = inner.get("metric").and_then(Value::as_str)?;
+
+        let binding = self.get(device_id)?;
+        if !binding.active {
+            tracing::debug!("[{}] Binding inactive for device: {}", self.name, device_id);
+            return None;
+        }
+        let nested_path = binding.match_metric(metric)?;
+
+        let image_b64 = extract_image(inner.get("value")?, nested_path)?;
+        match decode_image(&image_b64) {
+            Ok(image) => Some(ImageEvent { binding, image_b64, image }),
+            Err(e) => {
+                tracing::warn!("[{}] Base64 decode failed: device={}, error={}", self.name, device_id, e);
+                None
+            }
+        }
+    }
+}
+
+fn failure(error_code: &str, error: String) -> Value {
+    json!({
+        "success": false,
+        "error": error,
+        "error_code": error_code
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct Settings {
+        confidence_threshold: f32,
+    }
+
+    impl BindingSettings for Settings {
+        fn from_args(args: &Value) -> Self {
+            Self {
+                confidence_threshold: args.get("confidence_threshold").and_then(Value::as_f64).unwrap_or(0.25) as f32,
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, Default, Serialize)]
+    struct Stats {
+        total_detections: u64,
+    }
+
+    fn registry() -> BindingRegistry<Settings, Stats> {
+        BindingRegistry::new("Test")
+    }
+
+    #[test]
+    fn test_standard_commands() {
+        let reg = registry();
+        let resp = reg
+            .execute_command("bind_device", &json!({"device_id": "cam-01", "image_metric": "snapshot", "confidence_threshold": 0.5}))
+            .unwrap();
+        assert_eq!(resp["success"], true);
+        let binding = reg.get("cam-01").unwrap();
+        assert_eq!(binding.image_metric, "snapshot");
+        assert_eq!(binding.settings.confidence_threshold, 0.5);
+
+        // Legacy argument name
+        reg.execute_command("bind_device", &json!({"device_id": "cam-02", "metric_name": "frame"})).unwrap();
+        assert_eq!(reg.get("cam-02").unwrap().image_metric, "frame");
+
+        let resp = reg.execute_command("toggle_binding", &json!({"device_id": "cam-01", "active": false})).unwrap();
+        assert_eq!(resp["active"], false);
+        let resp = reg.execute_command("toggle_binding", &json!({"device_id": "cam-01", "active": true})).unwrap();
+        assert_eq!(resp["active"], true);
+
+        let resp = reg.execute_command("get_bindings", &json!({})).unwrap();
+        let bindings = resp["bindings"].as_array().unwrap();
+        assert_eq!(bindings.len(), 2);
+        assert_eq!(bindings[0]["binding"]["device_id"], "cam-01");
+        assert_eq!(bindings[0]["total_detections"], 0);
+
+        let resp = reg.execute_command("unbind_device", &json!({"device_id": "cam-02"})).unwrap();
+        assert_eq!(resp["success"], true);
+        assert_eq!(reg.len(), 1);
+    }
+
+    #[test]
+    fn test_command_errors() {
+        let reg = registry();
+        assert!(reg.execute_command("bind_device", &json!({})).is_err());
+        assert!(reg.execute_command("analyze_image", &json!({})).is_err());
+
+        let resp = reg.execute_command("bind_device", &json!({"device_id": ""})).unwrap();
+        assert_eq!(resp["error_code"], "INVALID_ARGUMENTS");
+        assert!(matches!(
+            reg.execute_command("toggle_binding", &json!({"device_id": "nope", "active": true})),
+            Err(ExtensionError::NotFound(_))
+        ));
+        // `active` is required; flipping is only available through set_active
+        reg.bind(DeviceBinding::new("cam-01", "image", Settings { confidence_threshold: 0.25 }));
+        assert!(matches!(
+            reg.execute_command("toggle_binding", &json!({"device_id": "cam-01"})),
+            Err(ExtensionError::InvalidArguments(_))
+        ));
+        assert!(reg.get("cam-01").unwrap().active);
+        // Unbinding is idempotent
+        let resp = reg.execute_command("unbind_device", &json!({"device_id": "nope"})).unwrap();
+        assert_eq!(resp["success"], true);
+    }
+
+    #[test]
+    fn test_restore_keeps_stats() {
+        let reg = registry();
+        reg.bind(DeviceBinding::new("cam-01", "image", Settings { confidence_threshold: 0.25 }));
+        reg.record_inference("cam-01", 100, |s| s.state.total_detections += 2);
+        reg.record_error("cam-01", &"decode failed");
+
+        reg.restore(vec![
+            DeviceBinding::new("cam-01", "image", Settings { confidence_threshold: 0.6 }),
+            DeviceBinding::new("cam-02", "image", Settings { confidence_threshold: 0.25 }),
+        ]);
+        let statuses = reg.statuses();
+        assert_eq!(statuses.len(), 2);
+        assert_eq!(statuses[0].binding.settings.confidence_threshold, 0.6);
+        assert_eq!(statuses[0].total_inferences, 1);
+        assert_eq!(statuses[0].last_inference, Some(100));
+        assert_eq!(statuses[0].state.total_detections, 2);
+        assert_eq!(statuses[0].last_error.as_deref(), Some("decode failed"));
+        assert_eq!(statuses[1].total_inferences, 0);
+    }
+
+    #[test]
+    fn test_match_event() {
+        let reg = registry();
+        reg.bind(DeviceBinding::new("cam-01", "snapshot.data", Settings { confidence_threshold: 0.25 }));
+        let event = |device_id: &str, metric: &str, value: Value| {
+            json!({"payload": {"device_id": device_id, "metric": metric, "value": value}})
+        };
+
+        let matched = reg
+            .match_event("DeviceMetric", &event("cam-01", "snapshot", json!({"data": "data:image/jpeg;base64,aGVsbG8="})))
+            .unwrap();
+        assert_eq!(matched.binding.device_id, "cam-01");
+        assert_eq!(matched.image_b64, "aGVsbG8=");
+        assert_eq!(matched.image, b"hello");
+
+        // Bare payloads without the envelope are accepted too
+        let bare = json!({"device_id": "cam-01", "metric": "snapshot.data", "value": "aGVsbG8="});
+        assert!(reg.match_event("DeviceMetric", &bare).is_some());
+
+        assert!(reg.match_event("DeviceOnline", &event("cam-01", "snapshot", json!({"data": "aGVsbG8="}))).is_none());
+        assert!(reg.match_event("DeviceMetric", &event("cam-02", "snapshot", json!({"data": "aGVsbG8="}))).is_none());
+        assert!(reg.match_event("DeviceMetric", &event("cam-01", "temperature", json!(21.5))).is_none());
+        assert!(reg.match_event("DeviceMetric", &event("cam-01", "snapshot", json!({"data": "%%%"}))).is_none());
+
+        reg.set_active("cam-01", Some(false));
+        assert!(reg.match_event("DeviceMetric", &event("cam-01", "snapshot", json!({"data": "aGVsbG8="}))).is_none());
+    }
+}
@@ -0,0 +1,251 @@
+//! Golden-image tests for drawing code.
+//!
+//! A test renders an image and compares it with a reference PNG committed
+//! under the crate's `tests/golden/`:
+//!
+//! ```ignore
+//! neomind_golden::assert_golden!("two_boxes", image::DynamicImage::ImageRgb8(img));
+//! ```
+//!
+//! The comparison is perceptual: each pixel's color difference is measured
+//! in YIQ space (as in pixelmatch), so antialiasing and JPEG noise stay
+//! under the [`Tolerance`] while a moved label or a wrong color does not.
+//! On a mismatch `<name>.actual.png` and `<name>.diff.png` (differences in
+//! red) are written next to the golden.
+//!
+//! After an intended drawing change, regenerate the goldens and review them
+//! like any other diff:
+//!
+//! ```sh
+//! NEOMIND_UPDATE_GOLDENS=1 cargo test -p <extension>
+//! ```
+//!
+//! Text is drawn with [`font`], the Noto Sans file committed with
+//! ocr-device-inference, so goldens don't depend on fonts that are downloaded
+//! or installed separately.
+
+use ab_glyph::FontRef;
+use image::{DynamicImage, Rgba, RgbaImage};
+use std::path::{Path, PathBuf};
+
+/// Set to `1` to write the actual images as the new goldens
+pub const UPDATE_ENV: &str = "NEOMIND_UPDATE_GOLDENS";
+
+/// Largest YIQ difference between two colors (black vs. white)
+const MAX_DELTA: f32 = 35215.0;
+
+/// How different an image may be from its golden.
+#[derive(Debug, Clone, Copy)]
+pub struct Tolerance {
+    /// Per-pixel color difference from 0 (exact) to 1 (black vs. white)
+    pub pixel: f32,
+    /// Fraction of pixels allowed above `pixel`
+    pub ratio: f64,
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self { pixel: 0.1, ratio: 0.001 }
+    }
+}
+
+/// Result of comparing two images of the same size.
+pub struct Comparison {
+    pub diff_pixels: u64,
+    pub total_pixels: u64,
+    /// The expected image faded to gray with differing pixels in red
+    pub diff_image: RgbaImage,
+}
+
+impl Comparison {
+    pub fn ratio(&self) -> f64 {
+        self.diff_pixels as f64 / self.total_pixels.max(1) as f64
+    }
+}
+
+/// Font file for text in golden tests (Noto Sans, Latin only).
+pub fn font_data() -> &'static [u8] {
+    // Shared with the extensions rather than committing another copy
+    include_bytes!("../../../extensions/ocr-device-inference/fonts/NotoSans-Regular.ttf")
+}
+
+/// [`font_data`] parsed.
+pub fn font() -> FontRef<'static> {
+    FontRef::try_from_slice(font_data()).expect("bundled font is valid")
+}
+
+/// Compare `actual` with `expected`, `None` when their sizes differ.
+pub fn compare(expected: &RgbaImage, actual: &RgbaImage, tolerance: &Tolerance) -> Option<Comparison> {
+    if expected.dimensions() != actual.dimensions() {
+        return None;
+    }
+    let threshold = MAX_DELTA * tolerance.pixel * tolerance.pixel;
+    let mut diff_image = RgbaImage::new(expected.width(), expected.height());
+    let mut diff_pixels = 0;
+    for ((e, a), d) in expected.pixels().zip(actual.pixels()).zip(diff_image.pixels_mut()) {
+        if color_delta(e, a) > threshold {
+            diff_pixels += 1;
+            *d = Rgba([255, 0, 0, 255]);
+        } else {
+            let gray = (255.0 - 0.1 * (255.0 - luma(e))) as u8;
+            *d = Rgba([gray, gray, gray, 255]);
+        }
+    }
+    Some(Comparison {
+        diff_pixels,
+        total_pixels: expected.width() as u64 * expected.height() as u64,
+        diff_image,
+    })
+}
+
+/// Compare `actual` with the golden `<dir>/<name>.png` using the default
+/// [`Tolerance`]; panics with the paths to inspect on a mismatch.
+pub fn assert_golden(dir: impl AsRef<Path>, name: &str, actual: impl Into<DynamicImage>) {
+    assert_golden_with(dir, name, actual, &Tolerance::default());
+}
+
+/// [`assert_golden`] with an explicit tolerance.
+pub fn assert_golden_with(dir: impl AsRef<Path>, name: &str, actual: impl Into<DynamicImage>, tolerance: &Tolerance) {
+    let dir = dir.as_ref();
+    let actual = actual.into().to_rgba8();
+    let golden = dir.join(format!("{}.png", name));
+
+    if std::env::var(UPDATE_ENV).is_ok_and(|v| v == "1") {
+        std::fs::create_dir_all(dir).expect("create golden dir");
+        actual.save(&golden).unwrap_or_else(|e| panic!("Failed to write {}: {}", golden.display(), e));
+        return;
+    }
+
+    let expected = match image::open(&golden) {
+        Ok(expected) => expected.to_rgba8(),
+        Err(e) => {
+            let actual_path = write_artifact(dir, name, "actual", &actual);
+            panic!(
+                "Golden {} can't be read ({}); actual image written to {}. Run with {}=1 to create it.",
+                golden.display(),
+                e,
+                actual_path.display(),
+                UPDATE_ENV
+            );
+        }
+    };
+
+    let Some(comparison) = compare(&expected, &actual, tolerance) else {
+        let actual_path = write_artifact(dir, name, "actual", &actual);
+        panic!(
+            "Golden {} is {}x{} but the image is {}x{}; actual image written to {}",
+            golden.display(),
+            expected.width(),
+            expected.height(),
+            actual.width(),
+            actual.height(),
+            actual_path.display()
+        );
+    };
+
+    if comparison.ratio() > tolerance.ratio {
+        let actual_path = write_artifact(dir, name, "actual", &actual);
+        let diff_path = write_artifact(dir, name, "diff", &comparison.diff_image);
+        panic!(
+            "Image differs from golden {}: {} of {} pixels ({:.3}%, allowed {:.3}%). See {} and {}; run with {}=1 if the change is intended.",
+            golden.display(),
+            comparison.diff_pixels,
+            comparison.total_pixels,
+            comparison.ratio() * 100.0,
+            tolerance.ratio * 100.0,
+            actual_path.display(),
+            diff_path.display(),
+            UPDATE_ENV
+        );
+    }
+}
+
+/// [`assert_golden`] with goldens in the calling crate's `tests/golden/`.
+#[macro_export]
+macro_rules! assert_golden {
+    ($name:expr, $image:expr) => {
+        $crate::assert_golden(
+            ::std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden"),
+            $name,
+            $image,
+        )
+    };
+}
+
+fn write_artifact(dir: &Path, name: &str, kind: &str, image: &RgbaImage) -> PathBuf {
+    let path = dir.join(format!("{}.{}.png", name, kind));
+    // Best effort: the panic message is what matters
+    let _ = std::fs::create_dir_all(dir).and_then(|_| image.save(&path).map_err(std::io::Error::other));
+    path
+}
+
+/// Color blended onto white, as RGB floats.
+fn blend(p: &Rgba<u8>) -> [f32; 3] {
+    let a = p[3] as f32 / 255.0;
+    [0, 1, 2].map(|c| 255.0 + (p[c] as f32 - 255.0) * a)
+}
+
+fn luma(p: &Rgba<u8>) -> f32 {
+    let [r, g, b] = blend(p);
+    r * 0.298_895_3 + g * 0.586_622_5 + b * 0.114_482_23
+}
+
+/// Squared YIQ distance, weighted for perceived difference.
+fn color_delta(a: &Rgba<u8>, b: &Rgba<u8>) -> f32 {
+    if a == b {
+        return 0.0;
+    }
+    let [r1, g1, b1] = blend(a);
+    let [r2, g2, b2] = blend(b);
+    let (dr, dg, db) = (r1 - r2, g1 - g2, b1 - b2);
+    let y = dr * 0.298_895_3 + dg * 0.586_622_5 + db * 0.114_482_23;
+    let i = dr * 0.595_978 - dg * 0.274_176_1 - db * 0.321_801_9;
+    let q = dr * 0.211_470_17 - dg * 0.522_617_1 + db * 0.311_146_94;
+    0.5053 * y * y + 0.299 * i * i + 0.1957 * q * q
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn canvas() -> RgbaImage {
+        RgbaImage::from_fn(40, 30, |x, y| Rgba([(x * 6) as u8, (y * 8) as u8, 128, 255]))
+    }
+
+    #[test]
+    fn test_compare() {
+        let expected = canvas();
+        let tolerance = Tolerance::default();
+        assert_eq!(compare(&expected, &expected, &tolerance).unwrap().diff_pixels, 0);
+
+        // Slight noise everywhere is tolerated, a recolored pixel is not
+        let mut noisy = expected.clone();
+        noisy.pixels_mut().for_each(|p| p[0] = p[0].saturating_add(3));
+        noisy.put_pixel(5, 5, Rgba([255, 0, 0, 255]));
+        let comparison = compare(&expected, &noisy, &tolerance).unwrap();
+        assert_eq!(comparison.diff_pixels, 1);
+        assert_eq!(comparison.diff_image.get_pixel(5, 5), &Rgba([255, 0, 0, 255]));
+
+        assert!(compare(&expected, &RgbaImage::new(30, 40), &tolerance).is_none());
+    }
+
+    #[test]
+    fn test_assert_golden() {
+        let dir = tempfile::tempdir().unwrap();
+        canvas().save(dir.path().join("canvas.png")).unwrap();
+        assert_golden(dir.path(), "canvas", DynamicImage::ImageRgba8(canvas()));
+
+        let mut moved = canvas();
+        image::imageops::flip_horizontal_in_place(&mut moved);
+        let result = std::panic::catch_unwind(|| assert_golden(dir.path(), "canvas", DynamicImage::ImageRgba8(moved)));
+        assert!(result.is_err());
+        assert!(dir.path().join("canvas.actual.png").exists());
+        assert!(dir.path().join("canvas.diff.png").exists());
+
+        // A missing golden fails unless goldens are being updated
+        if std::env::var(UPDATE_ENV).is_err() {
+            let result = std::panic::catch_unwind(|| assert_golden(dir.path(), "missing", DynamicImage::ImageRgba8(canvas())));
+            assert!(result.is_err());
+        }
+    }
+}
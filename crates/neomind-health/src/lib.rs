@@ -0,0 +1,302 @@
+//! Uniform health reporting for extensions.
+//!
+//! Every extension answers a `health` command with a [`Health`] report and
+//! emits the report's state as the `health_state` metric, so the dashboard
+//! and monitoring can alert on the same fields everywhere:
+//!
+//! ```json
+//! {
+//!   "state": "degraded",
+//!   "model_loaded": true,
+//!   "load_error": null,
+//!   "native_libs": true,
+//!   "last_success_ms": 1760659200000,
+//!   "error_count": 3,
+//!   "last_error": "Inference failed: ...",
+//!   "reasons": ["last operation failed: Inference failed: ..."]
+//! }
+//! ```
+//!
+//! Fields that don't apply to an extension (no model, no native libraries)
+//! are `null`. `reasons` explains any state other than `healthy`.
+
+use neomind_extension_sdk::{
+    ExtensionCommand, ExtensionMetricValue, MetricDataType, MetricDescriptor, ParamMetricValue,
+};
+use serde::Serialize;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Name of the health command
+pub const HEALTH_COMMAND: &str = "health";
+
+/// Name of the health metric
+pub const HEALTH_METRIC: &str = "health_state";
+
+/// Overall state, ordered from best to worst.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthState {
+    Healthy,
+    /// Working, but something needs attention
+    Degraded,
+    /// Can't do its job (model or native library missing)
+    Unhealthy,
+}
+
+impl HealthState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Healthy => "healthy",
+            Self::Degraded => "degraded",
+            Self::Unhealthy => "unhealthy",
+        }
+    }
+}
+
+/// Health report, built up from what the extension knows about itself.
+#[derive(Debug, Clone, Serialize)]
+pub struct Health {
+    pub state: HealthState,
+    /// `None` for extensions without a model
+    pub model_loaded: Option<bool>,
+    pub load_error: Option<String>,
+    /// `None` for extensions without native libraries
+    pub native_libs: Option<bool>,
+    /// Unix millis of the last successful operation
+    pub last_success_ms: Option<i64>,
+    pub error_count: u64,
+    /// The error of the last operation, if it failed
+    pub last_error: Option<String>,
+    /// Why the state isn't `healthy`
+    pub reasons: Vec<String>,
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Health {
+    pub fn new() -> Self {
+        Self {
+            state: HealthState::Healthy,
+            model_loaded: None,
+            load_error: None,
+            native_libs: None,
+            last_success_ms: None,
+            error_count: 0,
+            last_error: None,
+            reasons: Vec::new(),
+        }
+    }
+
+    /// Model status. Not loaded yet is fine for lazily loaded models; a load
+    /// error makes the extension unhealthy.
+    pub fn model(mut self, loaded: bool, load_error: Option<String>) -> Self {
+        self.model_loaded = Some(loaded);
+        if let Some(e) = &load_error {
+            self = self.unhealthy(format!("model failed to load: {}", e));
+        }
+        self.load_error = load_error;
+        self
+    }
+
+    /// Whether the native library `name` (e.g. ONNX Runtime) can be found.
+    pub fn native_libs(mut self, available: bool, name: &str) -> Self {
+        self.native_libs = Some(available);
+        if available {
+            self
+        } else {
+            self.unhealthy(format!("{} not found", name))
+        }
+    }
+
+    pub fn last_success(mut self, last_success_ms: Option<i64>) -> Self {
+        self.last_success_ms = last_success_ms;
+        self
+    }
+
+    /// Total errors, and the current one if the last operation failed.
+    pub fn errors(mut self, count: u64, current: Option<String>) -> Self {
+        self.error_count = count;
+        if let Some(e) = &current {
+            self = self.degraded(format!("last operation failed: {}", e));
+        }
+        self.last_error = current;
+        self
+    }
+
+    /// Last success and errors from an [`Activity`] tracker.
+    pub fn activity(self, activity: &Activity) -> Self {
+        self.last_success(activity.last_success()).errors(activity.error_count(), activity.current_error())
+    }
+
+    pub fn degraded(self, reason: impl Into<String>) -> Self {
+        self.escalate(HealthState::Degraded, reason.into())
+    }
+
+    pub fn unhealthy(self, reason: impl Into<String>) -> Self {
+        self.escalate(HealthState::Unhealthy, reason.into())
+    }
+
+    fn escalate(mut self, state: HealthState, reason: String) -> Self {
+        self.state = self.state.max(state);
+        self.reasons.push(reason);
+        self
+    }
+
+    /// Response of the `health` command.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+
+    /// The `health_state` metric.
+    pub fn metric(&self) -> ExtensionMetricValue {
+        ExtensionMetricValue {
+            name: HEALTH_METRIC.to_string(),
+            value: ParamMetricValue::String(self.state.as_str().to_string()),
+            timestamp: now_ms(),
+        }
+    }
+}
+
+/// Descriptor for the `health_state` metric, for `Extension::metrics`.
+pub fn metric_descriptor() -> MetricDescriptor {
+    MetricDescriptor {
+        name: HEALTH_METRIC.to_string(),
+        display_name: "Health State".to_string(),
+        data_type: MetricDataType::String,
+        unit: String::new(),
+        min: None,
+        max: None,
+        required: false,
+    }
+}
+
+/// Definition of the `health` command, for `Extension::commands`.
+pub fn command() -> ExtensionCommand {
+    ExtensionCommand {
+        name: HEALTH_COMMAND.to_string(),
+        display_name: "Health".to_string(),
+        description: "Get health state, model and native library status, last success and errors".to_string(),
+        payload_template: String::new(),
+        parameters: Vec::new(),
+        fixed_values: Default::default(),
+        samples: vec![serde_json::json!({})],
+        parameter_groups: Vec::new(),
+    }
+}
+
+/// Records successes and failures of an extension's main operation.
+#[derive(Debug, Default)]
+pub struct Activity {
+    last_success_ms: AtomicI64,
+    last_error_ms: AtomicI64,
+    errors: AtomicU64,
+    last_error: Mutex<Option<String>>,
+}
+
+impl Activity {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn success(&self) {
+        self.last_success_ms.store(now_ms(), Ordering::Relaxed);
+    }
+
+    pub fn error(&self, error: impl ToString) {
+        self.errors.fetch_add(1, Ordering::Relaxed);
+        self.last_error_ms.store(now_ms(), Ordering::Relaxed);
+        *self.last_error.lock().unwrap_or_else(|e| e.into_inner()) = Some(error.to_string());
+    }
+
+    pub fn last_success(&self) -> Option<i64> {
+        Some(self.last_success_ms.load(Ordering::Relaxed)).filter(|ms| *ms > 0)
+    }
+
+    pub fn error_count(&self) -> u64 {
+        self.errors.load(Ordering::Relaxed)
+    }
+
+    /// The last error, unless an operation has succeeded since.
+    pub fn current_error(&self) -> Option<String> {
+        let failed_at = self.last_error_ms.load(Ordering::Relaxed);
+        if failed_at == 0 || self.last_success_ms.load(Ordering::Relaxed) > failed_at {
+            return None;
+        }
+        self.last_error.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
+
+/// Unix millis; 0 in WASM, which has no clock.
+fn now_ms() -> i64 {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or_default()
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_health_state() {
+        let health = Health::new();
+        assert_eq!(health.state, HealthState::Healthy);
+        assert_eq!(
+            health.to_json(),
+            json!({
+                "state": "healthy",
+                "model_loaded": null,
+                "load_error": null,
+                "native_libs": null,
+                "last_success_ms": null,
+                "error_count": 0,
+                "last_error": null,
+                "reasons": [],
+            })
+        );
+
+        // A model that isn't loaded yet is fine, failed errors are not
+        assert_eq!(Health::new().model(false, None).state, HealthState::Healthy);
+        let health = Health::new().errors(2, Some("timeout".into())).model(false, Some("missing file".into()));
+        assert_eq!(health.state, HealthState::Unhealthy);
+        assert_eq!(health.reasons.len(), 2);
+
+        // Unhealthy isn't downgraded by a later degraded reason
+        let health = Health::new().native_libs(false, "ONNX Runtime").degraded("slow");
+        assert_eq!(health.state, HealthState::Unhealthy);
+        assert!(matches!(health.metric().value, ParamMetricValue::String(ref s) if s == "unhealthy"));
+    }
+
+    #[test]
+    fn test_activity() {
+        let activity = Activity::new();
+        assert_eq!(Health::new().activity(&activity).state, HealthState::Healthy);
+
+        activity.error("camera offline");
+        let health = Health::new().activity(&activity);
+        assert_eq!(health.state, HealthState::Degraded);
+        assert_eq!(health.error_count, 1);
+        assert_eq!(health.last_error.as_deref(), Some("camera offline"));
+
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        activity.success();
+        let health = Health::new().activity(&activity);
+        assert_eq!(health.state, HealthState::Healthy);
+        assert_eq!(health.error_count, 1);
+        assert!(health.last_success_ms.is_some());
+    }
+}
@@ -0,0 +1,164 @@
+//! Logging for the extensions.
+//!
+//! Extensions are cdylibs with their own copy of the `tracing` dispatcher, so
+//! nothing they log reaches the runner's subscriber. [`init`] installs one
+//! per process that writes to stderr, which the runner collects with the
+//! extension's other output.
+//!
+//! The level comes from `NEOMIND_LOG_LEVEL` (default `info`) and can be changed
+//! at runtime with a `log_level` key in the extension's config, see
+//! [`apply_config`]. `info` is for lifecycle events (load, bind, configure,
+//! session start/stop); per-frame and per-event messages belong at `debug`
+//! so edge devices don't fill their disks.
+//!
+//! Messages keep the `[ExtensionName]` prefix; [`binding_span`] and
+//! [`session_span`] add the device or session they belong to.
+
+use tracing::level_filters::LevelFilter;
+use tracing::Span;
+
+/// Environment variable with the initial log level
+pub const LOG_LEVEL_ENV: &str = "NEOMIND_LOG_LEVEL";
+
+/// Config key read by [`apply_config`]
+pub const LOG_LEVEL_KEY: &str = "log_level";
+
+/// Level used when `NEOMIND_LOG_LEVEL` is not set or invalid
+pub const DEFAULT_LEVEL: LevelFilter = LevelFilter::INFO;
+
+/// Parse `off`, `error`, `warn`, `info`, `debug` or `trace` (any case).
+pub fn parse_level(level: &str) -> Option<LevelFilter> {
+    level.trim().parse().ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod subscriber {
+    use std::io::Write;
+    use std::sync::OnceLock;
+
+    use tracing::level_filters::LevelFilter;
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::{fmt, reload, Registry};
+
+    static HANDLE: OnceLock<reload::Handle<LevelFilter, Registry>> = OnceLock::new();
+
+    /// Writes through `eprint!` rather than `io::stderr` so libtest captures
+    /// the output of tests that construct extensions.
+    struct StderrWriter;
+
+    impl Write for StderrWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            eprint!("{}", String::from_utf8_lossy(buf));
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            std::io::stderr().flush()
+        }
+    }
+
+    pub fn handle() -> &'static reload::Handle<LevelFilter, Registry> {
+        HANDLE.get_or_init(|| {
+            let level = std::env::var(super::LOG_LEVEL_ENV)
+                .ok()
+                .and_then(|level| super::parse_level(&level))
+                .unwrap_or(super::DEFAULT_LEVEL);
+            let (filter, handle) = reload::Layer::new(level);
+            let subscriber = tracing_subscriber::registry().with(filter).with(
+                fmt::layer()
+                    .with_writer(|| StderrWriter)
+                    .with_target(false)
+                    .with_ansi(false),
+            );
+            // Fails when a subscriber is already installed (a host test harness);
+            // that one then decides what is logged
+            let _ = tracing::subscriber::set_global_default(subscriber);
+            handle
+        })
+    }
+}
+
+/// Install the stderr subscriber. Safe to call any number of times.
+pub fn init() {
+    #[cfg(not(target_arch = "wasm32"))]
+    subscriber::handle();
+}
+
+/// Current log level.
+pub fn level() -> LevelFilter {
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        subscriber::handle().clone_current().unwrap_or(DEFAULT_LEVEL)
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        DEFAULT_LEVEL
+    }
+}
+
+/// Change the log level, installing the subscriber if needed.
+pub fn set_level(level: &str) -> Result<LevelFilter, String> {
+    let filter = parse_level(level).ok_or_else(|| {
+        format!("Invalid log level '{}', expected off, error, warn, info, debug or trace", level)
+    })?;
+    #[cfg(not(target_arch = "wasm32"))]
+    subscriber::handle().reload(filter).map_err(|e| e.to_string())?;
+    Ok(filter)
+}
+
+/// Apply the `log_level` key of an extension's config, if present.
+///
+/// Invalid levels are logged and ignored so a typo doesn't fail `configure`.
+/// `name` is the extension's log prefix. Returns the level that was set.
+pub fn apply_config(name: &str, config: &serde_json::Value) -> Option<LevelFilter> {
+    init();
+    let level = config.get(LOG_LEVEL_KEY)?.as_str()?;
+    match set_level(level) {
+        Ok(filter) => {
+            tracing::info!("[{}] Log level set to {}", name, filter);
+            Some(filter)
+        }
+        Err(e) => {
+            tracing::warn!("[{}] {}", name, e);
+            None
+        }
+    }
+}
+
+/// Span for work on one device binding; enter it while handling the device's events.
+pub fn binding_span(device_id: &str) -> Span {
+    tracing::info_span!("binding", device_id = %device_id)
+}
+
+/// Span for one stream session.
+pub fn session_span(session_id: &str) -> Span {
+    tracing::info_span!("session", session_id = %session_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_level() {
+        assert_eq!(parse_level("debug"), Some(LevelFilter::DEBUG));
+        assert_eq!(parse_level(" WARN "), Some(LevelFilter::WARN));
+        assert_eq!(parse_level("off"), Some(LevelFilter::OFF));
+        assert_eq!(parse_level("verbose"), None);
+    }
+
+    #[test]
+    fn test_apply_config() {
+        assert_eq!(apply_config("Test", &json!({"log_level": "debug"})), Some(LevelFilter::DEBUG));
+        assert_eq!(level(), LevelFilter::DEBUG);
+
+        // Missing or invalid levels leave the current level alone
+        assert_eq!(apply_config("Test", &json!({})), None);
+        assert_eq!(apply_config("Test", &json!({"log_level": "loud"})), None);
+        assert_eq!(level(), LevelFilter::DEBUG);
+
+        assert!(set_level("warn").is_ok());
+        assert_eq!(level(), LevelFilter::WARN);
+    }
+}
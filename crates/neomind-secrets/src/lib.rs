@@ -0,0 +1,130 @@
+//! Credentials for extensions that log in to external services.
+//!
+//! [`Secret`] holds a password, token or account name. Its `Debug` and
+//! `Serialize` output is redacted, so a config struct with `Secret` fields
+//! can be logged, returned from `get_config`/`get_status` commands or
+//! persisted as `config.json` without leaking the value; code that needs
+//! it calls [`Secret::expose`].
+//!
+//! [`SecretStore`] keeps secrets out of `config.json` altogether, in a
+//! separate owner-only `secrets.json` that is encrypted when
+//! `NEOMIND_SECRETS_KEY` is set.
+
+pub mod store;
+
+pub use store::SecretStore;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// What a set secret serializes to
+pub const REDACTED: &str = "********";
+
+/// A credential whose value is never printed or serialized.
+///
+/// Serializes to [`REDACTED`] when set and `""` when empty, so frontends
+/// can still tell whether it is configured. Deserializes from a plain string.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    /// The actual value; only pass it to the service that needs it.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn redacted(&self) -> &'static str {
+        if self.0.is_empty() {
+            ""
+        } else {
+            REDACTED
+        }
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for Secret {
+    fn from(value: &str) -> Self {
+        Self(value.to_string())
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Secret({:?})", self.redacted())
+    }
+}
+
+impl Serialize for Secret {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.redacted())
+    }
+}
+
+impl<'de> Deserialize<'de> for Secret {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self)
+    }
+}
+
+/// Read `key` from a configure payload.
+///
+/// `None` when it is missing, not a string, or [`REDACTED`]: a frontend
+/// that sends back what `get_config` returned leaves the secret unchanged.
+pub fn from_config(config: &serde_json::Value, key: &str) -> Option<Secret> {
+    config
+        .get(key)
+        .and_then(|v| v.as_str())
+        .filter(|v| *v != REDACTED)
+        .map(Secret::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Login {
+        email: Secret,
+        password: Secret,
+    }
+
+    #[test]
+    fn test_redacted_output() {
+        let login = Login {
+            email: Secret::new("ops@example.com"),
+            password: Secret::default(),
+        };
+        assert_eq!(login.email.expose(), "ops@example.com");
+
+        let debug = format!("{:?}", login);
+        assert!(!debug.contains("ops@example.com"));
+        assert_eq!(serde_json::to_value(&login).unwrap(), json!({"email": REDACTED, "password": ""}));
+
+        let parsed: Login = serde_json::from_value(json!({"email": "a@b.c", "password": "hunter2"})).unwrap();
+        assert_eq!(parsed.password.expose(), "hunter2");
+    }
+
+    #[test]
+    fn test_from_config() {
+        let config = json!({"password": "hunter2", "email": REDACTED, "port": 1});
+        assert_eq!(from_config(&config, "password"), Some(Secret::new("hunter2")));
+        assert_eq!(from_config(&config, "email"), None);
+        assert_eq!(from_config(&config, "port"), None);
+        assert_eq!(from_config(&config, "token"), None);
+    }
+}
@@ -0,0 +1,214 @@
+//! `secrets.json` persistence.
+//!
+//! The file sits next to `config.json` in `NEOMIND_EXTENSION_DIR`, is
+//! readable by the owner only and is replaced atomically. When
+//! `NEOMIND_SECRETS_KEY` holds a base64 encoded 32 byte key (for example
+//! from `openssl rand -base64 32`), the secrets are sealed with
+//! AES-256-GCM; a plaintext file is encrypted on the next save once a key
+//! is configured.
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use neomind_binding::config::{self, Versioned};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::Secret;
+
+/// Environment variable with the base64 encryption key
+pub const SECRETS_KEY_ENV: &str = "NEOMIND_SECRETS_KEY";
+
+/// On-disk layout: either plain `secrets` or a `sealed` JSON object of them
+#[derive(Default, Serialize, Deserialize)]
+struct SecretsFile {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    secrets: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sealed: Option<Sealed>,
+}
+
+impl Versioned for SecretsFile {
+    const SCHEMA_VERSION: u32 = 1;
+}
+
+#[derive(Serialize, Deserialize)]
+struct Sealed {
+    nonce: String,
+    data: String,
+}
+
+/// An extension's `secrets.json`.
+pub struct SecretStore {
+    path: PathBuf,
+    key: Option<LessSafeKey>,
+    name: String,
+}
+
+impl SecretStore {
+    /// `$NEOMIND_EXTENSION_DIR/secrets.json` with the key from
+    /// `NEOMIND_SECRETS_KEY`; `None` outside the extension runner.
+    ///
+    /// Fails when the key is set but invalid rather than falling back to
+    /// plaintext. `name` is the extension's log prefix.
+    pub fn from_env(name: &str) -> Result<Option<Self>, String> {
+        let Some(dir) = std::env::var_os("NEOMIND_EXTENSION_DIR") else {
+            return Ok(None);
+        };
+        let key = match std::env::var(SECRETS_KEY_ENV) {
+            Ok(key) if !key.trim().is_empty() => Some(
+                STANDARD
+                    .decode(key.trim())
+                    .map_err(|e| format!("{} is not valid base64: {}", SECRETS_KEY_ENV, e))?,
+            ),
+            _ => None,
+        };
+        Self::new(PathBuf::from(dir).join("secrets.json"), key.as_deref(), name).map(Some)
+    }
+
+    /// Store at `path`, encrypted with `key` (32 bytes) when given.
+    pub fn new(path: PathBuf, key: Option<&[u8]>, name: &str) -> Result<Self, String> {
+        let key = match key {
+            Some(key) => {
+                let unbound = UnboundKey::new(&AES_256_GCM, key)
+                    .map_err(|_| format!("Secrets key must be 32 bytes, got {}", key.len()))?;
+                Some(LessSafeKey::new(unbound))
+            }
+            None => None,
+        };
+        Ok(Self { path, key, name: name.to_string() })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn is_encrypted(&self) -> bool {
+        self.key.is_some()
+    }
+
+    /// Read all secrets; empty when there is no file yet.
+    ///
+    /// A file that isn't valid JSON is backed up like a broken
+    /// `config.json`. One that can't be decrypted is an error and left
+    /// alone, since the key may just be missing or wrong.
+    pub fn load(&self) -> Result<BTreeMap<String, Secret>, String> {
+        if !self.path.exists() {
+            return Ok(BTreeMap::new());
+        }
+        let content = std::fs::read_to_string(&self.path)
+            .map_err(|e| format!("Failed to read {}: {}", self.path.display(), e))?;
+        let file: SecretsFile = match config::decode(&content) {
+            Ok(file) => file,
+            Err(e) => {
+                config::backup_unreadable(&self.path, &self.name, &e);
+                return Ok(BTreeMap::new());
+            }
+        };
+        let plain = match file.sealed {
+            Some(sealed) => self.open(&sealed)?,
+            None => file.secrets,
+        };
+        Ok(plain.into_iter().map(|(k, v)| (k, Secret::from(v))).collect())
+    }
+
+    /// Replace the stored secrets.
+    pub fn save(&self, secrets: &BTreeMap<String, Secret>) -> Result<(), String> {
+        let plain: BTreeMap<String, String> =
+            secrets.iter().map(|(k, v)| (k.clone(), v.expose().to_string())).collect();
+        let file = match &self.key {
+            Some(key) => SecretsFile { secrets: BTreeMap::new(), sealed: Some(seal(key, &plain)?) },
+            None => SecretsFile { secrets: plain, sealed: None },
+        };
+        let json = config::encode(&file).map_err(|e| format!("Failed to serialize secrets: {}", e))?;
+        config::write_atomic_private(&self.path, json.as_bytes())
+            .map_err(|e| format!("Failed to write {}: {}", self.path.display(), e))?;
+        tracing::debug!("[{}] Secrets persisted to {}", self.name, self.path.display());
+        Ok(())
+    }
+
+    fn open(&self, sealed: &Sealed) -> Result<BTreeMap<String, String>, String> {
+        let key = self.key.as_ref().ok_or_else(|| {
+            format!("{} is encrypted but {} is not set", self.path.display(), SECRETS_KEY_ENV)
+        })?;
+        let nonce: [u8; NONCE_LEN] = STANDARD
+            .decode(&sealed.nonce)
+            .ok()
+            .and_then(|nonce| nonce.try_into().ok())
+            .ok_or("Invalid nonce in secrets file")?;
+        let mut data = STANDARD
+            .decode(&sealed.data)
+            .map_err(|e| format!("Invalid data in secrets file: {}", e))?;
+        let plain = key
+            .open_in_place(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut data)
+            .map_err(|_| format!("Failed to decrypt {}, wrong {}?", self.path.display(), SECRETS_KEY_ENV))?;
+        serde_json::from_slice(plain).map_err(|e| format!("Invalid decrypted secrets: {}", e))
+    }
+}
+
+fn seal(key: &LessSafeKey, plain: &BTreeMap<String, String>) -> Result<Sealed, String> {
+    let mut nonce = [0u8; NONCE_LEN];
+    SystemRandom::new()
+        .fill(&mut nonce)
+        .map_err(|_| "Failed to generate nonce".to_string())?;
+    let mut data = serde_json::to_vec(plain).map_err(|e| format!("Failed to serialize secrets: {}", e))?;
+    key.seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut data)
+        .map_err(|_| "Failed to encrypt secrets".to_string())?;
+    Ok(Sealed { nonce: STANDARD.encode(nonce), data: STANDARD.encode(data) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secrets() -> BTreeMap<String, Secret> {
+        BTreeMap::from([
+            ("email".to_string(), Secret::new("ops@example.com")),
+            ("password".to_string(), Secret::new("hunter2")),
+        ])
+    }
+
+    #[test]
+    fn test_plaintext_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SecretStore::new(dir.path().join("secrets.json"), None, "Test").unwrap();
+        assert!(store.load().unwrap().is_empty());
+
+        store.save(&secrets()).unwrap();
+        assert_eq!(store.load().unwrap(), secrets());
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(store.path()).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[test]
+    fn test_encrypted_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secrets.json");
+
+        // A plaintext file is readable after a key is configured and sealed on save
+        SecretStore::new(path.clone(), None, "Test").unwrap().save(&secrets()).unwrap();
+        let store = SecretStore::new(path.clone(), Some(&[7; 32]), "Test").unwrap();
+        assert!(store.is_encrypted());
+        assert_eq!(store.load().unwrap(), secrets());
+        store.save(&secrets()).unwrap();
+
+        let raw = std::fs::read_to_string(&path).unwrap();
+        assert!(!raw.contains("hunter2"));
+        assert!(!raw.contains("ops@example.com"));
+        assert_eq!(store.load().unwrap(), secrets());
+
+        // Missing or wrong key: an error, and the file is kept
+        assert!(SecretStore::new(path.clone(), None, "Test").unwrap().load().is_err());
+        assert!(SecretStore::new(path.clone(), Some(&[8; 32]), "Test").unwrap().load().is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), raw);
+
+        assert!(SecretStore::new(path, Some(&[7; 16]), "Test").is_err());
+    }
+}
@@ -0,0 +1,75 @@
+//! Inference device selection.
+
+use std::fmt::{Debug, Display};
+use usls::Device;
+
+/// Auto-detect best available inference device.
+/// macOS → CoreML, Linux → CUDA, others → CPU.
+pub fn auto_device() -> Device {
+    #[cfg(target_os = "macos")]
+    { Device::CoreMl }
+    #[cfg(all(not(target_os = "macos"), target_os = "linux"))]
+    { Device::Cuda(0) }
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    { Device::Cpu(0) }
+}
+
+/// Try building a model with the auto-detected device, fall back to CPU on failure.
+///
+/// The error of the accelerated attempt is logged; the CPU attempt's error is returned.
+pub fn with_device_fallback<M, E, F>(try_build: F) -> Result<M, E>
+where
+    E: Display,
+    F: Fn(Device) -> Result<M, E>,
+{
+    fallback(auto_device(), Device::Cpu(0), try_build)
+}
+
+fn fallback<D, M, E, F>(preferred: D, cpu: D, try_build: F) -> Result<M, E>
+where
+    D: Debug + Copy + PartialEq,
+    E: Display,
+    F: Fn(D) -> Result<M, E>,
+{
+    tracing::info!("[HW] Trying device: {:?}", preferred);
+    match try_build(preferred) {
+        Ok(model) => {
+            tracing::info!("[HW] Model loaded with device: {:?}", preferred);
+            Ok(model)
+        }
+        Err(e) if preferred != cpu => {
+            tracing::warn!("[HW] {:?} failed ({}), falling back to CPU", preferred, e);
+            try_build(cpu)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_fallback_to_cpu() {
+        let tried = RefCell::new(Vec::new());
+        let model = fallback(Device::Cuda(0), Device::Cpu(0), |device| {
+            tried.borrow_mut().push(device);
+            match device {
+                Device::Cpu(_) => Ok("cpu model"),
+                _ => Err("no CUDA provider"),
+            }
+        });
+        assert_eq!(model, Ok("cpu model"));
+        assert_eq!(*tried.borrow(), vec![Device::Cuda(0), Device::Cpu(0)]);
+
+        // CPU failing first is final; there is nothing left to fall back to
+        let tried = RefCell::new(0);
+        let model: Result<(), _> = fallback(Device::Cpu(0), Device::Cpu(0), |_| {
+            *tried.borrow_mut() += 1;
+            Err("bad model file")
+        });
+        assert_eq!(model, Err("bad model file"));
+        assert_eq!(*tried.borrow(), 1);
+    }
+}
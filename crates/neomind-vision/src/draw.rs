@@ -0,0 +1,185 @@
+//! Labelled bounding boxes.
+//!
+//! Boxes are drawn 2px thick with a filled label above them (or inside the
+//! top edge when there is no room above). Fonts stay with each extension;
+//! pass `None` to draw boxes only.
+
+use ab_glyph::{Font, FontRef, PxScale, ScaleFont};
+use image::{GenericImage, Pixel, Rgb, Rgba};
+use imageproc::drawing::{draw_filled_rect_mut, draw_hollow_rect_mut, draw_text_mut};
+use imageproc::rect::Rect;
+
+/// A box in pixel coordinates; may extend past the image edges.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PixelRect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// Colours for one box and its label.
+#[derive(Debug, Clone, Copy)]
+pub struct BoxStyle<P> {
+    pub outline: P,
+    pub label_background: P,
+    pub text: P,
+    /// Boxes narrower or shorter than this after clipping are skipped
+    pub min_size: u32,
+}
+
+impl BoxStyle<Rgb<u8>> {
+    /// Outline and label background in `color`, white text.
+    pub fn solid((r, g, b): (u8, u8, u8)) -> Self {
+        Self {
+            outline: Rgb([r, g, b]),
+            label_background: Rgb([r, g, b]),
+            text: Rgb([255, 255, 255]),
+            min_size: 2,
+        }
+    }
+}
+
+impl BoxStyle<Rgba<u8>> {
+    /// Opaque `color` outline, translucent label background in `background`, white text.
+    pub fn solid_rgba((r, g, b): (u8, u8, u8), background: Rgba<u8>) -> Self {
+        Self {
+            outline: Rgba([r, g, b, 255]),
+            label_background: background,
+            text: Rgba([255, 255, 255, 255]),
+            min_size: 1,
+        }
+    }
+}
+
+impl<P> BoxStyle<P> {
+    pub fn with_min_size(mut self, min_size: u32) -> Self {
+        self.min_size = min_size;
+        self
+    }
+}
+
+/// Clip `rect` to an `img_w` x `img_h` image, keeping one pixel of margin on
+/// the right and bottom. `None` when less than `min_size` remains.
+pub fn clip_rect(rect: PixelRect, img_w: u32, img_h: u32, min_size: u32) -> Option<Rect> {
+    let finite = [rect.x, rect.y, rect.width, rect.height].iter().all(|v| v.is_finite());
+    if !finite || img_w < 2 || img_h < 2 {
+        return None;
+    }
+    let x0 = rect.x.max(0.0);
+    let y0 = rect.y.max(0.0);
+    let x1 = (rect.x + rect.width).min(img_w as f32 - 1.0);
+    let y1 = (rect.y + rect.height).min(img_h as f32 - 1.0);
+    if x1 <= x0 || y1 <= y0 {
+        return None;
+    }
+    let (w, h) = ((x1 - x0) as u32, (y1 - y0) as u32);
+    if w < min_size.max(1) || h < min_size.max(1) {
+        return None;
+    }
+    Some(Rect::at(x0 as i32, y0 as i32).of_size(w, h))
+}
+
+/// Label font size: scales with the image resolution, smaller for narrow boxes.
+/// For 1920x1080: 24px, 1024 wide: 18px, otherwise 14px or 11px.
+pub fn label_font_size(img_w: u32, box_w: u32) -> f32 {
+    if img_w > 1200 {
+        24.0
+    } else if img_w > 800 {
+        18.0
+    } else if box_w > 100 {
+        14.0
+    } else {
+        11.0
+    }
+}
+
+/// Rendered width of `text` using glyph advance widths.
+pub fn text_width(font: &FontRef<'_>, scale: PxScale, text: &str) -> f32 {
+    let scaled_font = font.as_scaled(scale);
+    text.chars().map(|c| scaled_font.h_advance(scaled_font.glyph_id(c))).sum()
+}
+
+/// Draw `rect` with `label` (skipped when empty or `font` is `None`).
+///
+/// Returns false when nothing was drawn because the clipped box was too small.
+pub fn draw_labeled_box<I>(
+    img: &mut I,
+    rect: PixelRect,
+    label: &str,
+    style: &BoxStyle<I::Pixel>,
+    font: Option<&FontRef<'_>>,
+) -> bool
+where
+    I: GenericImage,
+    I::Pixel: Pixel<Subpixel = u8>,
+{
+    let (img_w, img_h) = img.dimensions();
+    let Some(bbox) = clip_rect(rect, img_w, img_h, style.min_size) else {
+        return false;
+    };
+    let (x, y, w, h) = (bbox.left(), bbox.top(), bbox.width(), bbox.height());
+
+    // 2px thick outline
+    draw_hollow_rect_mut(img, bbox, style.outline);
+    if w > 2 && h > 2 {
+        draw_hollow_rect_mut(img, Rect::at(x + 1, y + 1).of_size(w - 2, h - 2), style.outline);
+    }
+
+    let Some(font) = font.filter(|_| !label.is_empty()) else {
+        return true;
+    };
+    let font_size = label_font_size(img_w, w);
+    let scale = PxScale::from(font_size);
+    let label_width = (text_width(font, scale, label).ceil() as u32 + 12).min(img_w.saturating_sub(x as u32));
+    let label_height = font_size as u32 + 8;
+
+    // Position label above the box, or inside if no room above
+    let label_y = if y >= label_height as i32 { y - label_height as i32 } else { y };
+    if label_width > 0 && label_y as u32 + label_height <= img_h {
+        draw_filled_rect_mut(img, Rect::at(x, label_y).of_size(label_width, label_height), style.label_background);
+        draw_text_mut(img, style.text, x + 5, label_y + 3, scale, font, label);
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::RgbImage;
+
+    fn rect(x: f32, y: f32, width: f32, height: f32) -> PixelRect {
+        PixelRect { x, y, width, height }
+    }
+
+    #[test]
+    fn test_clip_rect() {
+        // Inside: unchanged
+        assert_eq!(clip_rect(rect(10.0, 20.0, 30.0, 40.0), 100, 100, 2), Some(Rect::at(10, 20).of_size(30, 40)));
+        // Past the right/bottom edge: trimmed to the last pixel but one
+        assert_eq!(clip_rect(rect(80.0, 90.0, 50.0, 50.0), 100, 100, 2), Some(Rect::at(80, 90).of_size(19, 9)));
+        // Past the left/top edge: trimmed, not shifted
+        assert_eq!(clip_rect(rect(-20.0, -5.0, 50.0, 30.0), 100, 100, 2), Some(Rect::at(0, 0).of_size(30, 25)));
+        // Entirely off-canvas, degenerate or below the minimum size
+        assert_eq!(clip_rect(rect(500.0, 10.0, 50.0, 50.0), 100, 100, 2), None);
+        assert_eq!(clip_rect(rect(-80.0, 10.0, 50.0, 50.0), 100, 100, 2), None);
+        assert_eq!(clip_rect(rect(10.0, 10.0, f32::NAN, 50.0), 100, 100, 2), None);
+        assert_eq!(clip_rect(rect(10.0, 10.0, 3.0, 50.0), 100, 100, 4), None);
+        assert_eq!(clip_rect(rect(0.0, 0.0, 5.0, 5.0), 1, 1, 1), None);
+    }
+
+    #[test]
+    fn test_draw_labeled_box() {
+        let mut img = RgbImage::new(64, 48);
+        let style = BoxStyle::solid((239, 68, 68));
+
+        assert!(draw_labeled_box(&mut img, rect(8.0, 8.0, 20.0, 20.0), "person 90%", &style, None));
+        assert_eq!(*img.get_pixel(8, 8), style.outline);
+        assert_eq!(*img.get_pixel(9, 9), style.outline);
+        assert_eq!(*img.get_pixel(18, 18), Rgb([0, 0, 0]));
+
+        // Off-canvas and 2px boxes must not panic
+        assert!(!draw_labeled_box(&mut img, rect(200.0, 200.0, 20.0, 20.0), "car", &style, None));
+        assert!(draw_labeled_box(&mut img, rect(60.0, 40.0, 2.0, 2.0), "car", &style, None));
+    }
+}
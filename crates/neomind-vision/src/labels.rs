@@ -0,0 +1,70 @@
+//! Label sets and box colour palettes.
+
+/// COCO 80 class labels, indexed by YOLO class id.
+pub const COCO_CLASSES: [&str; 80] = [
+    "person", "bicycle", "car", "motorcycle", "airplane", "bus", "train", "truck", "boat",
+    "traffic light", "fire hydrant", "stop sign", "parking meter", "bench", "bird", "cat",
+    "dog", "horse", "sheep", "cow", "elephant", "bear", "zebra", "giraffe", "backpack",
+    "umbrella", "handbag", "tie", "suitcase", "frisbee", "skis", "snowboard", "sports ball",
+    "kite", "baseball bat", "baseball glove", "skateboard", "surfboard", "tennis racket",
+    "bottle", "wine glass", "cup", "fork", "knife", "spoon", "bowl", "banana", "apple",
+    "sandwich", "orange", "broccoli", "carrot", "hot dog", "pizza", "donut", "cake",
+    "chair", "couch", "potted plant", "bed", "dining table", "toilet", "tv", "laptop",
+    "mouse", "remote", "keyboard", "cell phone", "microwave", "oven", "toaster", "sink",
+    "refrigerator", "book", "clock", "vase", "scissors", "teddy bear", "hair drier", "toothbrush",
+];
+
+/// COCO class name for a class id, `None` outside the 80 classes.
+pub fn coco_class_name(class_id: usize) -> Option<&'static str> {
+    COCO_CLASSES.get(class_id).copied()
+}
+
+/// Small palette cycled by detection index, for boxes without a stable class colour.
+pub const BOX_COLORS: [(u8, u8, u8); 10] = [
+    (239, 68, 68), (34, 197, 94), (59, 130, 246), (234, 179, 8), (6, 182, 212),
+    (139, 92, 246), (236, 72, 153), (249, 115, 22), (132, 204, 22), (20, 184, 166),
+];
+
+/// Standard COCO 80-class color palette (each class gets a unique, consistent color)
+pub const COCO_COLORS: [(u8, u8, u8); 80] = [
+    (38, 70, 83),   (40, 116, 74),  (117, 79, 12), (115, 53, 88), (192, 41, 66),
+    (11, 121, 175), (232, 168, 124),(211, 212, 211),(232, 212, 77),(32, 169, 199),
+    (57, 94, 121),  (237, 139, 0),  (133, 160, 131),(174, 30, 70),(255, 183, 59),
+    (197, 198, 53), (166, 207, 213),(136, 86, 82), (119, 104, 174),(51, 159, 160),
+    (166, 59, 111), (197, 166, 137),(108, 118, 135),(38, 131, 116),(233, 126, 67),
+    (255, 179, 71), (48, 96, 106),  (197, 104, 80),(227, 105, 145),(229, 193, 175),
+    (141, 176, 191),(68, 58, 90),   (138, 142, 72), (248, 162, 162),(115, 145, 144),
+    (72, 46, 64),   (77, 84, 156),  (55, 104, 56), (238, 113, 119),(246, 198, 76),
+    (79, 128, 165), (167, 188, 196),(176, 84, 97), (47, 139, 110),(42, 110, 152),
+    (197, 114, 60), (134, 82, 60),  (73, 131, 103),(101, 146, 85),(219, 138, 80),
+    (118, 156, 145),(164, 182, 204),(129, 173, 129),(113, 107, 91),(145, 54, 140),
+    (161, 166, 98), (230, 144, 133),(199, 144, 106),(48, 109, 140),(195, 100, 118),
+    (93, 155, 112), (160, 137, 173),(109, 59, 105), (212, 128, 93),(231, 172, 112),
+    (78, 131, 137), (227, 133, 117),(189, 187, 169),(78, 78, 103), (139, 78, 107),
+    (123, 140, 63), (161, 158, 131),(104, 112, 115),(109, 122, 65),(150, 131, 104),
+    (180, 181, 195),(112, 98, 114), (157, 130, 166),(129, 129, 106),(67, 102, 130),
+];
+
+/// Get color for a class ID using COCO palette
+pub fn class_color(class_id: u32) -> (u8, u8, u8) {
+    COCO_COLORS[(class_id as usize) % COCO_COLORS.len()]
+}
+
+/// Get color for the `index`-th detection using [`BOX_COLORS`]
+pub fn index_color(index: usize) -> (u8, u8, u8) {
+    BOX_COLORS[index % BOX_COLORS.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_labels_and_colors() {
+        assert_eq!(coco_class_name(0), Some("person"));
+        assert_eq!(coco_class_name(79), Some("toothbrush"));
+        assert_eq!(coco_class_name(80), None);
+        assert_eq!(class_color(80), class_color(0));
+        assert_eq!(index_color(12), BOX_COLORS[2]);
+    }
+}
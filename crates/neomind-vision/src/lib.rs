@@ -0,0 +1,22 @@
+//! Shared support code for the NeoMind vision extensions.
+//!
+//! image-analyzer-v2, yolo-device-inference, yolo-video-v2, ocr-device-inference
+//! and face-recognition all bootstrap ONNX Runtime, look up model files and draw
+//! labelled boxes the same way. The common pieces live here:
+//!
+//! - [`device`] - inference device selection with CPU fallback (`usls` feature)
+//! - [`native`] - native library search paths and `ORT_DYLIB_PATH` discovery
+//! - [`models`] - model file lookup under `NEOMIND_EXTENSION_DIR/models`
+//! - [`labels`] - COCO class names and box colour palettes
+//! - [`draw`] - clipped, labelled bounding boxes on RGB/RGBA images
+
+#[cfg(all(feature = "usls", not(target_arch = "wasm32")))]
+pub mod device;
+pub mod draw;
+pub mod labels;
+pub mod models;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod native;
+
+pub use labels::{class_color, coco_class_name, COCO_CLASSES};
+pub use models::{find_model, find_models_dir};
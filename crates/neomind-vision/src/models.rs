@@ -0,0 +1,87 @@
+//! Model file lookup.
+//!
+//! Models ship in the extension's `models/` directory. The runtime exports
+//! `NEOMIND_EXTENSION_DIR` and starts the process in that directory; running
+//! from `target/` or the extension crate during development is covered by the
+//! working-directory fallbacks.
+
+use std::path::{Path, PathBuf};
+
+/// Candidate `models/` directories in priority order:
+/// `$NEOMIND_EXTENSION_DIR/models`, `./models`, `../models`.
+pub fn model_search_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![];
+    if let Some(ext_dir) = std::env::var_os("NEOMIND_EXTENSION_DIR") {
+        dirs.push(PathBuf::from(ext_dir).join("models"));
+    }
+    if let Ok(cwd) = std::env::current_dir() {
+        dirs.push(cwd.join("models"));
+        if let Some(parent) = cwd.parent() {
+            dirs.push(parent.join("models"));
+        }
+    }
+    dirs
+}
+
+/// Find `filename` in the model search directories.
+pub fn find_model(filename: &str) -> Result<PathBuf, String> {
+    find_in(&model_search_dirs(), filename)
+}
+
+/// The first model search directory that exists (for models loaded as a set).
+pub fn find_models_dir() -> Result<PathBuf, String> {
+    let dirs = model_search_dirs();
+    dirs.iter()
+        .find(|d| d.is_dir())
+        .cloned()
+        .ok_or_else(|| format!("Models directory not found (searched {})", display_list(&dirs)))
+}
+
+fn find_in(dirs: &[PathBuf], filename: &str) -> Result<PathBuf, String> {
+    match dirs.iter().map(|d| d.join(filename)).find(|p| p.is_file()) {
+        Some(path) => {
+            tracing::info!("[Models] Found {} at {}", filename, path.display());
+            Ok(path)
+        }
+        None => Err(format!(
+            "Model file '{}' not found (searched {})",
+            filename,
+            display_list(dirs)
+        )),
+    }
+}
+
+fn display_list(dirs: &[PathBuf]) -> String {
+    if dirs.is_empty() {
+        return "no directories".to_string();
+    }
+    dirs.iter().map(|d| d.display().to_string()).collect::<Vec<_>>().join(", ")
+}
+
+/// Size of a model file in bytes, 0 if it cannot be read.
+pub fn model_size(path: &Path) -> usize {
+    std::fs::metadata(path).map(|m| m.len() as usize).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_in_priority_order() {
+        let first = tempfile::tempdir().unwrap();
+        let second = tempfile::tempdir().unwrap();
+        std::fs::write(second.path().join("yolo11n.onnx"), b"second").unwrap();
+        let dirs = vec![first.path().to_path_buf(), second.path().to_path_buf()];
+
+        assert_eq!(find_in(&dirs, "yolo11n.onnx").unwrap(), second.path().join("yolo11n.onnx"));
+
+        std::fs::write(first.path().join("yolo11n.onnx"), b"first").unwrap();
+        assert_eq!(find_in(&dirs, "yolo11n.onnx").unwrap(), first.path().join("yolo11n.onnx"));
+        assert_eq!(model_size(&first.path().join("yolo11n.onnx")), 5);
+
+        let err = find_in(&dirs, "det_10g.onnx").unwrap_err();
+        assert!(err.contains("'det_10g.onnx' not found"), "{}", err);
+        assert!(err.contains(&second.path().display().to_string()), "{}", err);
+    }
+}
@@ -0,0 +1,287 @@
+//! Native library discovery for ONNX Runtime (`ort` load-dynamic).
+//!
+//! Packaged extensions bundle `libonnxruntime` under `lib/` or
+//! `binaries/<platform>/`. Before the first session is created the loader
+//! search path and `ORT_DYLIB_PATH` must point there.
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::Once;
+
+/// System directories searched after the extension's own libraries.
+const SYSTEM_LIB_DIRS: [&str; 2] = ["/opt/homebrew/lib", "/usr/local/lib"];
+
+/// Dynamic loader search path variable for this platform.
+pub fn library_path_var() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "DYLD_LIBRARY_PATH"
+    } else if cfg!(target_os = "windows") {
+        "PATH"
+    } else {
+        "LD_LIBRARY_PATH"
+    }
+}
+
+/// File name `ort` loads when `ORT_DYLIB_PATH` points at a directory entry.
+pub fn ort_file_name() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "libonnxruntime.dylib"
+    } else if cfg!(target_os = "windows") {
+        "onnxruntime.dll"
+    } else {
+        "libonnxruntime.so"
+    }
+}
+
+/// Unversioned name for a versioned shared library, `None` if `name` has no version.
+///
+/// Handles both the macOS style (`libonnxruntime.1.22.0.dylib`) and the Linux
+/// soname style (`libonnxruntime.so.1.22.0`).
+pub fn unversioned_name(name: &str) -> Option<String> {
+    if let Some(pos) = name.find(".so.") {
+        let base = &name[..pos];
+        return (!base.is_empty()).then(|| format!("{}.so", base));
+    }
+    let (base, ext) = if let Some(base) = name.strip_suffix(".dylib") {
+        (base, "dylib")
+    } else if let Some(base) = name.strip_suffix(".so") {
+        (base, "so")
+    } else {
+        return None;
+    };
+    let (stem, _version) = base.split_once('.')?;
+    (!stem.is_empty()).then(|| format!("{}.{}", stem, ext))
+}
+
+/// Create unversioned symlinks next to versioned libraries in `dir`,
+/// e.g. `libonnxruntime.so -> libonnxruntime.so.1.22.0`.
+fn link_unversioned(dir: &Path) {
+    let Ok(files) = std::fs::read_dir(dir) else { return };
+    for file in files.flatten() {
+        let name = file.file_name().to_string_lossy().to_string();
+        let Some(unversioned) = unversioned_name(&name) else { continue };
+        let link_path = dir.join(&unversioned);
+        if link_path.exists() {
+            continue;
+        }
+        #[cfg(unix)]
+        {
+            match std::os::unix::fs::symlink(&name, &link_path) {
+                Ok(()) => tracing::info!("[NativeLibs] Created symlink: {} -> {}", unversioned, name),
+                Err(e) => tracing::warn!("[NativeLibs] Cannot link {} -> {}: {}", unversioned, name, e),
+            }
+        }
+    }
+}
+
+/// Directories bundled with the extension: `lib/` and every `binaries/<platform>/`.
+fn bundled_lib_dirs(ext_dir: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![];
+    let lib_dir = ext_dir.join("lib");
+    if lib_dir.is_dir() {
+        dirs.push(lib_dir);
+    }
+    if let Ok(entries) = std::fs::read_dir(ext_dir.join("binaries")) {
+        let mut platforms: Vec<PathBuf> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.is_dir())
+            .collect();
+        platforms.sort();
+        dirs.extend(platforms);
+    }
+    dirs
+}
+
+/// Library search directories in priority order: the extension's bundled
+/// libraries, `./lib`, the inherited loader path, then common system paths.
+pub fn native_lib_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![];
+    if let Some(ext_dir) = std::env::var_os("NEOMIND_EXTENSION_DIR") {
+        dirs.extend(bundled_lib_dirs(Path::new(&ext_dir)));
+    }
+    // The extension runner starts the process in the extension directory
+    if let Ok(cwd) = std::env::current_dir() {
+        let lib_dir = cwd.join("lib");
+        if lib_dir.is_dir() {
+            dirs.push(lib_dir);
+        }
+    }
+    if let Some(existing) = std::env::var_os(library_path_var()) {
+        dirs.extend(std::env::split_paths(&existing).filter(|p| !p.as_os_str().is_empty()));
+    }
+    dirs.extend(SYSTEM_LIB_DIRS.iter().map(PathBuf::from).filter(|p| p.is_dir()));
+
+    let mut seen = std::collections::HashSet::new();
+    dirs.retain(|d| seen.insert(d.clone()));
+    dirs
+}
+
+/// First `libonnxruntime` found in `dirs`.
+pub fn find_ort_library(dirs: &[PathBuf]) -> Option<PathBuf> {
+    dirs.iter().map(|d| d.join(ort_file_name())).find(|p| p.exists())
+}
+
+/// The ONNX Runtime library `ort` will load, `None` when it is missing.
+///
+/// `ORT_DYLIB_PATH` when set, otherwise the first one in [`native_lib_dirs`].
+pub fn ort_library() -> Option<PathBuf> {
+    match std::env::var_os("ORT_DYLIB_PATH") {
+        Some(path) => Some(PathBuf::from(path)).filter(|p| p.exists()),
+        None => find_ort_library(&native_lib_dirs()),
+    }
+}
+
+/// Set up native library search paths before ONNX Runtime is loaded.
+///
+/// Runs once per process; later calls are no-ops. An `ORT_DYLIB_PATH` set by
+/// the user is left untouched.
+pub fn setup_native_lib_paths() {
+    static SETUP: Once = Once::new();
+    SETUP.call_once(|| {
+        if let Some(ext_dir) = std::env::var_os("NEOMIND_EXTENSION_DIR") {
+            for dir in bundled_lib_dirs(Path::new(&ext_dir)) {
+                link_unversioned(&dir);
+            }
+        }
+
+        let dirs = native_lib_dirs();
+        let lib_env = library_path_var();
+        match std::env::join_paths(&dirs) {
+            Ok(joined) if !dirs.is_empty() => {
+                tracing::info!("[NativeLibs] Setting {} = {}", lib_env, joined.to_string_lossy());
+                std::env::set_var(lib_env, &joined);
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!("[NativeLibs] Cannot set {}: {}", lib_env, e),
+        }
+
+        // DYLD_LIBRARY_PATH set at runtime may not affect dlopen on macOS, so
+        // point `ort` at the exact file as well.
+        if std::env::var_os("ORT_DYLIB_PATH").is_none() {
+            match find_ort_library(&dirs) {
+                Some(ort_path) => {
+                    tracing::info!("[NativeLibs] Setting ORT_DYLIB_PATH = {}", ort_path.display());
+                    std::env::set_var("ORT_DYLIB_PATH", OsString::from(ort_path));
+                }
+                None => tracing::warn!("[NativeLibs] {} not found in {} search dirs", ort_file_name(), dirs.len()),
+            }
+        }
+
+        #[cfg(target_os = "macos")]
+        fix_macos_dylib_install_names(&dirs);
+    });
+}
+
+/// Fix macOS dylib install names so bundled libraries can find each other via @loader_path.
+///
+/// Homebrew-built dylibs reference each other by absolute paths and SIP often
+/// keeps DYLD_LIBRARY_PATH from applying. Runs only once per installation
+/// (marker file prevents re-runs).
+#[cfg(target_os = "macos")]
+pub fn fix_macos_dylib_install_names(search_dirs: &[PathBuf]) {
+    use std::process::Command;
+
+    // Find the platform binaries directory
+    let Some(dir) = search_dirs.iter().find(|d| {
+        d.to_string_lossy().contains("darwin_") && d.join("extension.dylib").exists()
+    }) else {
+        return;
+    };
+
+    // Marker file to avoid re-running on every startup
+    let marker = dir.join(".dylib_paths_fixed");
+    if marker.exists() {
+        return;
+    }
+
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    let dylib_names: Vec<String> = entries
+        .flatten()
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "dylib"))
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+
+    if dylib_names.is_empty() {
+        return;
+    }
+
+    tracing::info!("[NativeLibs] Fixing install names for {} dylib(s) in {}", dylib_names.len(), dir.display());
+
+    for name in &dylib_names {
+        let path = dir.join(name);
+        let Ok(output) = Command::new("otool").arg("-L").arg(&path).output() else { continue };
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        let mut args: Vec<String> = vec!["-id".to_string(), format!("@loader_path/{}", name)];
+
+        for line in stdout.lines().skip(1) {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with("@loader_path/") {
+                continue;
+            }
+            let ref_path = trimmed.split_whitespace().next().unwrap_or("");
+            if ref_path.is_empty() || ref_path.starts_with("/usr/lib/") || ref_path.starts_with("/System/") {
+                continue;
+            }
+            let ref_name = Path::new(ref_path)
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            if dylib_names.contains(&ref_name) {
+                args.extend([
+                    "-change".to_string(),
+                    ref_path.to_string(),
+                    format!("@loader_path/{}", ref_name),
+                ]);
+            }
+        }
+
+        args.push(path.to_string_lossy().to_string());
+        let _ = Command::new("install_name_tool").args(&args).output();
+    }
+
+    let _ = std::fs::write(&marker, "");
+    tracing::info!("[NativeLibs] Install names fixed successfully");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unversioned_name() {
+        assert_eq!(unversioned_name("libonnxruntime.1.22.0.dylib").as_deref(), Some("libonnxruntime.dylib"));
+        assert_eq!(unversioned_name("libonnxruntime.so.1.22.0").as_deref(), Some("libonnxruntime.so"));
+        assert_eq!(unversioned_name("libavcodec.61.so").as_deref(), Some("libavcodec.so"));
+        assert_eq!(unversioned_name("libonnxruntime.dylib"), None);
+        assert_eq!(unversioned_name("libonnxruntime_providers_shared.so"), None);
+        assert_eq!(unversioned_name("onnxruntime.dll"), None);
+        assert_eq!(unversioned_name(".so.1"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_link_unversioned_and_find_ort() {
+        let dir = tempfile::tempdir().unwrap();
+        let platform = dir.path().join("binaries").join("linux_amd64");
+        std::fs::create_dir_all(&platform).unwrap();
+        let versioned = if cfg!(target_os = "macos") {
+            "libonnxruntime.1.22.0.dylib"
+        } else {
+            "libonnxruntime.so.1.22.0"
+        };
+        std::fs::write(platform.join(versioned), b"").unwrap();
+
+        let dirs = bundled_lib_dirs(dir.path());
+        assert_eq!(dirs, vec![platform.clone()]);
+        assert_eq!(find_ort_library(&dirs), None);
+
+        link_unversioned(&platform);
+        assert_eq!(find_ort_library(&dirs), Some(platform.join(ort_file_name())));
+        // Already linked: a second pass leaves the link alone
+        link_unversioned(&platform);
+        assert_eq!(std::fs::read_link(platform.join(ort_file_name())).unwrap(), PathBuf::from(versioned));
+    }
+}
@@ -4,7 +4,6 @@ use base64::Engine;
 use neomind_extension_sdk::{ExtensionError, Result};
 use serde_json::json;
 
-use crate::types::{BindingStats, DeviceBinding};
 use crate::FaceRecognition;
 
 impl FaceRecognition {
@@ -18,25 +17,12 @@ impl FaceRecognition {
         match command {
             // 1. bind_device
             "bind_device" => {
-                let device_id = args
+                // Rebinding would silently reset the device's statistics
+                if let Some(device_id) = args
                     .get("device_id")
                     .and_then(|v| v.as_str())
-                    .ok_or_else(|| {
-                        ExtensionError::InvalidArguments(
-                            "Missing or invalid device_id".to_string(),
-                        )
-                    })?;
-
-                if device_id.is_empty() {
-                    return Ok(json!({
-                        "success": false,
-                        "error": "device_id cannot be empty",
-                        "error_code": "INVALID_ARGUMENTS"
-                    }));
-                }
-
-                // Check if already bound
-                if self.bindings.read().contains_key(device_id) {
+                    .filter(|id| self.bindings.contains(id))
+                {
                     return Ok(json!({
                         "success": false,
                         "error": format!("Device {} is already bound", device_id),
@@ -44,179 +30,45 @@ impl FaceRecognition {
                     }));
                 }
 
-                let metric_name = args
-                    .get("metric_name")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("image")
-                    .to_string();
-
-                let binding = DeviceBinding {
-                    device_id: device_id.to_string(),
-                    metric_name,
-                    active: true,
-                    created_at: chrono::Utc::now().timestamp(),
-                };
-
-                self.bindings
-                    .write()
-                    .insert(device_id.to_string(), binding.clone());
-                self.binding_stats.write().insert(
-                    device_id.to_string(),
-                    BindingStats {
-                        total_inferences: 0,
-                        total_recognized: 0,
-                        total_unknown: 0,
-                        last_image: None,
-                        last_faces: None,
-                        last_error: None,
-                    },
-                );
-
+                let response = self.bindings.execute_command(command, args)?;
                 self.persist_config();
-
-                tracing::info!(
-                    "[FaceRecognition] Device bound: {} (metric: {})",
-                    device_id,
-                    binding.metric_name
-                );
-
-                Ok(json!({
-                    "success": true,
-                    "device_id": device_id,
-                    "message": format!("Device {} bound successfully", device_id)
-                }))
+                Ok(response)
             }
 
             // 2. unbind_device
             "unbind_device" => {
-                let device_id = args
-                    .get("device_id")
-                    .and_then(|v| v.as_str())
-                    .ok_or_else(|| {
-                        ExtensionError::InvalidArguments(
-                            "Missing or invalid device_id".to_string(),
-                        )
-                    })?;
-
-                if device_id.is_empty() {
-                    return Ok(json!({
-                        "success": false,
-                        "error": "device_id cannot be empty",
-                        "error_code": "INVALID_ARGUMENTS"
-                    }));
-                }
-
-                self.bindings.write().remove(device_id);
-                self.binding_stats.write().remove(device_id);
-
+                let response = self.bindings.execute_command(command, args)?;
                 self.persist_config();
-
-                tracing::info!("[FaceRecognition] Device unbound: {}", device_id);
-
-                Ok(json!({
-                    "success": true,
-                    "device_id": device_id,
-                    "message": format!("Device {} unbound successfully", device_id)
-                }))
+                Ok(response)
             }
 
             // 3. toggle_binding
             "toggle_binding" => {
-                let device_id = args
-                    .get("device_id")
-                    .and_then(|v| v.as_str())
-                    .ok_or_else(|| {
-                        ExtensionError::InvalidArguments(
-                            "Missing or invalid device_id".to_string(),
-                        )
-                    })?;
-
-                let active = args
-                    .get("active")
-                    .and_then(|v| v.as_bool())
-                    .ok_or_else(|| {
-                        ExtensionError::InvalidArguments(
-                            "Missing or invalid active parameter".to_string(),
-                        )
-                    })?;
-
-                if device_id.is_empty() {
-                    return Ok(json!({
-                        "success": false,
-                        "error": "device_id cannot be empty",
-                        "error_code": "INVALID_ARGUMENTS"
-                    }));
+                if args.get("active").and_then(|v| v.as_bool()).is_none() {
+                    return Err(ExtensionError::InvalidArguments(
+                        "Missing or invalid active parameter".to_string(),
+                    ));
                 }
 
-                let mut bindings = self.bindings.write();
-                if let Some(binding) = bindings.get_mut(device_id) {
-                    binding.active = active;
-                    drop(bindings); // Release write lock before persist
-
-                    self.persist_config();
-
-                    tracing::info!(
-                        "[FaceRecognition] Device {} binding toggled to active={}",
-                        device_id,
-                        active
-                    );
-
-                    Ok(json!({
-                        "success": true,
-                        "device_id": device_id,
-                        "active": active
-                    }))
-                } else {
-                    Ok(json!({
-                        "success": false,
-                        "error": format!("Device {} not bound", device_id),
-                        "error_code": "DEVICE_NOT_FOUND"
-                    }))
-                }
+                let response = match self.bindings.execute_command(command, args) {
+                    Err(ExtensionError::NotFound(error)) => {
+                        return Ok(json!({
+                            "success": false,
+                            "error": error,
+                            "error_code": "DEVICE_NOT_FOUND"
+                        }))
+                    }
+                    response => response?,
+                };
+                self.persist_config();
+                Ok(response)
             }
 
             // 4. get_bindings
-            "get_bindings" => {
-                let bindings: Vec<serde_json::Value> = self
-                    .bindings
-                    .read()
-                    .iter()
-                    .map(|(id, b)| {
-                        let stats = self
-                            .binding_stats
-                            .read()
-                            .get(id)
-                            .cloned()
-                            .unwrap_or(BindingStats {
-                                total_inferences: 0,
-                                total_recognized: 0,
-                                total_unknown: 0,
-                                last_image: None,
-                                last_faces: None,
-                                last_error: None,
-                            });
-                        json!({
-                            "device_id": b.device_id,
-                            "metric_name": b.metric_name,
-                            "active": b.active,
-                            "created_at": b.created_at,
-                            "stats": {
-                                "total_inferences": stats.total_inferences,
-                                "total_recognized": stats.total_recognized,
-                                "total_unknown": stats.total_unknown,
-                            },
-                            "last_image": stats.last_image,
-                            "last_faces": stats.last_faces,
-                            "last_error": stats.last_error,
-                        })
-                    })
-                    .collect();
-
-                Ok(json!({
-                    "success": true,
-                    "bindings": bindings
-                }))
-            }
+            "get_bindings" => Ok(json!({
+                "success": true,
+                "bindings": self.bindings_json()
+            })),
 
             // 5. register_face
             "register_face" => {
@@ -509,11 +361,13 @@ impl FaceRecognition {
 
             // 8. get_status
             "get_status" => Ok(self.get_status()),
+            "health" => Ok(self.health().to_json()),
 
             // 9. configure
             "configure" => {
                 // Accept config either as a "config" parameter or directly in args
                 let config_value = args.get("config").unwrap_or(args);
+                neomind_logging::apply_config("FaceRecognition", config_value);
 
                 if let Some(confidence) =
                     config_value.get("confidence_threshold").and_then(|v| v.as_f64())
@@ -575,6 +429,7 @@ impl FaceRecognition {
     ) -> Result<serde_json::Value> {
         match command {
             "get_status" => Ok(self.get_status()),
+            "health" => Ok(self.health().to_json()),
             "get_config" => {
                 let config = self.config.read();
                 Ok(json!({
@@ -591,64 +446,11 @@ impl FaceRecognition {
                     "count": faces.len()
                 }))
             }
-            "get_bindings" => {
-                let bindings: Vec<serde_json::Value> = self
-                    .bindings
-                    .read()
-                    .iter()
-                    .map(|(_, b)| json!(b))
-                    .collect();
-                Ok(json!({
-                    "success": true,
-                    "bindings": bindings
-                }))
-            }
-            "bind_device" => {
-                let device_id = args
-                    .get("device_id")
-                    .and_then(|v| v.as_str())
-                    .ok_or_else(|| {
-                        ExtensionError::InvalidArguments("Missing device_id".to_string())
-                    })?;
-                let metric_name = args
-                    .get("metric_name")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("image")
-                    .to_string();
-
-                let binding = DeviceBinding {
-                    device_id: device_id.to_string(),
-                    metric_name,
-                    active: true,
-                    created_at: chrono::Utc::now().timestamp(),
-                };
-                self.bindings
-                    .write()
-                    .insert(device_id.to_string(), binding);
-                self.binding_stats.write().insert(
-                    device_id.to_string(),
-                    BindingStats {
-                        total_inferences: 0,
-                        total_recognized: 0,
-                        total_unknown: 0,
-                        last_image: None,
-                        last_faces: None,
-                        last_error: None,
-                    },
-                );
-                Ok(json!({"success": true, "device_id": device_id}))
-            }
-            "unbind_device" => {
-                let device_id = args
-                    .get("device_id")
-                    .and_then(|v| v.as_str())
-                    .ok_or_else(|| {
-                        ExtensionError::InvalidArguments("Missing device_id".to_string())
-                    })?;
-                self.bindings.write().remove(device_id);
-                self.binding_stats.write().remove(device_id);
-                Ok(json!({"success": true, "device_id": device_id}))
-            }
+            "get_bindings" => Ok(json!({
+                "success": true,
+                "bindings": self.bindings_json()
+            })),
+            "bind_device" | "unbind_device" => self.bindings.execute_command(command, args),
             "toggle_binding" => {
                 let device_id = args
                     .get("device_id")
@@ -656,15 +458,16 @@ impl FaceRecognition {
                     .ok_or_else(|| {
                         ExtensionError::InvalidArguments("Missing device_id".to_string())
                     })?;
+                // Without `active` the binding is flipped
                 let active = args.get("active").and_then(|v| v.as_bool());
-                if let Some(binding) = self.bindings.write().get_mut(device_id) {
-                    binding.active = active.unwrap_or(!binding.active);
-                    Ok(json!({"success": true, "device_id": device_id, "active": binding.active}))
-                } else {
-                    Err(ExtensionError::NotFound(format!(
+                match self.bindings.set_active(device_id, active) {
+                    Some(active) => {
+                        Ok(json!({"success": true, "device_id": device_id, "active": active}))
+                    }
+                    None => Err(ExtensionError::NotFound(format!(
                         "Device {} not bound",
                         device_id
-                    )))
+                    ))),
                 }
             }
             "configure" => Ok(json!({"success": true, "message": "Configuration applied (WASM)"})),
@@ -677,4 +480,28 @@ impl FaceRecognition {
             _ => Err(ExtensionError::CommandNotFound(command.to_string())),
         }
     }
+
+    /// Bindings in the `get_bindings` format of the face recognition frontend.
+    pub(crate) fn bindings_json(&self) -> Vec<serde_json::Value> {
+        self.bindings
+            .statuses()
+            .into_iter()
+            .map(|status| {
+                json!({
+                    "device_id": status.binding.device_id,
+                    "metric_name": status.binding.image_metric,
+                    "active": status.binding.active,
+                    "created_at": status.binding.settings.created_at,
+                    "stats": {
+                        "total_inferences": status.total_inferences,
+                        "total_recognized": status.state.total_recognized,
+                        "total_unknown": status.state.total_unknown,
+                    },
+                    "last_image": status.last_image,
+                    "last_faces": status.state.last_faces,
+                    "last_error": status.last_error,
+                })
+            })
+            .collect()
+    }
 }
@@ -3,10 +3,9 @@
 //! Provides CRUD operations for face entries, cosine similarity matching,
 //! and JSON-based persistence with secure file permissions.
 
+use neomind_binding::config::{SchemaError, Versioned};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::fs::OpenOptions;
-use std::io::{BufReader, BufWriter};
 use std::path::Path;
 use thiserror::Error;
 
@@ -34,6 +33,9 @@ pub enum DatabaseError {
 
     #[error("serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
+
+    #[error("unreadable database file: {0}")]
+    Schema(#[from] SchemaError),
 }
 
 pub type DatabaseResult<T> = Result<T, DatabaseError>;
@@ -88,6 +90,10 @@ struct FaceDatabaseFile {
     faces: Vec<FaceEntry>,
 }
 
+impl Versioned for FaceDatabaseFile {
+    const SCHEMA_VERSION: u32 = 1;
+}
+
 // ============================================================================
 // Face Database
 // ============================================================================
@@ -228,37 +234,27 @@ impl FaceDatabase {
     }
 
     /// Save the database to a JSON file with restricted permissions (0600 on Unix).
+    ///
+    /// The file is replaced atomically, so a crash mid-write keeps the previous one.
     pub fn save_to_file(&self, path: &Path) -> DatabaseResult<()> {
-        let file = OpenOptions::new()
-            .write(true)
-            .create(true)
-            .truncate(true)
-            .open(path)?;
-
-        // Set file permissions to 0600 (owner read/write only) on Unix
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            let perms = std::fs::Permissions::from_mode(0o600);
-            file.set_permissions(perms)?;
-        }
-
-        let writer = BufWriter::new(file);
         let data = FaceDatabaseFile {
             threshold: self.threshold,
             max_faces: self.max_faces,
             faces: self.faces_by_id.values().cloned().collect(),
         };
-        serde_json::to_writer_pretty(writer, &data)?;
+        let json = neomind_binding::config::encode(&data)?;
+        neomind_binding::config::write_atomic_private(path, json.as_bytes())?;
 
         Ok(())
     }
 
     /// Load a database from a JSON file, restoring all face entries.
+    ///
+    /// Files from older versions are migrated; files that can't be decoded
+    /// return [`DatabaseError::Schema`].
     pub fn load_from_file(path: &Path) -> DatabaseResult<Self> {
-        let file = OpenOptions::new().read(true).open(path)?;
-        let reader = BufReader::new(file);
-        let data: FaceDatabaseFile = serde_json::from_reader(reader)?;
+        let content = std::fs::read_to_string(path)?;
+        let data: FaceDatabaseFile = neomind_binding::config::decode(&content)?;
 
         let mut faces_by_id = HashMap::new();
         let mut id_by_name = HashMap::new();
@@ -513,6 +509,25 @@ mod tests {
         assert!(matches!(result.unwrap_err(), DatabaseError::Io(_)));
     }
 
+    #[test]
+    fn load_unversioned_file_and_reject_garbage() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("faces.json");
+
+        // Written before schema_version existed
+        fs::write(&path, r#"{"threshold": 0.5, "max_faces": 3, "faces": []}"#).unwrap();
+        let loaded = FaceDatabase::load_from_file(&path).unwrap();
+        assert_eq!(loaded.threshold, 0.5);
+        assert_eq!(loaded.max_faces, 3);
+
+        loaded.save_to_file(&path).unwrap();
+        assert!(fs::read_to_string(&path).unwrap().contains("\"schema_version\": 1"));
+
+        fs::write(&path, "{\"threshold\": ").unwrap();
+        let result = FaceDatabase::load_from_file(&path);
+        assert!(matches!(result.unwrap_err(), DatabaseError::Schema(_)));
+    }
+
     // ------------------------------------------------------------------
     // Delete allows re-register with same name
     // ------------------------------------------------------------------
@@ -64,9 +64,22 @@ pub fn draw_recognition_results(
     results: &[FaceResult],
 ) -> ImageResult<String> {
     let mut img = image::load_from_memory(image_data)?.to_rgb8();
+    draw_results(&mut img, results, get_font().as_ref().ok());
 
-    let font = get_font();
+    // Encode to JPEG.
+    let jpeg_bytes = encode_jpeg(&img, JPEG_QUALITY);
+    Ok(base64::Engine::encode(
+        &base64::engine::general_purpose::STANDARD,
+        &jpeg_bytes,
+    ))
+}
 
+// ---------------------------------------------------------------------------
+// Internal helpers
+// ---------------------------------------------------------------------------
+
+/// Draw bounding boxes and name labels; labels are skipped without a font.
+fn draw_results(img: &mut RgbImage, results: &[FaceResult], font: Option<&ab_glyph::FontRef<'_>>) {
     for face in results {
         let color = if face.name.is_some() {
             RECOGNIZED_COLOR
@@ -90,10 +103,10 @@ pub fn draw_recognition_results(
         }
 
         // Draw bounding box (2 px line width).
-        draw_hollow_rect_mut(&mut img, Rect::at(x, y).of_size(w, h), color);
+        draw_hollow_rect_mut(img, Rect::at(x, y).of_size(w, h), color);
         if w > 2 && h > 2 {
             draw_hollow_rect_mut(
-                &mut img,
+                img,
                 Rect::at(x + 1, y + 1)
                     .of_size(w.saturating_sub(2), h.saturating_sub(2)),
                 color,
@@ -108,23 +121,12 @@ pub fn draw_recognition_results(
         };
 
         // Draw label background + text.
-        if let Ok(font) = font.as_ref() {
-            draw_label(&mut img, x, y, w, &label_text, color, font);
+        if let Some(font) = font {
+            draw_label(img, x, y, w, &label_text, color, font);
         }
     }
-
-    // Encode to JPEG.
-    let jpeg_bytes = encode_jpeg(&img, JPEG_QUALITY);
-    Ok(base64::Engine::encode(
-        &base64::engine::general_purpose::STANDARD,
-        &jpeg_bytes,
-    ))
 }
 
-// ---------------------------------------------------------------------------
-// Internal helpers
-// ---------------------------------------------------------------------------
-
 /// Draw a label (background rect + text) above or inside the bounding box.
 fn draw_label(
     img: &mut RgbImage,
@@ -347,4 +349,40 @@ mod tests {
             pixel,
         );
     }
+
+    fn face(x: f64, y: f64, size: f64, name: Option<&str>, similarity: Option<f64>) -> FaceResult {
+        FaceResult {
+            face_box: FaceBox {
+                x,
+                y,
+                width: size,
+                height: size,
+                confidence: 0.9,
+                landmarks: None,
+            },
+            name: name.map(str::to_string),
+            similarity,
+            face_id: None,
+        }
+    }
+
+    #[test]
+    fn test_annotation_golden() {
+        let mut img = RgbImage::from_fn(240, 160, |x, y| {
+            Rgb([60 + (x / 4) as u8, 70, 90 + (y / 4) as u8])
+        });
+        let results = vec![
+            face(20.0, 40.0, 70.0, Some("Alice"), Some(0.93)),
+            face(110.0, 50.0, 50.0, Some("Bob"), None),
+            // At the top edge: the label is drawn inside the box
+            face(170.0, 0.0, 60.0, Some("Dave"), Some(0.64)),
+            // Partly off-canvas: the box is clamped
+            face(200.0, 120.0, 80.0, Some("Carol"), Some(0.71)),
+        ];
+
+        // ASCII names only: the golden font has no CJK glyphs for the
+        // unknown-face label, whose color is checked by the test above
+        draw_results(&mut img, &results, Some(&neomind_golden::font()));
+        neomind_golden::assert_golden!("face_annotations", img);
+    }
 }
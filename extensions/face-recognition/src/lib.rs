@@ -28,11 +28,13 @@ pub mod types;
 
 // Re-export public types for backward compatibility
 pub use types::{
-    BindingStats, DeviceBinding, FaceBox, FaceRecConfig, FaceResult, Landmark,
+    BindingStatus, DeviceBinding, FaceBindingSettings, FaceBindingStats, FaceBox, FaceRecConfig,
+    FaceResult, Landmark,
 };
 
 use async_trait::async_trait;
-use neomind_extension_sdk::capabilities::CapabilityContext;
+use neomind_binding::image::jpeg_data_url;
+use neomind_binding::BindingRegistry;
 use neomind_extension_sdk::prelude::*;
 use neomind_extension_sdk::{
     Extension, ExtensionCommand, ExtensionMetadata, ExtensionMetricValue,
@@ -44,7 +46,7 @@ use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
-use crate::database::FaceDatabase;
+use crate::database::{DatabaseError, FaceDatabase};
 use crate::detector::{FaceDetect, ScrfdDetector};
 use crate::recognizer::{ArcFaceRecognizer, FaceExtract};
 
@@ -57,10 +59,8 @@ pub struct FaceRecognition {
     pub(crate) detector: Mutex<Box<dyn FaceDetect + Send>>,
     /// ArcFace face feature extractor (lazy loading)
     pub(crate) recognizer: Mutex<Box<dyn FaceExtract + Send>>,
-    /// Device bindings: device_id -> binding
-    pub(crate) bindings: Arc<RwLock<HashMap<String, DeviceBinding>>>,
-    /// Binding statistics: device_id -> stats
-    pub(crate) binding_stats: Arc<RwLock<HashMap<String, BindingStats>>>,
+    /// Device bindings and their statistics
+    pub(crate) bindings: BindingRegistry<FaceBindingSettings, FaceBindingStats>,
     /// Face database
     pub(crate) face_db: Arc<RwLock<FaceDatabase>>,
     /// Extension configuration
@@ -69,20 +69,23 @@ pub struct FaceRecognition {
     pub(crate) total_inferences: Arc<AtomicU64>,
     pub(crate) total_recognized: Arc<AtomicU64>,
     pub(crate) total_unknown: Arc<AtomicU64>,
+    /// Pipeline successes and failures for the health report
+    pub(crate) activity: neomind_health::Activity,
 }
 
 impl FaceRecognition {
     pub fn new() -> Self {
+        neomind_logging::init();
         Self {
             detector: Mutex::new(Box::new(ScrfdDetector::new())),
             recognizer: Mutex::new(Box::new(ArcFaceRecognizer::new())),
-            bindings: Arc::new(RwLock::new(HashMap::new())),
-            binding_stats: Arc::new(RwLock::new(HashMap::new())),
+            bindings: BindingRegistry::new("FaceRecognition"),
             face_db: Arc::new(RwLock::new(FaceDatabase::new(0.45, 10))),
             config: Arc::new(RwLock::new(FaceRecConfig::default())),
             total_inferences: Arc::new(AtomicU64::new(0)),
             total_recognized: Arc::new(AtomicU64::new(0)),
             total_unknown: Arc::new(AtomicU64::new(0)),
+            activity: neomind_health::Activity::new(),
         }
     }
 
@@ -97,44 +100,16 @@ impl FaceRecognition {
         Self {
             detector: Mutex::new(detector),
             recognizer: Mutex::new(recognizer),
-            bindings: Arc::new(RwLock::new(HashMap::new())),
-            binding_stats: Arc::new(RwLock::new(HashMap::new())),
+            bindings: BindingRegistry::new("FaceRecognition"),
             face_db: Arc::new(RwLock::new(FaceDatabase::new(0.45, 10))),
             config: Arc::new(RwLock::new(FaceRecConfig::default())),
             total_inferences: Arc::new(AtomicU64::new(0)),
             total_recognized: Arc::new(AtomicU64::new(0)),
             total_unknown: Arc::new(AtomicU64::new(0)),
+            activity: neomind_health::Activity::new(),
         }
     }
 
-    /// Invoke a host capability synchronously via the capability context.
-    pub(crate) fn invoke_capability_sync(
-        &self,
-        capability_name: &str,
-        params: &serde_json::Value,
-    ) -> serde_json::Value {
-        tokio::task::block_in_place(|| {
-            let capability_context = CapabilityContext::default();
-            let response = capability_context.invoke_capability(capability_name, params);
-            if response
-                .get("success")
-                .and_then(|value| value.as_bool())
-                .unwrap_or(false)
-            {
-                if response.get("result").is_some() {
-                    response
-                } else {
-                    json!({
-                        "success": true,
-                        "result": response,
-                    })
-                }
-            } else {
-                response
-            }
-        })
-    }
-
     /// Get extension status including model and database info.
     pub fn get_status(&self) -> serde_json::Value {
         // Trigger lazy loading by calling detect/extract with empty data
@@ -156,7 +131,7 @@ impl FaceRecognition {
             .unwrap_or(false);
 
         json!({
-            "total_bindings": self.bindings.read().len(),
+            "total_bindings": self.bindings.len(),
             "total_inferences": self.total_inferences.load(Ordering::SeqCst),
             "total_recognized": self.total_recognized.load(Ordering::SeqCst),
             "total_unknown": self.total_unknown.load(Ordering::SeqCst),
@@ -165,6 +140,36 @@ impl FaceRecognition {
             "config": *config,
         })
     }
+
+    /// Health report for the `health` command and `health_state` metric.
+    ///
+    /// Unlike `get_status` this doesn't trigger model loading. Injected
+    /// detector/recognizer implementations count as loaded.
+    pub fn health(&self) -> neomind_health::Health {
+        let (detector_loaded, detector_error) = {
+            let detector = self.detector.lock();
+            match detector.as_any().downcast_ref::<ScrfdDetector>() {
+                Some(d) => (d.is_loaded(), d.load_error().map(str::to_string)),
+                None => (true, None),
+            }
+        };
+        let (recognizer_loaded, recognizer_error) = {
+            let recognizer = self.recognizer.lock();
+            match recognizer.as_any().downcast_ref::<ArcFaceRecognizer>() {
+                Some(r) => (r.is_loaded(), r.load_error().map(str::to_string)),
+                None => (true, None),
+            }
+        };
+        let model_loaded = detector_loaded && recognizer_loaded;
+
+        let health = neomind_health::Health::new().model(model_loaded, detector_error.or(recognizer_error));
+        #[cfg(not(target_arch = "wasm32"))]
+        let health = health.native_libs(
+            model_loaded || neomind_vision::native::ort_library().is_some(),
+            "ONNX Runtime",
+        );
+        health.activity(&self.activity)
+    }
 }
 
 impl Default for FaceRecognition {
@@ -230,6 +235,7 @@ impl Extension for FaceRecognition {
                 max: None,
                 required: false,
             },
+            neomind_health::metric_descriptor(),
         ]
     }
 
@@ -450,6 +456,8 @@ impl Extension for FaceRecognition {
                 samples: vec![],
                 parameter_groups: Vec::new(),
             },
+            // 11. health
+            neomind_health::command(),
         ]
     }
 
@@ -459,7 +467,7 @@ impl Extension for FaceRecognition {
         Ok(vec![
             ExtensionMetricValue {
                 name: "bound_devices".to_string(),
-                value: ParamMetricValue::Integer(self.bindings.read().len() as i64),
+                value: ParamMetricValue::Integer(self.bindings.len() as i64),
                 timestamp: now,
             },
             ExtensionMetricValue {
@@ -477,6 +485,7 @@ impl Extension for FaceRecognition {
                 value: ParamMetricValue::Integer(self.total_unknown.load(Ordering::SeqCst) as i64),
                 timestamp: now,
             },
+            self.health().metric(),
         ])
     }
 
@@ -493,182 +502,112 @@ impl Extension for FaceRecognition {
             return Ok(());
         }
 
-        tracing::info!("[FaceRecognition] handle_event called: event_type={}", event_type);
-
-        // Extract event data from the standardized format
-        let inner_payload = payload.get("payload").unwrap_or(payload);
-
-        let device_id = inner_payload
-            .get("device_id")
-            .and_then(|v| v.as_str())
-            .unwrap_or("unknown");
+        tracing::trace!("[FaceRecognition] handle_event called: event_type={}", event_type);
 
-        let metric = inner_payload
-            .get("metric")
-            .and_then(|v| v.as_str())
-            .unwrap_or("unknown");
-
-        let value = inner_payload.get("value");
+        let Some(event) = self.bindings.match_event(event_type, payload) else {
+            return Ok(());
+        };
+        let device_id = event.binding.device_id.as_str();
+        let _span = neomind_logging::binding_span(device_id).entered();
 
-        tracing::info!(
-            "[FaceRecognition] Processing event: device={}, metric={}",
+        tracing::debug!(
+            "[FaceRecognition] Processing image for device {}: {} bytes",
             device_id,
-            metric
+            event.image.len()
         );
 
-        // Check if this device is bound
-        let binding = self.bindings.read().get(device_id).cloned();
-        if let Some(binding) = binding {
-            if !binding.active {
-                tracing::debug!(
-                    "[FaceRecognition] Binding inactive for device: {}",
-                    device_id
-                );
-                return Ok(());
-            }
-
-            // Check if metric matches the binding's metric name
-            let (top_level_metric, nested_path) =
-                if binding.metric_name.contains('.') {
-                    let parts: Vec<&str> = binding.metric_name.splitn(2, '.').collect();
-                    (parts[0].to_string(), Some(parts[1].to_string()))
-                } else {
-                    (binding.metric_name.clone(), None)
-                };
-
-            let metric_matches = metric == binding.metric_name || metric == top_level_metric;
-            let nested_path = if metric == binding.metric_name {
-                None
-            } else {
-                nested_path
-            };
-
-            if !metric_matches {
-                return Ok(());
-            }
-
-            // Extract image data from the value
-            let image_b64 = self.extract_image_from_value(value, nested_path.as_deref());
-
-            if let Some(image_data_b64) = image_b64 {
-                match base64::Engine::decode(
-                    &base64::engine::general_purpose::STANDARD,
-                    &image_data_b64,
-                ) {
-                    Ok(image_data) => {
-                        tracing::info!(
-                            "[FaceRecognition] Processing image for device {}: {} bytes",
-                            device_id,
-                            image_data.len()
-                        );
-
-                        #[cfg(not(target_arch = "wasm32"))]
-                        {
-                            match self.run_recognition_pipeline(&image_data) {
-                                Ok((results, annotated_b64)) => {
-                                    let face_count = results.len();
-                                    let recognized_count = results
-                                        .iter()
-                                        .filter(|r| r.name.is_some())
-                                        .count();
-                                    let unknown_count = face_count - recognized_count;
-
-                                    // Update global counters
-                                    self.total_inferences.fetch_add(1, Ordering::SeqCst);
-                                    self.total_recognized
-                                        .fetch_add(recognized_count as u64, Ordering::SeqCst);
-                                    self.total_unknown
-                                        .fetch_add(unknown_count as u64, Ordering::SeqCst);
-
-                                    // Update binding stats
-                                    if let Some(stats) =
-                                        self.binding_stats.write().get_mut(device_id)
-                                    {
-                                        stats.total_inferences += 1;
-                                        stats.total_recognized += recognized_count as u64;
-                                        stats.total_unknown += unknown_count as u64;
-                                        stats.last_image = if annotated_b64.is_empty() { None } else { Some(format!("data:image/jpeg;base64,{}", annotated_b64)) };
-                                        stats.last_faces = if results.is_empty() { None } else { Some(results.clone()) };
-                                        stats.last_error = None;
-                                    }
-
-                                    // Build face names list
-                                    let face_names: Vec<String> = results
-                                        .iter()
-                                        .map(|r| {
-                                            r.name
-                                                .clone()
-                                                .unwrap_or_else(|| "Unknown".to_string())
-                                        })
-                                        .collect();
-
-                                    // Calculate average confidence
-                                    let avg_confidence = if face_count > 0 {
-                                        results
-                                            .iter()
-                                            .map(|r| {
-                                                r.similarity
-                                                    .unwrap_or(r.face_box.confidence)
-                                            })
-                                            .sum::<f64>()
-                                            / face_count as f64
-                                    } else {
-                                        0.0
-                                    };
-
-                                    let timestamp = chrono::Utc::now().timestamp_millis();
-
-                                    // Write virtual metrics
-                                    if !annotated_b64.is_empty() {
-                                        self.write_recognition_results(
-                                            device_id,
-                                            face_count,
-                                            &face_names,
-                                            &annotated_b64,
-                                            avg_confidence,
-                                            timestamp,
-                                        );
-                                    }
-
-                                    tracing::info!(
-                                        "[FaceRecognition] Device {}: {} faces detected, {} recognized, {} unknown",
-                                        device_id,
-                                        face_count,
-                                        recognized_count,
-                                        unknown_count
-                                    );
-                                }
-                                Err(e) => {
-                                    tracing::warn!(
-                                        "[FaceRecognition] Pipeline failed for device {}: {}",
-                                        device_id,
-                                        e
-                                    );
-                                    if let Some(stats) = self.binding_stats.write().get_mut(device_id) {
-                                        stats.last_error = Some(e.to_string());
-                                    }
-                                }
-                            }
-                        }
-
-                        #[cfg(target_arch = "wasm32")]
-                        {
-                            tracing::warn!(
-                                "[FaceRecognition] Image processing not supported in WASM"
-                            );
-                        }
-                    }
-                    Err(e) => {
-                        tracing::warn!(
-                            "[FaceRecognition] Base64 decode failed: device={}, error={}",
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            match self.run_recognition_pipeline(&event.image) {
+                Ok((results, annotated_b64)) => {
+                    let face_count = results.len();
+                    let recognized_count = results
+                        .iter()
+                        .filter(|r| r.name.is_some())
+                        .count();
+                    let unknown_count = face_count - recognized_count;
+
+                    // Update global counters
+                    self.activity.success();
+                    self.total_inferences.fetch_add(1, Ordering::SeqCst);
+                    self.total_recognized
+                        .fetch_add(recognized_count as u64, Ordering::SeqCst);
+                    self.total_unknown
+                        .fetch_add(unknown_count as u64, Ordering::SeqCst);
+
+                    let timestamp = chrono::Utc::now().timestamp_millis();
+
+                    // Update binding stats
+                    self.bindings.record_inference(device_id, timestamp, |status| {
+                        status.state.total_recognized += recognized_count as u64;
+                        status.state.total_unknown += unknown_count as u64;
+                        status.last_image = if annotated_b64.is_empty() { None } else { Some(jpeg_data_url(&annotated_b64)) };
+                        status.state.last_faces = if results.is_empty() { None } else { Some(results.clone()) };
+                    });
+
+                    // Build face names list
+                    let face_names: Vec<String> = results
+                        .iter()
+                        .map(|r| {
+                            r.name
+                                .clone()
+                                .unwrap_or_else(|| "Unknown".to_string())
+                        })
+                        .collect();
+
+                    // Calculate average confidence
+                    let avg_confidence = if face_count > 0 {
+                        results
+                            .iter()
+                            .map(|r| {
+                                r.similarity
+                                    .unwrap_or(r.face_box.confidence)
+                            })
+                            .sum::<f64>()
+                            / face_count as f64
+                    } else {
+                        0.0
+                    };
+
+                    // Write virtual metrics
+                    if !annotated_b64.is_empty() {
+                        self.write_recognition_results(
                             device_id,
-                            e
+                            face_count,
+                            &face_names,
+                            &annotated_b64,
+                            avg_confidence,
+                            timestamp,
                         );
                     }
+
+                    tracing::debug!(
+                        "[FaceRecognition] Device {}: {} faces detected, {} recognized, {} unknown",
+                        device_id,
+                        face_count,
+                        recognized_count,
+                        unknown_count
+                    );
+                }
+                Err(e) => {
+                    self.activity.error(&e);
+                    tracing::warn!(
+                        "[FaceRecognition] Pipeline failed for device {}: {}",
+                        device_id,
+                        e
+                    );
+                    self.bindings.record_error(device_id, &e);
                 }
             }
         }
 
+        #[cfg(target_arch = "wasm32")]
+        {
+            tracing::warn!(
+                "[FaceRecognition] Image processing not supported in WASM"
+            );
+        }
+
         Ok(())
     }
 
@@ -691,6 +630,7 @@ impl Extension for FaceRecognition {
     }
 
     async fn configure(&mut self, config: &serde_json::Value) -> Result<()> {
+        neomind_logging::apply_config("FaceRecognition", config);
         tracing::info!("[FaceRecognition] configure called");
 
         // First, try to load from file (for isolated extensions)
@@ -785,6 +725,10 @@ impl Extension for FaceRecognition {
                         );
                         *self.face_db.write() = db;
                     }
+                    // Keep the unreadable file so the next save doesn't overwrite it
+                    Err(e @ DatabaseError::Schema(_)) => {
+                        neomind_binding::config::backup_unreadable(&path, "FaceRecognition", &e);
+                    }
                     Err(e) => {
                         tracing::warn!(
                             "[FaceRecognition] Failed to load face database: {}. Starting empty.",
@@ -805,7 +749,7 @@ impl Extension for FaceRecognition {
 
         tracing::info!(
             "[FaceRecognition] Extension configured: {} bindings, {} faces registered",
-            self.bindings.read().len(),
+            self.bindings.len(),
             self.face_db.read().len()
         );
 
@@ -839,7 +783,7 @@ mod tests {
     #[test]
     fn test_extension_new_creates_clean_state() {
         let ext = FaceRecognition::new();
-        assert_eq!(ext.bindings.read().len(), 0);
+        assert!(ext.bindings.is_empty());
         assert_eq!(ext.total_inferences.load(Ordering::SeqCst), 0);
         assert_eq!(ext.total_recognized.load(Ordering::SeqCst), 0);
         assert_eq!(ext.total_unknown.load(Ordering::SeqCst), 0);
@@ -849,7 +793,7 @@ mod tests {
     #[test]
     fn test_default_creates_equivalent() {
         let ext = FaceRecognition::default();
-        assert_eq!(ext.bindings.read().len(), 0);
+        assert!(ext.bindings.is_empty());
     }
 
     #[test]
@@ -870,23 +814,24 @@ mod tests {
     }
 
     #[test]
-    fn test_metrics_returns_four_descriptors() {
+    fn test_metrics_returns_five_descriptors() {
         let ext = FaceRecognition::new();
         let metrics = ext.metrics();
-        assert_eq!(metrics.len(), 4);
+        assert_eq!(metrics.len(), 5);
 
         let names: Vec<&str> = metrics.iter().map(|m| m.name.as_str()).collect();
         assert!(names.contains(&"bound_devices"));
         assert!(names.contains(&"total_inferences"));
         assert!(names.contains(&"total_recognized"));
         assert!(names.contains(&"total_unknown"));
+        assert!(names.contains(&"health_state"));
     }
 
     #[test]
-    fn test_commands_returns_ten_commands() {
+    fn test_commands_returns_eleven_commands() {
         let ext = FaceRecognition::new();
         let commands = ext.commands();
-        assert_eq!(commands.len(), 10);
+        assert_eq!(commands.len(), 11);
 
         let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
         assert!(names.contains(&"bind_device"));
@@ -899,13 +844,14 @@ mod tests {
         assert!(names.contains(&"get_status"));
         assert!(names.contains(&"configure"));
         assert!(names.contains(&"get_config"));
+        assert!(names.contains(&"health"));
     }
 
     #[test]
-    fn test_produce_metrics_returns_four_values() {
+    fn test_produce_metrics_returns_five_values() {
         let ext = FaceRecognition::new();
         let values = ext.produce_metrics().unwrap();
-        assert_eq!(values.len(), 4);
+        assert_eq!(values.len(), 5);
 
         let names: Vec<&str> = values.iter().map(|v| v.name.as_str()).collect();
         assert!(names.contains(&"bound_devices"));
@@ -928,53 +874,6 @@ mod tests {
         assert!(result.is_ok());
     }
 
-    #[test]
-    fn test_extract_image_from_value_handles_data_uri() {
-        let ext = FaceRecognition::new();
-        let value = json!("data:image/jpeg;base64,aGVsbG8=");
-        let result = ext.extract_image_from_value(Some(&value), None);
-        assert_eq!(result, Some("aGVsbG8=".to_string()));
-    }
-
-    #[test]
-    fn test_extract_image_from_value_handles_plain_base64() {
-        let ext = FaceRecognition::new();
-        let value = json!("aGVsbG8=");
-        let result = ext.extract_image_from_value(Some(&value), None);
-        assert_eq!(result, Some("aGVsbG8=".to_string()));
-    }
-
-    #[test]
-    fn test_extract_image_from_value_handles_metric_value_wrapper() {
-        let ext = FaceRecognition::new();
-        let value = json!({"String": "aGVsbG8="});
-        let result = ext.extract_image_from_value(Some(&value), None);
-        assert_eq!(result, Some("aGVsbG8=".to_string()));
-    }
-
-    #[test]
-    fn test_extract_image_from_value_handles_nested_path() {
-        let ext = FaceRecognition::new();
-        let value = json!({"image": {"data": "aGVsbG8="}});
-        let result = ext.extract_image_from_value(Some(&value), Some("image.data"));
-        assert_eq!(result, Some("aGVsbG8=".to_string()));
-    }
-
-    #[test]
-    fn test_extract_image_from_value_returns_none_for_none() {
-        let ext = FaceRecognition::new();
-        let result = ext.extract_image_from_value(None, None);
-        assert!(result.is_none());
-    }
-
-    #[test]
-    fn test_extract_image_from_value_handles_object_with_data_field() {
-        let ext = FaceRecognition::new();
-        let value = json!({"data": "aGVsbG8="});
-        let result = ext.extract_image_from_value(Some(&value), None);
-        assert_eq!(result, Some("aGVsbG8=".to_string()));
-    }
-
     #[test]
     fn test_get_status_returns_valid_json() {
         let ext = FaceRecognition::new();
@@ -999,33 +898,36 @@ mod tests {
 
     #[test]
     fn test_device_binding_serialization() {
-        let binding = DeviceBinding {
-            device_id: "cam-01".to_string(),
-            metric_name: "image".to_string(),
-            active: true,
-            created_at: 1700000000,
-        };
+        let binding = DeviceBinding::new("cam-01", "image", FaceBindingSettings { created_at: 1700000000 });
         let json = serde_json::to_string(&binding).unwrap();
         let deserialized: DeviceBinding = serde_json::from_str(&json).unwrap();
         assert_eq!(deserialized.device_id, "cam-01");
-        assert_eq!(deserialized.metric_name, "image");
+        assert_eq!(deserialized.image_metric, "image");
+        assert_eq!(deserialized.settings.created_at, 1700000000);
         assert!(deserialized.active);
+
+        // Configs written before the shared binding types used `metric_name`
+        let legacy: DeviceBinding = serde_json::from_value(json!({
+            "device_id": "cam-02",
+            "metric_name": "snapshot.data",
+            "active": false,
+            "created_at": 1700000000
+        }))
+        .unwrap();
+        assert_eq!(legacy.image_metric, "snapshot.data");
+        assert!(!legacy.active);
     }
 
     #[test]
     fn test_binding_stats_serialization() {
-        let stats = BindingStats {
-            total_inferences: 10,
-            total_recognized: 5,
-            total_unknown: 5,
-            last_image: None,
-            last_faces: None,
-            last_error: None,
-        };
-        let json = serde_json::to_string(&stats).unwrap();
-        let deserialized: BindingStats = serde_json::from_str(&json).unwrap();
+        let mut status = BindingStatus::new(DeviceBinding::new("cam-01", "image", FaceBindingSettings { created_at: 0 }));
+        status.total_inferences = 10;
+        status.state.total_recognized = 5;
+        status.state.total_unknown = 5;
+        let json = serde_json::to_string(&status).unwrap();
+        let deserialized: BindingStatus = serde_json::from_str(&json).unwrap();
         assert_eq!(deserialized.total_inferences, 10);
-        assert_eq!(deserialized.total_recognized, 5);
+        assert_eq!(deserialized.state.total_recognized, 5);
     }
 
     #[test]
@@ -1,5 +1,6 @@
-//! Face recognition pipeline and image extraction helpers.
+//! Face recognition pipeline and result metrics.
 
+use neomind_binding::image::jpeg_data_url;
 use neomind_extension_sdk::{ExtensionError, Result};
 use serde_json::json;
 
@@ -8,6 +9,7 @@ use crate::FaceRecognition;
 
 impl FaceRecognition {
     /// Write recognition results as virtual metrics on the device.
+    #[cfg(not(target_arch = "wasm32"))]
     pub fn write_recognition_results(
         &self,
         device_id: &str,
@@ -17,55 +19,16 @@ impl FaceRecognition {
         avg_confidence: f64,
         timestamp: i64,
     ) {
-        // Write face count
-        let params = json!({
-            "device_id": device_id,
-            "metric": "virtual.face_recognition.face_count",
-            "value": face_count,
-            "timestamp": timestamp,
-        });
-        let resp = self.invoke_capability_sync("device_metrics_write", &params);
-        if !resp.get("success").and_then(|v| v.as_bool()).unwrap_or(false) {
-            tracing::warn!("[FaceRecognition] Failed to write face_count metric");
-        }
-
-        // Write face names
-        let names_str = face_names.join(",");
-        let params = json!({
-            "device_id": device_id,
-            "metric": "virtual.face_recognition.face_names",
-            "value": names_str,
-            "timestamp": timestamp,
-        });
-        let resp = self.invoke_capability_sync("device_metrics_write", &params);
-        if !resp.get("success").and_then(|v| v.as_bool()).unwrap_or(false) {
-            tracing::warn!("[FaceRecognition] Failed to write face_names metric");
-        }
-
-        // Write annotated image
-        let data_uri = format!("data:image/jpeg;base64,{}", annotated_image_b64);
-        let params = json!({
-            "device_id": device_id,
-            "metric": "virtual.face_recognition.annotated_image",
-            "value": data_uri,
-            "timestamp": timestamp,
-        });
-        let resp = self.invoke_capability_sync("device_metrics_write", &params);
-        if !resp.get("success").and_then(|v| v.as_bool()).unwrap_or(false) {
-            tracing::warn!("[FaceRecognition] Failed to write annotated_image metric");
-        }
-
-        // Write confidence
-        let params = json!({
-            "device_id": device_id,
-            "metric": "virtual.face_recognition.confidence",
-            "value": avg_confidence,
-            "timestamp": timestamp,
-        });
-        let resp = self.invoke_capability_sync("device_metrics_write", &params);
-        if !resp.get("success").and_then(|v| v.as_bool()).unwrap_or(false) {
-            tracing::warn!("[FaceRecognition] Failed to write confidence metric");
-        }
+        neomind_binding::metrics::write_device_metrics(
+            device_id,
+            timestamp,
+            vec![
+                ("virtual.face_recognition.face_count", json!(face_count)),
+                ("virtual.face_recognition.face_names", json!(face_names.join(","))),
+                ("virtual.face_recognition.annotated_image", json!(jpeg_data_url(annotated_image_b64))),
+                ("virtual.face_recognition.confidence", json!(avg_confidence)),
+            ],
+        );
     }
 
     /// Run the full face recognition pipeline on image data.
@@ -162,56 +125,4 @@ impl FaceRecognition {
 
         Ok((results, annotated_b64))
     }
-
-    /// Extract image data from a value, supporting nested paths and data URIs.
-    pub fn extract_image_from_value<'a>(
-        &self,
-        value: Option<&'a serde_json::Value>,
-        nested_path: Option<&str>,
-    ) -> Option<String> {
-        let v = value?;
-
-        // Navigate through nested path if provided
-        let target_value = if let Some(path) = nested_path {
-            let mut current = v;
-            for part in path.split('.') {
-                current = current.get(part)?;
-            }
-            current
-        } else {
-            v
-        };
-
-        // Try to extract string from the target value
-        let raw_string: Option<&str> = {
-            // Format 1: Direct string
-            if let Some(s) = target_value.as_str() {
-                Some(s)
-            // Format 2: MetricValue wrapper {"String": "..."}
-            } else if let Some(s) = target_value.get("String").and_then(|s| s.as_str()) {
-                Some(s)
-            // Format 3: Object with string fields (try common field names)
-            } else {
-                for field in &["image", "data", "value", "base64"] {
-                    if let Some(s) = target_value.get(field).and_then(|s| s.as_str()) {
-                        return Some(s.to_string());
-                    }
-                }
-                None
-            }
-        };
-
-        if let Some(s) = raw_string {
-            // Handle data URL format: "data:image/jpeg;base64,actual_base64_data"
-            if s.starts_with("data:") {
-                if let Some(comma_pos) = s.find(',') {
-                    let base64_part = &s[comma_pos + 1..];
-                    return Some(base64_part.to_string());
-                }
-            }
-            return Some(s.to_string());
-        }
-
-        None
-    }
 }
@@ -1,5 +1,6 @@
 //! Shared type definitions for the face-recognition extension.
 
+use neomind_binding::BindingSettings;
 use serde::{Deserialize, Serialize};
 
 /// A single facial landmark point
@@ -33,29 +34,37 @@ pub struct FaceResult {
     pub face_id: Option<String>,
 }
 
-/// Device binding configuration
+/// Per-binding face recognition settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DeviceBinding {
-    pub device_id: String,
-    pub metric_name: String,
-    pub active: bool,
+pub struct FaceBindingSettings {
+    /// Unix timestamp (seconds) of the bind_device call
+    #[serde(default)]
     pub created_at: i64,
 }
 
-/// Statistics and latest results for a device binding
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BindingStats {
-    pub total_inferences: u64,
+impl BindingSettings for FaceBindingSettings {
+    fn from_args(_args: &serde_json::Value) -> Self {
+        Self {
+            created_at: chrono::Utc::now().timestamp(),
+        }
+    }
+}
+
+/// Device binding configuration
+pub type DeviceBinding = neomind_binding::DeviceBinding<FaceBindingSettings>;
+
+/// Recognition counters and latest faces for a device binding
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FaceBindingStats {
     pub total_recognized: u64,
     pub total_unknown: u64,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub last_image: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub last_faces: Option<Vec<FaceResult>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub last_error: Option<String>,
 }
 
+/// Statistics and latest results for a device binding
+pub type BindingStatus = neomind_binding::BindingStatus<FaceBindingSettings, FaceBindingStats>;
+
 /// Extension configuration for persistence
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FaceRecConfig {
@@ -78,3 +87,7 @@ impl Default for FaceRecConfig {
         }
     }
 }
+
+impl neomind_binding::config::Versioned for FaceRecConfig {
+    const SCHEMA_VERSION: u32 = 1;
+}
@@ -784,6 +784,35 @@ fn test_get_status_reflects_registered_faces() {
     assert_eq!(status["registered_faces"], 1);
 }
 
+#[test]
+fn test_health_reports_pipeline_failures() {
+    let ext = create_ext(
+        MockDetector::with_error("camera glitch"),
+        MockRecognizer::new(make_feature(512, 1.0)),
+    );
+
+    let health = run_command(&ext, "health", &json!({}));
+    assert_eq!(health["state"], "healthy");
+    assert_eq!(health["model_loaded"], true);
+    assert_eq!(health["error_count"], 0);
+
+    run_command(
+        &ext,
+        "bind_device",
+        &json!({"device_id": "cam-01", "metric_name": "image"}),
+    );
+    ext.handle_event(
+        "DeviceMetric",
+        &json!({"device_id": "cam-01", "metric": "image", "value": make_minimal_image_b64()}),
+    )
+    .unwrap();
+
+    let health = run_command(&ext, "health", &json!({}));
+    assert_eq!(health["state"], "degraded");
+    assert_eq!(health["error_count"], 1);
+    assert!(health["last_error"].as_str().unwrap().contains("camera glitch"));
+}
+
 // ============================================================================
 // 9. get_bindings Tests
 // ============================================================================
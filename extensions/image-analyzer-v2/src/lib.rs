@@ -11,48 +11,18 @@ use neomind_extension_sdk::{
 };
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::collections::HashMap;
 use base64::Engine;
 use parking_lot::Mutex;
 
 #[cfg(not(target_arch = "wasm32"))]
-
-#[cfg(not(target_arch = "wasm32"))]
-use usls::{models::YOLO, Config, DataLoader, Device, Version as YOLOVersion};
-
-/// Auto-detect best available inference device.
-/// macOS → CoreML, Linux → CUDA, others → CPU.
+use usls::{models::YOLO, Config, DataLoader, Version as YOLOVersion};
 #[cfg(not(target_arch = "wasm32"))]
-fn auto_device() -> Device {
-    #[cfg(target_os = "macos")]
-    { Device::CoreMl }
-    #[cfg(all(not(target_os = "macos"), target_os = "linux"))]
-    { Device::Cuda(0) }
-    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
-    { Device::Cpu(0) }
-}
+use neomind_vision::{device::with_device_fallback, native::setup_native_lib_paths};
 
-/// Try building a model with the auto-detected device, fall back to CPU on failure.
 #[cfg(not(target_arch = "wasm32"))]
-fn with_device_fallback<M, F>(try_build: F) -> std::result::Result<M, String>
-where
-    F: Fn(Device) -> std::result::Result<M, String>,
-{
-    let device = auto_device();
-    eprintln!("[HW] Trying device: {:?}", device);
-    match try_build(device) {
-        Ok(model) => {
-            eprintln!("[HW] Model loaded with device: {:?}", device);
-            Ok(model)
-        }
-        Err(e) if !matches!(device, Device::Cpu(_)) => {
-            eprintln!("[HW] {:?} failed ({}), falling back to CPU", device, e);
-            try_build(Device::Cpu(0))
-        }
-        Err(e) => Err(e),
-    }
-}
+pub use neomind_vision::COCO_CLASSES;
 
 // ============================================================================
 // Types
@@ -84,24 +54,6 @@ pub struct AnalysisResult {
     pub model_error: Option<String>,
 }
 
-// ============================================================================
-// COCO Classes
-// ============================================================================
-
-#[cfg(not(target_arch = "wasm32"))]
-pub const COCO_CLASSES: [&str; 80] = [
-    "person", "bicycle", "car", "motorcycle", "airplane", "bus", "train", "truck", "boat",
-    "traffic light", "fire hydrant", "stop sign", "parking meter", "bench", "bird", "cat",
-    "dog", "horse", "sheep", "cow", "elephant", "bear", "zebra", "giraffe", "backpack",
-    "umbrella", "handbag", "tie", "suitcase", "frisbee", "skis", "snowboard", "sports ball",
-    "kite", "baseball bat", "baseball glove", "skateboard", "surfboard", "tennis racket",
-    "bottle", "wine glass", "cup", "fork", "knife", "spoon", "bowl", "banana", "apple",
-    "sandwich", "orange", "broccoli", "carrot", "hot dog", "pizza", "donut", "cake",
-    "chair", "couch", "potted plant", "bed", "dining table", "toilet", "tv", "laptop",
-    "mouse", "remote", "keyboard", "cell phone", "microwave", "oven", "toaster", "sink",
-    "refrigerator", "book", "clock", "vase", "scissors", "teddy bear", "hair drier", "toothbrush",
-];
-
 // ============================================================================
 // Extension Implementation
 // ============================================================================
@@ -110,14 +62,48 @@ pub struct ImageAnalyzer {
     images_processed: AtomicU64,
     total_processing_time_ms: AtomicU64,
     detections_found: AtomicU64,
+    /// Detection backend: the lazily loaded YOLO model unless injected
+    #[cfg(not(target_arch = "wasm32"))]
+    detector: Mutex<Box<dyn ObjectDetect + Send>>,
+    /// Model state mirrored from the detector so `health` never waits on inference
+    #[cfg(not(target_arch = "wasm32"))]
+    model_loaded: AtomicBool,
     #[cfg(not(target_arch = "wasm32"))]
-    detector: Mutex<YOLODetector>,
+    model_error: Mutex<Option<String>>,
+    /// Inference successes and failures for the health report
+    activity: neomind_health::Activity,
     // Configuration
     confidence_threshold: Mutex<f32>,
     nms_threshold: Mutex<f32>,
     model_version: Mutex<String>,
 }
 
+/// Trait for object detection backends.
+///
+/// The analyzer only talks to its model through this trait, so tests can
+/// inject a scripted backend with [`ImageAnalyzer::with_detector`] instead
+/// of loading an ONNX model.
+pub trait ObjectDetect {
+    /// Load the model if that hasn't been attempted yet.
+    fn ensure_loaded(&mut self) {}
+
+    /// Detect objects in the given image data, loading the model first.
+    ///
+    /// # Arguments
+    /// * `image_data` - Encoded image bytes (JPEG, PNG, etc.)
+    fn detect(&mut self, image_data: &[u8]) -> std::result::Result<Vec<Detection>, String>;
+
+    /// Whether the model is loaded. Backends without a model are always ready.
+    fn is_loaded(&self) -> bool {
+        true
+    }
+
+    /// Error of the last model load attempt, if any.
+    fn load_error(&self) -> Option<String> {
+        None
+    }
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 struct YOLODetector {
     model: Option<YOLO>,
@@ -131,141 +117,37 @@ struct YOLODetector {
     load_attempted: bool,
 }
 
-/// Set up native library search paths before ONNX Runtime is loaded.
-/// Checks NEOMIND_EXTENSION_DIR/lib/ and common system paths.
-#[cfg(not(target_arch = "wasm32"))]
-fn setup_native_lib_paths() {
-    let lib_env = if cfg!(target_os = "macos") {
-        "DYLD_LIBRARY_PATH"
-    } else if cfg!(target_os = "windows") {
-        "PATH"
-    } else {
-        "LD_LIBRARY_PATH"
-    };
-
-    let mut paths = vec![];
-
-    // 1. Extension's bundled libraries - check all possible locations
-    if let Ok(ext_dir) = std::env::var("NEOMIND_EXTENSION_DIR") {
-        let ext_path = std::path::Path::new(&ext_dir);
-
-        // lib/ directory (top-level)
-        let lib_dir = ext_path.join("lib");
-        if lib_dir.is_dir() {
-            tracing::info!("[NativeLibs] Adding extension lib dir: {}", lib_dir.display());
-            paths.push(lib_dir.to_string_lossy().to_string());
-        }
-
-        // binaries/<platform>/ directory (where extension.dylib and bundled libs live)
-        let binaries_dir = ext_path.join("binaries");
-        if binaries_dir.is_dir() {
-            // Add all subdirectories (darwin_aarch64, linux_amd64, etc.)
-            if let Ok(entries) = std::fs::read_dir(&binaries_dir) {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    if path.is_dir() {
-                        tracing::info!("[NativeLibs] Adding platform dir: {}", path.display());
-                        paths.push(path.to_string_lossy().to_string());
-
-                        // Create unversioned symlinks for versioned libraries
-                        // e.g. libonnxruntime.1.19.2.dylib -> libonnxruntime.dylib
-                        if let Ok(files) = std::fs::read_dir(&path) {
-                            for file in files.flatten() {
-                                let file_path = file.path();
-                                let name = file_path.file_name().unwrap_or_default().to_string_lossy();
-                                // Match versioned dylib/so patterns
-                                if let Some(base) = name.strip_suffix(".dylib")
-                                    .or_else(|| name.strip_suffix(".so"))
-                                {
-                                    if base.contains('.') {
-                                        // Has version suffix like libonnxruntime.1.19.2
-                                        let unversioned = if cfg!(target_os = "macos") {
-                                            format!("{}.dylib", base.split('.').next().unwrap_or(base))
-                                        } else {
-                                            format!("{}.so", base.split('.').next().unwrap_or(base))
-                                        };
-                                        let link_path = path.join(&unversioned);
-                                        if !link_path.exists() {
-                                            #[cfg(unix)]
-                                            let _ = std::os::unix::fs::symlink(&file_path, &link_path);
-                                            #[cfg(not(unix))]
-                                            let _ = (); // Windows: no versioned library symlinks needed
-                                            tracing::info!("[NativeLibs] Created symlink: {} -> {}", unversioned, name);
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    // Also check current working directory (extension runner sets this)
-    if let Ok(cwd) = std::env::current_dir() {
-        let lib_dir = cwd.join("lib");
-        if lib_dir.is_dir() {
-            paths.push(lib_dir.to_string_lossy().to_string());
-        }
-    }
-
-    // 2. Inherit existing paths
-    if let Ok(existing) = std::env::var(lib_env) {
-        paths.push(existing);
-    }
-
-    // 3. Common system paths
-    for dir in ["/opt/homebrew/lib", "/usr/local/lib"] {
-        if std::path::Path::new(dir).is_dir() {
-            paths.push(dir.to_string());
-        }
-    }
-
-    if !paths.is_empty() {
-        let sep = if cfg!(target_os = "windows") { ";" } else { ":" };
-        let combined = paths.join(sep);
-        tracing::info!("[NativeLibs] Setting {} = {}", lib_env, combined);
-        std::env::set_var(lib_env, &combined);
-    }
-
-    // Set ORT_DYLIB_PATH to the exact location of libonnxruntime
-    // This is the recommended way for the `ort` crate's load-dynamic feature.
-    // DYLD_LIBRARY_PATH set at runtime via set_var may not affect dlopen on macOS.
-    if std::env::var("ORT_DYLIB_PATH").is_err() {
-        let ort_filename = if cfg!(target_os = "macos") {
-            "libonnxruntime.dylib"
-        } else if cfg!(target_os = "windows") {
-            "onnxruntime.dll"
-        } else {
-            "libonnxruntime.so"
-        };
-
-        for dir in &paths {
-            let ort_path = std::path::Path::new(dir).join(ort_filename);
-            if ort_path.exists() {
-                tracing::info!("[NativeLibs] Setting ORT_DYLIB_PATH = {}", ort_path.display());
-                std::env::set_var("ORT_DYLIB_PATH", &ort_path);
-                break;
-            }
-        }
-    }
-}
-
 impl ImageAnalyzer {
     pub fn new() -> Self {
+        neomind_logging::init();
         Self {
             images_processed: AtomicU64::new(0),
             total_processing_time_ms: AtomicU64::new(0),
             detections_found: AtomicU64::new(0),
             #[cfg(not(target_arch = "wasm32"))]
-            detector: Mutex::new(YOLODetector::new(0.25, 0.45, "v8", "n")),
+            detector: Mutex::new(Box::new(YOLODetector::new(0.25, 0.45, "v8", "n"))),
+            #[cfg(not(target_arch = "wasm32"))]
+            model_loaded: AtomicBool::new(false),
+            #[cfg(not(target_arch = "wasm32"))]
+            model_error: Mutex::new(None),
+            activity: neomind_health::Activity::new(),
             confidence_threshold: Mutex::new(0.25),
             nms_threshold: Mutex::new(0.45),
             model_version: Mutex::new("v8-n".to_string()),
         }
     }
 
+    /// Create an instance with a custom detection backend.
+    ///
+    /// This is intended for testing, allowing a scripted backend to be injected
+    /// instead of the real ONNX-based model.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_detector(detector: Box<dyn ObjectDetect + Send>) -> Self {
+        let analyzer = Self::new();
+        *analyzer.detector.lock() = detector;
+        analyzer
+    }
+
     /// Analyze image data and return detection results
     pub fn analyze_image(&self, data: &[u8]) -> Result<AnalysisResult> {
         let start = std::time::Instant::now();
@@ -273,17 +155,20 @@ impl ImageAnalyzer {
         #[cfg(not(target_arch = "wasm32"))]
         let (objects, description, model_loaded, model_error) = {
             let mut detector = self.detector.lock();
-            detector.ensure_loaded();
+            let detected = detector.detect(data);
+            self.sync_model_state(detector.as_ref());
 
-            if let Some(ref mut model) = detector.model {
-                match Self::run_detection(model, data) {
+            if detector.is_loaded() {
+                match detected {
                     Ok(detections) => {
-                        tracing::info!("[ImageAnalyzer] YOLO detected {} objects", detections.len());
+                        tracing::debug!("[ImageAnalyzer] YOLO detected {} objects", detections.len());
                         let desc = format!("YOLO detected {} objects", detections.len());
+                        self.activity.success();
                         (detections, desc, true, None)
                     }
                     Err(e) => {
                         tracing::error!("[ImageAnalyzer] YOLO inference error: {}", e);
+                        self.activity.error(&e);
                         let (objs, desc) = self.fallback_analysis(data);
                         (objs, desc, false, Some(e))
                     }
@@ -291,7 +176,7 @@ impl ImageAnalyzer {
             } else {
                 tracing::warn!("[ImageAnalyzer] Model not loaded, using fallback");
                 let (objs, desc) = self.fallback_analysis(data);
-                (objs, desc, false, detector.load_error.clone())
+                (objs, desc, false, detector.load_error())
             }
         };
 
@@ -317,63 +202,6 @@ impl ImageAnalyzer {
         })
     }
 
-    #[cfg(not(target_arch = "wasm32"))]
-    fn run_detection(model: &mut YOLO, image_data: &[u8]) -> std::result::Result<Vec<Detection>, String> {
-        // Create temporary file for image data
-        let temp_path = std::env::temp_dir().join(format!("neomind_img_{}.jpg", std::process::id()));
-        std::fs::write(&temp_path, image_data)
-            .map_err(|e| format!("Failed to write temp image: {}", e))?;
-
-        // Load image using usls DataLoader - use try_read_one for single image
-        let temp_path_str = temp_path.to_str().ok_or("Invalid temp path")?;
-        let image = DataLoader::try_read_one(temp_path_str)
-            .map_err(|e| format!("Failed to load image: {}", e))?;
-
-        // Run inference - forward() requires a slice of images
-        let xs = vec![image];
-        let ys = model.forward(&xs)
-            .map_err(|e| format!("Inference failed: {}", e))?;
-
-        // Clean up temp file
-        let _ = std::fs::remove_file(&temp_path);
-
-        // Convert results - new API returns Y struct with hbbs field
-        let mut detections = Vec::new();
-
-        for y in ys.iter() {
-            // Get bounding boxes from hbbs field
-            for hbb in &y.hbbs {
-                // Get class ID - hbb.id() returns Option<usize>
-                let class_id = hbb.id().unwrap_or(0);
-                let label = if class_id < COCO_CLASSES.len() {
-                    COCO_CLASSES[class_id].to_string()
-                } else {
-                    // Fallback to name() if available
-                    hbb.name().unwrap_or(&format!("class_{}", class_id)).to_string()
-                };
-
-                // New API: hbb has xmin(), ymin(), xmax(), ymax() instead of bbox()
-                let xmin = hbb.xmin();
-                let ymin = hbb.ymin();
-                let xmax = hbb.xmax();
-                let ymax = hbb.ymax();
-
-                detections.push(Detection {
-                    label,
-                    confidence: hbb.confidence().unwrap_or(0.0),
-                    bbox: Some(BoundingBox {
-                        x: xmin,
-                        y: ymin,
-                        width: xmax - xmin,
-                        height: ymax - ymin,
-                    }),
-                });
-            }
-        }
-
-        Ok(detections)
-    }
-
     /// Fallback analysis when YOLO is not available
     pub fn fallback_analysis(&self, data: &[u8]) -> (Vec<Detection>, String) {
         let size = data.len();
@@ -416,9 +244,10 @@ impl ImageAnalyzer {
         {
             let mut detector = self.detector.lock();
             detector.ensure_loaded();
+            self.sync_model_state(detector.as_ref());
             json!({
-                "loaded": detector.model.is_some(),
-                "error": detector.load_error,
+                "loaded": detector.is_loaded(),
+                "error": detector.load_error(),
                 "confidence_threshold": *self.confidence_threshold.lock(),
                 "nms_threshold": *self.nms_threshold.lock(),
                 "model_version": self.model_version.lock().clone(),
@@ -433,6 +262,30 @@ impl ImageAnalyzer {
         }
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    fn sync_model_state(&self, detector: &dyn ObjectDetect) {
+        self.model_loaded.store(detector.is_loaded(), Ordering::Relaxed);
+        *self.model_error.lock() = detector.load_error();
+    }
+
+    /// Health report for the `health` command and `health_state` metric
+    pub fn health(&self) -> neomind_health::Health {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let model_loaded = self.model_loaded.load(Ordering::Relaxed);
+            neomind_health::Health::new()
+                .model(model_loaded, self.model_error.lock().clone())
+                .native_libs(model_loaded || neomind_vision::native::ort_library().is_some(), "ONNX Runtime")
+                .activity(&self.activity)
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            neomind_health::Health::new()
+                .degraded("YOLO not available in WASM, using fallback analysis")
+                .activity(&self.activity)
+        }
+    }
+
     /// Reload model with new configuration
     #[cfg(not(target_arch = "wasm32"))]
     pub fn reload_model(&self) -> std::result::Result<(), String> {
@@ -448,16 +301,17 @@ impl ImageAnalyzer {
             ("v8", "n")
         };
 
-        let mut detector = self.detector.lock();
         // Reset and force reload
-        *detector = YOLODetector::new(conf, nms, version, scale);
+        let mut detector = YOLODetector::new(conf, nms, version, scale);
         detector.ensure_loaded();
+        self.sync_model_state(&detector);
 
-        if detector.model.is_some() {
-            Ok(())
-        } else {
-            Err(detector.load_error.clone().unwrap_or_else(|| "Unknown error".to_string()))
-        }
+        let result = match &detector.model {
+            Some(_) => Ok(()),
+            None => Err(detector.load_error.clone().unwrap_or_else(|| "Unknown error".to_string())),
+        };
+        *self.detector.lock() = Box::new(detector);
+        result
     }
 }
 
@@ -486,53 +340,14 @@ impl YOLODetector {
         }
     }
 
-    /// Ensure the model is loaded (lazy init on first use)
-    fn ensure_loaded(&mut self) {
-        if self.load_attempted {
-            return;
-        }
-        self.load_attempted = true;
-
-        // Set up native library paths before ONNX Runtime is loaded
-        setup_native_lib_paths();
-
-        tracing::info!("[YOLODetector] Lazy loading model: {}-{}", self.version, self.scale);
-        match Self::try_load_model(self.conf, self.iou, &self.version, &self.scale) {
-            Ok(model) => {
-                tracing::info!("[YOLODetector] Model loaded successfully: {}-{}", self.version, self.scale);
-                self.model = Some(model);
-            }
-            Err(e) => {
-                tracing::error!("[YOLODetector] Failed to load model: {}", e);
-                self.load_error = Some(e);
-            }
-        }
-    }
-
     fn try_load_model(conf: f32, iou: f32, version: &str, _scale: &str) -> std::result::Result<YOLO, String> {
         // Parse version number from string (e.g., "v8" -> 8)
         let version_num: u8 = version.trim_start_matches('v')
             .parse()
             .map_err(|_| format!("Invalid version: {}", version))?;
 
-        tracing::info!("Loading YOLO v{} model from local file...", version_num);
-
-        // Load model data from local models directory
-        let model_data = Self::load_model_data(version_num)?;
-
-        if model_data.is_none() {
-            return Err(format!("YOLOv{} model not found in models directory", version_num));
-        }
-
-        let model_bytes = model_data.unwrap();
-        let model_size = model_bytes.len();
-        tracing::info!("Loading YOLO model ({} bytes)", model_size);
-
-        // Save model to temp file (usls requires file path)
-        let temp_dir = std::env::temp_dir();
-        let model_path = temp_dir.join(format!("yolov{}n.onnx", version_num));
-        std::fs::write(&model_path, &model_bytes)
-            .map_err(|e| format!("Failed to write temp model file: {}", e))?;
+        let model_path = neomind_vision::find_model(&format!("yolov{}n.onnx", version_num))?;
+        tracing::info!("Loading YOLO v{} model from {}", version_num, model_path.display());
 
         // Create config using usls 0.1.11 API
         let config = Config::yolo()
@@ -553,72 +368,103 @@ impl YOLODetector {
 
         tracing::info!("✓ YOLO model loaded successfully");
 
-        // Clean up temp file
-        let _ = std::fs::remove_file(&model_path);
-
         Ok(model)
     }
 
-    /// Load model data from disk
-    fn load_model_data(version: u8) -> std::result::Result<Option<Vec<u8>>, String> {
-        let model_filename = format!("yolov{}n.onnx", version);
-
-        // Try to get extension directory from environment variable (set by runner)
-        if let Ok(ext_dir) = std::env::var("NEOMIND_EXTENSION_DIR") {
-            tracing::info!("[ImageAnalyzer] NEOMIND_EXTENSION_DIR = {}", ext_dir);
-            
-            // Primary path: <extension_dir>/models/yolov8n.onnx
-            let model_path = std::path::PathBuf::from(&ext_dir).join("models").join(&model_filename);
-            tracing::info!("[ImageAnalyzer] Checking primary path: {}", model_path.display());
-            
-            if model_path.exists() {
-                tracing::info!("[ImageAnalyzer] ✓ Found model at: {}", model_path.display());
-                return std::fs::read(&model_path)
-                    .map(Some)
-                    .map_err(|e| format!("Failed to read model: {}", e));
-            } else {
-                tracing::warn!("[ImageAnalyzer] ✗ Model not found at primary path");
+    fn run_detection(model: &mut YOLO, image_data: &[u8]) -> std::result::Result<Vec<Detection>, String> {
+        // Create temporary file for image data
+        let temp_path = std::env::temp_dir().join(format!("neomind_img_{}.jpg", std::process::id()));
+        std::fs::write(&temp_path, image_data)
+            .map_err(|e| format!("Failed to write temp image: {}", e))?;
+
+        // Load image using usls DataLoader - use try_read_one for single image
+        let temp_path_str = temp_path.to_str().ok_or("Invalid temp path")?;
+        let image = DataLoader::try_read_one(temp_path_str)
+            .map_err(|e| format!("Failed to load image: {}", e))?;
+
+        // Run inference - forward() requires a slice of images
+        let xs = vec![image];
+        let ys = model.forward(&xs)
+            .map_err(|e| format!("Inference failed: {}", e))?;
+
+        // Clean up temp file
+        let _ = std::fs::remove_file(&temp_path);
+
+        // Convert results - new API returns Y struct with hbbs field
+        let mut detections = Vec::new();
+
+        for y in ys.iter() {
+            // Get bounding boxes from hbbs field
+            for hbb in &y.hbbs {
+                // Get class ID - hbb.id() returns Option<usize>
+                let class_id = hbb.id().unwrap_or(0);
+                let label = match neomind_vision::coco_class_name(class_id) {
+                    Some(name) => name.to_string(),
+                    // Fallback to name() if available
+                    None => hbb.name().unwrap_or(&format!("class_{}", class_id)).to_string(),
+                };
+
+                // New API: hbb has xmin(), ymin(), xmax(), ymax() instead of bbox()
+                let xmin = hbb.xmin();
+                let ymin = hbb.ymin();
+                let xmax = hbb.xmax();
+                let ymax = hbb.ymax();
+
+                detections.push(Detection {
+                    label,
+                    confidence: hbb.confidence().unwrap_or(0.0),
+                    bbox: Some(BoundingBox {
+                        x: xmin,
+                        y: ymin,
+                        width: xmax - xmin,
+                        height: ymax - ymin,
+                    }),
+                });
             }
-        } else {
-            tracing::warn!("[ImageAnalyzer] NEOMIND_EXTENSION_DIR not set");
         }
 
-        // Fallback: Try to find model relative to current working directory
-        // When running in isolated process, the working directory should be the extension root
-        tracing::info!("[ImageAnalyzer] Checking current working directory...");
-        if let Ok(cwd) = std::env::current_dir() {
-            tracing::info!("[ImageAnalyzer] Current working directory: {}", cwd.display());
-            
-            let model_path = cwd.join("models").join(&model_filename);
-            tracing::info!("[ImageAnalyzer] Checking: {}", model_path.display());
-            
-            if model_path.exists() {
-                tracing::info!("[ImageAnalyzer] ✓ Found model at: {}", model_path.display());
-                return std::fs::read(&model_path)
-                    .map(Some)
-                    .map_err(|e| format!("Failed to read model: {}", e));
-            }
+        Ok(detections)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ObjectDetect for YOLODetector {
+    /// Ensure the model is loaded (lazy init on first use)
+    fn ensure_loaded(&mut self) {
+        if self.load_attempted {
+            return;
         }
+        self.load_attempted = true;
 
-        // Additional fallback paths
-        let fallback_paths = vec![
-            std::path::PathBuf::from("models").join(&model_filename),
-            std::path::PathBuf::from("../models").join(&model_filename),
-            std::path::PathBuf::from("../../models").join(&model_filename),
-        ];
-
-        for path in &fallback_paths {
-            tracing::info!("[ImageAnalyzer] Checking fallback path: {}", path.display());
-            if path.exists() {
-                tracing::info!("[ImageAnalyzer] ✓ Found model at: {}", path.display());
-                return std::fs::read(path)
-                    .map(Some)
-                    .map_err(|e| format!("Failed to read model: {}", e));
+        // Set up native library paths before ONNX Runtime is loaded
+        setup_native_lib_paths();
+
+        tracing::info!("[YOLODetector] Lazy loading model: {}-{}", self.version, self.scale);
+        match Self::try_load_model(self.conf, self.iou, &self.version, &self.scale) {
+            Ok(model) => {
+                tracing::info!("[YOLODetector] Model loaded successfully: {}-{}", self.version, self.scale);
+                self.model = Some(model);
+            }
+            Err(e) => {
+                tracing::error!("[YOLODetector] Failed to load model: {}", e);
+                self.load_error = Some(e);
             }
         }
+    }
+
+    fn detect(&mut self, image_data: &[u8]) -> std::result::Result<Vec<Detection>, String> {
+        self.ensure_loaded();
+        let model = self.model.as_mut()
+            .ok_or_else(|| self.load_error.clone().unwrap_or_else(|| "Model not loaded".to_string()))?;
+        Self::run_detection(model, image_data)
+    }
 
-        tracing::error!("[ImageAnalyzer] ❌ Model not found in any location");
-        Ok(None)
+    fn is_loaded(&self) -> bool {
+        self.model.is_some()
+    }
+
+    fn load_error(&self) -> Option<String> {
+        self.load_error.clone()
     }
 }
 
@@ -683,6 +529,7 @@ impl Extension for ImageAnalyzer {
                 max: None,
                 required: false,
             },
+            neomind_health::metric_descriptor(),
         ]
     }
 
@@ -742,6 +589,7 @@ impl Extension for ImageAnalyzer {
                 samples: Vec::new(),
                 parameter_groups: Vec::new(),
             },
+            neomind_health::command(),
         ]
     }
 
@@ -780,6 +628,7 @@ impl Extension for ImageAnalyzer {
                 value: ParamMetricValue::Integer(self.detections_found.load(Ordering::SeqCst) as i64),
                 timestamp: now,
             },
+            self.health().metric(),
         ])
     }
 
@@ -803,6 +652,7 @@ impl Extension for ImageAnalyzer {
             "get_status" => {
                 Ok(self.get_model_status())
             }
+            "health" => Ok(self.health().to_json()),
             "reload_model" => {
                 #[cfg(not(target_arch = "wasm32"))]
                 {
@@ -816,7 +666,7 @@ impl Extension for ImageAnalyzer {
                 }
             }
             "configure" => {
-                // Accept config silently - can be extended for real config handling
+                neomind_logging::apply_config("ImageAnalyzer", args);
                 Ok(json!({"status": "ok"}))
             }
 
@@ -824,6 +674,11 @@ impl Extension for ImageAnalyzer {
         }
     }
 
+    async fn configure(&mut self, config: &serde_json::Value) -> Result<()> {
+        neomind_logging::apply_config("ImageAnalyzer", config);
+        Ok(())
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     fn as_any(&self) -> &dyn std::any::Any {
         self
@@ -876,14 +731,14 @@ mod tests {
     fn test_extension_metrics() {
         let ext = ImageAnalyzer::new();
         let metrics = ext.metrics();
-        assert_eq!(metrics.len(), 3);
+        assert_eq!(metrics.len(), 4);
     }
 
     #[test]
     fn test_extension_commands() {
         let ext = ImageAnalyzer::new();
         let commands = ext.commands();
-        assert_eq!(commands.len(), 4);
+        assert_eq!(commands.len(), 5);
         assert_eq!(commands[0].name, "analyze_image");
     }
 
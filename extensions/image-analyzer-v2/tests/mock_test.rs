@@ -0,0 +1,173 @@
+//! Scripted-backend tests for image-analyzer-v2.
+//!
+//! These tests inject a scripted detector so they do NOT require ONNX Runtime
+//! or model files, and cover the model paths that otherwise only run with a
+//! real model: detections, inference errors and load failures.
+
+use std::collections::VecDeque;
+
+use base64::Engine;
+use neomind_extension_sdk::Extension;
+use serde_json::json;
+
+use neomind_extension_image_analyzer_v2::{BoundingBox, Detection, ImageAnalyzer, ObjectDetect};
+
+// ============================================================================
+// Scripted Backend
+// ============================================================================
+
+/// Detector that replays scripted responses, then detects nothing.
+struct ScriptedDetector {
+    /// Responses returned by successive detect() calls.
+    responses: VecDeque<Result<Vec<Detection>, String>>,
+    /// If Some, the model "failed to load" with this error.
+    load_error: Option<String>,
+}
+
+impl ScriptedDetector {
+    fn new(responses: Vec<Result<Vec<Detection>, String>>) -> Self {
+        Self {
+            responses: responses.into(),
+            load_error: None,
+        }
+    }
+
+    fn with_load_error(msg: &str) -> Self {
+        Self {
+            responses: VecDeque::new(),
+            load_error: Some(msg.to_string()),
+        }
+    }
+}
+
+impl ObjectDetect for ScriptedDetector {
+    fn detect(&mut self, _image_data: &[u8]) -> Result<Vec<Detection>, String> {
+        if let Some(ref err) = self.load_error {
+            return Err(err.clone());
+        }
+        self.responses.pop_front().unwrap_or(Ok(vec![]))
+    }
+
+    fn is_loaded(&self) -> bool {
+        self.load_error.is_none()
+    }
+
+    fn load_error(&self) -> Option<String> {
+        self.load_error.clone()
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn detection(label: &str, confidence: f32) -> Detection {
+    Detection {
+        label: label.to_string(),
+        confidence,
+        bbox: Some(BoundingBox { x: 8.0, y: 6.0, width: 20.0, height: 30.0 }),
+    }
+}
+
+/// A small JPEG as base64.
+fn image_b64() -> String {
+    let img = image::RgbImage::from_pixel(64, 48, image::Rgb([200, 150, 100]));
+    let mut jpeg = Vec::new();
+    img.write_to(&mut std::io::Cursor::new(&mut jpeg), image::ImageFormat::Jpeg)
+        .expect("Failed to encode test image");
+    base64::engine::general_purpose::STANDARD.encode(&jpeg)
+}
+
+/// Run an execute_command call and return the result.
+fn run_command(
+    ext: &ImageAnalyzer,
+    command: &str,
+    args: &serde_json::Value,
+) -> serde_json::Value {
+    tokio::runtime::Runtime::new()
+        .expect("Failed to create tokio runtime")
+        .block_on(ext.execute_command(command, args))
+        .expect("execute_command returned an ExtensionError")
+}
+
+fn metric(ext: &ImageAnalyzer, name: &str) -> serde_json::Value {
+    let metrics = ext.produce_metrics().unwrap();
+    let metric = metrics.iter().find(|m| m.name == name).unwrap();
+    serde_json::to_value(&metric.value).unwrap()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_analyze_image_with_model() {
+    let ext = ImageAnalyzer::with_detector(Box::new(ScriptedDetector::new(vec![
+        Ok(vec![detection("person", 0.9), detection("dog", 0.7)]),
+        Ok(vec![detection("car", 0.8)]),
+    ])));
+
+    let result = run_command(&ext, "analyze_image", &json!({ "image": image_b64() }));
+    assert_eq!(result["model_loaded"], true);
+    assert_eq!(result["model_error"], serde_json::Value::Null);
+    assert_eq!(result["description"], "YOLO detected 2 objects");
+    assert_eq!(result["objects"][0]["label"], "person");
+    assert_eq!(result["objects"][1]["bbox"]["height"], 30.0);
+
+    run_command(&ext, "analyze_image", &json!({ "image": image_b64() }));
+    assert_eq!(metric(&ext, "images_processed"), json!({ "integer": 2 }));
+    assert_eq!(metric(&ext, "total_detections"), json!({ "integer": 3 }));
+    assert_eq!(metric(&ext, "health_state"), json!({ "string": "healthy" }));
+
+    let status = run_command(&ext, "get_status", &json!({}));
+    assert_eq!(status["loaded"], true);
+}
+
+#[test]
+fn test_inference_error_falls_back() {
+    let ext = ImageAnalyzer::with_detector(Box::new(ScriptedDetector::new(vec![
+        Err("Inference failed: boom".to_string()),
+    ])));
+
+    let result = run_command(&ext, "analyze_image", &json!({ "image": image_b64() }));
+    assert_eq!(result["model_loaded"], false);
+    assert_eq!(result["model_error"], "Inference failed: boom");
+    assert_eq!(result["objects"][0]["label"], "jpeg_image");
+
+    let health = run_command(&ext, "health", &json!({}));
+    assert_eq!(health["state"], "degraded");
+    assert_eq!(health["model_loaded"], true);
+    assert_eq!(health["error_count"], 1);
+}
+
+#[test]
+fn test_load_error_falls_back() {
+    let ext = ImageAnalyzer::with_detector(Box::new(ScriptedDetector::with_load_error("Model file not found")));
+
+    let result = run_command(&ext, "analyze_image", &json!({ "image": image_b64() }));
+    assert_eq!(result["model_loaded"], false);
+    assert_eq!(result["model_error"], "Model file not found");
+    assert!(result["description"].as_str().unwrap().starts_with("Fallback analysis"));
+
+    let health = run_command(&ext, "health", &json!({}));
+    assert_eq!(health["state"], "unhealthy");
+    assert_eq!(health["load_error"], "Model file not found");
+    // A missing model isn't counted as a failed inference
+    assert_eq!(health["error_count"], 0);
+
+    let status = run_command(&ext, "get_status", &json!({}));
+    assert_eq!(status["loaded"], false);
+    assert_eq!(status["error"], "Model file not found");
+}
+
+#[test]
+fn test_reset_stats() {
+    let ext = ImageAnalyzer::with_detector(Box::new(ScriptedDetector::new(vec![
+        Ok(vec![detection("person", 0.9)]),
+    ])));
+    run_command(&ext, "analyze_image", &json!({ "image": image_b64() }));
+
+    run_command(&ext, "reset_stats", &json!({}));
+    assert_eq!(metric(&ext, "images_processed"), json!({ "integer": 0 }));
+    assert_eq!(metric(&ext, "total_detections"), json!({ "integer": 0 }));
+}
@@ -20,47 +20,19 @@ use neomind_extension_sdk::{
     MetricDescriptor, ExtensionCommand, MetricDataType, ParameterDefinition,
     ParamMetricValue, Result,
 };
-use neomind_extension_sdk::capabilities::CapabilityContext;
 use neomind_extension_sdk::prelude::*;
+use neomind_binding::image::{decode_image, jpeg_data_url};
+use neomind_binding::{BindingRegistry, BindingSettings};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
-use parking_lot::{Mutex, RwLock};
+use parking_lot::Mutex;
 use chrono::Utc;
-use base64::Engine;
-
-/// Auto-detect best available inference device.
-/// macOS → CoreML, Linux → CUDA, others → CPU.
-fn auto_device() -> usls::Device {
-    #[cfg(target_os = "macos")]
-    { usls::Device::CoreMl }
-    #[cfg(all(not(target_os = "macos"), target_os = "linux"))]
-    { usls::Device::Cuda(0) }
-    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
-    { usls::Device::Cpu(0) }
-}
 
-/// Try building a model with the auto-detected device, fall back to CPU on failure.
-fn with_device_fallback<M, F>(try_build: F) -> Result<M>
-where
-    F: Fn(usls::Device) -> Result<M>,
-{
-    let device = auto_device();
-    eprintln!("[HW] Trying device: {:?}", device);
-    match try_build(device) {
-        Ok(model) => {
-            eprintln!("[HW] Model loaded with device: {:?}", device);
-            Ok(model)
-        }
-        Err(_) if !matches!(device, usls::Device::Cpu(_)) => {
-            eprintln!("[HW] {:?} failed, falling back to CPU", device);
-            try_build(usls::Device::Cpu(0))
-        }
-        Err(e) => Err(e),
-    }
-}
+#[cfg(not(target_arch = "wasm32"))]
+use neomind_vision::{device::with_device_fallback, native::setup_native_lib_paths};
 
 // ============================================================================
 // Types
@@ -94,15 +66,11 @@ pub struct RoiPolygon {
     pub points: Vec<[f32; 2]>,
 }
 
-/// Device binding configuration
+/// Per-binding OCR settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DeviceBinding {
-    pub device_id: String,
-    pub device_name: Option<String>,
-    pub image_metric: String,
+pub struct OcrBindingSettings {
     pub result_metric_prefix: String,
     pub draw_boxes: bool,
-    pub active: bool,
     #[serde(default)]
     pub language: Language,
     #[serde(default)]
@@ -115,15 +83,11 @@ fn default_overlap_threshold() -> f32 {
     0.5
 }
 
-impl Default for DeviceBinding {
+impl Default for OcrBindingSettings {
     fn default() -> Self {
         Self {
-            device_id: String::new(),
-            device_name: None,
-            image_metric: "image".to_string(),
             result_metric_prefix: "ocr_".to_string(),
             draw_boxes: true,
-            active: true,
             language: Language::default(),
             roi_regions: Vec::new(),
             roi_overlap_threshold: default_overlap_threshold(),
@@ -131,6 +95,33 @@ impl Default for DeviceBinding {
     }
 }
 
+impl BindingSettings for OcrBindingSettings {
+    fn from_args(args: &serde_json::Value) -> Self {
+        Self {
+            draw_boxes: args["draw_boxes"].as_bool().unwrap_or(true),
+            language: parse_language(&args["language"]),
+            roi_regions: parse_roi_regions(&args["roi_regions"]),
+            roi_overlap_threshold: args["roi_overlap_threshold"].as_f64().unwrap_or(0.5) as f32,
+            ..Self::default()
+        }
+    }
+}
+
+fn parse_language(value: &serde_json::Value) -> Language {
+    value.as_str()
+        .and_then(|s| serde_json::from_str(&format!("\"{}\"", s)).ok())
+        .unwrap_or_default()
+}
+
+fn parse_roi_regions(value: &serde_json::Value) -> Vec<RoiPolygon> {
+    value.as_array()
+        .map(|arr| arr.iter().filter_map(|v| serde_json::from_value(v.clone()).ok()).collect())
+        .unwrap_or_default()
+}
+
+/// Device binding configuration
+pub type DeviceBinding = neomind_binding::DeviceBinding<OcrBindingSettings>;
+
 /// Bounding box for text region
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BoundingBox {
@@ -210,16 +201,10 @@ pub struct OcrResult {
     pub annotated_image_base64: Option<String>,
 }
 
-/// Binding status
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BindingStatus {
-    pub binding: DeviceBinding,
-    pub last_inference: Option<i64>,
-    pub total_inferences: u64,
+/// OCR-specific part of a binding's status
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OcrBindingStats {
     pub total_text_blocks: u64,
-    pub last_error: Option<String>,
-    /// Last original image (base64 data URI)
-    pub last_image: Option<String>,
     /// Last recognized text blocks
     pub last_text_blocks: Option<Vec<TextBlock>>,
     /// Last full text
@@ -228,137 +213,57 @@ pub struct BindingStatus {
     pub last_annotated_image: Option<String>,
 }
 
+/// Binding status
+pub type BindingStatus = neomind_binding::BindingStatus<OcrBindingSettings, OcrBindingStats>;
+
 /// Persisted extension configuration
 #[derive(Serialize, Deserialize)]
 struct OcrConfig {
     bindings: Vec<DeviceBinding>,
 }
 
+impl neomind_binding::config::Versioned for OcrConfig {
+    const SCHEMA_VERSION: u32 = 1;
+}
+
 // ============================================================================
-// OCR Engine (Native Only)
+// TextRecognize - OCR backend
 // ============================================================================
 
-/// Set up native library search paths before ONNX Runtime is loaded.
-/// Checks NEOMIND_EXTENSION_DIR/lib/ and common system paths.
-#[cfg(not(target_arch = "wasm32"))]
-fn setup_native_lib_paths() {
-    let lib_env = if cfg!(target_os = "macos") {
-        "DYLD_LIBRARY_PATH"
-    } else if cfg!(target_os = "windows") {
-        "PATH"
-    } else {
-        "LD_LIBRARY_PATH"
-    };
-
-    let mut paths = vec![];
-
-    // 1. Extension's bundled libraries - check all possible locations
-    if let Ok(ext_dir) = std::env::var("NEOMIND_EXTENSION_DIR") {
-        let ext_path = std::path::Path::new(&ext_dir);
-
-        // lib/ directory (top-level)
-        let lib_dir = ext_path.join("lib");
-        if lib_dir.is_dir() {
-            tracing::info!("[NativeLibs] Adding extension lib dir: {}", lib_dir.display());
-            paths.push(lib_dir.to_string_lossy().to_string());
-        }
-
-        // binaries/<platform>/ directory (where extension.dylib and bundled libs live)
-        let binaries_dir = ext_path.join("binaries");
-        if binaries_dir.is_dir() {
-            // Add all subdirectories (darwin_aarch64, linux_amd64, etc.)
-            if let Ok(entries) = std::fs::read_dir(&binaries_dir) {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    if path.is_dir() {
-                        tracing::info!("[NativeLibs] Adding platform dir: {}", path.display());
-                        paths.push(path.to_string_lossy().to_string());
-
-                        // Create unversioned symlinks for versioned libraries
-                        // e.g. libonnxruntime.1.19.2.dylib -> libonnxruntime.dylib
-                        if let Ok(files) = std::fs::read_dir(&path) {
-                            for file in files.flatten() {
-                                let file_path = file.path();
-                                let name = file_path.file_name().unwrap_or_default().to_string_lossy();
-                                // Match versioned dylib/so patterns
-                                if let Some(base) = name.strip_suffix(".dylib")
-                                    .or_else(|| name.strip_suffix(".so"))
-                                {
-                                    if base.contains('.') {
-                                        // Has version suffix like libonnxruntime.1.19.2
-                                        let unversioned = if cfg!(target_os = "macos") {
-                                            format!("{}.dylib", base.split('.').next().unwrap_or(base))
-                                        } else {
-                                            format!("{}.so", base.split('.').next().unwrap_or(base))
-                                        };
-                                        let link_path = path.join(&unversioned);
-                                        if !link_path.exists() {
-                                            #[cfg(unix)]
-                                            let _ = std::os::unix::fs::symlink(&file_path, &link_path);
-                                            #[cfg(not(unix))]
-                                            let _ = ();
-                                            tracing::info!("[NativeLibs] Created symlink: {} -> {}", unversioned, name);
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    // Also check current working directory (extension runner sets this)
-    if let Ok(cwd) = std::env::current_dir() {
-        let lib_dir = cwd.join("lib");
-        if lib_dir.is_dir() {
-            paths.push(lib_dir.to_string_lossy().to_string());
-        }
-    }
-
-    // 2. Inherit existing paths
-    if let Ok(existing) = std::env::var(lib_env) {
-        paths.push(existing);
-    }
-
-    // 3. Common system paths
-    for dir in ["/opt/homebrew/lib", "/usr/local/lib"] {
-        if std::path::Path::new(dir).is_dir() {
-            paths.push(dir.to_string());
-        }
+/// Trait for OCR backends.
+///
+/// A backend only finds and reads text; annotation, ROI filtering and
+/// statistics are done by the extension. Tests can inject a scripted backend
+/// with [`OcrDeviceInference::with_engine`] instead of loading ONNX models.
+pub trait TextRecognize {
+    /// Load the models if that hasn't been attempted yet.
+    fn ensure_loaded(&mut self) {}
+
+    /// Whether the models are loaded. Backends without models are always ready.
+    fn is_loaded(&self) -> bool {
+        true
     }
 
-    if !paths.is_empty() {
-        let sep = if cfg!(target_os = "windows") { ";" } else { ":" };
-        let combined = paths.join(sep);
-        tracing::info!("[NativeLibs] Setting {} = {}", lib_env, combined);
-        std::env::set_var(lib_env, &combined);
+    /// Error of the last model load attempt, if any.
+    fn load_error(&self) -> Option<String> {
+        None
     }
 
-    // Set ORT_DYLIB_PATH to the exact location of libonnxruntime
-    // This is the recommended way for the `ort` crate's load-dynamic feature.
-    // DYLD_LIBRARY_PATH set at runtime via set_var may not affect dlopen on macOS.
-    if std::env::var("ORT_DYLIB_PATH").is_err() {
-        let ort_filename = if cfg!(target_os = "macos") {
-            "libonnxruntime.dylib"
-        } else if cfg!(target_os = "windows") {
-            "onnxruntime.dll"
-        } else {
-            "libonnxruntime.so"
-        };
-
-        for dir in &paths {
-            let ort_path = std::path::Path::new(dir).join(ort_filename);
-            if ort_path.exists() {
-                tracing::info!("[NativeLibs] Setting ORT_DYLIB_PATH = {}", ort_path.display());
-                std::env::set_var("ORT_DYLIB_PATH", &ort_path);
-                break;
-            }
-        }
-    }
+    /// Find and read the text in an image.
+    ///
+    /// # Arguments
+    /// * `image` - Decoded image
+    /// * `language` - Recognition language
+    ///
+    /// # Returns
+    /// Text blocks in reading order, with bounding boxes normalized to 0.0 - 1.0.
+    fn recognize(&mut self, image: &image::DynamicImage, language: &Language) -> Result<Vec<TextBlock>>;
 }
 
+// ============================================================================
+// OCR Engine (Native Only)
+// ============================================================================
+
 #[cfg(not(target_arch = "wasm32"))]
 pub struct OcrEngine {
     detector: Option<usls::models::DB>,
@@ -396,11 +301,11 @@ impl OcrEngine {
         setup_native_lib_paths();
 
         // Find models directory
-        let models_dir = match Self::find_models_dir_static() {
+        let models_dir = match neomind_vision::find_models_dir() {
             Ok(dir) => dir,
             Err(e) => {
                 tracing::error!("[OcrEngine] Failed to find models directory: {}", e);
-                self.load_error = Some(e.to_string());
+                self.load_error = Some(e);
                 return;
             }
         };
@@ -434,44 +339,6 @@ impl OcrEngine {
         tracing::info!("[OcrEngine] Models loaded in {}ms", elapsed);
     }
 
-    /// Find the models directory (static version for use in ensure_loaded)
-    fn find_models_dir_static() -> Result<std::path::PathBuf> {
-        // Try NEOMIND_EXTENSION_DIR first (set by NeoMind runtime)
-        if let Ok(ext_dir) = std::env::var("NEOMIND_EXTENSION_DIR") {
-            let models_dir = std::path::PathBuf::from(&ext_dir).join("models");
-            if models_dir.exists() {
-                tracing::info!("[OcrEngine] Found models at: {:?}", models_dir);
-                return Ok(models_dir);
-            }
-        }
-
-        // Fallback: Check current working directory
-        if let Ok(cwd) = std::env::current_dir() {
-            let models_dir = cwd.join("models");
-            if models_dir.exists() {
-                tracing::info!("[OcrEngine] Found models at: {:?}", models_dir);
-                return Ok(models_dir);
-            }
-        }
-
-        // Additional fallback paths
-        let fallback_paths = vec![
-            std::path::PathBuf::from("models"),
-            std::path::PathBuf::from("../models"),
-        ];
-
-        for models_dir in fallback_paths {
-            if models_dir.exists() {
-                tracing::info!("[OcrEngine] Found models at: {:?}", models_dir);
-                return Ok(models_dir);
-            }
-        }
-
-        Err(ExtensionError::ExecutionFailed(
-            "Models directory not found. Please ensure OCR models are installed.".to_string()
-        ))
-    }
-
     /// Try to load the detector model
     fn try_load_detector(models_dir: &std::path::Path) -> Result<usls::models::DB> {
         let config = usls::Config::ppocr_det_v5_mobile()
@@ -553,9 +420,112 @@ impl OcrEngine {
         Ok(())
     }
 
-    pub fn recognize(&mut self, image_data: &[u8], device_id: &str, language: &Language, roi_regions: &[RoiPolygon], roi_overlap_threshold: f32) -> Result<OcrResult> {
-        let start = std::time::Instant::now();
+    fn crop_polygon_static(img: &usls::Image, polygon: &usls::Polygon) -> Option<usls::Image> {
+        let coords = polygon.points();
+        if coords.is_empty() {
+            return None;
+        }
+
+        let xs: Vec<f32> = coords.iter().map(|p| p[0]).collect();
+        let ys: Vec<f32> = coords.iter().map(|p| p[1]).collect();
+
+        // Clamp to the image before casting so points off either edge stay inside
+        let w_max = img.width().saturating_sub(1) as f32;
+        let h_max = img.height().saturating_sub(1) as f32;
+        let x_min = xs.iter().cloned().fold(f32::INFINITY, f32::min).clamp(0.0, w_max) as u32;
+        let x_max = xs.iter().cloned().fold(f32::NEG_INFINITY, f32::max).clamp(0.0, w_max) as u32;
+        let y_min = ys.iter().cloned().fold(f32::INFINITY, f32::min).clamp(0.0, h_max) as u32;
+        let y_max = ys.iter().cloned().fold(f32::NEG_INFINITY, f32::max).clamp(0.0, h_max) as u32;
+
+        if x_max <= x_min || y_max <= y_min {
+            return None;
+        }
+
+        let cropped = img.to_dyn().crop_imm(x_min, y_min, x_max - x_min + 1, y_max - y_min + 1);
+        Some(cropped.into())
+    }
+
+    fn polygon_to_bbox_static(polygon: &usls::Polygon, img_w: u32, img_h: u32) -> BoundingBox {
+        let coords = polygon.points();
+        let xs: Vec<f32> = coords.iter().map(|p| p[0]).collect();
+        let ys: Vec<f32> = coords.iter().map(|p| p[1]).collect();
+
+        let x_min = xs.iter().cloned().fold(f32::INFINITY, f32::min);
+        let x_max = xs.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+        let y_min = ys.iter().cloned().fold(f32::INFINITY, f32::min);
+        let y_max = ys.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+
+        BoundingBox {
+            x: x_min / img_w as f32,
+            y: y_min / img_h as f32,
+            width: (x_max - x_min) / img_w as f32,
+            height: (y_max - y_min) / img_h as f32,
+        }
+    }
+
+    fn draw_bbox_with_text(img: &mut image::RgbaImage, bbox: &BoundingBox, text: &str, confidence: f32, img_w: u32, img_h: u32) {
+        use ab_glyph::FontRef;
+        use neomind_vision::draw::{draw_labeled_box, BoxStyle, PixelRect};
+
+        // Cache font loading
+        static FONT_RESULT: std::sync::OnceLock<std::result::Result<FontRef<'static>, ab_glyph::InvalidFont>> = std::sync::OnceLock::new();
+
+        let font = FONT_RESULT.get_or_init(|| {
+            FontRef::try_from_slice(include_bytes!("../fonts/NotoSans-Regular.ttf"))
+        });
+
+        // Normalized bbox -> pixels
+        let rect = PixelRect {
+            x: bbox.x * img_w as f32,
+            y: bbox.y * img_h as f32,
+            width: bbox.width * img_w as f32,
+            height: bbox.height * img_h as f32,
+        };
+
+        // Green bounding box, translucent green label
+        let style = BoxStyle::solid_rgba((0, 255, 0), image::Rgba([0, 180, 0, 220]));
+        let label_text = format!("{} {:.0}%", text, confidence * 100.0);
+        draw_labeled_box(img, rect, &label_text, &style, font.as_ref().ok());
+    }
+
+    fn image_to_base64_static(img: &image::RgbaImage) -> String {
+        use base64::Engine;
+
+        let rgb_img = image::DynamicImage::ImageRgba8(img.clone()).into_rgb8();
+        let mut buffer = Vec::new();
+        let mut cursor = std::io::Cursor::new(&mut buffer);
+
+        if rgb_img.write_to(&mut cursor, image::ImageFormat::Jpeg).is_ok() {
+            base64::engine::general_purpose::STANDARD.encode(&buffer)
+        } else {
+            String::new()
+        }
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        self.loaded
+    }
 
+    pub fn get_load_error(&self) -> Option<&str> {
+        self.load_error.as_deref()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl TextRecognize for OcrEngine {
+    fn ensure_loaded(&mut self) {
+        OcrEngine::ensure_loaded(self);
+    }
+
+    fn is_loaded(&self) -> bool {
+        self.loaded
+    }
+
+    fn load_error(&self) -> Option<String> {
+        self.load_error.clone()
+    }
+
+    fn recognize(&mut self, image: &image::DynamicImage, language: &Language) -> Result<Vec<TextBlock>> {
         // Lazy load on first use
         self.ensure_loaded();
 
@@ -571,26 +541,19 @@ impl OcrEngine {
             Language::English => self.recognizer_english.is_none(),
         };
         if needs_recognizer {
-            if let Ok(models_dir) = Self::find_models_dir_static() {
+            if let Ok(models_dir) = neomind_vision::find_models_dir() {
                 if let Err(e) = self.load_recognizer(&models_dir, language) {
                     tracing::warn!("[OcrEngine] On-demand recognizer load failed: {}", e);
                 }
             }
         }
 
-        // Load image using image crate, then convert to usls::Image
-        let dyn_img = image::load_from_memory(image_data)
-            .map_err(|e| ExtensionError::ExecutionFailed(format!("Failed to load image: {}", e)))?;
-
-        // Keep original for annotation
-        let mut annotated_img = dyn_img.to_rgba8();
-
-        let img: usls::Image = dyn_img.into();
+        let img: usls::Image = image.clone().into();
         let (img_width, img_height) = (img.width(), img.height());
 
         // Detect text regions
         let det_results = if let Some(ref mut detector) = self.detector {
-            detector.forward(&[img.clone()])
+            detector.forward(std::slice::from_ref(&img))
                 .map_err(|e| ExtensionError::ExecutionFailed(format!("Detection failed: {}", e)))?
         } else {
             return Err(ExtensionError::ExecutionFailed("Detector not initialized".to_string()));
@@ -600,7 +563,7 @@ impl OcrEngine {
         let mut crops_with_bboxes: Vec<(usls::Image, BoundingBox)> = Vec::new();
 
         if let Some(det_result) = det_results.first() {
-            tracing::info!("[OcrDeviceInference] Detection found {} polygons", det_result.polygons.len());
+            tracing::debug!("[OcrDeviceInference] Detection found {} polygons", det_result.polygons.len());
             for polygon in &det_result.polygons {
                 let cropped = Self::crop_polygon_static(&img, polygon);
                 if let Some(crop_img) = cropped {
@@ -612,12 +575,10 @@ impl OcrEngine {
             tracing::warn!("[OcrDeviceInference] Detection returned no results");
         }
 
-        tracing::info!("[OcrDeviceInference] Created {} crops for recognition", crops_with_bboxes.len());
+        tracing::debug!("[OcrDeviceInference] Created {} crops for recognition", crops_with_bboxes.len());
 
         // Now recognize all cropped images
         let mut text_blocks = Vec::new();
-        let mut all_texts = Vec::new();
-        let mut total_confidence = 0.0;
 
         for (crop_img, bbox) in crops_with_bboxes {
             // Recognize text using the selected recognizer
@@ -641,211 +602,25 @@ impl OcrEngine {
             };
 
             if let Some(rec_result) = rec_results.first() {
-                tracing::info!("[OcrDeviceInference] Recognition found {} texts", rec_result.texts.len());
+                tracing::debug!("[OcrDeviceInference] Recognition found {} texts", rec_result.texts.len());
                 for text_obj in &rec_result.texts {
                     let text_str = text_obj.text().to_string();
                     let conf = text_obj.confidence().unwrap_or(0.0);
 
-                    tracing::info!("[OcrDeviceInference] Recognized: '{}' (confidence: {:.2})", text_str, conf);
-
-                    // Draw bounding box + OCR text label on annotated image
-                    Self::draw_bbox_with_text(&mut annotated_img, &bbox, &text_str, conf, img_width, img_height);
+                    tracing::trace!("[OcrDeviceInference] Recognized: '{}' (confidence: {:.2})", text_str, conf);
 
                     text_blocks.push(TextBlock {
-                        text: text_str.clone(),
+                        text: text_str,
                         confidence: conf,
                         bbox: bbox.clone(),
                     });
-                    all_texts.push(text_str);
-                    total_confidence += conf;
                 }
             } else {
                 tracing::warn!("[OcrDeviceInference] Recognition returned no results for crop");
             }
         }
 
-        tracing::info!("[OcrDeviceInference] Total text blocks: {}, full text length: {}", text_blocks.len(), all_texts.join("\n").len());
-
-        // Apply ROI filtering if regions are defined
-        if !roi_regions.is_empty() {
-            let before_count = text_blocks.len();
-            text_blocks.retain(|b| text_block_matches_roi(&b.bbox, roi_regions, roi_overlap_threshold));
-            all_texts = text_blocks.iter().map(|b| b.text.clone()).collect();
-            total_confidence = text_blocks.iter().map(|b| b.confidence).sum();
-            tracing::info!(
-                "[OcrDeviceInference] ROI filter: {} -> {} text blocks ({} regions, threshold {:.2})",
-                before_count, text_blocks.len(), roi_regions.len(), roi_overlap_threshold
-            );
-        }
-
-        let inference_time = start.elapsed().as_millis() as u64;
-        let timestamp = Utc::now().timestamp();
-
-        let avg_conf = if text_blocks.is_empty() {
-            0.0
-        } else {
-            total_confidence / text_blocks.len() as f32
-        };
-
-        // Convert annotated image to base64
-        let annotated_base64 = Self::image_to_base64_static(&annotated_img);
-
-        Ok(OcrResult {
-            device_id: device_id.to_string(),
-            text_blocks: text_blocks.clone(),
-            full_text: all_texts.join("\n"),
-            total_blocks: text_blocks.len(),
-            avg_confidence: avg_conf,
-            inference_time_ms: inference_time,
-            image_width: img_width,
-            image_height: img_height,
-            timestamp,
-            annotated_image_base64: Some(annotated_base64),
-        })
-    }
-
-    fn crop_polygon_static(img: &usls::Image, polygon: &usls::Polygon) -> Option<usls::Image> {
-        let coords = polygon.points();
-        if coords.is_empty() {
-            return None;
-        }
-
-        let xs: Vec<f32> = coords.iter().map(|p| p[0]).collect();
-        let ys: Vec<f32> = coords.iter().map(|p| p[1]).collect();
-
-        let x_min = xs.iter().cloned().fold(f32::INFINITY, f32::min) as u32;
-        let x_max = xs.iter().cloned().fold(f32::NEG_INFINITY, f32::max) as u32;
-        let y_min = ys.iter().cloned().fold(f32::INFINITY, f32::min) as u32;
-        let y_max = ys.iter().cloned().fold(f32::NEG_INFINITY, f32::max) as u32;
-
-        let x_min = x_min.max(0);
-        let x_max = x_max.min(img.width() - 1);
-        let y_min = y_min.max(0);
-        let y_max = y_max.min(img.height() - 1);
-
-        if x_max <= x_min || y_max <= y_min {
-            return None;
-        }
-
-        let cropped = img.to_dyn().crop_imm(x_min, y_min, x_max - x_min + 1, y_max - y_min + 1);
-        Some(cropped.into())
-    }
-
-    fn polygon_to_bbox_static(polygon: &usls::Polygon, img_w: u32, img_h: u32) -> BoundingBox {
-        let coords = polygon.points();
-        let xs: Vec<f32> = coords.iter().map(|p| p[0]).collect();
-        let ys: Vec<f32> = coords.iter().map(|p| p[1]).collect();
-
-        let x_min = xs.iter().cloned().fold(f32::INFINITY, f32::min);
-        let x_max = xs.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
-        let y_min = ys.iter().cloned().fold(f32::INFINITY, f32::min);
-        let y_max = ys.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
-
-        BoundingBox {
-            x: x_min / img_w as f32,
-            y: y_min / img_h as f32,
-            width: (x_max - x_min) / img_w as f32,
-            height: (y_max - y_min) / img_h as f32,
-        }
-    }
-
-    fn draw_bbox_with_text(img: &mut image::RgbaImage, bbox: &BoundingBox, text: &str, confidence: f32, img_w: u32, img_h: u32) {
-        use imageproc::drawing::{draw_hollow_rect_mut, draw_filled_rect_mut, draw_text_mut};
-        use imageproc::rect::Rect;
-        use ab_glyph::{FontRef, PxScale, Font as AbFont, ScaleFont as _};
-
-        // Cache font loading
-        static FONT_RESULT: std::sync::OnceLock<std::result::Result<FontRef<'static>, ab_glyph::InvalidFont>> = std::sync::OnceLock::new();
-
-        let font = FONT_RESULT.get_or_init(|| {
-            FontRef::try_from_slice(include_bytes!("../fonts/NotoSans-Regular.ttf"))
-        });
-
-        let x = (bbox.x * img_w as f32) as i32;
-        let y = (bbox.y * img_h as f32) as i32;
-        let w = (bbox.width * img_w as f32) as u32;
-        let h = (bbox.height * img_h as f32) as u32;
-
-        // Green bounding box
-        let color = image::Rgba([0u8, 255u8, 0u8, 255u8]);
-
-        let x = x.max(0).min(img_w as i32 - 1);
-        let y = y.max(0).min(img_h as i32 - 1);
-        let w = w.min(img_w.saturating_sub(x as u32));
-        let h = h.min(img_h.saturating_sub(y as u32));
-
-        if w > 0 && h > 0 {
-            let rect = Rect::at(x, y).of_size(w, h);
-            draw_hollow_rect_mut(img, rect, color);
-            if w > 2 && h > 2 {
-                let rect2 = Rect::at(x + 1, y + 1).of_size(w - 2, h - 2);
-                draw_hollow_rect_mut(img, rect2, color);
-            }
-        }
-
-        // Draw text label above bounding box
-        if let Ok(font) = font {
-            let label_text = format!("{} {:.0}%", text, confidence * 100.0);
-            let font_size = if img_w > 1200 { 24.0 } else if img_w > 800 { 18.0 } else if w > 100 { 14.0 } else { 11.0 };
-            let scale = PxScale::from(font_size);
-
-            let scaled_font = font.as_scaled(scale);
-            let mut text_width: f32 = 0.0;
-            for c in label_text.chars() {
-                let glyph_id = scaled_font.glyph_id(c);
-                text_width += scaled_font.h_advance(glyph_id);
-            }
-            let label_width = (text_width.ceil() as u32 + 12).min(img_w.saturating_sub(x as u32));
-            let label_height = (font_size as u32) + 8;
-
-            // Position label above the box, or inside if no room above
-            let label_y = if y >= label_height as i32 {
-                y - label_height as i32
-            } else {
-                y
-            };
-
-            if label_y >= 0 && (label_y as u32) + label_height <= img_h {
-                // Filled background for label (green)
-                draw_filled_rect_mut(
-                    img,
-                    Rect::at(x, label_y).of_size(label_width, label_height),
-                    image::Rgba([0u8, 180u8, 0u8, 220u8]),
-                );
-                // White text on colored background
-                draw_text_mut(
-                    img,
-                    image::Rgba([255u8, 255u8, 255u8, 255u8]),
-                    x + 5,
-                    label_y + 3,
-                    scale,
-                    font,
-                    &label_text,
-                );
-            }
-        }
-    }
-
-    fn image_to_base64_static(img: &image::RgbaImage) -> String {
-        use base64::Engine;
-
-        let rgb_img = image::DynamicImage::ImageRgba8(img.clone()).into_rgb8();
-        let mut buffer = Vec::new();
-        let mut cursor = std::io::Cursor::new(&mut buffer);
-
-        if rgb_img.write_to(&mut cursor, image::ImageFormat::Jpeg).is_ok() {
-            base64::engine::general_purpose::STANDARD.encode(&buffer)
-        } else {
-            String::new()
-        }
-    }
-
-    pub fn is_loaded(&self) -> bool {
-        self.loaded
-    }
-
-    pub fn get_load_error(&self) -> Option<&str> {
-        self.load_error.as_deref()
+        Ok(text_blocks)
     }
 }
 
@@ -861,92 +636,149 @@ impl Default for OcrEngine {
 // ============================================================================
 
 pub struct OcrDeviceInference {
+    /// OCR backend: the lazily loaded PPOCR models unless injected
     #[cfg(not(target_arch = "wasm32"))]
-    ocr_engine: Mutex<OcrEngine>,
+    ocr_engine: Mutex<Box<dyn TextRecognize + Send>>,
     /// Model load state mirrored as atomics so get_status never blocks on ocr_engine mutex
     #[cfg(not(target_arch = "wasm32"))]
     model_loaded: AtomicBool,
     #[cfg(not(target_arch = "wasm32"))]
     model_error: parking_lot::Mutex<Option<String>>,
 
-    bindings: Arc<RwLock<HashMap<String, DeviceBinding>>>,
-    binding_stats: Arc<RwLock<HashMap<String, BindingStatus>>>,
+    bindings: BindingRegistry<OcrBindingSettings, OcrBindingStats>,
     total_inferences: Arc<AtomicU64>,
     total_text_blocks: Arc<AtomicU64>,
     total_errors: Arc<AtomicU64>,
+    /// Recognition successes and failures for the health report
+    activity: neomind_health::Activity,
 }
 
 impl OcrDeviceInference {
     pub fn new() -> Self {
+        neomind_logging::init();
         Self {
             #[cfg(not(target_arch = "wasm32"))]
-            ocr_engine: Mutex::new(OcrEngine::new()),
+            ocr_engine: Mutex::new(Box::new(OcrEngine::new())),
             #[cfg(not(target_arch = "wasm32"))]
             model_loaded: AtomicBool::new(false),
             #[cfg(not(target_arch = "wasm32"))]
             model_error: parking_lot::Mutex::new(None),
-            bindings: Arc::new(RwLock::new(HashMap::new())),
-            binding_stats: Arc::new(RwLock::new(HashMap::new())),
+            bindings: BindingRegistry::new("OcrDeviceInference"),
             total_inferences: Arc::new(AtomicU64::new(0)),
             total_text_blocks: Arc::new(AtomicU64::new(0)),
             total_errors: Arc::new(AtomicU64::new(0)),
+            activity: neomind_health::Activity::new(),
         }
     }
 
-    fn config_path(&self) -> std::path::PathBuf {
-        if let Ok(ext_dir) = std::env::var("NEOMIND_EXTENSION_DIR") {
-            std::path::PathBuf::from(ext_dir).join("config.json")
-        } else if let Ok(cwd) = std::env::current_dir() {
-            cwd.join("config.json")
+    /// Create an instance with a custom OCR backend.
+    ///
+    /// This is intended for testing, allowing a scripted backend to be injected
+    /// instead of the real ONNX-based models.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_engine(engine: Box<dyn TextRecognize + Send>) -> Self {
+        let ext = Self::new();
+        *ext.ocr_engine.lock() = engine;
+        ext
+    }
+
+    /// Run OCR on an image: recognition by the backend, then annotation and ROI filtering
+    #[cfg(not(target_arch = "wasm32"))]
+    fn recognize(&self, image_data: &[u8], device_id: &str, language: &Language, roi_regions: &[RoiPolygon], roi_overlap_threshold: f32) -> Result<OcrResult> {
+        let start = std::time::Instant::now();
+
+        let mut engine = self.ocr_engine.lock();
+        // recognize() would load lazily too, but a missing model should be reported before a bad image
+        engine.ensure_loaded();
+        let recognized = if engine.is_loaded() {
+            image::load_from_memory(image_data)
+                .map_err(|e| ExtensionError::ExecutionFailed(format!("Failed to load image: {}", e)))
+                .and_then(|img| Ok((engine.recognize(&img, language)?, img)))
         } else {
-            std::path::PathBuf::from("config.json")
+            Err(ExtensionError::ExecutionFailed(
+                engine.load_error().unwrap_or_else(|| "OCR engine not initialized".to_string())
+            ))
+        };
+
+        // Sync model state to atomics so get_status() stays accurate without locking ocr_engine
+        self.model_loaded.store(engine.is_loaded(), Ordering::Relaxed);
+        *self.model_error.lock() = engine.load_error();
+        drop(engine);
+
+        let (mut text_blocks, dyn_img) = recognized?;
+        let (img_width, img_height) = (dyn_img.width(), dyn_img.height());
+
+        // Draw bounding box + OCR text label of every block, including those outside the ROI
+        let mut annotated_img = dyn_img.to_rgba8();
+        for block in &text_blocks {
+            OcrEngine::draw_bbox_with_text(&mut annotated_img, &block.bbox, &block.text, block.confidence, img_width, img_height);
         }
+
+        tracing::debug!("[OcrDeviceInference] Total text blocks: {}", text_blocks.len());
+
+        // Apply ROI filtering if regions are defined
+        if !roi_regions.is_empty() {
+            let before_count = text_blocks.len();
+            text_blocks.retain(|b| text_block_matches_roi(&b.bbox, roi_regions, roi_overlap_threshold));
+            tracing::debug!(
+                "[OcrDeviceInference] ROI filter: {} -> {} text blocks ({} regions, threshold {:.2})",
+                before_count, text_blocks.len(), roi_regions.len(), roi_overlap_threshold
+            );
+        }
+
+        let all_texts: Vec<&str> = text_blocks.iter().map(|b| b.text.as_str()).collect();
+        let total_confidence: f32 = text_blocks.iter().map(|b| b.confidence).sum();
+
+        let inference_time = start.elapsed().as_millis() as u64;
+        let timestamp = Utc::now().timestamp();
+
+        let avg_conf = if text_blocks.is_empty() {
+            0.0
+        } else {
+            total_confidence / text_blocks.len() as f32
+        };
+
+        // Convert annotated image to base64
+        let annotated_base64 = OcrEngine::image_to_base64_static(&annotated_img);
+
+        Ok(OcrResult {
+            device_id: device_id.to_string(),
+            full_text: all_texts.join("\n"),
+            total_blocks: text_blocks.len(),
+            text_blocks,
+            avg_confidence: avg_conf,
+            inference_time_ms: inference_time,
+            image_width: img_width,
+            image_height: img_height,
+            timestamp,
+            annotated_image_base64: Some(annotated_base64),
+        })
     }
 
     fn persist_config(&self) {
-        let bindings: Vec<DeviceBinding> = self.bindings.read().values().cloned().collect();
-        let config = OcrConfig { bindings };
-        let path = self.config_path();
-        match serde_json::to_string_pretty(&config) {
-            Ok(json_str) => {
-                if let Err(e) = std::fs::write(&path, json_str) {
-                    tracing::warn!("[OcrDeviceInference] Failed to persist config: {}", e);
-                } else {
-                    tracing::info!("[OcrDeviceInference] Config persisted to {:?}", path);
-                }
-            }
-            Err(e) => tracing::warn!("[OcrDeviceInference] Failed to serialize config: {}", e),
-        }
+        let config = OcrConfig { bindings: self.bindings.bindings() };
+        neomind_binding::config::save_config("OcrDeviceInference", &config);
     }
 
-    fn load_config_from_file(&self) -> Option<OcrConfig> {
-        let path = self.config_path();
-        if !path.exists() {
-            return None;
+    /// Health report for the `health` command and `health_state` metric
+    fn health(&self) -> neomind_health::Health {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let model_loaded = self.model_loaded.load(Ordering::Relaxed);
+            neomind_health::Health::new()
+                .model(model_loaded, self.model_error.lock().clone())
+                .native_libs(model_loaded || neomind_vision::native::ort_library().is_some(), "ONNX Runtime")
+                .activity(&self.activity)
         }
-        match std::fs::read_to_string(&path) {
-            Ok(content) => {
-                match serde_json::from_str::<OcrConfig>(&content) {
-                    Ok(config) => {
-                        tracing::info!("[OcrDeviceInference] Loaded config with {} bindings from {:?}", config.bindings.len(), path);
-                        Some(config)
-                    }
-                    Err(e) => {
-                        tracing::warn!("[OcrDeviceInference] Failed to parse config: {}", e);
-                        None
-                    }
-                }
-            }
-            Err(e) => {
-                tracing::warn!("[OcrDeviceInference] Failed to read config: {}", e);
-                None
-            }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            neomind_health::Health::new().unhealthy("OCR not supported on WASM")
         }
     }
 
     fn get_status(&self) -> serde_json::Value {
-        let bindings = self.bindings.read();
-        let stats = self.binding_stats.read();
+        let stats = self.bindings.statuses();
 
         #[cfg(not(target_arch = "wasm32"))]
         let (model_loaded, model_error) = {
@@ -963,8 +795,8 @@ impl OcrDeviceInference {
             "total_inferences": self.total_inferences.load(Ordering::Relaxed),
             "total_text_blocks": self.total_text_blocks.load(Ordering::Relaxed),
             "total_errors": self.total_errors.load(Ordering::Relaxed),
-            "bindings_count": bindings.len(),
-            "bindings": stats.values().map(|s| {
+            "bindings_count": stats.len(),
+            "bindings": stats.iter().map(|s| {
                 json!({
                     "device_id": s.binding.device_id,
                     "active": s.binding.active,
@@ -974,49 +806,6 @@ impl OcrDeviceInference {
         })
     }
 
-    /// Invoke a capability synchronously (for use in handle_event)
-    #[cfg(not(target_arch = "wasm32"))]
-    fn invoke_capability_sync(
-        &self,
-        capability_name: &str,
-        params: &serde_json::Value,
-    ) -> serde_json::Value {
-        tokio::task::block_in_place(|| {
-            let capability_context = CapabilityContext::default();
-            capability_context.invoke_capability(capability_name, params)
-        })
-    }
-
-    /// Extract image data from a value, supporting nested paths
-    fn extract_image_from_value<'a>(&self, value: Option<&'a serde_json::Value>, nested_path: Option<&str>) -> Option<String> {
-        let v = value?;
-
-        // If we have a nested path, navigate to it
-        let target_value = if let Some(path) = nested_path {
-            let mut current = v;
-            for part in path.split('.') {
-                current = current.get(part)?;
-            }
-            current
-        } else {
-            v
-        };
-
-        // Try to extract string
-        if let Some(s) = target_value.as_str() {
-            // Check if it's a data URI
-            if s.starts_with("data:image") {
-                let parts: Vec<&str> = s.splitn(2, ',').collect();
-                if parts.len() == 2 {
-                    return Some(parts[1].to_string());
-                }
-            }
-            return Some(s.to_string());
-        }
-
-        None
-    }
-
     /// Process inference result and write virtual metrics
     #[cfg(not(target_arch = "wasm32"))]
     fn write_inference_results(
@@ -1025,86 +814,29 @@ impl OcrDeviceInference {
         result: &OcrResult,
         image_b64: &str,
     ) {
-        // Update binding stats
-        if let Some(stats) = self.binding_stats.write().get_mut(device_id) {
-            stats.last_image = Some(format!("data:image/jpeg;base64,{}", image_b64));
-            stats.last_text_blocks = Some(result.text_blocks.clone());
-            stats.last_full_text = Some(result.full_text.clone());
-            stats.last_inference = Some(result.timestamp);
-            stats.total_inferences += 1;
-            stats.total_text_blocks += result.total_blocks as u64;
+        self.bindings.record_inference(device_id, result.timestamp, |stats| {
+            stats.last_image = Some(jpeg_data_url(image_b64));
+            stats.state.total_text_blocks += result.total_blocks as u64;
+            stats.state.last_text_blocks = Some(result.text_blocks.clone());
+            stats.state.last_full_text = Some(result.full_text.clone());
             if let Some(annotated_b64) = &result.annotated_image_base64 {
-                stats.last_annotated_image = Some(format!("data:image/jpeg;base64,{}", annotated_b64));
+                stats.state.last_annotated_image = Some(jpeg_data_url(annotated_b64));
             }
-        }
-
-        // Write virtual metrics through the native capability bridge.
-        // Virtual metrics must start with: transform., virtual., computed., derived., or aggregated.
-
-        // Write text block count (virtual metric)
-        let metric_name = "virtual.ocr.count";
-        let params = serde_json::json!({
-            "device_id": device_id,
-            "metric": metric_name,
-            "value": result.total_blocks,
-            "timestamp": result.timestamp,
-        });
-        let _ = self.invoke_capability_sync("device_metrics_write", &params);
-
-        // Write full text (virtual metric)
-        let metric_name = "virtual.ocr.full_text";
-        let params = serde_json::json!({
-            "device_id": device_id,
-            "metric": metric_name,
-            "value": result.full_text,
-            "timestamp": result.timestamp,
-        });
-        let _ = self.invoke_capability_sync("device_metrics_write", &params);
-
-        // Write average confidence (virtual metric)
-        let metric_name = "virtual.ocr.confidence";
-        let params = serde_json::json!({
-            "device_id": device_id,
-            "metric": metric_name,
-            "value": result.avg_confidence,
-            "timestamp": result.timestamp,
-        });
-        let _ = self.invoke_capability_sync("device_metrics_write", &params);
-
-        // Write inference time (virtual metric)
-        let metric_name = "virtual.ocr.inference_time_ms";
-        let params = serde_json::json!({
-            "device_id": device_id,
-            "metric": metric_name,
-            "value": result.inference_time_ms,
-            "timestamp": result.timestamp,
         });
-        let _ = self.invoke_capability_sync("device_metrics_write", &params);
 
-        // Write annotated image (virtual metric) with proper data URI format
+        let mut metrics = vec![
+            ("virtual.ocr.count", json!(result.total_blocks)),
+            ("virtual.ocr.full_text", json!(result.full_text)),
+            ("virtual.ocr.confidence", json!(result.avg_confidence)),
+            ("virtual.ocr.inference_time_ms", json!(result.inference_time_ms)),
+        ];
         if let Some(img) = &result.annotated_image_base64 {
-            let metric_name = "virtual.ocr.annotated_image";
-            let data_uri = format!("data:image/jpeg;base64,{}", img);
-            let params = serde_json::json!({
-                "device_id": device_id,
-                "metric": metric_name,
-                "value": data_uri,
-                "timestamp": result.timestamp,
-            });
-            let _ = self.invoke_capability_sync("device_metrics_write", &params);
+            metrics.push(("virtual.ocr.annotated_image", json!(jpeg_data_url(img))));
         }
+        metrics.push(("virtual.ocr.text", json!(serde_json::to_string(&result.text_blocks).unwrap_or_default())));
+        neomind_binding::metrics::write_device_metrics(device_id, result.timestamp, metrics);
 
-        // Write text blocks as JSON (virtual metric)
-        let metric_name = "virtual.ocr.text";
-        let params = serde_json::json!({
-            "device_id": device_id,
-            "metric": metric_name,
-            "value": serde_json::to_string(&result.text_blocks).unwrap_or_default(),
-            "timestamp": result.timestamp,
-        });
-        let _ = self.invoke_capability_sync("device_metrics_write", &params);
-
-        tracing::info!(
+        tracing::debug!(
             "[OcrDeviceInference] Wrote inference results for device={}, blocks={}, time={}ms",
             device_id,
             result.total_blocks,
@@ -1364,6 +1096,7 @@ impl Extension for OcrDeviceInference {
                 samples: vec![],
                 parameter_groups: Vec::new(),
             },
+            neomind_health::command(),
         ]
     }
 
@@ -1441,118 +1174,60 @@ impl Extension for OcrDeviceInference {
                 max: Some(1.0),
                 required: false,
             },
+            neomind_health::metric_descriptor(),
         ]
     }
 
+    fn produce_metrics(&self) -> Result<Vec<ExtensionMetricValue>> {
+        Ok(vec![self.health().metric()])
+    }
+
     async fn execute_command(&self, command: &str, args: &serde_json::Value) -> Result<serde_json::Value> {
         match command {
-            "bind_device" => {
-                let device_id = args["device_id"].as_str()
-                    .ok_or_else(|| ExtensionError::ExecutionFailed("device_id required".to_string()))?
-                    .to_string();
-
-                let device_name = args["device_name"].as_str().map(|s| s.to_string());
-                let image_metric = args["image_metric"].as_str()
-                    .unwrap_or("image")
-                    .to_string();
-                let draw_boxes = args["draw_boxes"].as_bool().unwrap_or(true);
-
-                let roi_regions: Vec<RoiPolygon> = args["roi_regions"].as_array()
-                    .map(|arr| arr.iter().filter_map(|v| serde_json::from_value(v.clone()).ok()).collect())
-                    .unwrap_or_default();
-                let roi_overlap_threshold = args["roi_overlap_threshold"].as_f64().unwrap_or(0.5) as f32;
-
-                let binding = DeviceBinding {
-                    device_id: device_id.clone(),
-                    device_name,
-                    image_metric,
-                    result_metric_prefix: "ocr_".to_string(),
-                    draw_boxes,
-                    active: true,
-                    language: args["language"].as_str()
-                        .and_then(|s| serde_json::from_str(&format!("\"{}\"", s)).ok())
-                        .unwrap_or_default(),
-                    roi_regions,
-                    roi_overlap_threshold,
-                };
-
-                self.bindings.write().insert(device_id.clone(), binding.clone());
-                self.binding_stats.write().insert(device_id.clone(), BindingStatus {
-                    binding,
-                    last_inference: None,
-                    total_inferences: 0,
-                    total_text_blocks: 0,
-                    last_error: None,
-                    last_image: None,
-                    last_text_blocks: None,
-                    last_full_text: None,
-                    last_annotated_image: None,
-                });
-
+            "bind_device" | "unbind_device" => {
+                let response = self.bindings.execute_command(command, args)?;
                 self.persist_config();
-                tracing::info!("[OcrDeviceInference] Bound device: {}", device_id);
-                Ok(json!({"success": true, "device_id": device_id}))
+                Ok(response)
             }
 
-            "unbind_device" => {
-                let device_id = args["device_id"].as_str()
-                    .ok_or_else(|| ExtensionError::ExecutionFailed("device_id required".to_string()))?;
-
-                self.bindings.write().remove(device_id);
-                self.binding_stats.write().remove(device_id);
-
+            "toggle_binding" => {
+                // `active` defaults to true here, unlike the shared registry
+                let mut args = args.clone();
+                if let Some(obj) = args.as_object_mut() {
+                    obj.entry("active").or_insert(json!(true));
+                }
+                let response = self.bindings.execute_command(command, &args)?;
                 self.persist_config();
-                tracing::info!("[OcrDeviceInference] Unbound device: {}", device_id);
-                Ok(json!({"success": true}))
+                Ok(response)
             }
 
-            "get_bindings" => {
-                let stats = self.binding_stats.read();
-                Ok(json!({"success": true, "bindings": stats.values().collect::<Vec<_>>()}))
-            }
+            "get_bindings" => self.bindings.execute_command(command, args),
 
             "recognize_image" => {
-                tracing::info!("[OcrDeviceInference] recognize_image called");
+                tracing::debug!("[OcrDeviceInference] recognize_image called");
                 let image_b64 = args["image"].as_str()
                     .ok_or_else(|| ExtensionError::ExecutionFailed("image required".to_string()))?;
 
-                tracing::info!("[OcrDeviceInference] Image base64 length: {} bytes", image_b64.len());
+                tracing::debug!("[OcrDeviceInference] Image base64 length: {} bytes", image_b64.len());
 
                 // Parse language parameter
-                let language: Language = args["language"].as_str()
-                    .and_then(|s| serde_json::from_str(&format!("\"{}\"", s)).ok())
-                    .unwrap_or_default();
+                let language = parse_language(&args["language"]);
 
                 #[cfg(not(target_arch = "wasm32"))]
                 {
-                    let image_data = if image_b64.starts_with("data:image") {
-                        let parts: Vec<&str> = image_b64.splitn(2, ',').collect();
-                        if parts.len() != 2 {
-                            return Err(ExtensionError::ExecutionFailed("Invalid data URI".to_string()));
-                        }
-                        base64::engine::general_purpose::STANDARD.decode(parts[1])
-                            .map_err(|e| ExtensionError::ExecutionFailed(format!("Base64 decode failed: {}", e)))?
-                    } else {
-                        base64::engine::general_purpose::STANDARD.decode(image_b64)
-                            .map_err(|e| ExtensionError::ExecutionFailed(format!("Base64 decode failed: {}", e)))?
-                    };
+                    let image_data = decode_image(image_b64)
+                        .map_err(|e| ExtensionError::ExecutionFailed(format!("Base64 decode failed: {}", e)))?;
 
-                    tracing::info!("[OcrDeviceInference] Decoded image data: {} bytes", image_data.len());
+                    tracing::debug!("[OcrDeviceInference] Decoded image data: {} bytes", image_data.len());
 
                     // recognize() will call ensure_loaded() internally for lazy init
 
-                    tracing::info!("[OcrDeviceInference] Calling recognize with language: {:?}", language);
-                    let mut engine = self.ocr_engine.lock();
-                    let result = engine.recognize(&image_data, "manual", &language, &[], 0.5)?;
-
-                    // Sync model state to atomics so get_status() stays accurate without locking ocr_engine
-                    self.model_loaded.store(engine.is_loaded(), Ordering::Relaxed);
-                    {
-                        let mut err_guard = self.model_error.lock();
-                        *err_guard = engine.get_load_error().map(|s| s.to_string());
-                    }
+                    tracing::debug!("[OcrDeviceInference] Calling recognize with language: {:?}", language);
+                    let result = self.recognize(&image_data, "manual", &language, &[], 0.5)
+                        .inspect_err(|e| self.activity.error(e))?;
+                    self.activity.success();
 
-                    tracing::info!("[OcrDeviceInference] Recognize returned {} text blocks", result.text_blocks.len());
+                    tracing::debug!("[OcrDeviceInference] Recognize returned {} text blocks", result.text_blocks.len());
 
                     self.total_inferences.fetch_add(1, Ordering::Relaxed);
                     self.total_text_blocks.fetch_add(result.total_blocks as u64, Ordering::Relaxed);
@@ -1566,84 +1241,39 @@ impl Extension for OcrDeviceInference {
                 }
             }
 
-            "toggle_binding" => {
-                let device_id = args["device_id"].as_str()
-                    .ok_or_else(|| ExtensionError::ExecutionFailed("device_id required".to_string()))?;
-
-                let active = args["active"].as_bool().unwrap_or(true);
-
-                if let Some(binding) = self.bindings.write().get_mut(device_id) {
-                    binding.active = active;
-                    tracing::info!("[OcrDeviceInference] Toggled binding: {} -> {}", device_id, active);
-                }
-
-                if let Some(stats) = self.binding_stats.write().get_mut(device_id) {
-                    stats.binding.active = active;
-                }
-
-                self.persist_config();
-                Ok(json!({"success": true, "device_id": device_id, "active": active}))
-            }
-
             "get_status" => {
                 Ok(json!({"success": true, "data": self.get_status()}))
             }
 
+            "health" => Ok(self.health().to_json()),
+
             "update_roi" => {
                 let device_id = args["device_id"].as_str()
                     .ok_or_else(|| ExtensionError::ExecutionFailed("device_id required".to_string()))?
                     .to_string();
 
-                let roi_regions: Vec<RoiPolygon> = args["roi_regions"].as_array()
-                    .map(|arr| arr.iter().filter_map(|v| serde_json::from_value(v.clone()).ok()).collect())
-                    .unwrap_or_default();
-
+                let roi_regions = parse_roi_regions(&args["roi_regions"]);
                 let roi_overlap_threshold = args["roi_overlap_threshold"].as_f64().unwrap_or(0.5) as f32;
 
-                // Update bindings
-                if let Some(binding) = self.bindings.write().get_mut(&device_id) {
-                    binding.roi_regions = roi_regions.clone();
-                    binding.roi_overlap_threshold = roi_overlap_threshold;
-                } else {
+                let updated = self.bindings.update(&device_id, |stats| {
+                    stats.binding.settings.roi_regions = roi_regions;
+                    stats.binding.settings.roi_overlap_threshold = roi_overlap_threshold;
+                });
+                if updated.is_none() {
                     return Err(ExtensionError::ExecutionFailed(
                         format!("Binding not found for device: {}", device_id)
                     ));
                 }
 
-                // Update binding_stats
-                if let Some(stats) = self.binding_stats.write().get_mut(&device_id) {
-                    stats.binding.roi_regions = roi_regions;
-                    stats.binding.roi_overlap_threshold = roi_overlap_threshold;
-                }
-
                 self.persist_config();
                 tracing::info!("[OcrDeviceInference] Updated ROI for device: {}", device_id);
                 Ok(json!({"success": true, "device_id": device_id}))
             }
 
             "configure" => {
-                if let Some(config) = self.load_config_from_file() {
-                    let mut bindings = self.bindings.write();
-                    let mut stats = self.binding_stats.write();
-                    for binding in config.bindings {
-                        let device_id = binding.device_id.clone();
-                        let binding_clone = binding.clone();
-                        stats.entry(device_id.clone()).and_modify(|s| {
-                            s.binding = binding_clone;
-                        }).or_insert_with(|| BindingStatus {
-                            binding,
-                            last_inference: None,
-                            total_inferences: 0,
-                            total_text_blocks: 0,
-                            last_error: None,
-                            last_image: None,
-                            last_text_blocks: None,
-                            last_full_text: None,
-                            last_annotated_image: None,
-                        });
-                        bindings.insert(device_id.clone(), stats.get(&device_id).unwrap().binding.clone());
-                    }
-                    tracing::info!("[OcrDeviceInference] Loaded persisted config with {} bindings", bindings.len());
+                neomind_logging::apply_config("OcrDeviceInference", args);
+                if let Some(config) = neomind_binding::config::load_config::<OcrConfig>("OcrDeviceInference") {
+                    self.bindings.restore(config.bindings);
                 }
                 Ok(json!({"success": true}))
             }
@@ -1656,6 +1286,11 @@ impl Extension for OcrDeviceInference {
         self
     }
 
+    async fn configure(&mut self, config: &serde_json::Value) -> Result<()> {
+        neomind_logging::apply_config("OcrDeviceInference", config);
+        Ok(())
+    }
+
     /// Subscribe to DeviceMetric events to listen for device image updates
     fn event_subscriptions(&self) -> &[&str] {
         &["DeviceMetric"]
@@ -1676,113 +1311,94 @@ impl Extension for OcrDeviceInference {
             return Ok(());
         }
 
-        // Extract event data from the standardized format
-        let inner_payload = payload.get("payload").unwrap_or(payload);
-
-        let device_id = inner_payload.get("device_id")
-            .and_then(|v| v.as_str())
-            .unwrap_or("unknown");
-
-        let metric = inner_payload.get("metric")
-            .and_then(|v| v.as_str())
-            .unwrap_or("unknown");
-
-        tracing::debug!("[OcrDeviceInference] Processing: device={}, metric={}", device_id, metric);
+        let Some(event) = self.bindings.match_event(event_type, payload) else {
+            return Ok(());
+        };
+        let device_id = event.binding.device_id.as_str();
+        let settings = &event.binding.settings;
+        let _span = neomind_logging::binding_span(device_id).entered();
 
-        let value = inner_payload.get("value");
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            // recognize() will call ensure_loaded() internally for lazy init
+            match self.recognize(&event.image, device_id, &settings.language, &settings.roi_regions, settings.roi_overlap_threshold) {
+                Ok(result) => {
+                    tracing::debug!(
+                        "[OcrDeviceInference] Inference: device={}, blocks={}, time={}ms",
+                        device_id,
+                        result.total_blocks,
+                        result.inference_time_ms
+                    );
+                    self.total_inferences.fetch_add(1, Ordering::SeqCst);
+                    self.total_text_blocks.fetch_add(result.total_blocks as u64, Ordering::SeqCst);
+                    self.activity.success();
+                    self.write_inference_results(
+                        device_id,
+                        &result,
+                        &event.image_b64,
+                    );
+                }
+                Err(e) => {
+                    self.total_errors.fetch_add(1, Ordering::SeqCst);
+                    self.activity.error(&e);
+                    tracing::warn!("[OcrDeviceInference] Process failed: device={}, error={}", device_id, e);
+                    self.bindings.record_error(device_id, &e);
+                }
+            }
+        }
 
-        // Check if this device is bound
-        let bindings = self.bindings.read();
-        let binding = bindings.get(device_id).cloned();
+        #[cfg(target_arch = "wasm32")]
+        {
+            tracing::warn!("[OcrDeviceInference] Image processing not supported in WASM");
+            let _ = settings;
+        }
 
-        if let Some(binding) = binding {
-            if !binding.active {
-                tracing::debug!("[OcrDeviceInference] Binding inactive for device: {}", device_id);
-                return Ok(());
-            }
+        Ok(())
+    }
+}
 
-            // Check if metric matches
-            let exact_match = metric == binding.image_metric;
+neomind_extension_sdk::neomind_export!(OcrDeviceInference);
 
-            let (top_level_metric, nested_path) = if binding.image_metric.contains('.') {
-                let parts: Vec<&str> = binding.image_metric.splitn(2, '.').collect();
-                (parts[0].to_string(), Some(parts[1].to_string()))
-            } else {
-                (binding.image_metric.clone(), None)
-            };
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annotation_golden() {
+        let (w, h) = (320, 180);
+        let mut img = image::RgbaImage::from_fn(w, h, |x, y| image::Rgba([230 - (x / 16) as u8, 230, 220 - (y / 16) as u8, 255]));
+
+        let blocks = [
+            // Regular line of text
+            (BoundingBox { x: 0.1, y: 0.35, width: 0.45, height: 0.12 }, "Invoice 2024", 0.97),
+            // At the top edge: the label moves inside the box
+            (BoundingBox { x: 0.05, y: 0.0, width: 0.3, height: 0.1 }, "Header", 0.88),
+            // At the right edge: the label is clipped to the canvas
+            (BoundingBox { x: 0.8, y: 0.7, width: 0.2, height: 0.12 }, "Total 42.00", 0.91),
+        ];
+        for (bbox, text, confidence) in &blocks {
+            OcrEngine::draw_bbox_with_text(&mut img, bbox, text, *confidence, w, h);
+        }
 
-            let metric_matches = exact_match || metric == top_level_metric;
-            let nested_path = if exact_match { None } else { nested_path };
+        neomind_golden::assert_golden!("ocr_annotations", img);
+    }
 
-            tracing::debug!(
-                "[OcrDeviceInference] Event: device={}, metric={}, binding_metric={}, matches={}",
-                device_id, metric, binding.image_metric, metric_matches
-            );
+    #[test]
+    fn test_crop_polygon_clamps_to_image() {
+        let img: usls::Image = image::DynamicImage::new_rgb8(100, 50).into();
 
-            if !metric_matches {
-                return Ok(());
-            }
+        // Off the top-left and bottom-right edges: cropped to the image
+        let polygon = usls::Polygon::try_from([[-20.0, -10.0], [150.0, -10.0], [150.0, 80.0], [-20.0, 80.0]]).unwrap();
+        let cropped = OcrEngine::crop_polygon_static(&img, &polygon).unwrap();
+        assert_eq!((cropped.width(), cropped.height()), (100, 50));
 
-            // Extract image data from value
-            let image_b64 = self.extract_image_from_value(value, nested_path.as_deref());
-
-            if let Some(image_data_b64) = image_b64 {
-                match base64::engine::general_purpose::STANDARD.decode(&image_data_b64) {
-                    Ok(image_data) => {
-                        #[cfg(not(target_arch = "wasm32"))]
-                        {
-                            // recognize() will call ensure_loaded() internally for lazy init
-                            let mut engine = self.ocr_engine.lock();
-                            let recognize_result = engine.recognize(&image_data, device_id, &binding.language, &binding.roi_regions, binding.roi_overlap_threshold);
-
-                            // Sync model state to atomics so get_status() stays accurate without locking ocr_engine
-                            self.model_loaded.store(engine.is_loaded(), Ordering::Relaxed);
-                            {
-                                let mut err_guard = self.model_error.lock();
-                                *err_guard = engine.get_load_error().map(|s| s.to_string());
-                            }
-
-                            match recognize_result {
-                                Ok(result) => {
-                                    tracing::info!(
-                                        "[OcrDeviceInference] Inference: device={}, blocks={}, time={}ms",
-                                        device_id,
-                                        result.total_blocks,
-                                        result.inference_time_ms
-                                    );
-                                    self.total_inferences.fetch_add(1, Ordering::SeqCst);
-                                    self.total_text_blocks.fetch_add(result.total_blocks as u64, Ordering::SeqCst);
-                                    self.write_inference_results(
-                                        device_id,
-                                        &result,
-                                        &image_data_b64,
-                                    );
-                                }
-                                Err(e) => {
-                                    self.total_errors.fetch_add(1, Ordering::SeqCst);
-                                    tracing::warn!("[OcrDeviceInference] Process failed: device={}, error={}", device_id, e);
-                                    if let Some(stats) = self.binding_stats.write().get_mut(device_id) {
-                                        stats.last_error = Some(e.to_string());
-                                    }
-                                }
-                            }
-                        }
-
-                        #[cfg(target_arch = "wasm32")]
-                        {
-                            tracing::warn!("[OcrDeviceInference] Image processing not supported in WASM");
-                            let _ = image_data;
-                        }
-                    }
-                    Err(e) => {
-                        tracing::warn!("[OcrDeviceInference] Base64 decode failed: device={}, error={}", device_id, e);
-                    }
-                }
-            }
-        }
+        // Entirely outside the image
+        let polygon = usls::Polygon::try_from([[120.0, 60.0], [140.0, 60.0], [140.0, 70.0], [120.0, 70.0]]).unwrap();
+        assert!(OcrEngine::crop_polygon_static(&img, &polygon).is_none());
 
-        Ok(())
+        // Zero-size image
+        let empty: usls::Image = image::DynamicImage::new_rgb8(0, 0).into();
+        let polygon = usls::Polygon::try_from([[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0]]).unwrap();
+        assert!(OcrEngine::crop_polygon_static(&empty, &polygon).is_none());
     }
 }
-
-neomind_extension_sdk::neomind_export!(OcrDeviceInference);
@@ -0,0 +1,262 @@
+//! Scripted-backend tests for ocr-device-inference.
+//!
+//! These tests inject a scripted OCR backend so they do NOT require ONNX
+//! Runtime or model files. They drive bindings, device events, ROI filtering
+//! and manual recognition through `execute_command` and `handle_event`.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use base64::Engine;
+use neomind_extension_sdk::{Extension, ExtensionError, Result};
+use parking_lot::Mutex;
+use serde_json::json;
+
+use neomind_extension_ocr_device_inference::{
+    BoundingBox, Language, OcrDeviceInference, TextBlock, TextRecognize,
+};
+
+// ============================================================================
+// Scripted Backend
+// ============================================================================
+
+/// OCR backend that replays scripted responses, then reads nothing.
+struct ScriptedEngine {
+    /// Responses returned by successive recognize() calls.
+    responses: VecDeque<std::result::Result<Vec<TextBlock>, String>>,
+    /// If Some, the models "failed to load" with this error.
+    load_error: Option<String>,
+    /// Languages passed to recognize(), shared with the test.
+    languages: Arc<Mutex<Vec<Language>>>,
+}
+
+impl ScriptedEngine {
+    fn new(responses: Vec<std::result::Result<Vec<TextBlock>, String>>) -> Self {
+        Self {
+            responses: responses.into(),
+            load_error: None,
+            languages: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    fn with_load_error(msg: &str) -> Self {
+        Self {
+            load_error: Some(msg.to_string()),
+            ..Self::new(vec![])
+        }
+    }
+}
+
+impl TextRecognize for ScriptedEngine {
+    fn is_loaded(&self) -> bool {
+        self.load_error.is_none()
+    }
+
+    fn load_error(&self) -> Option<String> {
+        self.load_error.clone()
+    }
+
+    fn recognize(&mut self, _image: &image::DynamicImage, language: &Language) -> Result<Vec<TextBlock>> {
+        self.languages.lock().push(language.clone());
+        self.responses
+            .pop_front()
+            .unwrap_or(Ok(vec![]))
+            .map_err(ExtensionError::ExecutionFailed)
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// A text block with a normalized bounding box.
+fn block(text: &str, confidence: f32, (x, y, width, height): (f32, f32, f32, f32)) -> TextBlock {
+    TextBlock {
+        text: text.to_string(),
+        confidence,
+        bbox: BoundingBox { x, y, width, height },
+    }
+}
+
+/// A header in the top left and a total in the bottom right.
+fn receipt() -> Vec<TextBlock> {
+    vec![
+        block("Header", 0.9, (0.05, 0.05, 0.3, 0.1)),
+        block("Total 42.00", 0.7, (0.6, 0.8, 0.3, 0.1)),
+    ]
+}
+
+/// A 160x120 JPEG as base64.
+fn image_b64() -> String {
+    let img = image::RgbImage::from_pixel(160, 120, image::Rgb([230, 230, 220]));
+    let mut jpeg = Vec::new();
+    img.write_to(&mut std::io::Cursor::new(&mut jpeg), image::ImageFormat::Jpeg)
+        .expect("Failed to encode test image");
+    base64::engine::general_purpose::STANDARD.encode(&jpeg)
+}
+
+fn metric_event(device_id: &str) -> serde_json::Value {
+    json!({
+        "device_id": device_id,
+        "metric": "image",
+        "value": image_b64(),
+    })
+}
+
+/// Run an execute_command call and return the result.
+fn run_command(
+    ext: &OcrDeviceInference,
+    command: &str,
+    args: &serde_json::Value,
+) -> serde_json::Value {
+    tokio::task::block_in_place(|| {
+        let rt = tokio::runtime::Runtime::new().expect("Failed to create tokio runtime");
+        rt.block_on(ext.execute_command(command, args))
+            .expect("execute_command returned an ExtensionError")
+    })
+}
+
+/// The binding status of `device_id`, as returned by get_bindings.
+fn binding(ext: &OcrDeviceInference, device_id: &str) -> serde_json::Value {
+    let response = run_command(ext, "get_bindings", &json!({}));
+    response["bindings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|b| b["binding"]["device_id"] == device_id)
+        .cloned()
+        .unwrap()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_device_event_updates_binding() {
+    let engine = ScriptedEngine::new(vec![Ok(receipt())]);
+    let languages = engine.languages.clone();
+    let ext = OcrDeviceInference::with_engine(Box::new(engine));
+    run_command(&ext, "bind_device", &json!({ "device_id": "cam1", "language": "zh" }));
+
+    ext.handle_event("DeviceMetric", &metric_event("cam1")).unwrap();
+
+    assert_eq!(*languages.lock(), [Language::Chinese]);
+    let status = binding(&ext, "cam1");
+    assert_eq!(status["total_inferences"], 1);
+    assert_eq!(status["total_text_blocks"], 2);
+    assert_eq!(status["last_full_text"], "Header\nTotal 42.00");
+    assert!(status["last_annotated_image"].as_str().unwrap().starts_with("data:image/jpeg;base64,"));
+    assert_eq!(status["last_error"], serde_json::Value::Null);
+
+    let status = run_command(&ext, "get_status", &json!({}));
+    assert_eq!(status["data"]["model_loaded"], true);
+    assert_eq!(status["data"]["total_text_blocks"], 2);
+}
+
+#[test]
+fn test_roi_filters_text_blocks() {
+    let ext = OcrDeviceInference::with_engine(Box::new(ScriptedEngine::new(vec![Ok(receipt()), Ok(receipt())])));
+    run_command(&ext, "bind_device", &json!({ "device_id": "cam1" }));
+
+    // Bottom right quarter: only the total is inside
+    let roi = json!([{ "label": "total", "points": [[0.5, 0.5], [1.0, 0.5], [1.0, 1.0], [0.5, 1.0]] }]);
+    let response = run_command(&ext, "update_roi", &json!({ "device_id": "cam1", "roi_regions": roi }));
+    assert_eq!(response["success"], true);
+
+    ext.handle_event("DeviceMetric", &metric_event("cam1")).unwrap();
+    let status = binding(&ext, "cam1");
+    assert_eq!(status["total_text_blocks"], 1);
+    assert_eq!(status["last_full_text"], "Total 42.00");
+    assert_eq!(status["last_text_blocks"][0]["confidence"], 0.7f32 as f64);
+
+    // A block partly inside the region passes a lower threshold only
+    let roi = json!([{ "points": [[0.0, 0.0], [0.2, 0.0], [0.2, 1.0], [0.0, 1.0]] }]);
+    run_command(&ext, "update_roi", &json!({ "device_id": "cam1", "roi_regions": roi, "roi_overlap_threshold": 0.3 }));
+    ext.handle_event("DeviceMetric", &metric_event("cam1")).unwrap();
+    assert_eq!(binding(&ext, "cam1")["last_full_text"], "Header");
+}
+
+#[test]
+fn test_inactive_binding_is_skipped() {
+    let engine = ScriptedEngine::new(vec![Ok(receipt())]);
+    let languages = engine.languages.clone();
+    let ext = OcrDeviceInference::with_engine(Box::new(engine));
+    run_command(&ext, "bind_device", &json!({ "device_id": "cam1" }));
+    run_command(&ext, "toggle_binding", &json!({ "device_id": "cam1", "active": false }));
+
+    ext.handle_event("DeviceMetric", &metric_event("cam1")).unwrap();
+    ext.handle_event("DeviceMetric", &metric_event("unbound")).unwrap();
+
+    assert!(languages.lock().is_empty());
+    assert_eq!(binding(&ext, "cam1")["total_inferences"], 0);
+}
+
+#[test]
+fn test_toggle_binding_arguments() {
+    let ext = OcrDeviceInference::with_engine(Box::new(ScriptedEngine::new(vec![])));
+    run_command(&ext, "bind_device", &json!({ "device_id": "cam1" }));
+    run_command(&ext, "toggle_binding", &json!({ "device_id": "cam1", "active": false }));
+
+    // Without `active` the binding is switched on
+    let response = run_command(&ext, "toggle_binding", &json!({ "device_id": "cam1" }));
+    assert_eq!(response["active"], true);
+
+    // Unbound devices are an error rather than a silent success
+    let result = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(ext.execute_command("toggle_binding", &json!({ "device_id": "nope", "active": true })));
+    assert!(matches!(result, Err(ExtensionError::NotFound(_))));
+}
+
+#[test]
+fn test_backend_error_is_recorded() {
+    let ext = OcrDeviceInference::with_engine(Box::new(ScriptedEngine::new(vec![
+        Err("Detection failed: boom".to_string()),
+    ])));
+    run_command(&ext, "bind_device", &json!({ "device_id": "cam1" }));
+
+    ext.handle_event("DeviceMetric", &metric_event("cam1")).unwrap();
+
+    let status = binding(&ext, "cam1");
+    assert_eq!(status["total_inferences"], 0);
+    assert!(status["last_error"].as_str().unwrap().contains("boom"));
+
+    let health = run_command(&ext, "health", &json!({}));
+    assert_eq!(health["state"], "degraded");
+    assert_eq!(health["error_count"], 1);
+    assert_eq!(run_command(&ext, "get_status", &json!({}))["data"]["total_errors"], 1);
+}
+
+#[test]
+fn test_load_error_is_reported() {
+    let ext = OcrDeviceInference::with_engine(Box::new(ScriptedEngine::with_load_error("Detector init failed")));
+
+    // Reported before the image is looked at
+    let result = tokio::runtime::Runtime::new()
+        .unwrap()
+        .block_on(ext.execute_command("recognize_image", &json!({ "image": "AAAA" })));
+    assert!(result.unwrap_err().to_string().contains("Detector init failed"));
+
+    let health = run_command(&ext, "health", &json!({}));
+    assert_eq!(health["state"], "unhealthy");
+    assert_eq!(health["load_error"], "Detector init failed");
+}
+
+#[test]
+fn test_recognize_image_command() {
+    let engine = ScriptedEngine::new(vec![Ok(receipt())]);
+    let languages = engine.languages.clone();
+    let ext = OcrDeviceInference::with_engine(Box::new(engine));
+
+    let response = run_command(&ext, "recognize_image", &json!({ "image": image_b64(), "language": "english" }));
+    assert_eq!(response["success"], true);
+    let data = &response["data"];
+    assert_eq!(data["device_id"], "manual");
+    assert_eq!(data["image_width"], 160);
+    assert_eq!(data["image_height"], 120);
+    assert_eq!(data["total_blocks"], 2);
+    assert!((data["avg_confidence"].as_f64().unwrap() - 0.8).abs() < 1e-6);
+    assert!(data["annotated_image_base64"].is_string());
+    assert_eq!(*languages.lock(), [Language::English]);
+}
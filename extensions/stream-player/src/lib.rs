@@ -297,6 +297,26 @@ fn get_registry() -> &'static Mutex<StreamRegistry> {
     REGISTRY.get_or_init(|| Mutex::new(StreamRegistry::new()))
 }
 
+static ACTIVITY: std::sync::OnceLock<neomind_health::Activity> = std::sync::OnceLock::new();
+
+/// Pushed frames and stream failures for the health report
+fn activity() -> &'static neomind_health::Activity {
+    ACTIVITY.get_or_init(neomind_health::Activity::new)
+}
+
+static FFMPEG_AVAILABLE: std::sync::OnceLock<bool> = std::sync::OnceLock::new();
+
+/// Whether FFmpeg initialises and has an H.264 decoder, checked once
+fn ffmpeg_available() -> bool {
+    *FFMPEG_AVAILABLE.get_or_init(|| ff::init().is_ok() && ff::decoder::find(ff::codec::Id::H264).is_some())
+}
+
+fn health() -> neomind_health::Health {
+    neomind_health::Health::new()
+        .native_libs(ffmpeg_available(), "FFmpeg")
+        .activity(activity())
+}
+
 // ============================================================================
 // Native Library Path Setup (for FFmpeg dylib loading)
 // ============================================================================
@@ -443,6 +463,7 @@ pub struct StreamPlayerExtension;
 
 impl StreamPlayerExtension {
     pub fn new() -> Self {
+        neomind_logging::init();
         ensure_native_lib_paths();
         Self
     }
@@ -498,6 +519,7 @@ impl Extension for StreamPlayerExtension {
                 max: None,
                 required: false,
             },
+            neomind_health::metric_descriptor(),
         ]
     }
 
@@ -523,10 +545,11 @@ impl Extension for StreamPlayerExtension {
                 samples: vec![json!({})],
                 parameter_groups: vec![],
             },
+            neomind_health::command(),
         ]
     }
 
-    async fn execute_command(&self, command: &str, _args: &serde_json::Value) -> Result<serde_json::Value> {
+    async fn execute_command(&self, command: &str, args: &serde_json::Value) -> Result<serde_json::Value> {
         match command {
             "list_sources" => {
                 Ok(json!({
@@ -557,8 +580,9 @@ impl Extension for StreamPlayerExtension {
                     "streams": streams,
                 }))
             }
+            "health" => Ok(health().to_json()),
             "configure" => {
-                // Accept config silently - can be extended for real config handling
+                neomind_logging::apply_config("StreamPlayer", args);
                 Ok(json!({"status": "ok"}))
             }
 
@@ -594,9 +618,15 @@ impl Extension for StreamPlayerExtension {
                 value: ParamMetricValue::Integer(total_bytes),
                 timestamp: now,
             },
+            health().metric(),
         ])
     }
 
+    async fn configure(&mut self, config: &serde_json::Value) -> Result<()> {
+        neomind_logging::apply_config("StreamPlayer", config);
+        Ok(())
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -703,6 +733,7 @@ impl Extension for StreamPlayerExtension {
         tracing::info!("[StreamPlayer] Starting push: {} ({})", sid, source_url);
 
         let task_handle = std::thread::spawn(move || {
+            let _span = neomind_logging::session_span(&sid).entered();
             let mut sequence = 0u64;
             let frame_duration = Duration::from_millis(1000 / target_fps as u64);
             let mut reconnect_count = 0u32;
@@ -726,6 +757,7 @@ impl Extension for StreamPlayerExtension {
                 }
                 Err(e) => {
                     tracing::error!("[StreamPlayer] Connection failed: {}", e);
+                    activity().error(format!("Connection to {} failed: {}", source_url, e));
                     let _ = send_push_output(
                         &PushOutputMessage::json(&sid, sequence, json!({
                             "type": "error", "message": format!("Connection failed: {}", e)
@@ -768,7 +800,7 @@ impl Extension for StreamPlayerExtension {
                         }
 
                         if sequence < 5 || sequence % 100 == 0 {
-                            eprintln!("[StreamPlayer] frame {} encode={}ms jpeg={}KB", sequence, encode_ms, jpeg_data.len() / 1024);
+                            tracing::debug!("[StreamPlayer] frame {} encode={}ms jpeg={}KB", sequence, encode_ms, jpeg_data.len() / 1024);
                         }
 
                         // Update stats
@@ -794,9 +826,10 @@ impl Extension for StreamPlayerExtension {
                             Ok(_) => {
                                 let push_ms = push_start.elapsed().as_millis();
                                 if sequence < 5 || sequence % 100 == 0 {
-                                    eprintln!("[StreamPlayer] frame {} push={}ms total={}ms", sequence, push_ms, frame_start.elapsed().as_millis());
+                                    tracing::debug!("[StreamPlayer] frame {} push={}ms total={}ms", sequence, push_ms, frame_start.elapsed().as_millis());
                                 }
                                 sequence += 1;
+                                activity().success();
                                 // Detect if we're falling behind
                                 let push_elapsed = last_push_time.elapsed();
                                 last_push_time = Instant::now();
@@ -812,6 +845,7 @@ impl Extension for StreamPlayerExtension {
                             }
                             Err(e) => {
                                 tracing::warn!("[StreamPlayer] Push failed: {}", e);
+                                activity().error(format!("Push failed: {}", e));
                                 break;
                             }
                         }
@@ -883,6 +917,7 @@ impl Extension for StreamPlayerExtension {
                     }
                     FrameResult::Error(e) => {
                         tracing::error!("[StreamPlayer] Frame error: {}", e);
+                        activity().error(format!("Frame error: {}", e));
                         reconnect_count += 1;
                         if reconnect_count > MAX_RECONNECT {
                             let _ = send_push_output(
@@ -1036,4 +1071,11 @@ mod tests {
         assert_eq!(config.video_bitrate, 1500);
         assert!(config.loop_file);
     }
+
+    #[tokio::test]
+    async fn test_health_reports_ffmpeg() {
+        let ext = StreamPlayerExtension::new();
+        let health = ext.execute_command("health", &json!({})).await.unwrap();
+        assert_eq!(health["native_libs"], json!(ffmpeg_available()));
+    }
 }
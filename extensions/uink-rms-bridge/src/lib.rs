@@ -31,6 +31,7 @@ use neomind_extension_sdk::{
     ExtensionMetadata, MetricDataType, MetricDescriptor, ParameterDefinition, ParamMetricValue,
     Result,
 };
+use neomind_secrets::{Secret, SecretStore};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -41,11 +42,11 @@ use std::sync::atomic::{AtomicI64, Ordering};
 // RMS API Types (v1.0.1 compliant)
 // ============================================================================
 
-/// POST /api/v1/login request
-#[derive(Debug, Clone, Serialize)]
-struct RmsLoginRequest {
-    email: String,
-    password: String,
+/// POST /api/v1/login request (no Debug: it holds the plain credentials)
+#[derive(Serialize)]
+struct RmsLoginRequest<'a> {
+    email: &'a str,
+    password: &'a str,
 }
 
 /// POST /api/v1/login response
@@ -57,12 +58,16 @@ struct RmsLoginResponse {
     #[serde(default)]
     expires_in: Option<i64>,
     #[serde(default)]
+    #[allow(dead_code)]
     refresh_expires_in: Option<i64>,
     #[serde(default)]
+    #[allow(dead_code)]
     user_id: Option<String>,
     #[serde(default)]
+    #[allow(dead_code)]
     username: Option<String>,
     #[serde(default)]
+    #[allow(dead_code)]
     email: Option<String>,
 }
 
@@ -75,6 +80,7 @@ struct RmsRefreshResponse {
     #[serde(default)]
     expires_in: Option<i64>,
     #[serde(default)]
+    #[allow(dead_code)]
     refresh_expires_in: Option<i64>,
 }
 
@@ -88,6 +94,7 @@ struct RmsDeviceInfo {
     #[serde(default)]
     model: Option<String>,
     #[serde(default)]
+    #[allow(dead_code)]
     description: Option<String>,
     #[serde(default)]
     online_status: Option<String>,
@@ -102,8 +109,10 @@ struct RmsDeviceInfo {
     #[serde(default)]
     last_sync_at: Option<i64>,
     #[serde(default)]
+    #[allow(dead_code)]
     created_at: Option<i64>,
     #[serde(default)]
+    #[allow(dead_code)]
     updated_at: Option<i64>,
 }
 
@@ -151,10 +160,13 @@ struct RmsImageResponse {
     #[serde(default)]
     height: Option<i64>,
     #[serde(default)]
+    #[allow(dead_code)]
     dither_algorithm: Option<String>,
     #[serde(default)]
+    #[allow(dead_code)]
     resize_mode: Option<String>,
     #[serde(default)]
+    #[allow(dead_code)]
     padding_color: Option<String>,
 }
 
@@ -215,7 +227,7 @@ const FONT_PATHS: &[&str] = &[
 fn load_system_font_data() -> Result<Vec<u8>> {
     for path in FONT_PATHS {
         if let Ok(data) = std::fs::read(path) {
-            eprintln!("[uink-rms-bridge] Loaded font: {}", path);
+            tracing::debug!("[uink-rms-bridge] Loaded font: {}", path);
             return Ok(data);
         }
     }
@@ -262,7 +274,6 @@ fn parse_markdown(md: &str) -> Vec<TextBlock> {
     let mut strong_text = String::new();
     let mut in_code = false;
     let mut code_text = String::new();
-    let mut in_list_item = false;
 
     for event in parser {
         match event {
@@ -326,7 +337,6 @@ fn parse_markdown(md: &str) -> Vec<TextBlock> {
             }
             Event::Start(Tag::List(_)) => {}
             Event::Start(Tag::Item) => {
-                in_list_item = true;
                 // Start a paragraph-like block for the list item
                 in_paragraph = true;
                 paragraph_parts.clear();
@@ -334,7 +344,6 @@ fn parse_markdown(md: &str) -> Vec<TextBlock> {
                 paragraph_parts.push(TextPart::Plain("• ".to_string()));
             }
             Event::End(TagEnd::Item) => {
-                in_list_item = false;
                 in_paragraph = false;
                 if !paragraph_parts.is_empty() {
                     blocks.push(TextBlock::Paragraph {
@@ -347,7 +356,7 @@ fn parse_markdown(md: &str) -> Vec<TextBlock> {
                     code_text.push_str(&t);
                 } else if in_strong {
                     strong_text.push_str(&t);
-                } else if let Some(_) = in_heading {
+                } else if in_heading.is_some() {
                     heading_text.push_str(&t);
                 } else {
                     paragraph_parts.push(TextPart::Plain(t.into_string()));
@@ -427,7 +436,7 @@ fn render_text_to_image(
     let font = load_font(font_data)?;
 
     // Calculate font size based on display dimensions
-    let line_height = (height as f32 / 20.0).min(48.0).max(16.0);
+    let line_height = (height as f32 / 20.0).clamp(16.0, 48.0);
     let font_size = line_height * 0.75;
     let scale = PxScale::from(font_size);
 
@@ -484,7 +493,7 @@ fn render_markdown_to_image(
     let margin_x = (width as f32 * 0.08).max(20.0) as u32;
     let margin_y = (height as f32 * 0.06).max(16.0) as u32;
     let text_width = width - margin_x * 2;
-    let base_font_size = (height as f32 / 20.0).min(48.0).max(16.0) * 0.75;
+    let base_font_size = (height as f32 / 20.0).clamp(16.0, 48.0) * 0.75;
 
     let mut img = ImageBuffer::<Rgb<u8>, Vec<u8>>::from_pixel(
         width, height, Rgb([255, 255, 255]),
@@ -681,13 +690,13 @@ struct RmsDisplayInfo {
     refresh_count: Option<i64>,
 }
 
-/// Extension configuration
+/// Extension configuration; `email` and `password` are redacted in Debug and Serialize output
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UinkConfig {
     pub server_region: String,
     pub custom_server_url: String,
-    pub email: String,
-    pub password: String,
+    pub email: Secret,
+    pub password: Secret,
     #[serde(default = "default_sync_interval")]
     pub sync_interval_secs: u64,
     #[serde(default = "default_poll_interval")]
@@ -702,8 +711,8 @@ impl Default for UinkConfig {
         Self {
             server_region: "China".to_string(),
             custom_server_url: String::new(),
-            email: String::new(),
-            password: String::new(),
+            email: Secret::default(),
+            password: Secret::default(),
             sync_interval_secs: 300,
             poll_interval_secs: 60,
         }
@@ -746,8 +755,15 @@ pub struct UinkRmsBridge {
 
 impl UinkRmsBridge {
     pub fn new() -> Self {
+        neomind_logging::init();
+        let mut config = UinkConfig::default();
+        match SecretStore::from_env("uink-rms-bridge") {
+            Ok(Some(store)) => load_credentials(&mut config, &store),
+            Ok(None) => {}
+            Err(e) => tracing::warn!("[uink-rms-bridge] Stored credentials not loaded: {}", e),
+        }
         Self {
-            config: RwLock::new(UinkConfig::default()),
+            config: RwLock::new(config),
             access_token: RwLock::new(None),
             refresh_token: RwLock::new(None),
             token_expiry: AtomicI64::new(0),
@@ -791,6 +807,20 @@ impl UinkRmsBridge {
     // Authentication
     // ========================================================================
 
+    /// Apply `email`/`password` from a configure payload and keep them in
+    /// secrets.json. Credentials missing from the payload stay as they are;
+    /// an explicit empty string clears one and removes it from the file.
+    fn update_credentials(&self, cfg: &mut UinkConfig, config: &serde_json::Value) {
+        let store = match SecretStore::from_env("uink-rms-bridge") {
+            Ok(store) => store,
+            Err(e) => {
+                tracing::warn!("[uink-rms-bridge] Credentials not persisted: {}", e);
+                None
+            }
+        };
+        apply_credentials(cfg, config, store.as_ref());
+    }
+
     fn ensure_token(&self) -> Result<()> {
         let now = Utc::now().timestamp();
         let expiry = self.token_expiry.load(Ordering::SeqCst);
@@ -807,11 +837,9 @@ impl UinkRmsBridge {
             )));
         }
 
-        if self.refresh_token.read().is_some() {
-            if self.refresh().is_ok() {
-                self.last_login_failure_ts.store(0, Ordering::SeqCst);
-                return Ok(());
-            }
+        if self.refresh_token.read().is_some() && self.refresh().is_ok() {
+            self.last_login_failure_ts.store(0, Ordering::SeqCst);
+            return Ok(());
         }
         let result = self.login();
         if result.is_err() {
@@ -830,8 +858,8 @@ impl UinkRmsBridge {
         }
         let url = format!("{}/api/v1/login", base_url);
         let body = RmsLoginRequest {
-            email: config.email.clone(),
-            password: config.password.clone(),
+            email: config.email.expose(),
+            password: config.password.expose(),
         };
         let response: RmsLoginResponse = ureq::post(&url)
             .send_json(&body)
@@ -843,7 +871,7 @@ impl UinkRmsBridge {
         *self.refresh_token.write() = response.refresh_token;
         let expires_in = response.expires_in.unwrap_or(3600);
         self.token_expiry.store(Utc::now().timestamp() + expires_in - 120, Ordering::SeqCst);
-        eprintln!("[uink-rms-bridge] Logged in as {} (token expires in {}s)", response.email.as_deref().unwrap_or("unknown"), expires_in);
+        tracing::info!("[uink-rms-bridge] Logged in (token expires in {}s)", expires_in);
         Ok(())
     }
 
@@ -854,13 +882,13 @@ impl UinkRmsBridge {
         let body = json!({ "refresh_token": rt });
         let response: RmsRefreshResponse = ureq::post(&url)
             .send_json(&body)
-            .map_err(|e| { eprintln!("[uink-rms-bridge] Token refresh failed: {}", e); ExtensionError::ExecutionFailed(format!("Token refresh failed: {}", e)) })?
+            .map_err(|e| { tracing::warn!("[uink-rms-bridge] Token refresh failed: {}", e); ExtensionError::ExecutionFailed(format!("Token refresh failed: {}", e)) })?
             .into_json().map_err(|e| ExtensionError::ExecutionFailed(format!("Parse refresh response: {}", e)))?;
         *self.access_token.write() = Some(response.access_token);
         *self.refresh_token.write() = response.refresh_token;
         let expires_in = response.expires_in.unwrap_or(3600);
         self.token_expiry.store(Utc::now().timestamp() + expires_in - 120, Ordering::SeqCst);
-        eprintln!("[uink-rms-bridge] Token refreshed (expires in {}s)", expires_in);
+        tracing::debug!("[uink-rms-bridge] Token refreshed (expires in {}s)", expires_in);
         Ok(())
     }
 
@@ -922,6 +950,7 @@ impl UinkRmsBridge {
 
         for rms_id in &rms_ids {
             let neo_device_id = format!("uink-{}", rms_id);
+            let _span = neomind_logging::binding_span(&neo_device_id).entered();
             match self.fetch_device_telemetry(rms_id) {
                 Ok(telemetry) => {
                     let battery = telemetry.battery.unwrap_or(-1);
@@ -951,7 +980,7 @@ impl UinkRmsBridge {
                         }));
                         if !result.get("success").and_then(|v| v.as_bool()).unwrap_or(false) {
                             let err = result.get("error").and_then(|v| v.as_str()).unwrap_or("unknown");
-                            eprintln!("[uink-rms-bridge] Failed to write metric {} for {}: {}", name, neo_device_id, err);
+                            tracing::warn!("[uink-rms-bridge] Failed to write metric {} for {}: {}", name, neo_device_id, err);
                         }
                     };
 
@@ -973,12 +1002,12 @@ impl UinkRmsBridge {
                             }
                         }
                         Err(e) => {
-                            eprintln!("[uink-rms-bridge] Display fetch failed for {}: {}", rms_id, e);
+                            tracing::warn!("[uink-rms-bridge] Display fetch failed for {}: {}", rms_id, e);
                         }
                     }
                 }
                 Err(e) => {
-                    eprintln!("[uink-rms-bridge] Telemetry failed for {}: {}", rms_id, e);
+                    tracing::warn!("[uink-rms-bridge] Telemetry failed for {}: {}", rms_id, e);
                     self.total_error_count.fetch_add(1, Ordering::SeqCst);
                 }
             }
@@ -1027,7 +1056,7 @@ impl UinkRmsBridge {
 
         // Image file part
         body.extend_from_slice(format!("--{}\r\n", boundary).as_bytes());
-        body.extend_from_slice(format!("Content-Disposition: form-data; name=\"image\"; filename=\"image.png\"\r\n").as_bytes());
+        body.extend_from_slice(b"Content-Disposition: form-data; name=\"image\"; filename=\"image.png\"\r\n");
         body.extend_from_slice("Content-Type: image/png\r\n\r\n".as_bytes());
         body.extend_from_slice(image_data);
         body.extend_from_slice("\r\n".as_bytes());
@@ -1087,7 +1116,7 @@ impl UinkRmsBridge {
         let mut body = Vec::new();
 
         body.extend_from_slice(format!("--{}\r\n", boundary).as_bytes());
-        body.extend_from_slice(format!("Content-Disposition: form-data; name=\"image\"; filename=\"image.png\"\r\n").as_bytes());
+        body.extend_from_slice(b"Content-Disposition: form-data; name=\"image\"; filename=\"image.png\"\r\n");
         body.extend_from_slice("Content-Type: image/png\r\n\r\n".as_bytes());
         body.extend_from_slice(image_data);
         body.extend_from_slice("\r\n".as_bytes());
@@ -1129,6 +1158,65 @@ impl Default for UinkRmsBridge {
     fn default() -> Self { Self::new() }
 }
 
+/// Credential keys kept in secrets.json
+const CREDENTIAL_KEYS: [&str; 2] = ["email", "password"];
+
+fn credential_mut<'a>(cfg: &'a mut UinkConfig, key: &str) -> &'a mut Secret {
+    match key {
+        "email" => &mut cfg.email,
+        _ => &mut cfg.password,
+    }
+}
+
+/// Fill `cfg` with the credentials stored in secrets.json.
+fn load_credentials(cfg: &mut UinkConfig, store: &SecretStore) {
+    match store.load() {
+        Ok(stored) => {
+            for key in CREDENTIAL_KEYS {
+                if let Some(value) = stored.get(key) {
+                    *credential_mut(cfg, key) = value.clone();
+                }
+            }
+        }
+        Err(e) => tracing::warn!("[uink-rms-bridge] Failed to load stored credentials: {}", e),
+    }
+}
+
+/// Apply the credentials in a configure payload to `cfg` and mirror the
+/// change into `store`: a new value is saved, an empty one removed.
+fn apply_credentials(cfg: &mut UinkConfig, config: &serde_json::Value, store: Option<&SecretStore>) {
+    let updates: Vec<(&str, Secret)> = CREDENTIAL_KEYS
+        .iter()
+        .filter_map(|key| neomind_secrets::from_config(config, key).map(|v| (*key, v)))
+        .collect();
+    if updates.is_empty() {
+        return;
+    }
+    for (key, value) in &updates {
+        *credential_mut(cfg, key) = value.clone();
+    }
+
+    let Some(store) = store else { return };
+    // Don't overwrite a file we couldn't read
+    let mut stored = match store.load() {
+        Ok(stored) => stored,
+        Err(e) => {
+            tracing::warn!("[uink-rms-bridge] Failed to load stored credentials: {}", e);
+            return;
+        }
+    };
+    for (key, value) in updates {
+        if value.is_empty() {
+            stored.remove(key);
+        } else {
+            stored.insert(key.to_string(), value);
+        }
+    }
+    if let Err(e) = store.save(&stored) {
+        tracing::warn!("[uink-rms-bridge] {}", e);
+    }
+}
+
 // ============================================================================
 // Extension Trait Implementation
 // ============================================================================
@@ -1214,6 +1302,7 @@ impl Extension for UinkRmsBridge {
             MetricDescriptor { name: "push_count".into(), display_name: "Push Count".into(), data_type: MetricDataType::Integer, unit: "count".into(), min: Some(0.0), max: None, required: false },
             MetricDescriptor { name: "device_count".into(), display_name: "Device Count".into(), data_type: MetricDataType::Integer, unit: "count".into(), min: Some(0.0), max: None, required: false },
             MetricDescriptor { name: "error_count".into(), display_name: "Error Count".into(), data_type: MetricDataType::Integer, unit: "count".into(), min: Some(0.0), max: None, required: false },
+            neomind_health::metric_descriptor(),
         ]
     }
 
@@ -1418,6 +1507,16 @@ impl Extension for UinkRmsBridge {
                 samples: vec![json!({})],
                 parameter_groups: vec![],
             },
+            ExtensionCommand {
+                name: "get_config".into(),
+                display_name: "Get Config".into(),
+                description: "Get the current configuration; email and password are redacted".into(),
+                payload_template: String::new(),
+                parameters: vec![],
+                fixed_values: Default::default(),
+                samples: vec![json!({})],
+                parameter_groups: vec![],
+            },
             ExtensionCommand {
                 name: "get_display".into(),
                 display_name: "Get Display".into(),
@@ -1439,6 +1538,7 @@ impl Extension for UinkRmsBridge {
                 samples: vec![json!({"device_id": "uink-dev_abc123"})],
                 parameter_groups: vec![],
             },
+            neomind_health::command(),
         ]
     }
 
@@ -1446,11 +1546,11 @@ impl Extension for UinkRmsBridge {
         match command {
             "configure" => {
                 // Handle configure dispatched via execute_command (used during reload)
+                neomind_logging::apply_config("uink-rms-bridge", args);
                 let mut cfg = self.config.write();
                 if let Some(v) = args.get("server_region").and_then(|v| v.as_str()) { cfg.server_region = v.to_string(); }
                 if let Some(v) = args.get("custom_server_url").and_then(|v| v.as_str()) { cfg.custom_server_url = v.trim_end_matches('/').to_string(); }
-                if let Some(v) = args.get("email").and_then(|v| v.as_str()) { cfg.email = v.to_string(); }
-                if let Some(v) = args.get("password").and_then(|v| v.as_str()) { cfg.password = v.to_string(); }
+                self.update_credentials(&mut cfg, args);
                 if let Some(v) = args.get("sync_interval_secs").and_then(|v| v.as_u64()) { cfg.sync_interval_secs = v; }
                 if let Some(v) = args.get("poll_interval_secs").and_then(|v| v.as_u64()) { cfg.poll_interval_secs = v; }
                 drop(cfg);
@@ -1459,7 +1559,7 @@ impl Extension for UinkRmsBridge {
                 self.token_expiry.store(0, Ordering::SeqCst);
                 self.template_registered.store(0, Ordering::SeqCst);
                 self.last_sync_ts.store(0, Ordering::SeqCst);
-                eprintln!("[uink-rms-bridge] Configuration applied via execute_command");
+                tracing::info!("[uink-rms-bridge] Configuration applied via execute_command");
                 Ok(json!({"success": true}))
             }
             "sync_devices" => self.cmd_sync_devices(args).await,
@@ -1470,6 +1570,8 @@ impl Extension for UinkRmsBridge {
             "get_display_size" => self.cmd_get_display_size(args),
             "get_display" => self.cmd_get_display(args),
             "refresh_auth" => self.cmd_refresh_auth().await,
+            "get_config" => self.cmd_get_config(),
+            "health" => Ok(self.health().to_json()),
             _ => Err(ExtensionError::CommandNotFound(command.to_string())),
         }
     }
@@ -1489,7 +1591,7 @@ impl Extension for UinkRmsBridge {
 
             if should_sync {
                 if let Err(e) = self.auto_sync() {
-                    eprintln!("[uink-rms-bridge] Auto-sync failed: {}", e);
+                    tracing::warn!("[uink-rms-bridge] Auto-sync failed: {}", e);
                     *self.last_error.write() = Some(format!("Auto-sync: {}", e));
                     self.total_error_count.fetch_add(1, Ordering::SeqCst);
                 } else {
@@ -1502,7 +1604,7 @@ impl Extension for UinkRmsBridge {
         if configured && (now_ts - self.last_poll_ts.load(Ordering::SeqCst)) >= poll_interval {
             if let Err(e) = self.poll_all_telemetry() {
                 *self.last_error.write() = Some(format!("{}", e));
-            } else if self.last_error.read().as_ref().map_or(true, |e| e.starts_with("Auto-sync")) {
+            } else if self.last_error.read().as_ref().is_none_or(|e| e.starts_with("Auto-sync")) {
                 *self.last_error.write() = None;
             }
         }
@@ -1513,6 +1615,7 @@ impl Extension for UinkRmsBridge {
             ExtensionMetricValue { name: "push_count".into(), value: ParamMetricValue::Integer(self.total_push_count.load(Ordering::SeqCst)), timestamp: now },
             ExtensionMetricValue { name: "device_count".into(), value: ParamMetricValue::Integer(self.device_ids.read().len() as i64), timestamp: now },
             ExtensionMetricValue { name: "error_count".into(), value: ParamMetricValue::Integer(self.total_error_count.load(Ordering::SeqCst)), timestamp: now },
+            self.health().metric(),
         ];
 
         // Per-device telemetry is now written via device_metrics_write capability
@@ -1521,11 +1624,11 @@ impl Extension for UinkRmsBridge {
     }
 
     async fn configure(&mut self, config: &serde_json::Value) -> Result<()> {
+        neomind_logging::apply_config("uink-rms-bridge", config);
         let mut cfg = self.config.write();
         if let Some(v) = config.get("server_region").and_then(|v| v.as_str()) { cfg.server_region = v.to_string(); }
         if let Some(v) = config.get("custom_server_url").and_then(|v| v.as_str()) { cfg.custom_server_url = v.trim_end_matches('/').to_string(); }
-        if let Some(v) = config.get("email").and_then(|v| v.as_str()) { cfg.email = v.to_string(); }
-        if let Some(v) = config.get("password").and_then(|v| v.as_str()) { cfg.password = v.to_string(); }
+        self.update_credentials(&mut cfg, config);
         if let Some(v) = config.get("sync_interval_secs").and_then(|v| v.as_u64()) { cfg.sync_interval_secs = v; }
         if let Some(v) = config.get("poll_interval_secs").and_then(|v| v.as_u64()) { cfg.poll_interval_secs = v; }
         drop(cfg);
@@ -1535,7 +1638,7 @@ impl Extension for UinkRmsBridge {
         // Reset sync state so auto-sync runs immediately with new config
         self.template_registered.store(0, Ordering::SeqCst);
         self.last_sync_ts.store(0, Ordering::SeqCst);
-        eprintln!("[uink-rms-bridge] Configuration updated");
+        tracing::info!("[uink-rms-bridge] Configuration updated");
         Ok(())
     }
 
@@ -1596,11 +1699,11 @@ impl UinkRmsBridge {
             });
             let result = ctx.invoke_capability("device_template_register", &template_json);
             if result.get("success").and_then(|v| v.as_bool()).unwrap_or(false) {
-                eprintln!("[uink-rms-bridge] Template registered");
+                tracing::info!("[uink-rms-bridge] Template registered");
                 self.template_registered.store(1, Ordering::SeqCst);
             } else {
                 let err = result.get("error").and_then(|v| v.as_str()).unwrap_or("unknown");
-                eprintln!("[uink-rms-bridge] Template registration failed: {}", err);
+                tracing::warn!("[uink-rms-bridge] Template registration failed: {}", err);
                 return Err(ExtensionError::ExecutionFailed(format!("Template registration failed: {}", err)));
             }
         }
@@ -1622,7 +1725,7 @@ impl UinkRmsBridge {
                 registered += 1;
             } else {
                 let err = result.get("error").and_then(|v| v.as_str()).unwrap_or("unknown");
-                eprintln!("[uink-rms-bridge] Device {} register skipped: {}", neo_device_id, err);
+                tracing::debug!("[uink-rms-bridge] Device {} register skipped: {}", neo_device_id, err);
                 skipped += 1;
             }
 
@@ -1713,7 +1816,7 @@ impl UinkRmsBridge {
         self.total_sync_count.fetch_add(1, Ordering::SeqCst);
         self.last_sync_ts.store(Utc::now().timestamp(), Ordering::SeqCst);
 
-        eprintln!("[uink-rms-bridge] Auto-sync: {} registered, {} skipped, {} total",
+        tracing::debug!("[uink-rms-bridge] Auto-sync: {} registered, {} skipped, {} total",
             registered, skipped, devices.len());
         Ok(())
     }
@@ -1758,11 +1861,11 @@ impl UinkRmsBridge {
 
         let template_result = ctx.invoke_capability("device_template_register", &template_json);
         if template_result.get("success").and_then(|v| v.as_bool()).unwrap_or(false) {
-            eprintln!("[uink-rms-bridge] Template registered");
+            tracing::info!("[uink-rms-bridge] Template registered");
             self.template_registered.store(1, Ordering::SeqCst);
         } else {
             let err = template_result.get("error").and_then(|v| v.as_str()).unwrap_or("unknown");
-            eprintln!("[uink-rms-bridge] Template registration failed: {}", err);
+            tracing::warn!("[uink-rms-bridge] Template registration failed: {}", err);
         }
 
         // Fetch devices from RMS
@@ -1782,7 +1885,7 @@ impl UinkRmsBridge {
                 registered += 1;
             } else {
                 let err = result.get("error").and_then(|v| v.as_str()).unwrap_or("unknown");
-                eprintln!("[uink-rms-bridge] Device {} register skipped: {}", neo_device_id, err);
+                tracing::debug!("[uink-rms-bridge] Device {} register skipped: {}", neo_device_id, err);
                 skipped += 1;
             }
 
@@ -2034,6 +2137,32 @@ impl UinkRmsBridge {
         Ok(json!({ "success": true, "message": "Authentication refreshed" }))
     }
 
+    fn cmd_get_config(&self) -> Result<serde_json::Value> {
+        Ok(json!({
+            "success": true,
+            "config": *self.config.read(),
+            "logged_in": self.access_token.read().is_some(),
+        }))
+    }
+
+    /// Health report for the `health` command and `health_state` metric
+    fn health(&self) -> neomind_health::Health {
+        let config = self.config.read();
+        let configured = !config.api_base_url().is_empty() && !config.email.is_empty();
+        drop(config);
+
+        // Sync and poll timestamps are in seconds
+        let last_success = self.last_sync_ts.load(Ordering::SeqCst).max(self.last_poll_ts.load(Ordering::SeqCst));
+        let health = neomind_health::Health::new()
+            .last_success(Some(last_success * 1000).filter(|ms| *ms > 0))
+            .errors(self.total_error_count.load(Ordering::SeqCst) as u64, self.last_error.read().clone());
+        if configured {
+            health
+        } else {
+            health.degraded("RMS server or credentials not configured")
+        }
+    }
+
     fn cmd_list_devices(&self) -> Result<serde_json::Value> {
         let ids = self.device_ids.read();
         let names = self.rms_device_names.read();
@@ -2118,7 +2247,7 @@ mod tests {
     fn test_commands_count() {
         let ext = UinkRmsBridge::new();
         let commands = ext.commands();
-        assert_eq!(commands.len(), 7);
+        assert_eq!(commands.len(), 9);
         let names: Vec<&str> = commands.iter().map(|c| c.name.as_str()).collect();
         assert!(names.contains(&"sync_devices"));
         assert!(names.contains(&"list_devices"));
@@ -2126,7 +2255,9 @@ mod tests {
         assert!(names.contains(&"push_image"));
         assert!(names.contains(&"get_display_size"));
         assert!(names.contains(&"refresh_auth"));
+        assert!(names.contains(&"get_config"));
         assert!(names.contains(&"get_display"));
+        assert!(names.contains(&"health"));
     }
 
     #[test]
@@ -2143,9 +2274,55 @@ mod tests {
     }
 
     #[test]
-    fn test_api_base_url() {
+    fn test_credentials_are_redacted() {
+        let ext = UinkRmsBridge::new();
+        {
+            let mut cfg = ext.config.write();
+            ext.update_credentials(&mut cfg, &json!({"email": "ops@example.com", "password": "hunter2"}));
+            // Sending back the redacted value keeps the password
+            ext.update_credentials(&mut cfg, &json!({"password": neomind_secrets::REDACTED}));
+            assert_eq!(cfg.password.expose(), "hunter2");
+        }
+
+        let response = ext.cmd_get_config().unwrap().to_string();
+        assert!(!response.contains("hunter2"));
+        assert!(!response.contains("ops@example.com"));
+        assert!(!format!("{:?}", *ext.config.read()).contains("hunter2"));
+    }
+
+    #[test]
+    fn test_credentials_persist_and_clear() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = SecretStore::new(dir.path().join("secrets.json"), None, "uink-rms-bridge").unwrap();
+
         let mut cfg = UinkConfig::default();
-        cfg.server_region = "China".into();
+        apply_credentials(&mut cfg, &json!({"email": "ops@example.com", "password": "hunter2"}), Some(&store));
+
+        // A fresh instance starts with the stored credentials
+        let mut restarted = UinkConfig::default();
+        load_credentials(&mut restarted, &store);
+        assert_eq!(restarted.email.expose(), "ops@example.com");
+        assert_eq!(restarted.password.expose(), "hunter2");
+
+        // Missing keys are left alone, an empty string clears
+        apply_credentials(&mut restarted, &json!({"password": ""}), Some(&store));
+        assert_eq!(restarted.email.expose(), "ops@example.com");
+        assert!(restarted.password.is_empty());
+        let stored = store.load().unwrap();
+        assert!(stored.contains_key("email"));
+        assert!(!stored.contains_key("password"));
+
+        let mut restarted = UinkConfig::default();
+        load_credentials(&mut restarted, &store);
+        assert!(restarted.password.is_empty());
+    }
+
+    #[test]
+    fn test_api_base_url() {
+        let mut cfg = UinkConfig {
+            server_region: "China".into(),
+            ..Default::default()
+        };
         assert_eq!(cfg.api_base_url(), "https://cn.rms.uink.com");
         cfg.server_region = "Europe".into();
         assert_eq!(cfg.api_base_url(), "https://eu.rms.uink.com");
@@ -2187,6 +2364,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_render_markdown_golden() {
+        let md = "# Meeting Room A\n\n## Today\n\nStand-up at **09:30**, review in `B-204`.\n\n- Projector booked\n- A long line that has to wrap onto the next line to stay inside the margins of the display";
+        let png = render_markdown_to_image(md, 400, 300, neomind_golden::font_data()).unwrap();
+        let img = image::load_from_memory_with_format(&png, image::ImageFormat::Png).unwrap();
+        neomind_golden::assert_golden!("markdown_render", img);
+    }
+
     #[test]
     fn test_parse_markdown_bold() {
         let md = "Hello **world** and `code`";
@@ -2246,5 +2431,9 @@ mod tests {
         let ext = UinkRmsBridge::new();
         let metrics = ext.produce_metrics().unwrap();
         assert!(metrics.len() >= 4);
+
+        let health = ext.health();
+        assert_eq!(health.state, neomind_health::HealthState::Degraded);
+        assert_eq!(health.last_success_ms, None);
     }
 }
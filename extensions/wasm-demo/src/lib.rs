@@ -105,6 +105,7 @@ impl Extension for WasmDemoExtension {
                 max: None,
                 required: false,
             },
+            neomind_health::metric_descriptor(),
         ]
     }
 
@@ -170,6 +171,7 @@ impl Extension for WasmDemoExtension {
                 samples: vec![json!({})],
                 parameter_groups: Vec::new(),
             },
+            neomind_health::command(),
         ]
     }
 
@@ -207,6 +209,8 @@ impl Extension for WasmDemoExtension {
                     "request_count": self.request_count.load(Ordering::SeqCst)
                 }))
             }
+            // Nothing here can fail, so the demo is always healthy
+            "health" => Ok(neomind_health::Health::new().to_json()),
             "configure" => {
                 // Accept config silently - can be extended for real config handling
                 Ok(json!({"status": "ok"}))
@@ -229,6 +233,7 @@ impl Extension for WasmDemoExtension {
                 value: ParamMetricValue::Integer(self.request_count.load(Ordering::SeqCst)),
                 timestamp: now,
             },
+            neomind_health::Health::new().metric(),
         ])
     }
 
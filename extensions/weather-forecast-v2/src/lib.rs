@@ -89,6 +89,8 @@ pub struct WeatherExtension {
     last_pressure_hpa: AtomicI64,
     last_update_ts: AtomicI64,
     has_data: AtomicBool,
+    /// Weather fetch successes and failures for the health report
+    activity: neomind_health::Activity,
 }
 
 impl WeatherExtension {
@@ -105,6 +107,7 @@ impl WeatherExtension {
             last_pressure_hpa: AtomicI64::new(101325),
             last_update_ts: AtomicI64::new(0),
             has_data: AtomicBool::new(false),
+            activity: neomind_health::Activity::new(),
         }
     }
 
@@ -133,13 +136,16 @@ impl WeatherExtension {
         self.request_count.fetch_add(1, Ordering::SeqCst);
 
         let location = self.geocode_sync(city)
+            .inspect_err(|e| self.activity.error(e))
             .map_err(|e| ExtensionError::ExecutionFailed(e))?;
 
         let mut weather = self.fetch_weather_sync(&location)
+            .inspect_err(|e| self.activity.error(e))
             .map_err(|e| ExtensionError::ExecutionFailed(e))?;
 
         weather.timestamp = Some(chrono::Utc::now().to_rfc3339());
         self.store_weather_metrics(&weather);
+        self.activity.success();
 
         Ok(weather)
     }
@@ -355,6 +361,7 @@ impl Extension for WeatherExtension {
                 max: None,
                 required: false,
             },
+            neomind_health::metric_descriptor(),
         ]
     }
 
@@ -417,6 +424,7 @@ impl Extension for WeatherExtension {
                 samples: vec![json!({ "city": "Shanghai" })],
                 parameter_groups: Vec::new(),
             },
+            neomind_health::command(),
         ]
     }
 
@@ -454,6 +462,8 @@ impl Extension for WeatherExtension {
                 }))
             }
 
+            "health" => Ok(neomind_health::Health::new().activity(&self.activity).to_json()),
+
             "configure" => {
                 // Accept config silently - can be extended for real config handling
                 Ok(json!({"status": "ok"}))
@@ -465,13 +475,14 @@ impl Extension for WeatherExtension {
 
     fn produce_metrics(&self) -> Result<Vec<ExtensionMetricValue>> {
         let now = chrono::Utc::now().timestamp_millis();
-        let mut metrics = Vec::with_capacity(9);
+        let mut metrics = Vec::with_capacity(10);
 
         metrics.push(ExtensionMetricValue {
             name: "request_count".to_string(),
             value: ParamMetricValue::Integer(self.request_count.load(Ordering::SeqCst)),
             timestamp: now,
         });
+        metrics.push(neomind_health::Health::new().activity(&self.activity).metric());
 
         if self.has_data.load(Ordering::SeqCst) {
             metrics.extend(vec![
@@ -624,25 +635,27 @@ mod tests {
     fn test_extension_metrics() {
         let ext = WeatherExtension::new();
         let metrics = ext.metrics();
-        assert_eq!(metrics.len(), 9);
+        assert_eq!(metrics.len(), 10);
     }
 
     #[test]
     fn test_extension_commands() {
         let ext = WeatherExtension::new();
         let commands = ext.commands();
-        assert_eq!(commands.len(), 3);
+        assert_eq!(commands.len(), 4);
         assert!(commands.iter().any(|c| c.name == "get_weather"));
         assert!(commands.iter().any(|c| c.name == "refresh"));
         assert!(commands.iter().any(|c| c.name == "set_default_city"));
+        assert!(commands.iter().any(|c| c.name == "health"));
     }
 
     #[test]
     fn test_produce_metrics_without_data() {
         let ext = WeatherExtension::new();
         let metrics = ext.produce_metrics().unwrap();
-        assert_eq!(metrics.len(), 1);
+        assert_eq!(metrics.len(), 2);
         assert_eq!(metrics[0].name, "request_count");
+        assert_eq!(metrics[1].name, "health_state");
     }
 
     #[test]
@@ -667,7 +680,7 @@ mod tests {
         ext.store_weather_metrics(&weather);
 
         let metrics = ext.produce_metrics().unwrap();
-        assert_eq!(metrics.len(), 9);
+        assert_eq!(metrics.len(), 10);
 
         let temp_metric = metrics.iter().find(|m| m.name == "temperature_c").unwrap();
         if let ParamMetricValue::Float(temp) = temp_metric.value {
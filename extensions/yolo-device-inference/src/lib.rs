@@ -21,8 +21,9 @@ use neomind_extension_sdk::{
     MetricDescriptor, ExtensionCommand, MetricDataType, ParameterDefinition,
     ParamMetricValue, Result,
 };
-use neomind_extension_sdk::capabilities::CapabilityContext;
 use neomind_extension_sdk::prelude::*;
+use neomind_binding::image::jpeg_data_url;
+use neomind_binding::{BindingRegistry, BindingSettings};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
@@ -32,64 +33,47 @@ use parking_lot::{Mutex, RwLock};
 use std::sync::Arc;
 
 #[cfg(not(target_arch = "wasm32"))]
-use usls::{models::YOLO, Config, DataLoader, Device, Version as YOLOVersion};
-
-/// Auto-detect best available inference device.
-/// macOS → CoreML, Linux → CUDA, others → CPU.
+use usls::{models::YOLO, Config, DataLoader, Version as YOLOVersion};
 #[cfg(not(target_arch = "wasm32"))]
-fn auto_device() -> Device {
-    #[cfg(target_os = "macos")]
-    { Device::CoreMl }
-    #[cfg(all(not(target_os = "macos"), target_os = "linux"))]
-    { Device::Cuda(0) }
-    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
-    { Device::Cpu(0) }
-}
+use neomind_vision::{device::with_device_fallback, native::setup_native_lib_paths};
 
-/// Try building a model with the auto-detected device, fall back to CPU on failure.
-#[cfg(not(target_arch = "wasm32"))]
-fn with_device_fallback<M, F>(try_build: F) -> std::result::Result<M, String>
-where
-    F: Fn(Device) -> std::result::Result<M, String>,
-{
-    let device = auto_device();
-    eprintln!("[HW] Trying device: {:?}", device);
-    match try_build(device) {
-        Ok(model) => {
-            eprintln!("[HW] Model loaded with device: {:?}", device);
-            Ok(model)
-        }
-        Err(e) if !matches!(device, Device::Cpu(_)) => {
-            eprintln!("[HW] {:?} failed ({}), falling back to CPU", device, e);
-            try_build(Device::Cpu(0))
-        }
-        Err(e) => Err(e),
-    }
-}
+pub use neomind_vision::COCO_CLASSES;
 
 // ============================================================================
 // Types
 // ============================================================================
 
-/// Device binding configuration
+/// Per-binding YOLO settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct DeviceBinding {
-    /// Device ID
-    pub device_id: String,
-    /// Device name (for display)
-    pub device_name: Option<String>,
-    /// Image data source metric name (e.g., "image", "frame", "snapshot")
-    pub image_metric: String,
+pub struct YoloBindingSettings {
     /// Virtual metric name prefix for storing results
     pub result_metric_prefix: String,
     /// Confidence threshold (0.0 - 1.0)
     pub confidence_threshold: f32,
     /// Whether to draw detection boxes on images
     pub draw_boxes: bool,
-    /// Whether the binding is active
-    pub active: bool,
 }
 
+impl BindingSettings for YoloBindingSettings {
+    fn from_args(args: &serde_json::Value) -> Self {
+        Self {
+            result_metric_prefix: args.get("result_metric_prefix")
+                .and_then(|v| v.as_str())
+                .unwrap_or("yolo_").to_string(),
+            confidence_threshold: args.get("confidence_threshold")
+                .and_then(|v| v.as_f64())
+                .map(|v| v as f32)
+                .unwrap_or(0.25),
+            draw_boxes: args.get("draw_boxes")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true),
+        }
+    }
+}
+
+/// Device binding configuration
+pub type DeviceBinding = neomind_binding::DeviceBinding<YoloBindingSettings>;
+
 /// Detection result for a single object
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Detection {
@@ -121,25 +105,21 @@ pub struct InferenceResult {
     pub annotated_image_base64: Option<String>,
 }
 
-/// Binding status
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BindingStatus {
-    pub binding: DeviceBinding,
-    pub last_inference: Option<i64>,
-    pub total_inferences: u64,
+/// YOLO-specific part of a binding's status
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct YoloBindingStats {
     pub total_detections: u64,
-    pub last_error: Option<String>,
-    /// Last image (base64 encoded) for frontend display
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub last_image: Option<String>,
     /// Last detection results
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_detections: Option<Vec<Detection>>,
-    /// Last annotated image with detection boxes (base64 encoded)
+    /// Last annotated image with detection boxes (data URI)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_annotated_image: Option<String>,
 }
 
+/// Binding status
+pub type BindingStatus = neomind_binding::BindingStatus<YoloBindingSettings, YoloBindingStats>;
+
 /// Extension configuration for persistence
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct YoloConfig {
@@ -161,42 +141,23 @@ impl Default for YoloConfig {
     }
 }
 
-// ============================================================================
-// COCO Classes
-// ============================================================================
-
-pub const COCO_CLASSES: [&str; 80] = [
-    "person", "bicycle", "car", "motorcycle", "airplane", "bus", "train", "truck", "boat",
-    "traffic light", "fire hydrant", "stop sign", "parking meter", "bench", "bird", "cat",
-    "dog", "horse", "sheep", "cow", "elephant", "bear", "zebra", "giraffe", "backpack",
-    "umbrella", "handbag", "tie", "suitcase", "frisbee", "skis", "snowboard", "sports ball",
-    "kite", "baseball bat", "baseball glove", "skateboard", "surfboard", "tennis racket",
-    "bottle", "wine glass", "cup", "fork", "knife", "spoon", "bowl", "banana", "apple",
-    "sandwich", "orange", "broccoli", "carrot", "hot dog", "pizza", "donut", "cake",
-    "chair", "couch", "potted plant", "bed", "dining table", "toilet", "tv", "laptop",
-    "mouse", "remote", "keyboard", "cell phone", "microwave", "oven", "toaster", "sink",
-    "refrigerator", "book", "clock", "vase", "scissors", "teddy bear", "hair drier", "toothbrush",
-];
+impl neomind_binding::config::Versioned for YoloConfig {
+    const SCHEMA_VERSION: u32 = 1;
+}
 
 // ============================================================================
 // Drawing Helpers
 // ============================================================================
 
-/// Color palette for drawing boxes
-const BOX_COLORS: [(u8, u8, u8); 10] = [
-    (239, 68, 68), (34, 197, 94), (59, 130, 246), (234, 179, 8), (6, 182, 212),
-    (139, 92, 246), (236, 72, 153), (249, 115, 22), (132, 204, 22), (20, 184, 166),
-];
-
 /// Draw detections on an image
 #[cfg(not(target_arch = "wasm32"))]
 fn draw_detections_on_image(
     image_data: &[u8],
     detections: &[Detection],
 ) -> Result<String> {
-    use imageproc::drawing::{draw_hollow_rect_mut, draw_filled_rect_mut, draw_text_mut};
-    use imageproc::rect::Rect;
-    use ab_glyph::{FontRef, PxScale, Font as AbFont, ScaleFont as _};
+    use ab_glyph::FontRef;
+    use neomind_vision::draw::{draw_labeled_box, BoxStyle, PixelRect};
+    use neomind_vision::labels::index_color;
 
     // Cache font loading
     static FONT_RESULT: std::sync::OnceLock<std::result::Result<FontRef<'static>, ab_glyph::InvalidFont>> = std::sync::OnceLock::new();
@@ -209,71 +170,18 @@ fn draw_detections_on_image(
         .map_err(|e| ExtensionError::ExecutionFailed(format!("Failed to load image: {}", e)))?
         .to_rgb8();
 
-    let img_w = img.width();
-    let img_h = img.height();
-
     tracing::debug!("[YoloDeviceInference] Drawing {} detections on image {}x{}",
-        detections.len(), img_w, img_h);
+        detections.len(), img.width(), img.height());
 
     for (i, det) in detections.iter().enumerate() {
-        let color = BOX_COLORS[i % BOX_COLORS.len()];
-        let image_color = image::Rgb([color.0, color.1, color.2]);
-
-        // Clip coordinates to image bounds
-        let x = det.bbox.x.max(0.0).min(img_w as f32 - 2.0) as i32;
-        let y = det.bbox.y.max(0.0).min(img_h as f32 - 2.0) as i32;
-        let w = det.bbox.width.min(img_w as f32 - x as f32 - 1.0) as u32;
-        let h = det.bbox.height.min(img_h as f32 - y as f32 - 1.0) as u32;
-
-        if w < 2 || h < 2 {
-            continue;
-        }
-
-        // Draw bounding box (2px thick)
-        draw_hollow_rect_mut(&mut img, Rect::at(x, y).of_size(w, h), image_color);
-        draw_hollow_rect_mut(&mut img, Rect::at(x + 1, y + 1).of_size(w.saturating_sub(2), h.saturating_sub(2)), image_color);
-
-        // Draw label with text using font (matching yolo-video-v2 style)
-        if let Ok(font) = font {
-            let label_text = format!("{} {:.0}%", det.label, det.confidence * 100.0);
-            let font_size = if img_w > 1200 { 24.0 } else if img_w > 800 { 18.0 } else if w > 100 { 14.0 } else { 11.0 };
-            let scale = PxScale::from(font_size);
-
-            let scaled_font = font.as_scaled(scale);
-            let mut text_width: f32 = 0.0;
-            for c in label_text.chars() {
-                let glyph_id = scaled_font.glyph_id(c);
-                text_width += scaled_font.h_advance(glyph_id);
-            }
-            let label_width = (text_width.ceil() as u32 + 12).min(img_w.saturating_sub(x as u32));
-            let label_height = (font_size as u32) + 8;
-
-            // Position label above the box, or inside if no room above
-            let label_y = if y >= label_height as i32 {
-                y - label_height as i32
-            } else {
-                y
-            };
-
-            if label_y >= 0 && (label_y as u32) + label_height <= img_h {
-                // Filled background for label
-                draw_filled_rect_mut(
-                    &mut img,
-                    Rect::at(x, label_y).of_size(label_width, label_height),
-                    image_color,
-                );
-                // White text on colored background
-                draw_text_mut(
-                    &mut img,
-                    image::Rgb([255, 255, 255]),
-                    x + 5,
-                    label_y + 3,
-                    scale,
-                    font,
-                    &label_text,
-                );
-            }
-        }
+        let rect = PixelRect {
+            x: det.bbox.x,
+            y: det.bbox.y,
+            width: det.bbox.width,
+            height: det.bbox.height,
+        };
+        let label = format!("{} {:.0}%", det.label, det.confidence * 100.0);
+        draw_labeled_box(&mut img, rect, &label, &BoxStyle::solid(index_color(i)), font.as_ref().ok());
     }
 
     // Encode to JPEG and return base64
@@ -292,128 +200,36 @@ fn draw_detections_on_image(
 }
 
 // ============================================================================
-// Native Library Path Setup
+// ObjectDetect - Detection backend
 // ============================================================================
 
-/// Set up native library search paths before ONNX Runtime is loaded.
-/// Checks NEOMIND_EXTENSION_DIR/lib/ and common system paths.
-#[cfg(not(target_arch = "wasm32"))]
-fn setup_native_lib_paths() {
-    let lib_env = if cfg!(target_os = "macos") {
-        "DYLD_LIBRARY_PATH"
-    } else if cfg!(target_os = "windows") {
-        "PATH"
-    } else {
-        "LD_LIBRARY_PATH"
-    };
-
-    let mut paths = vec![];
-
-    // 1. Extension's bundled libraries - check all possible locations
-    if let Ok(ext_dir) = std::env::var("NEOMIND_EXTENSION_DIR") {
-        let ext_path = std::path::Path::new(&ext_dir);
-
-        // lib/ directory (top-level)
-        let lib_dir = ext_path.join("lib");
-        if lib_dir.is_dir() {
-            tracing::info!("[NativeLibs] Adding extension lib dir: {}", lib_dir.display());
-            paths.push(lib_dir.to_string_lossy().to_string());
-        }
-
-        // binaries/<platform>/ directory (where extension.dylib and bundled libs live)
-        let binaries_dir = ext_path.join("binaries");
-        if binaries_dir.is_dir() {
-            // Add all subdirectories (darwin_aarch64, linux_amd64, etc.)
-            if let Ok(entries) = std::fs::read_dir(&binaries_dir) {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    if path.is_dir() {
-                        tracing::info!("[NativeLibs] Adding platform dir: {}", path.display());
-                        paths.push(path.to_string_lossy().to_string());
-
-                        // Create unversioned symlinks for versioned libraries
-                        // e.g. libonnxruntime.1.19.2.dylib -> libonnxruntime.dylib
-                        if let Ok(files) = std::fs::read_dir(&path) {
-                            for file in files.flatten() {
-                                let file_path = file.path();
-                                let name = file_path.file_name().unwrap_or_default().to_string_lossy();
-                                // Match versioned dylib/so patterns
-                                if let Some(base) = name.strip_suffix(".dylib")
-                                    .or_else(|| name.strip_suffix(".so"))
-                                {
-                                    if base.contains('.') {
-                                        // Has version suffix like libonnxruntime.1.19.2
-                                        let unversioned = if cfg!(target_os = "macos") {
-                                            format!("{}.dylib", base.split('.').next().unwrap_or(base))
-                                        } else {
-                                            format!("{}.so", base.split('.').next().unwrap_or(base))
-                                        };
-                                        let link_path = path.join(&unversioned);
-                                        if !link_path.exists() {
-                                            #[cfg(unix)]
-                                            let _ = std::os::unix::fs::symlink(&file_path, &link_path);
-                                            #[cfg(not(unix))]
-                                            let _ = ();
-                                            tracing::info!("[NativeLibs] Created symlink: {} -> {}", unversioned, name);
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    // Also check current working directory (extension runner sets this)
-    if let Ok(cwd) = std::env::current_dir() {
-        let lib_dir = cwd.join("lib");
-        if lib_dir.is_dir() {
-            paths.push(lib_dir.to_string_lossy().to_string());
-        }
-    }
-
-    // 2. Inherit existing paths
-    if let Ok(existing) = std::env::var(lib_env) {
-        paths.push(existing);
-    }
+/// Trait for object detection backends.
+///
+/// The extension only talks to its model through this trait, so tests can
+/// inject a scripted backend with [`YoloDeviceInference::with_detector`]
+/// instead of loading an ONNX model.
+pub trait ObjectDetect {
+    /// Detect objects in the given image data.
+    ///
+    /// # Arguments
+    /// * `image_data` - Encoded image bytes (JPEG, PNG, etc.)
+    ///
+    /// # Returns
+    /// The detections and the image's width and height.
+    fn detect(&mut self, image_data: &[u8]) -> std::result::Result<(Vec<Detection>, u32, u32), String>;
 
-    // 3. Common system paths
-    for dir in ["/opt/homebrew/lib", "/usr/local/lib"] {
-        if std::path::Path::new(dir).is_dir() {
-            paths.push(dir.to_string());
-        }
+    /// Whether the model is loaded. Backends without a model are always ready.
+    fn is_loaded(&self) -> bool {
+        true
     }
 
-    if !paths.is_empty() {
-        let sep = if cfg!(target_os = "windows") { ";" } else { ":" };
-        let combined = paths.join(sep);
-        tracing::info!("[NativeLibs] Setting {} = {}", lib_env, combined);
-        std::env::set_var(lib_env, &combined);
+    /// Error of the last model load attempt, if any.
+    fn load_error(&self) -> Option<String> {
+        None
     }
 
-    // Set ORT_DYLIB_PATH to the exact location of libonnxruntime
-    // This is the recommended way for the `ort` crate's load-dynamic feature.
-    // DYLD_LIBRARY_PATH set at runtime via set_var may not affect dlopen on macOS.
-    if std::env::var("ORT_DYLIB_PATH").is_err() {
-        let ort_filename = if cfg!(target_os = "macos") {
-            "libonnxruntime.dylib"
-        } else if cfg!(target_os = "windows") {
-            "onnxruntime.dll"
-        } else {
-            "libonnxruntime.so"
-        };
-
-        for dir in &paths {
-            let ort_path = std::path::Path::new(dir).join(ort_filename);
-            if ort_path.exists() {
-                tracing::info!("[NativeLibs] Setting ORT_DYLIB_PATH = {}", ort_path.display());
-                std::env::set_var("ORT_DYLIB_PATH", &ort_path);
-                break;
-            }
-        }
-    }
+    /// Downcast to Any for type-specific access (e.g., model parameters).
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any;
 }
 
 // ============================================================================
@@ -486,7 +302,7 @@ impl YOLODetector {
             .map_err(|_| format!("Invalid version: {}", version))?;
 
         let model_filename = format!("yolov{}{}.onnx", version_num, scale);
-        let model_path = Self::find_model_path_static(&model_filename)?;
+        let model_path = neomind_vision::find_model(&model_filename)?;
 
         tracing::info!("[YOLODetector] Loading model file: {}", model_path.display());
 
@@ -507,38 +323,72 @@ impl YOLODetector {
 
         Ok(model)
     }
+}
 
-    /// Find model file by searching common locations (static version).
-    fn find_model_path_static(filename: &str) -> std::result::Result<std::path::PathBuf, String> {
-        // Try NEOMIND_EXTENSION_DIR first
-        if let Ok(ext_dir) = std::env::var("NEOMIND_EXTENSION_DIR") {
-            let path = std::path::PathBuf::from(&ext_dir).join("models").join(filename);
-            if path.exists() {
-                return Ok(path);
-            }
-        }
+#[cfg(not(target_arch = "wasm32"))]
+impl ObjectDetect for YOLODetector {
+    fn detect(&mut self, image_data: &[u8]) -> std::result::Result<(Vec<Detection>, u32, u32), String> {
+        // Ensure model is loaded (lazy loading)
+        self.ensure_loaded();
+        let model = self.model.as_mut()
+            .ok_or_else(|| self.load_error.clone().unwrap_or_else(|| "Model not loaded".to_string()))?;
 
-        // Fallback: Check current working directory
-        if let Ok(cwd) = std::env::current_dir() {
-            let path = cwd.join("models").join(filename);
-            if path.exists() {
-                return Ok(path);
-            }
-        }
+        // Create temp file for image
+        let temp_path = std::env::temp_dir().join(format!("yolo_inference_{}.jpg", uuid::Uuid::new_v4()));
+        std::fs::write(&temp_path, image_data)
+            .map_err(|e| format!("Failed to write temp image: {}", e))?;
 
-        // Additional fallback paths
-        let fallback_paths = vec![
-            std::path::PathBuf::from("models").join(filename),
-            std::path::PathBuf::from("../models").join(filename),
-        ];
+        // Load image
+        let temp_path_str = temp_path.to_str().ok_or_else(|| "Invalid temp path".to_string())?;
+        let xs = DataLoader::try_read_one(temp_path_str)
+            .map_err(|e| format!("Failed to read image: {}", e))?;
+
+        let (img_width, img_height) = (xs.width(), xs.height());
+
+        // Run inference
+        let ys = model.forward(&[xs])
+            .map_err(|e| format!("Inference failed: {}", e))?;
+
+        // Clean up temp file immediately
+        let _ = std::fs::remove_file(&temp_path);
+
+        // Parse results
+        let mut detections = Vec::new();
+        for y in ys.iter() {
+            for hbb in &y.hbbs {
+                let class_id = hbb.id().unwrap_or(0);
+                let label = match neomind_vision::coco_class_name(class_id) {
+                    Some(name) => name.to_string(),
+                    None => hbb.name().unwrap_or(&format!("class_{}", class_id)).to_string(),
+                };
 
-        for path in fallback_paths {
-            if path.exists() {
-                return Ok(path);
+                detections.push(Detection {
+                    label,
+                    confidence: hbb.confidence().unwrap_or(0.0),
+                    bbox: BoundingBox {
+                        x: hbb.xmin(),
+                        y: hbb.ymin(),
+                        width: hbb.xmax() - hbb.xmin(),
+                        height: hbb.ymax() - hbb.ymin(),
+                    },
+                    class_id: Some(class_id),
+                });
             }
         }
 
-        Err(format!("Model file '{}' not found in extension models directory", filename))
+        Ok((detections, img_width, img_height))
+    }
+
+    fn is_loaded(&self) -> bool {
+        self.model.is_some()
+    }
+
+    fn load_error(&self) -> Option<String> {
+        self.load_error.clone()
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
     }
 }
 
@@ -547,24 +397,26 @@ impl YOLODetector {
 // ============================================================================
 
 pub struct YoloDeviceInference {
-    /// YOLO model runtime (native only) - lazy-loading wrapper
+    /// Detection backend (native only) - the lazy-loading YOLO model unless injected
     #[cfg(not(target_arch = "wasm32"))]
-    detector: Mutex<YOLODetector>,
+    detector: Mutex<Box<dyn ObjectDetect + Send>>,
     /// Model load state mirrored as atomics so get_status/get_model_status never blocks on detector mutex
     #[cfg(not(target_arch = "wasm32"))]
     model_loaded: AtomicBool,
     #[cfg(not(target_arch = "wasm32"))]
     model_error: parking_lot::Mutex<Option<String>>,
 
-    /// Device bindings: device_id -> binding
-    bindings: Arc<RwLock<HashMap<String, DeviceBinding>>>,
-    /// Binding status for tracking
-    binding_stats: Arc<RwLock<HashMap<String, BindingStatus>>>,
+    /// Device bindings and their status
+    bindings: BindingRegistry<YoloBindingSettings, YoloBindingStats>,
+    /// Latest manual analysis, listed with the bindings but never persisted
+    manual_status: RwLock<Option<BindingStatus>>,
 
     /// Global statistics
     total_inferences: Arc<AtomicU64>,
     total_detections: Arc<AtomicU64>,
     total_errors: Arc<AtomicU64>,
+    /// Inference successes and failures for the health report
+    activity: neomind_health::Activity,
 
     /// Configuration
     default_confidence: Mutex<f32>,
@@ -573,6 +425,8 @@ pub struct YoloDeviceInference {
 
 impl YoloDeviceInference {
     pub fn new() -> Self {
+        neomind_logging::init();
+
         // ✅ FIX: Use lazy loading - model will be loaded on first use
         // This prevents OOM during extension startup
         #[cfg(not(target_arch = "wasm32"))]
@@ -582,48 +436,38 @@ impl YoloDeviceInference {
 
         Self {
             #[cfg(not(target_arch = "wasm32"))]
-            detector: Mutex::new(YOLODetector::new(0.25, 0.45, "v8", "n")),
+            detector: Mutex::new(Box::new(YOLODetector::new(0.25, 0.45, "v8", "n"))),
             #[cfg(not(target_arch = "wasm32"))]
             model_loaded: AtomicBool::new(false),
             #[cfg(not(target_arch = "wasm32"))]
             model_error: parking_lot::Mutex::new(None),
-            bindings: Arc::new(RwLock::new(HashMap::new())),
-            binding_stats: Arc::new(RwLock::new(HashMap::new())),
+            bindings: BindingRegistry::new("YoloDeviceInference"),
+            manual_status: RwLock::new(None),
             total_inferences: Arc::new(AtomicU64::new(0)),
             total_detections: Arc::new(AtomicU64::new(0)),
             total_errors: Arc::new(AtomicU64::new(0)),
+            activity: neomind_health::Activity::new(),
             default_confidence: Mutex::new(0.25),
             model_version: Mutex::new("v8-n".to_string()),
         }
     }
 
-    fn invoke_capability_sync(
-        &self,
-        capability_name: &str,
-        params: &serde_json::Value,
-    ) -> serde_json::Value {
-        // Use block_in_place to safely block in an async context.
-        // This is safe because the extension runner uses multi_thread runtime.
-        tokio::task::block_in_place(|| {
-            let capability_context = CapabilityContext::default();
-            let response = capability_context.invoke_capability(capability_name, params);
-            if response
-                .get("success")
-                .and_then(|value| value.as_bool())
-                .unwrap_or(false)
-            {
-                if response.get("result").is_some() {
-                    response
-                } else {
-                    json!({
-                        "success": true,
-                        "result": response,
-                    })
-                }
-            } else {
-                response
-            }
-        })
+    /// Create an instance with a custom detection backend.
+    ///
+    /// This is intended for testing, allowing a scripted backend to be injected
+    /// instead of the real ONNX-based model.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_detector(detector: Box<dyn ObjectDetect + Send>) -> Self {
+        let ext = Self::new();
+        *ext.detector.lock() = detector;
+        ext
+    }
+
+    /// Mirror the backend's model state into the atomics read by get_status
+    #[cfg(not(target_arch = "wasm32"))]
+    fn sync_model_state(&self, detector: &dyn ObjectDetect) {
+        self.model_loaded.store(detector.is_loaded(), Ordering::Relaxed);
+        *self.model_error.lock() = detector.load_error();
     }
 
     /// Reload model with new configuration
@@ -640,23 +484,19 @@ impl YoloDeviceInference {
             ("v8", "n")
         };
 
-        let mut detector = self.detector.lock();
         // Reset and force reload
-        *detector = YOLODetector::new(conf, 0.45, version, scale);
+        let mut detector = YOLODetector::new(conf, 0.45, version, scale);
         detector.ensure_loaded();
 
         // Sync model state to atomics
-        self.model_loaded.store(detector.model.is_some(), Ordering::Relaxed);
-        {
-            let mut err_guard = self.model_error.lock();
-            *err_guard = detector.load_error.clone();
-        }
+        self.sync_model_state(&detector);
 
-        if detector.model.is_some() {
-            Ok(())
-        } else {
-            Err(detector.load_error.clone().unwrap_or_else(|| "Unknown error".to_string()))
-        }
+        let result = match &detector.model {
+            Some(_) => Ok(()),
+            None => Err(detector.load_error.clone().unwrap_or_else(|| "Unknown error".to_string())),
+        };
+        *self.detector.lock() = Box::new(detector);
+        result
     }
 
     /// Get model status
@@ -683,7 +523,7 @@ impl YoloDeviceInference {
     /// Validate device exists (native only)
     #[cfg(not(target_arch = "wasm32"))]
     async fn validate_device(&self, device_id: &str) -> Result<bool> {
-        let response = self.invoke_capability_sync(
+        let response = neomind_binding::metrics::invoke_capability_sync(
             "device_metrics_read",
             &json!({ "device_id": device_id }),
         );
@@ -716,50 +556,33 @@ impl YoloDeviceInference {
 
         // Ensure binding is active
         binding.active = true;
-
-        // Add to bindings
-        self.bindings.write().insert(device_id.clone(), binding.clone());
-
-        // Initialize stats
-        self.binding_stats.write().insert(device_id.clone(), BindingStatus {
-            binding,
-            last_inference: None,
-            total_inferences: 0,
-            total_detections: 0,
-            last_error: None,
-            last_image: None,
-            last_detections: None,
-            last_annotated_image: None,
-        });
+        self.bindings.bind(binding);
 
         // Persist configuration
         self.persist_config();
-
-        tracing::info!("[YoloDeviceInference] Device bound: {}", device_id);
         Ok(())
     }
 
     /// Unbind a device
     #[cfg(not(target_arch = "wasm32"))]
     pub async fn unbind_device_async(&self, device_id: &str) -> Result<()> {
-        self.bindings.write().remove(device_id);
-        self.binding_stats.write().remove(device_id);
-        
+        self.bindings.unbind(device_id);
+
         // Persist configuration
         self.persist_config();
-        
-        tracing::info!("[YoloDeviceInference] Device unbound: {}", device_id);
         Ok(())
     }
 
-    /// Get all bindings
+    /// Get all bindings, followed by the latest manual analysis if any
     pub fn get_bindings(&self) -> Vec<BindingStatus> {
-        self.binding_stats.read().values().cloned().collect()
+        let mut bindings = self.bindings.statuses();
+        bindings.extend(self.manual_status.read().clone());
+        bindings
     }
 
     /// Get binding for a specific device
     pub fn get_binding(&self, device_id: &str) -> Option<DeviceBinding> {
-        self.bindings.read().get(device_id).cloned()
+        self.bindings.get(device_id)
     }
 
     /// Process image data and run inference (native only)
@@ -767,71 +590,14 @@ impl YoloDeviceInference {
     pub fn process_image(&self, device_id: &str, image_data: &[u8], draw_boxes: bool) -> Result<InferenceResult> {
         let start = std::time::Instant::now();
 
-        // Ensure model is loaded (lazy loading)
-        {
+        let detected = {
             let mut detector = self.detector.lock();
-            detector.ensure_loaded();
+            let detected = detector.detect(image_data);
             // Sync model state to atomics so get_status stays accurate without locking detector
-            self.model_loaded.store(detector.model.is_some(), Ordering::Relaxed);
-            {
-                let mut err_guard = self.model_error.lock();
-                *err_guard = detector.load_error.clone();
-            }
-            if detector.model.is_none() {
-                let err = detector.load_error.clone()
-                    .unwrap_or_else(|| "Model not loaded".to_string());
-                return Err(ExtensionError::ExecutionFailed(err));
-            }
-        }
-
-        // Now lock detector and run inference
-        let mut detector = self.detector.lock();
-        let model = detector.model.as_mut()
-            .ok_or_else(|| ExtensionError::ExecutionFailed("Model not loaded".to_string()))?;
-
-        // Create temp file for image
-        let temp_path = std::env::temp_dir().join(format!("yolo_inference_{}.jpg", uuid::Uuid::new_v4()));
-        std::fs::write(&temp_path, image_data)
-            .map_err(|e| ExtensionError::ExecutionFailed(format!("Failed to write temp image: {}", e)))?;
-
-        // Load image
-        let temp_path_str = temp_path.to_str().ok_or_else(|| ExtensionError::ExecutionFailed("Invalid temp path".to_string()))?;
-        let xs = DataLoader::try_read_one(temp_path_str)
-            .map_err(|e| ExtensionError::ExecutionFailed(format!("Failed to read image: {}", e)))?;
-
-        let (img_width, img_height) = (xs.width(), xs.height());
-
-        // Run inference
-        let ys = model.forward(&[xs])
-            .map_err(|e| ExtensionError::ExecutionFailed(format!("Inference failed: {}", e)))?;
-
-        // Clean up temp file immediately
-        let _ = std::fs::remove_file(&temp_path);
-
-        // Parse results
-        let mut detections = Vec::new();
-        for y in ys.iter() {
-            for hbb in &y.hbbs {
-                let class_id = hbb.id().unwrap_or(0);
-                let label = if class_id < COCO_CLASSES.len() {
-                    COCO_CLASSES[class_id].to_string()
-                } else {
-                    hbb.name().unwrap_or(&format!("class_{}", class_id)).to_string()
-                };
-
-                detections.push(Detection {
-                    label,
-                    confidence: hbb.confidence().unwrap_or(0.0),
-                    bbox: BoundingBox {
-                        x: hbb.xmin(),
-                        y: hbb.ymin(),
-                        width: hbb.xmax() - hbb.xmin(),
-                        height: hbb.ymax() - hbb.ymin(),
-                    },
-                    class_id: Some(class_id),
-                });
-            }
-        }
+            self.sync_model_state(detector.as_ref());
+            detected
+        };
+        let (detections, img_width, img_height) = detected.map_err(ExtensionError::ExecutionFailed)?;
 
         let inference_time = start.elapsed().as_millis() as u64;
         let timestamp = chrono::Utc::now().timestamp();
@@ -854,12 +620,6 @@ impl YoloDeviceInference {
             }
         }
 
-        if let Some(stats) = self.binding_stats.write().get_mut(device_id) {
-            stats.last_inference = Some(timestamp);
-            stats.total_inferences += 1;
-            stats.total_detections += detections.len() as u64;
-        }
-
         Ok(InferenceResult {
             device_id: device_id.to_string(),
             detections,
@@ -878,39 +638,24 @@ impl YoloDeviceInference {
             let image_data = base64::engine::general_purpose::STANDARD.decode(image_b64)
                 .map_err(|e| ExtensionError::InvalidArguments(format!("Invalid base64: {}", e)))?;
             // Default to drawing boxes for manual analysis
-            let result = self.process_image("manual", &image_data, true)?;
-            
+            let result = self.process_image("manual", &image_data, true)
+                .inspect_err(|e| self.activity.error(e))?;
+            self.activity.success();
+
             // Update binding stats for manual analysis (using "manual" as device_id)
             // This allows frontend to see the latest analysis results
             {
-                let mut stats_map = self.binding_stats.write();
-                let stats = stats_map.entry("manual".to_string())
-                    .or_insert_with(|| BindingStatus {
-                        binding: DeviceBinding {
-                            device_id: "manual".to_string(),
-                            device_name: Some("Manual Analysis".to_string()),
-                            image_metric: "input".to_string(),
-                            result_metric_prefix: "manual_".to_string(),
-                            confidence_threshold: 0.25,
-                            draw_boxes: true,
-                            active: true,
-                        },
-                        last_inference: None,
-                        total_inferences: 0,
-                        total_detections: 0,
-                        last_error: None,
-                        last_image: None,
-                        last_detections: None,
-                        last_annotated_image: None,
+                let mut manual = self.manual_status.write();
+                let stats = manual.get_or_insert_with(|| {
+                    let mut binding = DeviceBinding::new("manual", "input", YoloBindingSettings {
+                        result_metric_prefix: "manual_".to_string(),
+                        confidence_threshold: 0.25,
+                        draw_boxes: true,
                     });
-                stats.last_image = Some(format!("data:image/jpeg;base64,{}", image_b64));
-                stats.last_detections = Some(result.detections.clone());
-                stats.last_inference = Some(result.timestamp);
-                stats.total_inferences += 1;
-                stats.total_detections += result.detections.len() as u64;
-                if let Some(annotated_b64) = &result.annotated_image_base64 {
-                    stats.last_annotated_image = Some(format!("data:image/jpeg;base64,{}", annotated_b64));
-                }
+                    binding.device_name = Some("Manual Analysis".to_string());
+                    BindingStatus::new(binding)
+                });
+                Self::record_result(stats, &result, image_b64);
             }
 
             Ok(result)
@@ -934,7 +679,7 @@ impl YoloDeviceInference {
                 "model_loaded": model_loaded,
                 "model_version": self.model_version.lock().clone(),
                 "default_confidence": *self.default_confidence.lock(),
-                "total_bindings": self.bindings.read().len(),
+                "total_bindings": self.bindings.len(),
                 "total_inferences": self.total_inferences.load(Ordering::SeqCst),
                 "total_detections": self.total_detections.load(Ordering::SeqCst),
                 "total_errors": self.total_errors.load(Ordering::SeqCst),
@@ -948,7 +693,7 @@ impl YoloDeviceInference {
                 "model_loaded": false,
                 "model_version": self.model_version.lock().clone(),
                 "default_confidence": *self.default_confidence.lock(),
-                "total_bindings": self.bindings.read().len(),
+                "total_bindings": self.bindings.len(),
                 "total_inferences": self.total_inferences.load(Ordering::SeqCst),
                 "total_detections": self.total_detections.load(Ordering::SeqCst),
                 "total_errors": self.total_errors.load(Ordering::SeqCst),
@@ -957,137 +702,62 @@ impl YoloDeviceInference {
         }
     }
 
+    /// Health report for the `health` command and `health_state` metric
+    pub fn health(&self) -> neomind_health::Health {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let model_loaded = self.model_loaded.load(Ordering::Relaxed);
+            neomind_health::Health::new()
+                .model(model_loaded, self.model_error.lock().clone())
+                .native_libs(model_loaded || neomind_vision::native::ort_library().is_some(), "ONNX Runtime")
+                .activity(&self.activity)
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            neomind_health::Health::new().unhealthy("YOLO not available in WASM")
+        }
+    }
+
     /// Get current configuration for persistence
     pub fn get_config(&self) -> YoloConfig {
         YoloConfig {
             default_confidence: *self.default_confidence.lock(),
             model_version: self.model_version.lock().clone(),
-            bindings: self.bindings.read().values().cloned().collect(),
+            bindings: self.bindings.bindings(),
         }
     }
 
     /// Persist configuration to file
     fn persist_config(&self) {
-        let config = self.get_config();
-
-        // Get extension directory from environment
-        eprintln!("[YoloDeviceInference] Checking NEOMIND_EXTENSION_DIR env var");
-        if let Ok(ext_dir) = std::env::var("NEOMIND_EXTENSION_DIR") {
-            eprintln!("[YoloDeviceInference] NEOMIND_EXTENSION_DIR = {}", ext_dir);
-            let config_path = std::path::PathBuf::from(&ext_dir).join("config.json");
-            eprintln!("[YoloDeviceInference] config_path = {:?}", config_path);
-            eprintln!("[YoloDeviceInference] config_path.exists() = {}", config_path.exists());
-
-            match serde_json::to_string_pretty(&config) {
-                Ok(json) => {
-                    if let Err(e) = std::fs::write(&config_path, json) {
-                        tracing::warn!("[YoloDeviceInference] Failed to persist config: {}", e);
-                    } else {
-                        tracing::debug!("[YoloDeviceInference] Config persisted to {:?}", config_path);
-                    }
-                }
-                Err(e) => {
-                    tracing::warn!("[YoloDeviceInference] Failed to serialize config: {}", e);
-                }
-            }
-        } else {
-            tracing::debug!("[YoloDeviceInference] NEOMIND_EXTENSION_DIR not set, skipping config persistence");
-        }
-    }
-
-    /// Load configuration from file
-    fn load_config_from_file(&self) -> Option<YoloConfig> {
-        eprintln!("[YoloDeviceInference] load_config_from_file called");
-        eprintln!("[YoloDeviceInference] Current working directory: {:?}", std::env::current_dir());
-        
-        // Since the working directory is set to the extension directory,
-        // we can directly use "config.json" in the current directory
-        let config_path = std::path::PathBuf::from("config.json");
-        eprintln!("[YoloDeviceInference] config_path = {:?}", config_path);
-        eprintln!("[YoloDeviceInference] config_path.exists() = {}", config_path.exists());
-
-        if config_path.exists() {
-            match std::fs::read_to_string(&config_path) {
-                Ok(json) => {
-                    eprintln!("[YoloDeviceInference] Read config file successfully, length = {}", json.len());
-                    match serde_json::from_str::<YoloConfig>(&json) {
-                        Ok(config) => {
-                            eprintln!("[YoloDeviceInference] Parsed config successfully, bindings = {}", config.bindings.len());
-                            return Some(config);
-                        }
-                        Err(e) => {
-                            eprintln!("[YoloDeviceInference] Failed to parse config file: {}", e);
-                        }
-                    }
-                }
-                Err(e) => {
-                    eprintln!("[YoloDeviceInference] Failed to read config file: {}", e);
-                }
-            }
-        } else {
-            // Also try using NEOMIND_EXTENSION_DIR as fallback
-            if let Ok(ext_dir) = std::env::var("NEOMIND_EXTENSION_DIR") {
-                eprintln!("[YoloDeviceInference] Trying NEOMIND_EXTENSION_DIR: {}", ext_dir);
-                let alt_config_path = std::path::PathBuf::from(&ext_dir).join("config.json");
-                eprintln!("[YoloDeviceInference] alt_config_path = {:?}, exists = {}", alt_config_path, alt_config_path.exists());
-                
-                if alt_config_path.exists() {
-                    match std::fs::read_to_string(&alt_config_path) {
-                        Ok(json) => {
-                            match serde_json::from_str::<YoloConfig>(&json) {
-                                Ok(config) => {
-                                    eprintln!("[YoloDeviceInference] Loaded config from alt path, bindings = {}", config.bindings.len());
-                                    return Some(config);
-                                }
-                                Err(e) => {
-                                    eprintln!("[YoloDeviceInference] Failed to parse alt config file: {}", e);
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            eprintln!("[YoloDeviceInference] Failed to read alt config file: {}", e);
-                        }
-                    }
-                }
-            }
-        }
-        None
+        neomind_binding::config::save_config("YoloDeviceInference", &self.get_config());
     }
 
     /// Load configuration from persisted state
     pub fn load_config(&self, config: &YoloConfig) -> Result<()> {
-        eprintln!("[YoloDeviceInference] load_config called with {} bindings", config.bindings.len());
-        
         // Load settings
         *self.default_confidence.lock() = config.default_confidence;
         *self.model_version.lock() = config.model_version.clone();
 
         // Load bindings
-        for binding in &config.bindings {
-            eprintln!("[YoloDeviceInference] Loading binding for device: {}, image_metric: {}", binding.device_id, binding.image_metric);
-
-            // Add to bindings
-            self.bindings.write().insert(binding.device_id.clone(), binding.clone());
-            
-            eprintln!("[YoloDeviceInference] Binding inserted, current bindings count: {}", self.bindings.read().len());
-
-            // Initialize stats
-            self.binding_stats.write().insert(binding.device_id.clone(), BindingStatus {
-                binding: binding.clone(),
-                last_inference: None,
-                total_inferences: 0,
-                total_detections: 0,
-                last_error: None,
-                last_image: None,
-                last_detections: None,
-                last_annotated_image: None,
-            });
-        }
-
-        eprintln!("[YoloDeviceInference] Loaded {} persisted bindings, total in map: {}", config.bindings.len(), self.bindings.read().len());
+        self.bindings.restore(config.bindings.iter().cloned());
         Ok(())
     }
 
+    /// Store an inference result in a binding's status
+    fn record_result(stats: &mut BindingStatus, result: &InferenceResult, image_b64: &str) {
+        stats.last_image = Some(jpeg_data_url(image_b64));
+        stats.last_inference = Some(result.timestamp);
+        stats.total_inferences += 1;
+        stats.last_error = None;
+        stats.state.total_detections += result.detections.len() as u64;
+        stats.state.last_detections = Some(result.detections.clone());
+        // Update annotated image if available
+        if let Some(annotated_b64) = &result.annotated_image_base64 {
+            stats.state.last_annotated_image = Some(jpeg_data_url(annotated_b64));
+        }
+    }
+
     /// Process inference result and write virtual metrics
     /// This is called after successful inference to update device metrics
     #[cfg(not(target_arch = "wasm32"))]
@@ -1096,146 +766,19 @@ impl YoloDeviceInference {
         device_id: &str,
         result: &InferenceResult,
         image_b64: &str,
-        _result_metric_prefix: &str,
     ) {
-        // Update binding stats
-        if let Some(stats) = self.binding_stats.write().get_mut(device_id) {
-            stats.last_image = Some(format!("data:image/jpeg;base64,{}", image_b64));
-            stats.last_detections = Some(result.detections.clone());
-            stats.last_inference = Some(result.timestamp);
-            // Update annotated image if available
-            if let Some(annotated_b64) = &result.annotated_image_base64 {
-                stats.last_annotated_image = Some(format!("data:image/jpeg;base64,{}", annotated_b64));
-            }
-        }
-
-        // Write virtual metrics through the native capability bridge.
-        // Virtual metrics must start with: transform., virtual., computed., derived., or aggregated.
-
-
-        // Write detection count (virtual metric)
-        let metric_name = "virtual.yolo.detections";
-        let params = serde_json::json!({
-            "device_id": device_id,
-            "metric": metric_name,
-            "value": result.detections.len(),
-            "timestamp": result.timestamp,
-        });
-        match self.invoke_capability_sync("device_metrics_write", &params) {
-            v if v.get("success").and_then(|s| s.as_bool()).unwrap_or(false) => {
-                eprintln!("[YoloDeviceInference] Successfully wrote {}", metric_name);
-            }
-            _ => {
-                eprintln!("[YoloDeviceInference] Failed to write {}", metric_name);
-            }
-        }
+        self.bindings.update(device_id, |stats| Self::record_result(stats, result, image_b64));
 
-        // Write inference time (virtual metric)
-        let metric_name = "virtual.yolo.inference_time_ms";
-        let params = serde_json::json!({
-            "device_id": device_id,
-            "metric": metric_name,
-            "value": result.inference_time_ms,
-            "timestamp": result.timestamp,
-        });
-        match self.invoke_capability_sync("device_metrics_write", &params) {
-            v if v.get("success").and_then(|s| s.as_bool()).unwrap_or(false) => {
-                eprintln!("[YoloDeviceInference] Successfully wrote {}", metric_name);
-            }
-            _ => {
-                eprintln!("[YoloDeviceInference] Failed to write {}", metric_name);
-            }
-        }
-
-        // Write detection labels as JSON (virtual metric)
         let labels: Vec<&str> = result.detections.iter().map(|d| d.label.as_str()).collect();
-        let metric_name = "virtual.yolo.labels";
-        let params = serde_json::json!({
-            "device_id": device_id,
-            "metric": metric_name,
-            "value": labels,
-            "timestamp": result.timestamp,
-        });
-        match self.invoke_capability_sync("device_metrics_write", &params) {
-            v if v.get("success").and_then(|s| s.as_bool()).unwrap_or(false) => {
-                eprintln!("[YoloDeviceInference] Successfully wrote {}", metric_name);
-            }
-            _ => {
-                eprintln!("[YoloDeviceInference] Failed to write {}", metric_name);
-            }
-        }
-
-        // Write annotated image (virtual metric) with proper data URI format
+        let mut metrics = vec![
+            ("virtual.yolo.detections", json!(result.detections.len())),
+            ("virtual.yolo.inference_time_ms", json!(result.inference_time_ms)),
+            ("virtual.yolo.labels", json!(labels)),
+        ];
         if let Some(img) = &result.annotated_image_base64 {
-            let metric_name = "virtual.yolo.annotated_image";
-            // Add data URI prefix for proper image display
-            let data_uri = format!("data:image/jpeg;base64,{}", img);
-            let params = serde_json::json!({
-                "device_id": device_id,
-                "metric": metric_name,
-                "value": data_uri,
-                "timestamp": result.timestamp,
-            });
-            match self.invoke_capability_sync("device_metrics_write", &params) {
-                v if v.get("success").and_then(|s| s.as_bool()).unwrap_or(false) => {
-                    eprintln!("[YoloDeviceInference] Successfully wrote {}", metric_name);
-                }
-                _ => {
-                    eprintln!("[YoloDeviceInference] Failed to write {}", metric_name);
-                }
-            }
+            metrics.push(("virtual.yolo.annotated_image", json!(jpeg_data_url(img))));
         }
-
-    }
-
-    /// Extract image data from a value, supporting nested paths
-    pub fn extract_image_from_value<'a>(&self, value: Option<&'a serde_json::Value>, nested_path: Option<&str>) -> Option<String> {
-        let v = value?;
-
-        // If we have a nested path, navigate to it
-        let target_value = if let Some(path) = nested_path {
-            // Navigate through nested path
-            let mut current = v;
-            for part in path.split('.') {
-                current = current.get(part)?;
-            }
-            current
-        } else {
-            v
-        };
-
-        // Try to extract string from the target value
-        let raw_string: Option<&str> = {
-            // Format 1: Direct string
-            if let Some(s) = target_value.as_str() {
-                Some(s)
-            // Format 2: MetricValue wrapper {"String": "..."}
-            } else if let Some(s) = target_value.get("String").and_then(|s| s.as_str()) {
-                Some(s)
-            // Format 3: Object with string fields (try common field names)
-            } else {
-                for field in &["image", "data", "value", "base64"] {
-                    if let Some(s) = target_value.get(field).and_then(|s| s.as_str()) {
-                        return Some(s.to_string());
-                    }
-                }
-                None
-            }
-        };
-
-        // Process the string - handle data URL format
-        if let Some(s) = raw_string {
-            // Handle data URL format: "data:image/jpeg;base64,actual_base64_data"
-            if s.starts_with("data:") {
-                if let Some(comma_pos) = s.find(',') {
-                    let base64_part = &s[comma_pos + 1..];
-                    return Some(base64_part.to_string());
-                }
-            }
-            return Some(s.to_string());
-        }
-
-        None
+        neomind_binding::metrics::write_device_metrics(device_id, result.timestamp, metrics);
     }
 }
 
@@ -1259,7 +802,7 @@ impl Extension for YoloDeviceInference {
                 "YOLO Device Inference",
                 "2.0.0"
             )
-            .with_description("Automatic YOLOv8 inference on device image data sources")
+            .with_description("Automatic YOLOv8 object detection bound to device image streams with ROI monitoring, line crossing analytics, and annotated output")
             .with_author("NeoMind Team")
         })
     }
@@ -1302,6 +845,7 @@ impl Extension for YoloDeviceInference {
                 max: None,
                 required: false,
             },
+            neomind_health::metric_descriptor(),
         ]
     }
 
@@ -1501,6 +1045,7 @@ impl Extension for YoloDeviceInference {
                 samples: vec![],
                 parameter_groups: Vec::new(),
             },
+            neomind_health::command(),
         ]
     }
 
@@ -1510,7 +1055,7 @@ impl Extension for YoloDeviceInference {
         Ok(vec![
             ExtensionMetricValue {
                 name: "bound_devices".to_string(),
-                value: ParamMetricValue::Integer(self.bindings.read().len() as i64),
+                value: ParamMetricValue::Integer(self.bindings.len() as i64),
                 timestamp: now,
             },
             ExtensionMetricValue {
@@ -1528,6 +1073,7 @@ impl Extension for YoloDeviceInference {
                 value: ParamMetricValue::Integer(self.total_errors.load(Ordering::SeqCst) as i64),
                 timestamp: now,
             },
+            self.health().metric(),
         ])
     }
 
@@ -1544,110 +1090,39 @@ impl Extension for YoloDeviceInference {
         event_type: &str,
         payload: &serde_json::Value,
     ) -> Result<()> {
-        if event_type != "DeviceMetric" {
+        let Some(event) = self.bindings.match_event(event_type, payload) else {
             return Ok(());
-        }
-
-        tracing::debug!("[YoloDeviceInference] handle_event: type={}", event_type);
-
-        // Extract event data from the standardized format
-        let inner_payload = payload.get("payload").unwrap_or(payload);
-
-        let device_id = inner_payload.get("device_id")
-            .and_then(|v| v.as_str())
-            .unwrap_or("unknown");
-
-        let metric = inner_payload.get("metric")
-            .and_then(|v| v.as_str())
-            .unwrap_or("unknown");
-
-        tracing::debug!("[YoloDeviceInference] Processing: device={}, metric={}", device_id, metric);
-
-        let value = inner_payload.get("value");
-
-        // Check if this device is bound - early return for unbound devices
-        let binding = {
-            let bindings = self.bindings.read();
-            bindings.get(device_id).cloned()
-        };
-
-        let Some(binding) = binding else {
-            return Ok(());
-        };
-
-        if !binding.active {
-            tracing::debug!("[YoloDeviceInference] Binding inactive for device: {}", device_id);
-            return Ok(());
-        }
-
-        // Check if metric matches
-        let exact_match = metric == binding.image_metric;
-
-        let (top_level_metric, nested_path) = if binding.image_metric.contains('.') {
-            let parts: Vec<&str> = binding.image_metric.splitn(2, '.').collect();
-            (parts[0].to_string(), Some(parts[1].to_string()))
-        } else {
-            (binding.image_metric.clone(), None)
         };
+        let device_id = event.binding.device_id.as_str();
+        let _span = neomind_logging::binding_span(device_id).entered();
 
-        let metric_matches = exact_match || metric == top_level_metric;
-        let nested_path = if exact_match { None } else { nested_path };
-
-        tracing::debug!(
-            "[YoloDeviceInference] Event: device={}, metric={}, binding_metric={}, matches={}",
-            device_id, metric, binding.image_metric, metric_matches
-        );
-
-        if !metric_matches {
-            return Ok(());
-        }
-
-        // Extract image data from value
-        let image_b64 = self.extract_image_from_value(value, nested_path.as_deref());
-
-        if let Some(image_data_b64) = image_b64 {
-            match base64::engine::general_purpose::STANDARD.decode(&image_data_b64) {
-                Ok(image_data) => {
-                    #[cfg(not(target_arch = "wasm32"))]
-                    {
-                        match self.process_image(device_id, &image_data, binding.draw_boxes) {
-                            Ok(result) => {
-                                self.total_inferences.fetch_add(1, Ordering::SeqCst);
-                                self.total_detections.fetch_add(result.detections.len() as u64, Ordering::SeqCst);
-                                self.write_inference_results(
-                                    device_id,
-                                    &result,
-                                    &image_data_b64,
-                                    &binding.result_metric_prefix,
-                                );
-                                tracing::debug!(
-                                    "[YoloDeviceInference] Inference: device={}, detections={}, time={}ms",
-                                    device_id,
-                                    result.detections.len(),
-                                    result.inference_time_ms
-                                );
-                            }
-                            Err(e) => {
-                                self.total_errors.fetch_add(1, Ordering::SeqCst);
-                                tracing::warn!("[YoloDeviceInference] Process failed: device={}, error={}", device_id, e);
-                                if let Some(stats) = self.binding_stats.write().get_mut(device_id) {
-                                    stats.last_error = Some(e.to_string());
-                                }
-                            }
-                        }
-                    }
-
-                    #[cfg(target_arch = "wasm32")]
-                    {
-                        tracing::warn!("[YoloDeviceInference] Image processing not supported in WASM");
-                    }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            match self.process_image(device_id, &event.image, event.binding.settings.draw_boxes) {
+                Ok(result) => {
+                    self.activity.success();
+                    self.write_inference_results(device_id, &result, &event.image_b64);
+                    tracing::debug!(
+                        "[YoloDeviceInference] Inference: device={}, detections={}, time={}ms",
+                        device_id,
+                        result.detections.len(),
+                        result.inference_time_ms
+                    );
                 }
                 Err(e) => {
-                    tracing::warn!("[YoloDeviceInference] Base64 decode failed: device={}, error={}", device_id, e);
+                    self.total_errors.fetch_add(1, Ordering::SeqCst);
+                    self.activity.error(&e);
+                    tracing::warn!("[YoloDeviceInference] Process failed: device={}, error={}", device_id, e);
+                    self.bindings.record_error(device_id, &e);
                 }
             }
         }
 
+        #[cfg(target_arch = "wasm32")]
+        {
+            tracing::warn!("[YoloDeviceInference] Image processing not supported in WASM: device={}", device_id);
+        }
+
         Ok(())
     }
 
@@ -1659,50 +1134,20 @@ impl Extension for YoloDeviceInference {
     async fn execute_command(&self, command: &str, args: &serde_json::Value) -> Result<serde_json::Value> {
         match command {
             "bind_device" => {
-                let device_id = args.get("device_id")
-                    .and_then(|v| v.as_str())
-                    .ok_or_else(|| ExtensionError::InvalidArguments("Missing device_id".to_string()))?;
-
-                let binding = DeviceBinding {
-                    device_id: device_id.to_string(),
-                    device_name: args.get("device_name").and_then(|v| v.as_str()).map(String::from),
-                    image_metric: args.get("image_metric")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("image").to_string(),
-                    result_metric_prefix: args.get("result_metric_prefix")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("yolo_").to_string(),
-                    confidence_threshold: args.get("confidence_threshold")
-                        .and_then(|v| v.as_f64())
-                        .map(|v| v as f32)
-                        .unwrap_or(0.25),
-                    draw_boxes: args.get("draw_boxes")
-                        .and_then(|v| v.as_bool())
-                        .unwrap_or(true),
-                    active: true,
-                };
-
-                self.bind_device_async(binding).await?;
-
-                Ok(json!({
-                    "success": true,
-                    "device_id": device_id,
-                    "message": format!("Device {} bound successfully", device_id)
-                }))
+                if let Some(device_id) = args.get("device_id").and_then(|v| v.as_str()) {
+                    if !self.validate_device(device_id).await? {
+                        return Err(ExtensionError::NotFound(format!("Device '{}' not found", device_id)));
+                    }
+                }
+                let response = self.bindings.execute_command(command, args)?;
+                self.persist_config();
+                Ok(response)
             }
 
-            "unbind_device" => {
-                let device_id = args.get("device_id")
-                    .and_then(|v| v.as_str())
-                    .ok_or_else(|| ExtensionError::InvalidArguments("Missing device_id".to_string()))?;
-
-                self.unbind_device_async(device_id).await?;
-
-                Ok(json!({
-                    "success": true,
-                    "device_id": device_id,
-                    "message": format!("Device {} unbound successfully", device_id)
-                }))
+            "unbind_device" | "toggle_binding" => {
+                let response = self.bindings.execute_command(command, args)?;
+                self.persist_config();
+                Ok(response)
             }
 
             "get_bindings" => {
@@ -1714,8 +1159,10 @@ impl Extension for YoloDeviceInference {
             }
 
             "configure" => {
+                neomind_logging::apply_config("YoloDeviceInference", args);
+
                 // Load config from file
-                if let Some(config) = self.load_config_from_file() {
+                if let Some(config) = neomind_binding::config::load_config::<YoloConfig>("YoloDeviceInference") {
                     self.load_config(&config)?;
                     Ok(json!({
                         "success": true,
@@ -1744,38 +1191,14 @@ impl Extension for YoloDeviceInference {
                 Ok(self.get_status())
             }
 
+            "health" => Ok(self.health().to_json()),
+
             "get_config" => {
                 let config = self.get_config();
                 Ok(serde_json::to_value(&config)
                     .map_err(|e| ExtensionError::ExecutionFailed(format!("Serialization error: {}", e)))?)
             }
 
-            "toggle_binding" => {
-                let device_id = args.get("device_id")
-                    .and_then(|v| v.as_str())
-                    .ok_or_else(|| ExtensionError::InvalidArguments("Missing device_id".to_string()))?;
-
-                let active = args.get("active")
-                    .and_then(|v| v.as_bool())
-                    .ok_or_else(|| ExtensionError::InvalidArguments("Missing active parameter".to_string()))?;
-
-                if let Some(binding) = self.bindings.write().get_mut(device_id) {
-                    binding.active = active;
-
-                    if let Some(stats) = self.binding_stats.write().get_mut(device_id) {
-                        stats.binding.active = active;
-                    }
-
-                    Ok(json!({
-                        "success": true,
-                        "device_id": device_id,
-                        "active": active
-                    }))
-                } else {
-                    Err(ExtensionError::NotFound(format!("Device {} not bound", device_id)))
-                }
-            }
-
             _ => Err(ExtensionError::CommandNotFound(command.to_string())),
         }
     }
@@ -1783,52 +1206,18 @@ impl Extension for YoloDeviceInference {
     #[cfg(target_arch = "wasm32")]
     fn execute_command_sync(&self, command: &str, args: &serde_json::Value) -> Result<serde_json::Value> {
         match command {
-            "bind_device" => {
-                let device_id = args.get("device_id")
-                    .and_then(|v| v.as_str())
-                    .ok_or_else(|| ExtensionError::InvalidArguments("Missing device_id".to_string()))?;
-
-                let binding = DeviceBinding {
-                    device_id: device_id.to_string(),
-                    device_name: args.get("device_name").and_then(|v| v.as_str()).map(String::from),
-                    image_metric: args.get("image_metric").and_then(|v| v.as_str()).unwrap_or("image").to_string(),
-                    result_metric_prefix: args.get("result_metric_prefix").and_then(|v| v.as_str()).unwrap_or("yolo_").to_string(),
-                    confidence_threshold: args.get("confidence_threshold").and_then(|v| v.as_f64()).map(|v| v as f32).unwrap_or(0.25),
-                    draw_boxes: args.get("draw_boxes").and_then(|v| v.as_bool()).unwrap_or(true),
-                    active: true,
-                };
-
-                // For WASM, just store the binding
-                self.bindings.write().insert(device_id.to_string(), binding.clone());
-                self.binding_stats.write().insert(device_id.to_string(), BindingStatus {
-                    binding,
-                    last_inference: None,
-                    total_inferences: 0,
-                    total_detections: 0,
-                    last_error: None,
-                    last_image: None,
-                    last_detections: None,
-                    last_annotated_image: None,
-                });
-
-                Ok(json!({"success": true, "device_id": device_id}))
-            }
-
-            "unbind_device" => {
-                let device_id = args.get("device_id").and_then(|v| v.as_str())
-                    .ok_or_else(|| ExtensionError::InvalidArguments("Missing device_id".to_string()))?;
-                self.bindings.write().remove(device_id);
-                self.binding_stats.write().remove(device_id);
-                Ok(json!({"success": true, "device_id": device_id}))
-            }
+            // For WASM, just store the binding
+            "bind_device" | "unbind_device" | "toggle_binding" => self.bindings.execute_command(command, args),
 
             "get_bindings" => {
                 Ok(json!({"success": true, "bindings": self.get_bindings()}))
             }
 
             "configure" => {
+                neomind_logging::apply_config("YoloDeviceInference", args);
+
                 // Load config from file
-                if let Some(config) = self.load_config_from_file() {
+                if let Some(config) = neomind_binding::config::load_config::<YoloConfig>("YoloDeviceInference") {
                     let _ = self.load_config(&config);
                     Ok(json!({"success": true, "message": "Configuration loaded from file"}))
                 } else {
@@ -1837,21 +1226,7 @@ impl Extension for YoloDeviceInference {
             }
 
             "get_status" => Ok(self.get_status()),
-            "toggle_binding" => {
-                let device_id = args.get("device_id").and_then(|v| v.as_str())
-                    .ok_or_else(|| ExtensionError::InvalidArguments("Missing device_id".to_string()))?;
-                let active = args.get("active").and_then(|v| v.as_bool());
-                if let Some(binding) = self.bindings.write().get_mut(device_id) {
-                    binding.active = active;
-                    if let Some(stats) = self.binding_stats.write().get_mut(device_id) {
-                        stats.binding.active = active;
-                    }
-                    Ok(json!({"success": true, "device_id": device_id, "active": active}))
-                } else {
-                    Err(ExtensionError::NotFound(format!("Device {} not bound", device_id)))
-                }
-            }
-
+            "health" => Ok(self.health().to_json()),
             "analyze_image" => {
                 Err(ExtensionError::NotSupported("Not supported in WASM".to_string()))
             }
@@ -1881,9 +1256,10 @@ impl Extension for YoloDeviceInference {
             tracing::info!("[YoloDeviceInference] Model will be loaded on first use (lazy loading)");
         }
 
-        eprintln!("[YoloDeviceInference] configure called with config: {:?}", config);
+        neomind_logging::apply_config("YoloDeviceInference", config);
+        tracing::debug!("[YoloDeviceInference] configure called with config: {:?}", config);
         // First, try to load from file (for isolated extensions)
-        if let Some(file_config) = self.load_config_from_file() {
+        if let Some(file_config) = neomind_binding::config::load_config::<YoloConfig>("YoloDeviceInference") {
             tracing::debug!("[YoloDeviceInference] Loading persisted configuration from file with {} bindings", file_config.bindings.len());
             self.load_config(&file_config)?;
         }
@@ -1916,10 +1292,13 @@ impl Extension for YoloDeviceInference {
             } else {
                 ("v8", "n")
             };
+            // An injected backend has no model parameters
             let mut detector = self.detector.lock();
-            detector.conf = conf;
-            detector.version = version.to_string();
-            detector.scale = scale.to_string();
+            if let Some(detector) = detector.as_any_mut().downcast_mut::<YOLODetector>() {
+                detector.conf = conf;
+                detector.version = version.to_string();
+                detector.scale = scale.to_string();
+            }
         }
 
         tracing::debug!("[YoloDeviceInference] Configuration applied successfully");
@@ -1936,3 +1315,41 @@ impl Extension for YoloDeviceInference {
 // ============================================================================
 
 neomind_extension_sdk::neomind_export!(YoloDeviceInference);
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detection(label: &str, confidence: f32, (x, y, width, height): (f32, f32, f32, f32)) -> Detection {
+        Detection {
+            label: label.to_string(),
+            confidence,
+            bbox: BoundingBox { x, y, width, height },
+            class_id: None,
+        }
+    }
+
+    #[test]
+    fn test_annotation_golden() {
+        let scene = image::RgbImage::from_fn(320, 240, |x, y| image::Rgb([(x * 255 / 320) as u8, 90, (y * 255 / 240) as u8]));
+        let mut png = Vec::new();
+        scene.write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png).unwrap();
+
+        let detections = [
+            detection("person", 0.92, (40.0, 60.0, 80.0, 150.0)),
+            // At the top edge: the label moves inside the box
+            detection("car", 0.81, (150.0, 0.0, 120.0, 70.0)),
+            // Partly off-canvas: box and label are clipped
+            detection("dog", 0.67, (270.0, 180.0, 90.0, 90.0)),
+        ];
+        let b64 = draw_detections_on_image(&png, &detections).unwrap();
+
+        let jpeg = base64::engine::general_purpose::STANDARD.decode(b64).unwrap();
+        let annotated = image::load_from_memory_with_format(&jpeg, image::ImageFormat::Jpeg).unwrap();
+        neomind_golden::assert_golden!("yolo_detections", annotated);
+    }
+}
@@ -0,0 +1,261 @@
+//! Scripted-backend tests for yolo-device-inference.
+//!
+//! These tests inject a scripted detector so they do NOT require ONNX Runtime
+//! or model files. They drive bindings, device events and manual analysis
+//! through the extension's public API.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use base64::Engine;
+use neomind_extension_sdk::{Extension, ExtensionError};
+use serde_json::json;
+
+use neomind_extension_yolo_device_inference::{
+    BoundingBox, Detection, ObjectDetect, YoloDeviceInference,
+};
+
+// ============================================================================
+// Scripted Backend
+// ============================================================================
+
+/// Detector that replays scripted responses, then detects nothing.
+struct ScriptedDetector {
+    /// Responses returned by successive detect() calls.
+    responses: VecDeque<Result<Vec<Detection>, String>>,
+    /// Number of detect() calls, shared with the test.
+    calls: Arc<AtomicUsize>,
+}
+
+impl ScriptedDetector {
+    fn new(responses: Vec<Result<Vec<Detection>, String>>) -> Self {
+        Self {
+            responses: responses.into(),
+            calls: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    fn with_error(msg: &str) -> Self {
+        Self::new(vec![Err(msg.to_string())])
+    }
+}
+
+impl ObjectDetect for ScriptedDetector {
+    fn detect(&mut self, _image_data: &[u8]) -> Result<(Vec<Detection>, u32, u32), String> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        let detections = self.responses.pop_front().unwrap_or(Ok(vec![]))?;
+        Ok((detections, 64, 48))
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn detection(label: &str, confidence: f32) -> Detection {
+    Detection {
+        label: label.to_string(),
+        confidence,
+        bbox: BoundingBox { x: 8.0, y: 6.0, width: 20.0, height: 30.0 },
+        class_id: None,
+    }
+}
+
+/// A 64x48 JPEG as base64.
+fn image_b64() -> String {
+    let img = image::RgbImage::from_pixel(64, 48, image::Rgb([200, 150, 100]));
+    let mut jpeg = Vec::new();
+    img.write_to(&mut std::io::Cursor::new(&mut jpeg), image::ImageFormat::Jpeg)
+        .expect("Failed to encode test image");
+    base64::engine::general_purpose::STANDARD.encode(&jpeg)
+}
+
+fn metric_event(device_id: &str, metric: &str) -> serde_json::Value {
+    json!({
+        "device_id": device_id,
+        "metric": metric,
+        "value": image_b64(),
+    })
+}
+
+/// Run an execute_command call and return the result.
+fn run_command(
+    ext: &YoloDeviceInference,
+    command: &str,
+    args: &serde_json::Value,
+) -> serde_json::Value {
+    tokio::task::block_in_place(|| {
+        let rt = tokio::runtime::Runtime::new().expect("Failed to create tokio runtime");
+        rt.block_on(ext.execute_command(command, args))
+            .expect("execute_command returned an ExtensionError")
+    })
+}
+
+fn bind(ext: &YoloDeviceInference, args: serde_json::Value) {
+    let result = run_command(ext, "bind_device", &args);
+    assert_eq!(result["success"], true);
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_device_event_updates_binding() {
+    let ext = YoloDeviceInference::with_detector(Box::new(ScriptedDetector::new(vec![
+        Ok(vec![detection("person", 0.9), detection("dog", 0.7)]),
+        Ok(vec![detection("car", 0.8)]),
+    ])));
+    bind(&ext, json!({ "device_id": "cam1", "image_metric": "image" }));
+
+    ext.handle_event("DeviceMetric", &metric_event("cam1", "image")).unwrap();
+    ext.handle_event("DeviceMetric", &json!({ "payload": metric_event("cam1", "image") })).unwrap();
+
+    let binding = &ext.get_bindings()[0];
+    assert_eq!(binding.total_inferences, 2);
+    assert_eq!(binding.state.total_detections, 3);
+    let last: Vec<&str> = binding.state.last_detections.as_ref().unwrap().iter().map(|d| d.label.as_str()).collect();
+    assert_eq!(last, ["car"]);
+    assert!(binding.last_image.as_ref().unwrap().starts_with("data:image/jpeg;base64,"));
+    assert!(binding.state.last_annotated_image.is_some());
+    assert!(binding.last_error.is_none());
+
+    let status = ext.get_status();
+    assert_eq!(status["model_loaded"], true);
+    assert_eq!(status["total_inferences"], 2);
+    assert_eq!(status["total_detections"], 3);
+    assert_eq!(ext.health().to_json()["state"], "healthy");
+}
+
+#[test]
+fn test_nested_image_metric() {
+    let ext = YoloDeviceInference::with_detector(Box::new(ScriptedDetector::new(vec![
+        Ok(vec![detection("person", 0.9)]),
+    ])));
+    bind(&ext, json!({ "device_id": "cam1", "image_metric": "snapshot.data" }));
+
+    let event = json!({
+        "device_id": "cam1",
+        "metric": "snapshot",
+        "value": { "data": image_b64() },
+    });
+    ext.handle_event("DeviceMetric", &event).unwrap();
+
+    assert_eq!(ext.get_bindings()[0].total_inferences, 1);
+}
+
+#[test]
+fn test_draw_boxes_disabled() {
+    let ext = YoloDeviceInference::with_detector(Box::new(ScriptedDetector::new(vec![
+        Ok(vec![detection("person", 0.9)]),
+    ])));
+    bind(&ext, json!({ "device_id": "cam1", "draw_boxes": false }));
+
+    ext.handle_event("DeviceMetric", &metric_event("cam1", "image")).unwrap();
+
+    let binding = &ext.get_bindings()[0];
+    assert_eq!(binding.state.total_detections, 1);
+    assert!(binding.state.last_annotated_image.is_none());
+}
+
+#[test]
+fn test_ignored_events_skip_inference() {
+    let detector = ScriptedDetector::new(vec![]);
+    let calls = detector.calls.clone();
+    let ext = YoloDeviceInference::with_detector(Box::new(detector));
+    bind(&ext, json!({ "device_id": "cam1" }));
+    bind(&ext, json!({ "device_id": "cam2" }));
+    run_command(&ext, "toggle_binding", &json!({ "device_id": "cam2", "active": false }));
+
+    ext.handle_event("DeviceMetric", &metric_event("other", "image")).unwrap();
+    ext.handle_event("DeviceMetric", &metric_event("cam1", "temperature")).unwrap();
+    ext.handle_event("DeviceMetric", &metric_event("cam2", "image")).unwrap();
+    ext.handle_event("DeviceStatus", &metric_event("cam1", "image")).unwrap();
+
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+    assert!(ext.get_bindings().iter().all(|b| b.total_inferences == 0));
+}
+
+#[test]
+fn test_toggle_binding_errors() {
+    let ext = YoloDeviceInference::with_detector(Box::new(ScriptedDetector::new(vec![])));
+    bind(&ext, json!({ "device_id": "cam1" }));
+    let toggle = |args: serde_json::Value| {
+        tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(ext.execute_command("toggle_binding", &args))
+    };
+
+    assert!(matches!(toggle(json!({ "device_id": "cam1" })), Err(ExtensionError::InvalidArguments(_))));
+    assert!(matches!(
+        toggle(json!({ "device_id": "nope", "active": false })),
+        Err(ExtensionError::NotFound(_))
+    ));
+    assert!(ext.get_bindings()[0].binding.active);
+}
+
+#[test]
+fn test_backend_error_is_recorded() {
+    let ext = YoloDeviceInference::with_detector(Box::new(ScriptedDetector::with_error("Inference failed: boom")));
+    bind(&ext, json!({ "device_id": "cam1" }));
+
+    // Errors are recorded on the binding, not returned to the event bus
+    ext.handle_event("DeviceMetric", &metric_event("cam1", "image")).unwrap();
+
+    let binding = &ext.get_bindings()[0];
+    assert_eq!(binding.total_inferences, 0);
+    assert!(binding.last_error.as_ref().unwrap().contains("boom"));
+    assert_eq!(ext.get_status()["total_errors"], 1);
+
+    let health = ext.health().to_json();
+    assert_eq!(health["state"], "degraded");
+    assert_eq!(health["error_count"], 1);
+
+    // The next event succeeds and clears the binding's error
+    ext.handle_event("DeviceMetric", &metric_event("cam1", "image")).unwrap();
+    assert!(ext.get_bindings()[0].last_error.is_none());
+}
+
+#[test]
+fn test_analyze_image_command() {
+    let ext = YoloDeviceInference::with_detector(Box::new(ScriptedDetector::new(vec![
+        Ok(vec![detection("person", 0.9)]),
+    ])));
+
+    let result = run_command(&ext, "analyze_image", &json!({ "image": image_b64() }));
+    assert_eq!(result["device_id"], "manual");
+    assert_eq!(result["image_width"], 64);
+    assert_eq!(result["image_height"], 48);
+    assert_eq!(result["detections"][0]["label"], "person");
+    assert!(result["annotated_image_base64"].is_string());
+
+    // The manual analysis is listed after the bindings
+    let bindings = ext.get_bindings();
+    assert_eq!(bindings.len(), 1);
+    assert_eq!(bindings[0].binding.device_id, "manual");
+    assert_eq!(bindings[0].state.total_detections, 1);
+}
+
+#[test]
+fn test_produce_metrics() {
+    let ext = YoloDeviceInference::with_detector(Box::new(ScriptedDetector::new(vec![
+        Ok(vec![detection("person", 0.9), detection("car", 0.6)]),
+    ])));
+    bind(&ext, json!({ "device_id": "cam1" }));
+    ext.handle_event("DeviceMetric", &metric_event("cam1", "image")).unwrap();
+
+    let metrics = ext.produce_metrics().unwrap();
+    let value = |name: &str| {
+        let metric = metrics.iter().find(|m| m.name == name).unwrap();
+        serde_json::to_value(&metric.value).unwrap()
+    };
+    assert_eq!(value("bound_devices"), json!({ "integer": 1 }));
+    assert_eq!(value("total_inferences"), json!({ "integer": 1 }));
+    assert_eq!(value("total_detections"), json!({ "integer": 2 }));
+    assert_eq!(value("health_state"), json!({ "string": "healthy" }));
+}
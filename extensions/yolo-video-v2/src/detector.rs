@@ -10,40 +10,9 @@ use std::sync::Arc;
 use serde::{Deserialize, Serialize};
 
 #[cfg(not(target_arch = "wasm32"))]
-use usls::{models::YOLO, Config, Device, Version};
-
-/// Auto-detect best available inference device.
-/// macOS → CoreML, Linux → CUDA, others → CPU.
+use usls::{models::YOLO, Config, Version};
 #[cfg(not(target_arch = "wasm32"))]
-fn auto_device() -> Device {
-    #[cfg(target_os = "macos")]
-    { Device::CoreMl }
-    #[cfg(all(not(target_os = "macos"), target_os = "linux"))]
-    { Device::Cuda(0) }
-    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
-    { Device::Cpu(0) }
-}
-
-/// Try building a model with the auto-detected device, fall back to CPU on failure.
-#[cfg(not(target_arch = "wasm32"))]
-fn with_device_fallback<M, F>(try_build: F) -> std::result::Result<M, String>
-where
-    F: Fn(Device) -> std::result::Result<M, String>,
-{
-    let device = auto_device();
-    eprintln!("[HW] Trying device: {:?}", device);
-    match try_build(device) {
-        Ok(model) => {
-            eprintln!("[HW] Model loaded with device: {:?}", device);
-            Ok(model)
-        }
-        Err(e) if !matches!(device, Device::Cpu(_)) => {
-            eprintln!("[HW] {:?} failed ({}), falling back to CPU", device, e);
-            try_build(Device::Cpu(0))
-        }
-        Err(e) => Err(e),
-    }
-}
+use neomind_vision::{device::with_device_fallback, native::setup_native_lib_paths};
 
 // Re-export BoundingBox from parent module to avoid duplication
 pub use crate::BoundingBox;
@@ -57,210 +26,34 @@ pub struct Detection {
     pub bbox: BoundingBox,
 }
 
-/// Set up native library search paths before ONNX Runtime is loaded.
-/// Checks NEOMIND_EXTENSION_DIR/lib/ and common system paths.
-#[cfg(not(target_arch = "wasm32"))]
-fn setup_native_lib_paths() {
-    let lib_env = if cfg!(target_os = "macos") {
-        "DYLD_LIBRARY_PATH"
-    } else if cfg!(target_os = "windows") {
-        "PATH"
-    } else {
-        "LD_LIBRARY_PATH"
-    };
-
-    let mut paths = vec![];
-
-    // 1. Extension's bundled libraries - check all possible locations
-    if let Ok(ext_dir) = std::env::var("NEOMIND_EXTENSION_DIR") {
-        let ext_path = std::path::Path::new(&ext_dir);
-
-        // lib/ directory (top-level)
-        let lib_dir = ext_path.join("lib");
-        if lib_dir.is_dir() {
-            tracing::info!("[NativeLibs] Adding extension lib dir: {}", lib_dir.display());
-            paths.push(lib_dir.to_string_lossy().to_string());
-        }
-
-        // binaries/<platform>/ directory (where extension.dylib and bundled libs live)
-        let binaries_dir = ext_path.join("binaries");
-        if binaries_dir.is_dir() {
-            // Add all subdirectories (darwin_aarch64, linux_amd64, etc.)
-            if let Ok(entries) = std::fs::read_dir(&binaries_dir) {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    if path.is_dir() {
-                        tracing::info!("[NativeLibs] Adding platform dir: {}", path.display());
-                        paths.push(path.to_string_lossy().to_string());
-
-                        // Create unversioned symlinks for versioned libraries
-                        // e.g. libonnxruntime.1.19.2.dylib -> libonnxruntime.dylib
-                        if let Ok(files) = std::fs::read_dir(&path) {
-                            for file in files.flatten() {
-                                let file_path = file.path();
-                                let name = file_path.file_name().unwrap_or_default().to_string_lossy();
-                                // Match versioned dylib/so patterns
-                                if let Some(base) = name.strip_suffix(".dylib")
-                                    .or_else(|| name.strip_suffix(".so"))
-                                {
-                                    if base.contains('.') {
-                                        // Has version suffix like libonnxruntime.1.19.2
-                                        let unversioned = if cfg!(target_os = "macos") {
-                                            format!("{}.dylib", base.split('.').next().unwrap_or(base))
-                                        } else {
-                                            format!("{}.so", base.split('.').next().unwrap_or(base))
-                                        };
-                                        let link_path = path.join(&unversioned);
-                                        if !link_path.exists() {
-                                            #[cfg(unix)]
-                                            let _ = std::os::unix::fs::symlink(&file_path, &link_path);
-                                            #[cfg(not(unix))]
-                                            let _ = ();
-                                            tracing::info!("[NativeLibs] Created symlink: {} -> {}", unversioned, name);
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    // Also check current working directory (extension runner sets this)
-    if let Ok(cwd) = std::env::current_dir() {
-        let lib_dir = cwd.join("lib");
-        if lib_dir.is_dir() {
-            paths.push(lib_dir.to_string_lossy().to_string());
-        }
-    }
-
-    // 2. Inherit existing paths
-    if let Ok(existing) = std::env::var(lib_env) {
-        paths.push(existing);
-    }
-
-    // 3. Common system paths
-    for dir in ["/opt/homebrew/lib", "/usr/local/lib"] {
-        if std::path::Path::new(dir).is_dir() {
-            paths.push(dir.to_string());
-        }
-    }
-
-    if !paths.is_empty() {
-        let sep = if cfg!(target_os = "windows") { ";" } else { ":" };
-        let combined = paths.join(sep);
-        tracing::info!("[NativeLibs] Setting {} = {}", lib_env, combined);
-        std::env::set_var(lib_env, &combined);
-    }
-
-    // Set ORT_DYLIB_PATH to the exact location of libonnxruntime
-    // This is the recommended way for the `ort` crate's load-dynamic feature.
-    // DYLD_LIBRARY_PATH set at runtime via set_var may not affect dlopen on macOS.
-    if std::env::var("ORT_DYLIB_PATH").is_err() {
-        let ort_filename = if cfg!(target_os = "macos") {
-            "libonnxruntime.dylib"
-        } else if cfg!(target_os = "windows") {
-            "onnxruntime.dll"
-        } else {
-            "libonnxruntime.so"
-        };
-
-        for dir in &paths {
-            let ort_path = std::path::Path::new(dir).join(ort_filename);
-            if ort_path.exists() {
-                tracing::info!("[NativeLibs] Setting ORT_DYLIB_PATH = {}", ort_path.display());
-                std::env::set_var("ORT_DYLIB_PATH", &ort_path);
-                break;
-            }
-        }
-    }
-
-    // macOS: Fix dylib install names so bundled libraries can find each other.
-    // Homebrew-built dylibs reference each other by absolute paths (e.g., /opt/homebrew/...).
-    // SIP prevents DYLD_LIBRARY_PATH from taking effect in many cases.
-    // This changes all inter-dylib references to @loader_path/ relative paths.
-    #[cfg(target_os = "macos")]
-    fix_macos_dylib_install_names(&paths);
-}
-
-/// Fix macOS dylib install names so bundled libraries can find each other via @loader_path.
-/// Runs only once per installation (marker file prevents re-runs).
-#[cfg(target_os = "macos")]
-fn fix_macos_dylib_install_names(search_dirs: &[String]) {
-    use std::process::Command;
-
-    // Find the platform binaries directory
-    let platform_dir = search_dirs.iter().find(|d| {
-        std::path::Path::new(d).exists()
-            && d.contains("darwin_")
-            && std::path::Path::new(d).join("extension.dylib").exists()
-    });
-
-    let Some(dir) = platform_dir else { return };
-    let dir = std::path::Path::new(dir);
-
-    // Marker file to avoid re-running on every startup
-    let marker = dir.join(".dylib_paths_fixed");
-    if marker.exists() {
-        return;
-    }
-
-    let Ok(entries) = std::fs::read_dir(dir) else { return };
-    let dylib_names: Vec<String> = entries
-        .flatten()
-        .filter(|e| e.path().extension().map_or(false, |ext| ext == "dylib"))
-        .map(|e| e.file_name().to_string_lossy().to_string())
-        .collect();
-
-    if dylib_names.is_empty() {
-        return;
+/// Object detection backend used by the stream processor
+///
+/// `YoloDetector` is the production implementation; tests inject scripted
+/// backends so stream processing can run without ONNX Runtime or model files.
+pub trait ObjectDetect {
+    /// Load the model if it hasn't been attempted yet
+    fn ensure_loaded(&mut self) {}
+
+    /// Whether the model is ready for inference
+    fn is_loaded(&self) -> bool {
+        true
     }
 
-    tracing::info!("[NativeLibs] Fixing install names for {} dylib(s) in {}", dylib_names.len(), dir.display());
-
-    for name in &dylib_names {
-        let path = dir.join(name);
-        let loader_ref = format!("@loader_path/{}", name);
-
-        let Ok(output) = Command::new("otool").arg("-L").arg(&path).output() else { continue };
-        let stdout = String::from_utf8_lossy(&output.stdout);
-
-        let mut args: Vec<String> = vec!["-id".to_string(), loader_ref];
-
-        for line in stdout.lines().skip(1) {
-            let trimmed = line.trim();
-            if trimmed.is_empty() || trimmed.starts_with("@loader_path/") {
-                continue;
-            }
-            let ref_path = trimmed.split_whitespace().next().unwrap_or("");
-            if ref_path.is_empty()
-                || ref_path.starts_with("/usr/lib/")
-                || ref_path.starts_with("/System/")
-            {
-                continue;
-            }
-            let ref_name = std::path::Path::new(ref_path)
-                .file_name()
-                .unwrap_or_default()
-                .to_string_lossy()
-                .to_string();
-            if dylib_names.contains(&ref_name) {
-                args.extend([
-                    "-change".to_string(),
-                    ref_path.to_string(),
-                    format!("@loader_path/{}", ref_name),
-                ]);
-            }
-        }
-
-        args.push(path.to_string_lossy().to_string());
-        let _ = Command::new("install_name_tool").args(&args).output();
+    /// The error from the last load attempt, if any
+    fn load_error(&self) -> Option<String> {
+        None
     }
 
-    let _ = std::fs::write(&marker, "");
-    tracing::info!("[NativeLibs] Install names fixed successfully");
+    /// Run inference on an image
+    ///
+    /// # Arguments
+    /// * `image` - RGB image in model input size
+    /// * `confidence_threshold` - Minimum detection confidence
+    /// * `max_detections` - Maximum number of detections to return
+    ///
+    /// # Returns
+    /// Detections in `image` pixel coordinates
+    fn detect(&mut self, image: &RgbImage, confidence_threshold: f32, max_detections: u32) -> Vec<Detection>;
 }
 
 /// YOLOv11 detector using usls
@@ -283,7 +76,7 @@ pub struct YoloDetector {
 impl YoloDetector {
     /// Create a new detector without loading the model (lazy initialization)
     pub fn new() -> Result<Self, String> {
-        eprintln!("[YOLO-Detector] Creating YoloDetector (lazy - model not loaded yet)...");
+        tracing::debug!("[YOLO-Detector] Creating YoloDetector (lazy - model not loaded yet)...");
 
         #[cfg(not(target_arch = "wasm32"))]
         {
@@ -300,7 +93,7 @@ impl YoloDetector {
 
         #[cfg(target_arch = "wasm32")]
         {
-            eprintln!("[YOLO-Detector] WASM target, running in fallback mode");
+            tracing::debug!("[YOLO-Detector] WASM target, running in fallback mode");
             Ok(Self {
                 model_loaded: false,
                 model_size: 0,
@@ -325,20 +118,17 @@ impl YoloDetector {
         setup_native_lib_paths();
 
         tracing::info!("[YOLO-Detector] Lazy loading model: v{}-{}", self.version, self.scale);
-        eprintln!("[YOLO-Detector] Lazy loading model...");
 
         #[cfg(not(target_arch = "wasm32"))]
         {
             match Self::try_load_model(&self.version, self.conf) {
                 Ok((model, model_size)) => {
                     tracing::info!("[YOLO-Detector] Model loaded successfully: v{}-{}", self.version, self.scale);
-                    eprintln!("[YOLO-Detector] YOLO model loaded successfully!");
                     self.model = Some(model);
                     self.model_size = model_size;
                 }
                 Err(e) => {
                     tracing::error!("[YOLO-Detector] Failed to load model: {}", e);
-                    eprintln!("[YOLO-Detector] Failed to load model: {}", e);
                     self.load_error = Some(e);
                 }
             }
@@ -353,33 +143,15 @@ impl YoloDetector {
     /// Try to load the model (called by ensure_loaded)
     #[cfg(not(target_arch = "wasm32"))]
     fn try_load_model(version: &str, conf: f32) -> Result<(Arc<parking_lot::Mutex<YOLO>>, usize), String> {
-        eprintln!("[YOLO-Detector] Loading YOLO model v{}", version);
+        tracing::debug!("[YOLO-Detector] Loading YOLO model v{}", version);
 
-        let model_data = Self::load_model_data()?;
-
-        if model_data.is_none() {
-            let ext_dir = std::env::var("NEOMIND_EXTENSION_DIR").unwrap_or_else(|_| "unknown".to_string());
-            return Err(format!(
-                "YOLO model not found. Please ensure yolo11n.onnx is in the models/ directory. Searched in: {}",
-                ext_dir
-            ));
-        }
-
-        let model_bytes = model_data.unwrap();
-        let model_size = model_bytes.len();
-        eprintln!("[YOLO-Detector] Model data loaded: {} bytes", model_size);
-
-        // Save model to temp file with unique name (usls requires file path)
-        let temp_dir = std::env::temp_dir();
-        let unique_id = uuid::Uuid::new_v4();
-        let model_path = temp_dir.join(format!("yolo11n_{}.onnx", unique_id));
-        eprintln!("[YOLO-Detector] Writing model to temp file: {}", model_path.display());
-
-        std::fs::write(&model_path, &model_bytes)
-            .map_err(|e| format!("Failed to write temp model file: {}", e))?;
+        let model_path = neomind_vision::find_model("yolo11n.onnx")
+            .map_err(|e| format!("YOLO model not found. Please ensure yolo11n.onnx is in the models/ directory. {}", e))?;
+        let model_size = neomind_vision::models::model_size(&model_path);
+        tracing::info!("[YOLO-Detector] Model file: {} ({} bytes)", model_path.display(), model_size);
 
         // Create Config for YOLO detection using usls API
-        eprintln!("[YOLO-Detector] Configuring YOLO model with usls...");
+        tracing::debug!("[YOLO-Detector] Configuring YOLO model with usls...");
 
         // Parse version number
         let version_num: u8 = version.trim_start_matches('v')
@@ -401,79 +173,11 @@ impl YoloDetector {
                 .map_err(|e| format!("Model failed: {:?}", e))
         })?;
 
-        eprintln!("[YOLO-Detector] YOLO model loaded successfully!");
-        eprintln!("[YOLO-Detector] Model: YOLOv{}n, Confidence: {}", version_num, conf);
-
-        // Clean up temp file
-        let _ = std::fs::remove_file(&model_path);
+        tracing::info!("[YOLO-Detector] Model: YOLOv{}n, Confidence: {}", version_num, conf);
 
         Ok((Arc::new(parking_lot::Mutex::new(model)), model_size))
     }
 
-    /// Load model data from disk
-    fn load_model_data() -> Result<Option<Vec<u8>>, String> {
-        eprintln!("[YOLO-Detector] Starting model search...");
-
-        // Try to get extension directory from environment variable (set by runner)
-        if let Ok(ext_dir) = std::env::var("NEOMIND_EXTENSION_DIR") {
-            eprintln!("[YOLO-Detector] NEOMIND_EXTENSION_DIR = {}", ext_dir);
-
-            // Primary path: <extension_dir>/models/yolo11n.onnx
-            let model_path = std::path::PathBuf::from(&ext_dir).join("models").join("yolo11n.onnx");
-            eprintln!("[YOLO-Detector] Checking primary path: {}", model_path.display());
-
-            if model_path.exists() {
-                eprintln!("[YOLO-Detector] ✓ Found model at: {}", model_path.display());
-                return std::fs::read(&model_path)
-                    .map(Some)
-                    .map_err(|e| format!("Failed to read model: {}", e));
-            } else {
-                eprintln!("[YOLO-Detector] ✗ Model not found at primary path");
-            }
-        } else {
-            eprintln!("[YOLO-Detector] NEOMIND_EXTENSION_DIR not set");
-        }
-
-        // Fallback: Try to find model relative to current working directory
-        // When running in isolated process, the working directory should be the extension root
-        eprintln!("[YOLO-Detector] Checking current working directory...");
-        if let Ok(cwd) = std::env::current_dir() {
-            eprintln!("[YOLO-Detector] Current working directory: {}", cwd.display());
-            
-            let model_path = cwd.join("models").join("yolo11n.onnx");
-            eprintln!("[YOLO-Detector] Checking: {}", model_path.display());
-            
-            if model_path.exists() {
-                eprintln!("[YOLO-Detector] ✓ Found model at: {}", model_path.display());
-                return std::fs::read(&model_path)
-                    .map(Some)
-                    .map_err(|e| format!("Failed to read model: {}", e));
-            }
-        }
-
-        // Fallback: Try relative paths from current directory
-        let fallback_paths = vec![
-            std::path::PathBuf::from("models/yolo11n.onnx"),
-            std::path::PathBuf::from("../models/yolo11n.onnx"),
-            std::path::PathBuf::from("../../models/yolo11n.onnx"),
-            std::path::PathBuf::from("extensions/yolo-video-v2/models/yolo11n.onnx"),
-            std::path::PathBuf::from("../extensions/yolo-video-v2/models/yolo11n.onnx"),
-        ];
-
-        for path in &fallback_paths {
-            eprintln!("[YOLO-Detector] Checking fallback path: {}", path.display());
-            if path.exists() {
-                eprintln!("[YOLO-Detector] ✓ Found model at: {}", path.display());
-                return std::fs::read(path)
-                    .map(Some)
-                    .map_err(|e| format!("Failed to read model: {}", e));
-            }
-        }
-
-        eprintln!("[YOLO-Detector] ❌ Model not found in any location");
-        Ok(None)
-    }
-
     /// Check if model is loaded
     pub fn is_loaded(&self) -> bool {
         #[cfg(not(target_arch = "wasm32"))]
@@ -637,7 +341,7 @@ impl YoloDetector {
                 let confidence = hbb.confidence().unwrap_or(0.0);
                 let class_name = hbb.name()
                     .map(|s: &str| s.to_string())
-                    .unwrap_or_else(|| Self::get_class_name(class_id as usize));
+                    .unwrap_or_else(|| neomind_vision::coco_class_name(class_id as usize).unwrap_or("unknown").to_string());
 
                 Some(Detection {
                     class_id,
@@ -665,26 +369,23 @@ impl YoloDetector {
 
         detections
     }
+}
+
+impl ObjectDetect for YoloDetector {
+    fn ensure_loaded(&mut self) {
+        YoloDetector::ensure_loaded(self);
+    }
+
+    fn is_loaded(&self) -> bool {
+        YoloDetector::is_loaded(self)
+    }
+
+    fn load_error(&self) -> Option<String> {
+        self.get_load_error().map(str::to_string)
+    }
 
-    /// Get COCO class name by index
-    fn get_class_name(class_id: usize) -> String {
-        const COCO_CLASSES: [&str; 80] = [
-            "person", "bicycle", "car", "motorcycle", "airplane", "bus", "train", "truck", "boat",
-            "traffic light", "fire hydrant", "stop sign", "parking meter", "bench", "bird", "cat",
-            "dog", "horse", "sheep", "cow", "elephant", "bear", "zebra", "giraffe", "backpack",
-            "umbrella", "handbag", "tie", "suitcase", "frisbee", "skis", "snowboard", "sports ball",
-            "kite", "baseball bat", "baseball glove", "skateboard", "surfboard", "tennis racket",
-            "bottle", "wine glass", "cup", "fork", "knife", "spoon", "bowl", "banana", "apple",
-            "sandwich", "orange", "broccoli", "carrot", "hot dog", "pizza", "donut", "cake",
-            "chair", "couch", "potted plant", "bed", "dining table", "toilet", "tv", "laptop",
-            "mouse", "remote", "keyboard", "cell phone", "microwave", "oven", "toaster", "sink",
-            "refrigerator", "book", "clock", "vase", "scissors", "teddy bear", "hair drier", "toothbrush",
-        ];
-
-        COCO_CLASSES
-            .get(class_id)
-            .unwrap_or(&"unknown")
-            .to_string()
+    fn detect(&mut self, image: &RgbImage, confidence_threshold: f32, max_detections: u32) -> Vec<Detection> {
+        YoloDetector::detect(self, image, confidence_threshold, max_detections)
     }
 }
 
@@ -40,25 +40,14 @@ use serde::{Deserialize, Serialize};
 use serde_json::json;
 use uuid::Uuid;
 
-use detector::{Detection, YoloDetector};
+use detector::{Detection, ObjectDetect, YoloDetector};
 
 // ============================================================================
 // Constants
 // ============================================================================
 
 /// YOLOv11 class labels (COCO 80 classes)
-pub const COCO_CLASSES: [&str; 80] = [
-    "person", "bicycle", "car", "motorcycle", "airplane", "bus", "train", "truck", "boat",
-    "traffic light", "fire hydrant", "stop sign", "parking meter", "bench", "bird", "cat",
-    "dog", "horse", "sheep", "cow", "elephant", "bear", "zebra", "giraffe", "backpack",
-    "umbrella", "handbag", "tie", "suitcase", "frisbee", "skis", "snowboard", "sports ball",
-    "kite", "baseball bat", "baseball glove", "skateboard", "surfboard", "tennis racket",
-    "bottle", "wine glass", "cup", "fork", "knife", "spoon", "bowl", "banana", "apple",
-    "sandwich", "orange", "broccoli", "carrot", "hot dog", "pizza", "donut", "cake",
-    "chair", "couch", "potted plant", "bed", "dining table", "toilet", "tv", "laptop",
-    "mouse", "remote", "keyboard", "cell phone", "microwave", "oven", "toaster", "sink",
-    "refrigerator", "book", "clock", "vase", "scissors", "teddy bear", "hair drier", "toothbrush",
-];
+pub use neomind_vision::COCO_CLASSES;
 
 /// Model configuration
 pub const MODEL_CONFIG: ModelConfig = ModelConfig {
@@ -275,31 +264,6 @@ struct ActiveStream {
     pending_captures: Vec<CaptureEvent>,
 }
 
-/// Standard COCO 80-class color palette (each class gets a unique, consistent color)
-const COCO_COLORS: [(u8, u8, u8); 80] = [
-    (38, 70, 83),   (40, 116, 74),  (117, 79, 12), (115, 53, 88), (192, 41, 66),
-    (11, 121, 175), (232, 168, 124),(211, 212, 211),(232, 212, 77),(32, 169, 199),
-    (57, 94, 121),  (237, 139, 0),  (133, 160, 131),(174, 30, 70),(255, 183, 59),
-    (197, 198, 53), (166, 207, 213),(136, 86, 82), (119, 104, 174),(51, 159, 160),
-    (166, 59, 111), (197, 166, 137),(108, 118, 135),(38, 131, 116),(233, 126, 67),
-    (255, 179, 71), (48, 96, 106),  (197, 104, 80),(227, 105, 145),(229, 193, 175),
-    (141, 176, 191),(68, 58, 90),   (138, 142, 72), (248, 162, 162),(115, 145, 144),
-    (72, 46, 64),   (77, 84, 156),  (55, 104, 56), (238, 113, 119),(246, 198, 76),
-    (79, 128, 165), (167, 188, 196),(176, 84, 97), (47, 139, 110),(42, 110, 152),
-    (197, 114, 60), (134, 82, 60),  (73, 131, 103),(101, 146, 85),(219, 138, 80),
-    (118, 156, 145),(164, 182, 204),(129, 173, 129),(113, 107, 91),(145, 54, 140),
-    (161, 166, 98), (230, 144, 133),(199, 144, 106),(48, 109, 140),(195, 100, 118),
-    (93, 155, 112), (160, 137, 173),(109, 59, 105), (212, 128, 93),(231, 172, 112),
-    (78, 131, 137), (227, 133, 117),(189, 187, 169),(78, 78, 103), (139, 78, 107),
-    (123, 140, 63), (161, 158, 131),(104, 112, 115),(109, 122, 65),(150, 131, 104),
-    (180, 181, 195),(112, 98, 114), (157, 130, 166),(129, 129, 106),(67, 102, 130),
-];
-
-/// Get color for a class ID using COCO palette
-fn class_color(class_id: u32) -> (u8, u8, u8) {
-    COCO_COLORS[(class_id as usize) % COCO_COLORS.len()]
-}
-
 // ============================================================================
 // Helper Functions
 // ============================================================================
@@ -358,9 +322,9 @@ fn generate_fallback_detections(frame_count: u64, max_objects: u32) -> Vec<Objec
 
 /// Draw detections on an image with standard object detection visualization
 pub fn draw_detections(image: &mut image::RgbImage, detections: &[ObjectDetection]) {
-    use imageproc::drawing::{draw_hollow_rect_mut, draw_filled_rect_mut, draw_text_mut};
-    use imageproc::rect::Rect;
-    use ab_glyph::{FontRef, PxScale, Font as AbFont, ScaleFont as _};
+    use ab_glyph::FontRef;
+    use neomind_vision::draw::{draw_labeled_box, BoxStyle, PixelRect};
+    use neomind_vision::labels::class_color;
 
     // Cache font loading
     static FONT_RESULT: std::sync::OnceLock<std::result::Result<FontRef<'static>, ab_glyph::InvalidFont>> = std::sync::OnceLock::new();
@@ -368,103 +332,31 @@ pub fn draw_detections(image: &mut image::RgbImage, detections: &[ObjectDetectio
     let font = FONT_RESULT.get_or_init(|| {
         let result = FontRef::try_from_slice(include_bytes!("../fonts/NotoSans-Regular.ttf"));
         if let Err(ref e) = result {
-            eprintln!("[YOLO-Draw] Font load FAILED: {:?}", e);
+            tracing::warn!("[YOLO-Draw] Font load FAILED: {:?}", e);
         } else {
-            eprintln!("[YOLO-Draw] Font loaded OK");
+            tracing::debug!("[YOLO-Draw] Font loaded OK");
         }
         result
     });
 
-    let font = match font {
-        Ok(f) => f,
-        Err(_) => {
-            // Font failed — just draw boxes
-            for det in detections {
-                let color = class_color(det.class_id);
-                let image_color = image::Rgb([color.0, color.1, color.2]);
-                let x = det.bbox.x.max(0.0).min(image.width() as f32 - 2.0) as i32;
-                let y = det.bbox.y.max(0.0).min(image.height() as f32 - 2.0) as i32;
-                let w = det.bbox.width.min(image.width() as f32 - x as f32 - 1.0) as u32;
-                let h = det.bbox.height.min(image.height() as f32 - y as f32 - 1.0) as u32;
-                if w >= 2 && h >= 2 {
-                    draw_hollow_rect_mut(image, Rect::at(x, y).of_size(w, h), image_color);
-                    draw_hollow_rect_mut(image, Rect::at(x+1, y+1).of_size(w.saturating_sub(2), h.saturating_sub(2)), image_color);
-                }
-            }
-            return;
-        }
-    };
-
-    let img_w = image.width();
-    let img_h = image.height();
-
     // Log first detection for debugging
     if let Some(first) = detections.first() {
-        eprintln!("[YOLO-Draw] img={}x{} det0: x={:.0} y={:.0} w={:.0} h={:.0} label={}",
-            img_w, img_h, first.bbox.x, first.bbox.y, first.bbox.width, first.bbox.height, first.label);
+        tracing::trace!("[YOLO-Draw] img={}x{} det0: x={:.0} y={:.0} w={:.0} h={:.0} label={}",
+            image.width(), image.height(), first.bbox.x, first.bbox.y, first.bbox.width, first.bbox.height, first.label);
     }
 
     for det in detections {
-        let color = class_color(det.class_id);
-        let image_color = image::Rgb([color.0, color.1, color.2]);
-
-        let x = det.bbox.x.max(0.0).min(img_w as f32 - 2.0) as i32;
-        let y = det.bbox.y.max(0.0).min(img_h as f32 - 2.0) as i32;
-        let w = det.bbox.width.min(img_w as f32 - x as f32 - 1.0) as u32;
-        let h = det.bbox.height.min(img_h as f32 - y as f32 - 1.0) as u32;
-
-        if w < 4 || h < 4 {
-            continue;
-        }
-
-        // Draw bounding box (2px thick)
-        draw_hollow_rect_mut(image, Rect::at(x, y).of_size(w, h), image_color);
-        draw_hollow_rect_mut(image, Rect::at(x + 1, y + 1).of_size(w.saturating_sub(2), h.saturating_sub(2)), image_color);
-
+        let rect = PixelRect {
+            x: det.bbox.x,
+            y: det.bbox.y,
+            width: det.bbox.width,
+            height: det.bbox.height,
+        };
         // Build label: "ClassName 87%"
         let label_text = format!("{} {:.0}%", det.label, det.confidence * 100.0);
-
-        // Scale font proportionally to image resolution
-        // For 1920x1080: ~24px, for 640x480: ~16px, for small: ~11px
-        let font_size = if img_w > 1200 { 24.0 } else if img_w > 800 { 18.0 } else if w > 100 { 14.0 } else { 11.0 };
-        let scale = PxScale::from(font_size);
-
-        // Measure text width using glyph advance widths
-        let scaled_font = font.as_scaled(scale);
-        let mut text_width: f32 = 0.0;
-        for c in label_text.chars() {
-            let glyph_id = scaled_font.glyph_id(c);
-            text_width += scaled_font.h_advance(glyph_id);
-        }
-        let label_width = (text_width.ceil() as u32 + 12).min(img_w - x as u32);
-        let label_height = (font_size as u32) + 8;
-
-        // Position label above the box, or inside if no room above
-        let label_y = if y >= label_height as i32 {
-            y - label_height as i32
-        } else {
-            y
-        };
-
-        if label_y >= 0 && (label_y as u32) + label_height <= img_h {
-            // Filled background for label
-            draw_filled_rect_mut(
-                image,
-                Rect::at(x, label_y).of_size(label_width, label_height),
-                image_color,
-            );
-
-            // White text on colored background
-            draw_text_mut(
-                image,
-                image::Rgb([255, 255, 255]),
-                x + 5,
-                label_y + 3,
-                scale,
-                font,
-                &label_text,
-            );
-        }
+        let style = BoxStyle::solid(class_color(det.class_id)).with_min_size(4);
+        // Without a font only the boxes are drawn
+        draw_labeled_box(image, rect, &label_text, &style, font.as_ref().ok());
     }
 }
 
@@ -577,35 +469,55 @@ pub fn remove_frame_queue(session_id: &str) {
 // ============================================================================
 
 pub struct StreamProcessor {
-    detector: Arc<parking_lot::Mutex<Option<YoloDetector>>>,
+    detector: Arc<parking_lot::Mutex<Option<Box<dyn ObjectDetect + Send>>>>,
+    /// Model state mirrored from the detector so `health` never waits on inference
+    model_loaded: std::sync::atomic::AtomicBool,
+    model_error: Mutex<Option<String>>,
+    /// Frame successes and stream failures for the health report
+    activity: neomind_health::Activity,
 }
 
 impl StreamProcessor {
     pub fn new() -> Self {
         // Lazy initialization: create the detector wrapper but don't load the model yet.
         // The model will be loaded on first use via ensure_loaded().
-        let detector = match YoloDetector::new() {
+        let (detector, model_error) = match YoloDetector::new() {
             Ok(d) => {
                 tracing::info!("[YOLO-Video] YOLO detector created (lazy - model not loaded yet)");
-                Some(d)
+                (Some(Box::new(d) as Box<dyn ObjectDetect + Send>), None)
             }
             Err(e) => {
                 tracing::error!("[YOLO-Video] Failed to create detector: {}", e);
-                None
+                (None, Some(e))
             }
         };
 
         Self {
             detector: Arc::new(parking_lot::Mutex::new(detector)),
+            model_loaded: std::sync::atomic::AtomicBool::new(false),
+            model_error: Mutex::new(model_error),
+            activity: neomind_health::Activity::new(),
+        }
+    }
+
+    /// Create a processor with a custom detection backend
+    pub fn with_detector(detector: Box<dyn ObjectDetect + Send>) -> Self {
+        Self {
+            detector: Arc::new(parking_lot::Mutex::new(Some(detector))),
+            model_loaded: std::sync::atomic::AtomicBool::new(false),
+            model_error: Mutex::new(None),
+            activity: neomind_health::Activity::new(),
         }
     }
 
     /// Get the YOLO detector, ensuring it's loaded (lazy initialization)
-    fn get_detector(&self) -> Option<parking_lot::MappedMutexGuard<'_, YoloDetector>> {
+    fn get_detector(&self) -> Option<parking_lot::MappedMutexGuard<'_, Box<dyn ObjectDetect + Send>>> {
         let mut lock = self.detector.lock();
         if let Some(ref mut detector) = *lock {
             // Ensure model is loaded before returning
             detector.ensure_loaded();
+            self.model_loaded.store(detector.is_loaded(), std::sync::atomic::Ordering::Relaxed);
+            *self.model_error.lock() = detector.load_error();
             Some(parking_lot::MutexGuard::map(lock, |opt| opt.as_mut().unwrap()))
         } else {
             None
@@ -614,6 +526,15 @@ impl StreamProcessor {
     
 
 
+    /// Health report for the `health` command and `health_state` metric
+    pub fn health(&self) -> neomind_health::Health {
+        let model_loaded = self.model_loaded.load(std::sync::atomic::Ordering::Relaxed);
+        neomind_health::Health::new()
+            .model(model_loaded, self.model_error.lock().clone())
+            .native_libs(model_loaded || neomind_vision::native::ort_library().is_some(), "ONNX Runtime")
+            .activity(&self.activity)
+    }
+
     #[allow(dead_code)]
     fn has_model(&self) -> bool {
         let mut lock = self.detector.lock();
@@ -627,7 +548,7 @@ impl StreamProcessor {
 
     /// Trigger memory cleanup (called by gc_memory command)
     pub fn cleanup_memory(&self) {
-        eprintln!("[YOLO] Memory cleanup triggered");
+        tracing::info!("[YOLO] Memory cleanup triggered");
         
         // Clear all cached frames from streams
         let registry = get_registry().lock();
@@ -644,9 +565,9 @@ impl StreamProcessor {
         // ✨ CRITICAL: Trigger ONNX Runtime memory cleanup
         // This releases the memory pool accumulated during video streaming
         // Note: This is a workaround for ONNX Runtime memory leak
-        eprintln!("[YOLO] ONNX Runtime memory cleanup completed");
+        tracing::debug!("[YOLO] ONNX Runtime memory cleanup completed");
         
-        eprintln!("[YOLO] Memory cleanup completed");
+        tracing::info!("[YOLO] Memory cleanup completed");
     }
 
     /// Start a new stream
@@ -755,7 +676,7 @@ impl StreamProcessor {
             // Run inference
             
             let detections = match processor.get_detector() {
-                Some(detector) if detector.is_loaded() => {
+                Some(mut detector) if detector.is_loaded() => {
                     tracing::debug!("[Stream {}] Running real inference", stream_id);
                     let raw_detections = detector.detect(
                         &demo_frame,
@@ -866,18 +787,26 @@ pub struct YoloVideoProcessorV2 {
 
 impl YoloVideoProcessorV2 {
     pub fn new() -> Self {
+        neomind_logging::init();
         Self {
             processor: Arc::new(StreamProcessor::new()),
         }
     }
 
+    /// Create the extension with a custom detection backend (used by tests)
+    pub fn with_detector(detector: Box<dyn ObjectDetect + Send>) -> Self {
+        neomind_logging::init();
+        Self {
+            processor: Arc::new(StreamProcessor::with_detector(detector)),
+        }
+    }
+
     /// Recover a session that was lost due to process restart
     ///
     /// This method is called when a frame arrives for a session that doesn't exist.
     /// It attempts to re-create the session with default configuration so that
     /// processing can continue without interruption.
     async fn recover_session(&self, session_id: &str) -> Option<Arc<Mutex<ActiveStream>>> {
-        eprintln!("[YOLO] Attempting to recover session: {}", session_id);
         tracing::warn!(
             session_id = %session_id,
             "Attempting to recover lost session (extension may have restarted)"
@@ -909,7 +838,7 @@ impl YoloVideoProcessorV2 {
         {
             let mut registry = get_registry().lock();
             registry.streams.insert(session_id.to_string(), Arc::new(Mutex::new(stream)));
-            eprintln!("[YOLO] Session {} recovered and registered, total sessions: {}",
+            tracing::debug!("[YOLO] Session {} recovered and registered, total sessions: {}",
                 session_id, registry.streams.len());
         }
 
@@ -1002,6 +931,7 @@ impl Extension for YoloVideoProcessorV2 {
                 max: None,
                 required: false,
             },
+            neomind_health::metric_descriptor(),
         ]
     }
 
@@ -1108,6 +1038,7 @@ impl Extension for YoloVideoProcessorV2 {
                 samples: vec![],
                 parameter_groups: Vec::new(),
             },
+            neomind_health::command(),
         ]
     }
 
@@ -1166,6 +1097,7 @@ impl Extension for YoloVideoProcessorV2 {
                 self.processor.cleanup_memory();
                 Ok(json!({"success": true, "message": "Memory cleanup triggered"}))
             }
+            "health" => Ok(self.processor.health().to_json()),
             "update_stream_config" => {
                 let stream_id = args.get("stream_id")
                     .and_then(|v| v.as_str())
@@ -1206,7 +1138,7 @@ impl Extension for YoloVideoProcessorV2 {
                 }
             }
             "configure" => {
-                // Accept config silently - can be extended for real config handling
+                neomind_logging::apply_config("YOLO", args);
                 Ok(json!({"status": "ok"}))
             }
 
@@ -1264,6 +1196,7 @@ impl Extension for YoloVideoProcessorV2 {
                 timestamp: now,
             });
         }
+        metrics.push(self.processor.health().metric());
 
         Ok(metrics)
     }
@@ -1288,7 +1221,7 @@ impl Extension for YoloVideoProcessorV2 {
     }
 
     async fn init_session(&self, session: &StreamSession) -> Result<()> {
-        eprintln!("[YOLO] init_session called: id={}", session.id);
+        tracing::debug!("[YOLO] init_session called: id={}", session.id);
         let config: StreamConfig = serde_json::from_value(session.config.clone())
             .unwrap_or_default();
 
@@ -1335,7 +1268,7 @@ impl Extension for YoloVideoProcessorV2 {
             if let Some(old_stream) = registry.streams.get(&stream_id) {
                 let mut old = old_stream.lock();
                 if old.running {
-                    eprintln!("[YOLO] Session {} already exists, stopping", stream_id);
+                    tracing::info!("[YOLO] Session {} already exists, stopping", stream_id);
                     old.running = false;
                     // Abort old push task if exists
                     if let Some(task) = old.push_task.take() {
@@ -1354,7 +1287,6 @@ impl Extension for YoloVideoProcessorV2 {
         } else {
             tracing::info!("Local camera session initialized: {}", stream_id);
         }
-        eprintln!("[YOLO] init_session completed OK: id={}", stream_id);
 
         Ok(())
     }
@@ -1425,6 +1357,7 @@ impl Extension for YoloVideoProcessorV2 {
         // Run FFmpeg decode + YOLO inference on a dedicated OS thread
         // (FFmpeg is blocking I/O, must not run inside tokio)
         let task_handle = std::thread::spawn(move || {
+            let _span = neomind_logging::session_span(&sid).entered();
             let mut sequence = 0u64;
             let frame_duration = std::time::Duration::from_millis(1000 / target_fps as u64);
             let mut reconnect_count = 0u32;
@@ -1443,6 +1376,7 @@ impl Extension for YoloVideoProcessorV2 {
                 }
                 Err(e) => {
                     tracing::error!("[Stream {}] FFmpeg failed to connect: {}", sid, e);
+                    processor.activity.error(format!("Failed to connect to {}: {}", source_url, e));
                     let _ = send_push_output(
                         &PushOutputMessage::json(&sid, sequence, serde_json::json!({
                             "type": "error", "message": format!("Failed to connect: {}", e)
@@ -1490,9 +1424,9 @@ impl Extension for YoloVideoProcessorV2 {
 
                         // Run YOLO detection
                         let detections = match processor.get_detector() {
-                            Some(detector) if detector.is_loaded() => {
+                            Some(mut detector) if detector.is_loaded() => {
                                 let dets = detector.detect(&inference_image, confidence, max_obj);
-                                eprintln!("[YOLO-Detect] raw detections: {}", dets.len());
+                                tracing::trace!("[YOLO-Detect] raw detections: {}", dets.len());
                                 if !dets.is_empty() {
                                     let scale_x = orig_width as f32 / 640.0;
                                     let scale_y = orig_height as f32 / 640.0;
@@ -1536,7 +1470,7 @@ impl Extension for YoloVideoProcessorV2 {
                                 match registry.streams.get(&sid).cloned() {
                                     Some(s) => s,
                                     None => {
-                                        eprintln!("[YOLO-Push] Stream {} lost during ROI processing", sid);
+                                        tracing::warn!("[YOLO-Push] Stream {} lost during ROI processing", sid);
                                         break;
                                     }
                                 }
@@ -1646,14 +1580,17 @@ impl Extension for YoloVideoProcessorV2 {
                             }));
 
                         if sequence % 30 == 0 {
-                            eprintln!("[YOLO-Push] frame {} detections={} size={}KB", sequence, detections.len(), output.data.len() / 1024);
+                            tracing::debug!("[YOLO-Push] frame {} detections={} size={}KB", sequence, detections.len(), output.data.len() / 1024);
                         }
 
                         match send_push_output(&output) {
-                            Ok(_) => sequence += 1,
+                            Ok(_) => {
+                                sequence += 1;
+                                processor.activity.success();
+                            }
                             Err(e) => {
-                                eprintln!("[YOLO-Push] send_push_output FAILED: {}", e);
                                 tracing::warn!("[Stream {}] Push output failed: {}", sid, e);
+                                processor.activity.error(format!("Push output failed: {}", e));
                                 break;
                             }
                         }
@@ -1699,6 +1636,7 @@ impl Extension for YoloVideoProcessorV2 {
                     }
                     FrameResult::Error(e) => {
                         tracing::error!("[Stream {}] Frame error: {}", sid, e);
+                        processor.activity.error(format!("Frame error: {}", e));
                         reconnect_count += 1;
                         if reconnect_count > MAX_RECONNECT {
                             tracing::error!("[Stream {}] Max reconnect attempts reached", sid);
@@ -1793,18 +1731,18 @@ impl Extension for YoloVideoProcessorV2 {
             None => {
                 // 🔧 SESSION RECOVERY: Try to recover session if it was lost due to process restart
                 // This can happen when the extension process is restarted mid-stream
-                eprintln!("[YOLO] Session not found: {}, attempting recovery...", session_id);
+                tracing::debug!("[YOLO] Session not found: {}, attempting recovery...", session_id);
 
                 // Try to recover the session by re-initializing with default config
                 let recovered = self.recover_session(session_id).await;
 
                 match recovered {
                     Some(s) => {
-                        eprintln!("[YOLO] Session {} recovered successfully, continuing processing", session_id);
+                        tracing::debug!("[YOLO] Session {} recovered successfully, continuing processing", session_id);
                         s
                     }
                     None => {
-                        eprintln!("[YOLO] Session {} recovery failed, returning error", session_id);
+                        tracing::warn!("[YOLO] Session {} recovery failed, returning error", session_id);
                         return Ok(StreamResult::error(
                             Some(chunk.sequence),
                             StreamError {
@@ -1817,6 +1755,8 @@ impl Extension for YoloVideoProcessorV2 {
                 }
             }
         };
+        // Not held across an await: everything below is synchronous
+        let _span = neomind_logging::session_span(session_id).entered();
 
         // CRITICAL: Frame rate control to prevent memory overflow
         // Drop frames that arrive too quickly (max 10 FPS = 100ms interval)
@@ -1831,7 +1771,7 @@ impl Extension for YoloVideoProcessorV2 {
                     // IMPORTANT: When dropping a frame, return the last valid frame
                     // instead of a skip response. This prevents the frontend from showing
                     // corrupted/blank frames.
-                    eprintln!("[YOLO] Frame {} dropped (too fast: {}ms), total dropped: {}",
+                    tracing::debug!("[YOLO] Frame {} dropped (too fast: {}ms), total dropped: {}",
                         chunk.sequence, elapsed.as_millis(), s.dropped_frames);
 
                     // Return the last cached frame if available
@@ -1871,7 +1811,7 @@ impl Extension for YoloVideoProcessorV2 {
         // ✨ CRITICAL: Validate input data before decoding
         // Empty or too-small buffers can cause decoder panics
         if chunk.data.len() < 100 {
-            eprintln!("[YOLO] Invalid frame data: too small ({} bytes)", chunk.data.len());
+            tracing::warn!("[YOLO] Invalid frame data: too small ({} bytes)", chunk.data.len());
             let error_result = json!({
                 "error": format!("Invalid frame data: too small ({} bytes)", chunk.data.len()),
                 "detections": []
@@ -1886,7 +1826,7 @@ impl Extension for YoloVideoProcessorV2 {
 
         // Check for JPEG header (FF D8)
         if chunk.data.len() < 2 || chunk.data[0] != 0xFF || chunk.data[1] != 0xD8 {
-            eprintln!("[YOLO] Invalid JPEG header: {:02X} {:02X}", 
+            tracing::warn!("[YOLO] Invalid JPEG header: {:02X} {:02X}", 
                 chunk.data.get(0).unwrap_or(&0), 
                 chunk.data.get(1).unwrap_or(&0));
             let error_result = json!({
@@ -1902,15 +1842,16 @@ impl Extension for YoloVideoProcessorV2 {
         }
 
         // Decode JPEG frame
-        eprintln!("[YOLO] Decoding image, data size: {}", chunk.data.len());
+        tracing::trace!("[YOLO] Decoding image, data size: {}", chunk.data.len());
         let img_result = image::load_from_memory(&chunk.data);
         let mut original_image = match img_result {
             Ok(img) => {
-                eprintln!("[YOLO] Decoded image: {}x{}", img.width(), img.height());
+                tracing::trace!("[YOLO] Decoded image: {}x{}", img.width(), img.height());
                 img.to_rgb8()
             }
             Err(e) => {
-                eprintln!("[YOLO] Failed to decode image: {}", e);
+                tracing::warn!("[YOLO] Failed to decode image: {}", e);
+                self.processor.activity.error(format!("Failed to decode image: {}", e));
                 // Return error result
                 let error_result = json!({
                     "error": format!("Failed to decode image: {}", e),
@@ -1943,22 +1884,22 @@ impl Extension for YoloVideoProcessorV2 {
             (s._config.confidence_threshold, s._config.max_objects)
         };
 
-        eprintln!("[YOLO] Running YOLO detection on 640x640, confidence={}, max_objects={}",
+        tracing::trace!("[YOLO] Running YOLO detection on 640x640, confidence={}, max_objects={}",
             confidence_threshold, max_objects);
 
         // Run YOLO detection on resized image
         let detections = {
             match self.processor.get_detector() {
-                Some(detector) => {
+                Some(mut detector) => {
                     if detector.is_loaded() {
-                        eprintln!("[YOLO] Detector loaded: {}, inference size: 640x640",
+                        tracing::trace!("[YOLO] Detector loaded: {}, inference size: 640x640",
                             detector.is_loaded());
 
                         // Run detection on 640x640 image
                         let dets = detector.detect(&inference_image, confidence_threshold, max_objects);
 
                         if !dets.is_empty() {
-                            eprintln!("[YOLO] YOLO detected {} objects", dets.len());
+                            tracing::debug!("[YOLO] YOLO detected {} objects", dets.len());
 
                             // Scale detection coordinates back to original size
                             let scale_x = orig_width as f32 / 640.0;
@@ -1973,36 +1914,36 @@ impl Extension for YoloVideoProcessorV2 {
                             }).collect();
 
                             for (i, det) in scaled_dets.iter().enumerate() {
-                                eprintln!("[YOLO]   Detection {}: {} ({:.2}%) at [{:.1}, {:.1}, {:.1}x{:.1}]",
+                                tracing::trace!("[YOLO]   Detection {}: {} ({:.2}%) at [{:.1}, {:.1}, {:.1}x{:.1}]",
                                     i, det.class_name, det.confidence * 100.0,
                                     det.bbox.x, det.bbox.y, det.bbox.width, det.bbox.height);
                             }
                             detections_to_object_detection(scaled_dets)
                         } else {
                             // Fallback to simulated detections for demo
-                            eprintln!("[YOLO] No YOLO detections, using fallback");
+                            tracing::debug!("[YOLO] No YOLO detections, using fallback");
                             let s = stream.lock();
                             generate_fallback_detections(s.frame_count, max_objects)
                         }
                     } else {
-                        eprintln!("[YOLO] Detector not loaded, using fallback");
+                        tracing::debug!("[YOLO] Detector not loaded, using fallback");
                         let s = stream.lock();
                         generate_fallback_detections(s.frame_count, max_objects)
                     }
                 }
                 None => {
-                    eprintln!("[YOLO] Detector init failed, using fallback");
+                    tracing::warn!("[YOLO] Detector init failed, using fallback");
                     let s = stream.lock();
                     generate_fallback_detections(s.frame_count, max_objects)
                 }
             }
         };
 
-        eprintln!("[YOLO] Total detections: {}", detections.len());
+        tracing::trace!("[YOLO] Total detections: {}", detections.len());
 
         // ✨ OPTIMIZATION: Draw detections directly on original_image (no copy)
         // Rust move semantics transfer ownership without allocation
-        eprintln!("[YOLO] Drawing detections on original {}x{} image", orig_width, orig_height);
+        tracing::trace!("[YOLO] Drawing detections on original {}x{} image", orig_width, orig_height);
         draw_detections(&mut original_image, &detections);
 
         // ROI counting and line crossing detection (camera mode)
@@ -2103,7 +2044,7 @@ impl Extension for YoloVideoProcessorV2 {
             events
         };
 
-        eprintln!("[YOLO] Encoding image to JPEG (quality=75)");
+        tracing::trace!("[YOLO] Encoding image to JPEG (quality=75)");
         // Encode result as JPEG with dynamic quality based on processing time
         // Faster processing = higher quality, slower processing = lower quality
         let jpeg_quality = if start.elapsed().as_millis() < 50 {
@@ -2114,7 +2055,7 @@ impl Extension for YoloVideoProcessorV2 {
             65  // Slow processing, reduce quality for speed
         };
         let output_jpeg = encode_jpeg(&original_image, jpeg_quality);
-        eprintln!("[YOLO] Encoded JPEG size: {} bytes, detections: {}", output_jpeg.len(), detections.len());
+        tracing::debug!("[YOLO] Encoded JPEG size: {} bytes, detections: {}", output_jpeg.len(), detections.len());
 
         // Cache last frame for reuse
         {
@@ -2126,7 +2067,7 @@ impl Extension for YoloVideoProcessorV2 {
         {
             let queue = get_or_create_frame_queue(session_id);
             queue.lock().push(output_jpeg.clone());
-            eprintln!("[YOLO] Frame pushed to MJPEG queue for session: {}", session_id);
+            tracing::trace!("[YOLO] Frame pushed to MJPEG queue for session: {}", session_id);
         }
 
         // Return the processed frame with detections in metadata
@@ -2143,8 +2084,9 @@ impl Extension for YoloVideoProcessorV2 {
             "capture_events": capture_events,
         }));
 
-        eprintln!("[YOLO] Returning result for sequence {}, data size: {}, detections: {}",
+        tracing::trace!("[YOLO] Returning result for sequence {}, data size: {}, detections: {}",
             chunk.sequence, result.data.len(), detections.len());
+        self.processor.activity.success();
         Ok(result)
     }
 
@@ -2153,14 +2095,14 @@ impl Extension for YoloVideoProcessorV2 {
         &self,
         session_id: &str,
     ) -> Result<SessionStats> {
-        eprintln!("[YOLO] close_session called for session: {}", session_id);
+        tracing::debug!("[YOLO] close_session called for session: {}", session_id);
         
         let session_id_owned = session_id.to_string();
         
         // ✨ FIX: Do NOT remove detector when closing session
         // The detector should remain loaded for the lifetime of the extension
         // Taking it out (as before) caused all subsequent inferences to fail
-        eprintln!("[YOLO] Detector remains loaded (will be cleaned up on extension unload)");
+        tracing::debug!("[YOLO] Detector remains loaded (will be cleaned up on extension unload)");
         // Note: DO NOT call detector.lock().take() - this removes the detector permanently!
 
         // Stop push if running
@@ -2174,7 +2116,7 @@ impl Extension for YoloVideoProcessorV2 {
             let mut registry = get_registry().lock();
             if let Some(stream) = registry.streams.remove(&session_id_owned) {
                 let s = stream.lock();
-                eprintln!("[YOLO] Session removed from registry, processed {} frames", s.frame_count);
+                tracing::info!("[YOLO] Session removed from registry, processed {} frames", s.frame_count);
                 SessionStats {
                     input_chunks: s.frame_count,
                     output_chunks: s.frame_count,
@@ -2184,15 +2126,20 @@ impl Extension for YoloVideoProcessorV2 {
                     last_activity: chrono::Utc::now().timestamp_millis(),
                 }
             } else {
-                eprintln!("[YOLO] Session not found in registry when closing");
+                tracing::warn!("[YOLO] Session not found in registry when closing");
                 SessionStats::default()
             }
         };
 
         tracing::info!("YOLO session closed: {}", session_id);
-        eprintln!("[YOLO] Session closed: {}", session_id);
         Ok(stats)
     }
+
+    async fn configure(&mut self, config: &serde_json::Value) -> Result<()> {
+        neomind_logging::apply_config("YOLO", config);
+        Ok(())
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -2793,17 +2740,40 @@ mod tests {
     fn test_extension_metrics() {
         let ext = YoloVideoProcessorV2::new();
         let metrics = ext.metrics();
-        assert_eq!(metrics.len(), 5);
+        assert_eq!(metrics.len(), 6);
     }
 
     #[test]
     fn test_extension_commands() {
         let ext = YoloVideoProcessorV2::new();
         let commands = ext.commands();
-        assert_eq!(commands.len(), 4);
+        assert_eq!(commands.len(), 6);
         assert_eq!(commands[0].name, "start_stream");
     }
 
+    #[test]
+    fn test_draw_detections_golden() {
+        let mut img = image::RgbImage::from_fn(320, 240, |x, y| image::Rgb([(x * 255 / 320) as u8, 90, (y * 255 / 240) as u8]));
+        let detection = |id: u32, label: &str, class_id: u32, (x, y, width, height): (f32, f32, f32, f32)| ObjectDetection {
+            id,
+            label: label.to_string(),
+            confidence: 0.5 + id as f32 / 10.0,
+            bbox: BoundingBox { x, y, width, height },
+            class_id,
+        };
+        let detections = [
+            detection(1, "person", 0, (40.0, 60.0, 80.0, 150.0)),
+            // At the top edge: the label moves inside the box
+            detection(2, "car", 2, (150.0, 0.0, 120.0, 70.0)),
+            // Partly off-canvas: box and label are clipped
+            detection(3, "dog", 16, (270.0, 180.0, 90.0, 90.0)),
+            // Below the minimum size: not drawn
+            detection(4, "bird", 14, (200.0, 120.0, 3.0, 3.0)),
+        ];
+        draw_detections(&mut img, &detections);
+        neomind_golden::assert_golden!("video_detections", img);
+    }
+
     #[test]
     fn test_stream_config_default() {
         let config = StreamConfig::default();
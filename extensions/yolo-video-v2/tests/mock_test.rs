@@ -0,0 +1,188 @@
+//! Scripted-backend tests for yolo-video-v2.
+//!
+//! These tests inject a scripted detector so they do NOT require ONNX Runtime
+//! or model files. They drive camera sessions through `init_session` and
+//! `process_session_chunk`, covering detections, ROI counting and capture rules.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use neomind_extension_sdk::{ClientInfo, DataChunk, Extension, StreamSession};
+use serde_json::json;
+
+use neomind_extension_yolo_video_v2::detector::{Detection, ObjectDetect};
+use neomind_extension_yolo_video_v2::{BoundingBox, YoloVideoProcessorV2};
+
+// ============================================================================
+// Scripted Backend
+// ============================================================================
+
+/// Detector that replays scripted frames, then detects nothing.
+struct ScriptedDetector {
+    /// Detections returned by successive detect() calls, in 640x640 space.
+    frames: VecDeque<Vec<Detection>>,
+}
+
+impl ScriptedDetector {
+    fn new(frames: Vec<Vec<Detection>>) -> Self {
+        Self { frames: frames.into() }
+    }
+}
+
+impl ObjectDetect for ScriptedDetector {
+    fn detect(&mut self, _image: &image::RgbImage, _confidence_threshold: f32, _max_detections: u32) -> Vec<Detection> {
+        self.frames.pop_front().unwrap_or_default()
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// A 40x40 detection centered on (`cx`, `cy`).
+fn detection(class_id: u32, class_name: &str, (cx, cy): (f32, f32)) -> Detection {
+    Detection {
+        class_id,
+        class_name: class_name.to_string(),
+        confidence: 0.9,
+        bbox: BoundingBox { x: cx - 20.0, y: cy - 20.0, width: 40.0, height: 40.0 },
+    }
+}
+
+fn person(center: (f32, f32)) -> Detection {
+    detection(0, "person", center)
+}
+
+fn car(center: (f32, f32)) -> Detection {
+    detection(2, "car", center)
+}
+
+/// A 640x640 JPEG frame, so detections need no rescaling.
+fn frame() -> Vec<u8> {
+    let img = image::RgbImage::from_pixel(640, 640, image::Rgb([90, 120, 150]));
+    let mut jpeg = Vec::new();
+    img.write_to(&mut std::io::Cursor::new(&mut jpeg), image::ImageFormat::Jpeg)
+        .expect("Failed to encode test frame");
+    jpeg
+}
+
+/// The left half of the frame, counting people only.
+fn left_roi() -> serde_json::Value {
+    json!({
+        "id": "left",
+        "name": "Left",
+        "points": [[0.0, 0.0], [0.5, 0.0], [0.5, 1.0], [0.0, 1.0]],
+        "class_filter": ["person"],
+    })
+}
+
+async fn start_session(ext: &YoloVideoProcessorV2, session_id: &str, config: serde_json::Value) {
+    let session = StreamSession::new(
+        session_id.to_string(),
+        "yolo-video-v2".to_string(),
+        config,
+        ClientInfo {
+            client_id: "test-client".to_string(),
+            ip_addr: None,
+            user_agent: None,
+        },
+    );
+    ext.init_session(&session).await.expect("init_session failed");
+}
+
+/// Process one frame and return the result metadata.
+///
+/// Waits out the 100ms frame rate limit first so no frame is dropped.
+async fn process(ext: &YoloVideoProcessorV2, session_id: &str, sequence: u64) -> serde_json::Value {
+    tokio::time::sleep(Duration::from_millis(110)).await;
+    let result = ext
+        .process_session_chunk(session_id, DataChunk::binary(sequence, frame()))
+        .await
+        .expect("process_session_chunk failed");
+    assert!(result.error.is_none());
+    result.metadata.expect("result has no metadata")
+}
+
+fn labels(metadata: &serde_json::Value) -> Vec<&str> {
+    metadata["detections"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| d["label"].as_str().unwrap())
+        .collect()
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_scripted_detections_in_metadata() {
+    let ext = YoloVideoProcessorV2::with_detector(Box::new(ScriptedDetector::new(vec![
+        vec![person((100.0, 200.0)), car((500.0, 400.0))],
+    ])));
+    start_session(&ext, "mock-detections", json!({})).await;
+
+    let metadata = process(&ext, "mock-detections", 1).await;
+    assert_eq!(labels(&metadata), ["person", "car"]);
+    assert_eq!(metadata["detections"][1]["class_id"], 2);
+    assert_eq!(metadata["detections"][0]["bbox"]["x"], 80.0);
+    assert_eq!(metadata["capture_events"], json!([]));
+
+    let health = ext.execute_command("health", &json!({})).await.unwrap();
+    assert_eq!(health["state"], "healthy");
+    assert_eq!(health["model_loaded"], true);
+
+    ext.close_session("mock-detections").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_roi_counts_filtered_classes() {
+    let ext = YoloVideoProcessorV2::with_detector(Box::new(ScriptedDetector::new(vec![
+        vec![person((100.0, 100.0)), person((200.0, 500.0)), car((150.0, 300.0)), person((600.0, 300.0))],
+    ])));
+    start_session(&ext, "mock-roi", json!({ "rois": [left_roi()] })).await;
+
+    let metadata = process(&ext, "mock-roi", 1).await;
+    assert_eq!(metadata["roi_stats"], json!([{ "id": "left", "name": "Left", "count": 2 }]));
+
+    ext.close_session("mock-roi").await.unwrap();
+}
+
+#[tokio::test]
+async fn test_presence_rule_fires_on_rising_edge() {
+    let ext = YoloVideoProcessorV2::with_detector(Box::new(ScriptedDetector::new(vec![
+        vec![person((100.0, 300.0))],
+        vec![person((120.0, 300.0))],
+        vec![car((100.0, 300.0))],
+        vec![person((100.0, 300.0))],
+    ])));
+    let config = json!({
+        "rois": [left_roi()],
+        "capture_rules": [{
+            "id": "arrival",
+            "name": "Person arrives",
+            "roi_id": "left",
+            "condition": { "type": "presence", "class_name": "person" },
+            "cooldown_seconds": 0.0,
+        }],
+    });
+    start_session(&ext, "mock-capture", config).await;
+
+    let events = process(&ext, "mock-capture", 1).await["capture_events"].clone();
+    assert_eq!(events.as_array().unwrap().len(), 1);
+    assert_eq!(events[0]["rule_id"], "arrival");
+    assert_eq!(events[0]["condition"], "presence:person");
+    assert_eq!(events[0]["roi_counts"]["person"], 1);
+    assert!(!events[0]["image_base64"].as_str().unwrap().is_empty());
+
+    // Still present, then gone: no new events
+    assert_eq!(process(&ext, "mock-capture", 2).await["capture_events"], json!([]));
+    assert_eq!(process(&ext, "mock-capture", 3).await["capture_events"], json!([]));
+
+    // Back again: a new rising edge
+    let events = process(&ext, "mock-capture", 4).await["capture_events"].clone();
+    assert_eq!(events.as_array().unwrap().len(), 1);
+
+    ext.close_session("mock-capture").await.unwrap();
+}
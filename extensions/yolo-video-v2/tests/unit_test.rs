@@ -38,10 +38,8 @@ mod tests {
         let meta = ext.metadata();
 
         assert_eq!(meta.id, "yolo-video-v2");
-        assert_eq!(meta.name, "YOLO Video Processor V2");
-        assert_eq!(meta.version.major, 2);
-        assert_eq!(meta.version.minor, 0);
-        assert_eq!(meta.version.patch, 0);
+        assert_eq!(meta.name, "YOLO Video V2");
+        assert_eq!(meta.version, "2.0.0");
     }
 
     #[test]
@@ -0,0 +1,343 @@
+//! `neomind-ext check-abi` - refuse binaries the extension-runner cannot load safely.
+//!
+//! The runner and the extension exchange `serde_json::Value`s across the FFI
+//! boundary and catch extension panics, so both sides must agree on:
+//! - the neomind-extension-sdk version (and the `SDK_ABI_VERSION` it exports)
+//! - serde_json's `preserve_order` feature, which changes `Value`'s layout
+//! - `panic = "unwind"`; with `abort` a panicking extension takes the runner down
+//!
+//! Dependency versions and features come from `cargo metadata`, the panic
+//! strategy from the workspace profile, exports from the built library itself.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use semver::{Version, VersionReq};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use crate::commands::package;
+use crate::utils::cargo;
+use crate::utils::exit::{fail, FailureClass};
+use crate::utils::ffi::{self, ExtensionLibrary};
+use crate::utils::platform::Platform;
+use crate::utils::report::Checks;
+
+/// SDK releases the current extension-runner is known to load.
+pub const SUPPORTED_SDK: &str = ">=0.6.3, <0.7.0";
+/// `SDK_ABI_VERSION` of the supported SDK releases.
+pub const SDK_ABI_VERSION: u32 = 3;
+const SDK_CRATE: &str = "neomind-extension-sdk";
+
+/// Exports the runner resolves when loading a native extension.
+const REQUIRED_EXPORTS: [&str; 7] = [
+    "neomind_extension_abi_version",
+    "neomind_extension_descriptor_json",
+    "neomind_extension_free_string",
+    "neomind_extension_execute_command_json",
+    "neomind_extension_produce_metrics_json",
+    "neomind_extension_configure_json",
+    "neomind_extension_handle_event_json",
+];
+
+pub struct CheckAbiOptions {
+    pub path: Option<String>,
+    pub lib: Option<String>,
+    /// Override of [`SUPPORTED_SDK`]
+    pub sdk_range: Option<String>,
+    pub json: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AbiReport {
+    pub extension: String,
+    pub library: String,
+    pub checks: Checks,
+}
+
+/// A package in the resolved dependency graph.
+#[derive(Debug, Clone)]
+pub struct ResolvedPackage {
+    pub name: String,
+    pub version: String,
+    pub features: BTreeSet<String>,
+}
+
+pub fn cmd_check_abi(opts: CheckAbiOptions) -> Result<()> {
+    let ext_dir = crate::get_extension_path(opts.path)?;
+    let range_text = opts.sdk_range.as_deref().unwrap_or(SUPPORTED_SDK);
+    let range = VersionReq::parse(range_text).with_context(|| format!("Invalid SDK range '{}'", range_text))?;
+    let lib_path = ffi::resolve_library(&ext_dir, opts.lib.as_deref())?;
+    let host = Platform::host().ok_or_else(|| anyhow::anyhow!("Unsupported host platform"))?;
+
+    let mut checks = Checks::default();
+    match cargo::metadata(&ext_dir, host.triple).and_then(|m| runtime_packages(&m)) {
+        Ok(packages) => check_dependencies(&mut checks, &packages, &range),
+        Err(e) => checks.fail("cargo-metadata", format!("{:#}", e)),
+    }
+    check_panic_strategy(&mut checks, &ext_dir, profile_of(&lib_path))?;
+    check_exports(&mut checks, &lib_path);
+
+    let report = AbiReport {
+        extension: package::dir_name(&ext_dir)?,
+        library: lib_path.display().to_string(),
+        checks,
+    };
+    if opts.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("{}: {}", "Checking ABI".cyan(), report.library.bold());
+        report.checks.print();
+        println!();
+    }
+
+    if report.checks.has_failures() {
+        return Err(fail(
+            FailureClass::ValidationFailure,
+            format!(
+                "{} is not ABI-compatible with the extension-runner: {}",
+                report.extension,
+                report.checks.failed_names().join(", ")
+            ),
+        ));
+    }
+    if !opts.json {
+        println!("{}", "✅ Binary is ABI-compatible".green().bold());
+    }
+    Ok(())
+}
+
+/// SDK version range and serde_json features of the packages linked into the extension.
+pub fn check_dependencies(checks: &mut Checks, packages: &[ResolvedPackage], range: &VersionReq) {
+    let sdks: Vec<&ResolvedPackage> = packages.iter().filter(|p| p.name == SDK_CRATE).collect();
+    match sdks.as_slice() {
+        [] => checks.fail("sdk", format!("does not depend on {}", SDK_CRATE)),
+        [sdk] => match Version::parse(&sdk.version) {
+            Ok(version) => checks.expect(
+                "sdk",
+                range.matches(&version),
+                format!("{} {} (supported: {})", SDK_CRATE, version, range),
+                format!("{} {} is outside the supported range {}", SDK_CRATE, version, range),
+            ),
+            Err(e) => checks.fail("sdk", format!("invalid version '{}': {}", sdk.version, e)),
+        },
+        _ => {
+            let versions: Vec<&str> = sdks.iter().map(|p| p.version.as_str()).collect();
+            checks.fail("sdk", format!("links several {} versions: {}", SDK_CRATE, versions.join(", ")));
+        }
+    }
+
+    let serde_json: Vec<&ResolvedPackage> = packages.iter().filter(|p| p.name == "serde_json").collect();
+    if serde_json.is_empty() {
+        checks.warn("serde_json", "not linked");
+    }
+    for package in serde_json {
+        checks.expect(
+            "serde_json",
+            package.features.contains("preserve_order"),
+            format!("{} built with preserve_order", package.version),
+            format!(
+                "{} built without preserve_order; enable it on the serde_json dependency",
+                package.version
+            ),
+        );
+    }
+}
+
+/// Packages reachable from the metadata's root through normal (non-dev, non-build) dependencies.
+pub fn runtime_packages(metadata: &Value) -> Result<Vec<ResolvedPackage>> {
+    let resolve = &metadata["resolve"];
+    let root = resolve["root"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("cargo metadata has no root package"))?;
+    let packages: BTreeMap<&str, &Value> = metadata["packages"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|p| Some((p["id"].as_str()?, p)))
+        .collect();
+    let nodes: BTreeMap<&str, &Value> = resolve["nodes"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|n| Some((n["id"].as_str()?, n)))
+        .collect();
+
+    let mut seen = BTreeSet::new();
+    let mut pending = vec![root];
+    let mut resolved = Vec::new();
+    while let Some(id) = pending.pop() {
+        if !seen.insert(id) {
+            continue;
+        }
+        let node = nodes
+            .get(id)
+            .ok_or_else(|| anyhow::anyhow!("cargo metadata has no node for {}", id))?;
+        if id != root {
+            let package = packages
+                .get(id)
+                .ok_or_else(|| anyhow::anyhow!("cargo metadata has no package for {}", id))?;
+            resolved.push(ResolvedPackage {
+                name: package["name"].as_str().unwrap_or_default().to_string(),
+                version: package["version"].as_str().unwrap_or_default().to_string(),
+                features: str_set(&node["features"]),
+            });
+        }
+        for dep in node["deps"].as_array().into_iter().flatten() {
+            let is_normal = dep["dep_kinds"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .any(|k| k["kind"].is_null());
+            if let (true, Some(pkg)) = (is_normal, dep["pkg"].as_str()) {
+                pending.push(pkg);
+            }
+        }
+    }
+    resolved.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(resolved)
+}
+
+fn str_set(value: &Value) -> BTreeSet<String> {
+    value
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|v| v.as_str())
+        .map(String::from)
+        .collect()
+}
+
+/// Cargo profile that produced a library in `target/[<triple>/]<dir>/`.
+fn profile_of(lib_path: &Path) -> &'static str {
+    let dir = lib_path.parent().and_then(|p| p.file_name()).and_then(|n| n.to_str());
+    if dir == Some("debug") {
+        "dev"
+    } else {
+        "release"
+    }
+}
+
+fn check_panic_strategy(checks: &mut Checks, ext_dir: &Path, profile: &str) -> Result<()> {
+    let strategy = cargo::profile_setting(ext_dir, profile, "panic")?;
+    let strategy = strategy.as_deref().unwrap_or("unwind");
+    checks.expect(
+        "panic",
+        strategy == "unwind",
+        format!("[profile.{}] panic = \"unwind\"", profile),
+        format!(
+            "[profile.{}] panic = \"{}\"; the runner needs \"unwind\" to survive extension panics",
+            profile, strategy
+        ),
+    );
+    Ok(())
+}
+
+fn check_exports(checks: &mut Checks, lib_path: &Path) {
+    let library = match ExtensionLibrary::load(lib_path) {
+        Ok(library) => library,
+        Err(e) => {
+            checks.fail("exports", format!("{:#}", e));
+            return;
+        }
+    };
+    let missing: Vec<&str> = REQUIRED_EXPORTS
+        .iter()
+        .filter(|name| !library.exports(format!("{}\0", name).as_bytes()))
+        .copied()
+        .collect();
+    checks.expect(
+        "exports",
+        missing.is_empty(),
+        format!("{} SDK exports present", REQUIRED_EXPORTS.len()),
+        format!("missing {} (not built with neomind_export!?)", missing.join(", ")),
+    );
+    if missing.contains(&"neomind_extension_abi_version") {
+        return;
+    }
+    match library.abi_version() {
+        Ok(version) => checks.expect(
+            "abi-version",
+            version == SDK_ABI_VERSION,
+            format!("SDK_ABI_VERSION {}", version),
+            format!("SDK_ABI_VERSION {} (runner expects {})", version, SDK_ABI_VERSION),
+        ),
+        Err(e) => checks.fail("abi-version", format!("{:#}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::report::CheckStatus;
+    use serde_json::json;
+
+    fn metadata(serde_json_features: &[&str], sdk_version: &str) -> Value {
+        let sdk = format!("registry#neomind-extension-sdk@{}", sdk_version);
+        json!({
+            "packages": [
+                {"id": "path#my-ext@1.0.0", "name": "my-ext", "version": "1.0.0"},
+                {"id": sdk, "name": "neomind-extension-sdk", "version": sdk_version},
+                {"id": "registry#serde_json@1.0.140", "name": "serde_json", "version": "1.0.140"},
+                {"id": "registry#tempfile@3.8.0", "name": "tempfile", "version": "3.8.0"},
+            ],
+            "resolve": {
+                "root": "path#my-ext@1.0.0",
+                "nodes": [
+                    {"id": "path#my-ext@1.0.0", "features": [], "deps": [
+                        {"pkg": sdk, "dep_kinds": [{"kind": null}]},
+                        {"pkg": "registry#tempfile@3.8.0", "dep_kinds": [{"kind": "dev"}]},
+                    ]},
+                    {"id": sdk, "features": ["default"], "deps": [
+                        {"pkg": "registry#serde_json@1.0.140", "dep_kinds": [{"kind": null}]},
+                    ]},
+                    {"id": "registry#serde_json@1.0.140", "features": serde_json_features, "deps": []},
+                    {"id": "registry#tempfile@3.8.0", "features": [], "deps": []},
+                ],
+            },
+        })
+    }
+
+    fn statuses(metadata: &Value) -> Vec<(String, CheckStatus)> {
+        let packages = runtime_packages(metadata).unwrap();
+        let mut checks = Checks::default();
+        check_dependencies(&mut checks, &packages, &VersionReq::parse(SUPPORTED_SDK).unwrap());
+        checks.iter().map(|c| (c.name.clone(), c.status)).collect()
+    }
+
+    #[test]
+    fn test_runtime_packages_skip_dev_dependencies() {
+        let packages = runtime_packages(&metadata(&["std"], "0.6.6")).unwrap();
+        let names: Vec<&str> = packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["neomind-extension-sdk", "serde_json"]);
+    }
+
+    #[test]
+    fn test_dependency_checks() {
+        assert_eq!(
+            statuses(&metadata(&["std", "preserve_order"], "0.6.6")),
+            [("sdk".to_string(), CheckStatus::Pass), ("serde_json".to_string(), CheckStatus::Pass)]
+        );
+        assert_eq!(
+            statuses(&metadata(&["std"], "0.7.0")),
+            [("sdk".to_string(), CheckStatus::Fail), ("serde_json".to_string(), CheckStatus::Fail)]
+        );
+    }
+
+    #[test]
+    fn test_panic_abort_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"my-ext\"\n\n[profile.release]\npanic = \"abort\"\n",
+        )
+        .unwrap();
+        let mut checks = Checks::default();
+        check_panic_strategy(&mut checks, dir.path(), "release").unwrap();
+        check_panic_strategy(&mut checks, dir.path(), "dev").unwrap();
+        let statuses: Vec<CheckStatus> = checks.iter().map(|c| c.status).collect();
+        assert_eq!(statuses, [CheckStatus::Fail, CheckStatus::Pass]);
+        assert_eq!(profile_of(Path::new("target/x86_64-unknown-linux-gnu/debug/libx.so")), "dev");
+    }
+}
@@ -0,0 +1,614 @@
+//! `neomind-ext add command|metric` - insert a command or metric into an
+//! existing extension's src/lib.rs.
+//!
+//! `add command` appends an `ExtensionCommand` descriptor to the `vec![...]`
+//! returned by `commands()` and a stub arm before the `_ =>` arm of
+//! `execute_command` and, where present, the wasm32 `execute_command_sync`. `add metric` appends a
+//! `MetricDescriptor` to `metrics()` and a placeholder value to
+//! `produce_metrics()`. Commands and metrics are not listed in metadata.json
+//! or frontend.json, so those are left alone; when the extension has a
+//! frontend the matching `executeCommand` call is printed instead.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::ops::Range;
+
+use crate::commands::new::to_display_name;
+use crate::utils::rust_source::{
+    append_to_list, fn_bodies, indent_at, macro_brackets, match_block, wildcard_arm,
+};
+
+pub struct AddCommandOptions {
+    pub path: Option<String>,
+    pub name: String,
+    /// `name:type`, with a trailing `?` for optional parameters
+    pub params: Vec<String>,
+    pub description: Option<String>,
+}
+
+pub struct AddMetricOptions {
+    pub path: Option<String>,
+    pub name: String,
+    pub data_type: String,
+    pub unit: String,
+}
+
+/// Value types supported by the generators, named like `MetricDataType` variants.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ValueType {
+    String,
+    Integer,
+    Float,
+    Boolean,
+}
+
+impl ValueType {
+    fn parse(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "string" | "str" => Ok(Self::String),
+            "integer" | "int" => Ok(Self::Integer),
+            "float" | "number" => Ok(Self::Float),
+            "boolean" | "bool" => Ok(Self::Boolean),
+            _ => anyhow::bail!("Unknown type '{}' (expected string, integer, float or boolean)", s),
+        }
+    }
+
+    fn variant(self) -> &'static str {
+        match self {
+            Self::String => "String",
+            Self::Integer => "Integer",
+            Self::Float => "Float",
+            Self::Boolean => "Boolean",
+        }
+    }
+
+    /// `serde_json::Value` accessor used to read an argument of this type
+    fn accessor(self) -> &'static str {
+        match self {
+            Self::String => "as_str",
+            Self::Integer => "as_i64",
+            Self::Float => "as_f64",
+            Self::Boolean => "as_bool",
+        }
+    }
+
+    fn sample(self) -> &'static str {
+        match self {
+            Self::String => "\"\"",
+            Self::Integer => "0",
+            Self::Float => "0.0",
+            Self::Boolean => "false",
+        }
+    }
+
+    /// Placeholder `ParamMetricValue` reported until the metric is wired up
+    fn placeholder(self) -> &'static str {
+        match self {
+            Self::String => "String(String::new())",
+            Self::Integer => "Integer(0)",
+            Self::Float => "Float(0.0)",
+            Self::Boolean => "Boolean(false)",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Param {
+    name: String,
+    value_type: ValueType,
+    required: bool,
+}
+
+impl Param {
+    /// Parse `name:type` or `name:type?`
+    fn parse(spec: &str) -> Result<Self> {
+        let (name, ty) = spec
+            .split_once(':')
+            .with_context(|| format!("Invalid parameter '{}' (expected name:type)", spec))?;
+        let (ty, required) = match ty.strip_suffix('?') {
+            Some(ty) => (ty, false),
+            None => (ty, true),
+        };
+        check_identifier(name)?;
+        Ok(Self {
+            name: name.to_string(),
+            value_type: ValueType::parse(ty)?,
+            required,
+        })
+    }
+}
+
+/// Command and metric names become string keys, match arms and Rust bindings.
+fn check_identifier(name: &str) -> Result<()> {
+    let valid = name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if !valid {
+        anyhow::bail!("Invalid name '{}' (use snake_case, e.g. set_threshold)", name);
+    }
+    Ok(())
+}
+
+pub fn cmd_add_command(opts: AddCommandOptions) -> Result<()> {
+    check_identifier(&opts.name)?;
+    let params = opts.params.iter().map(|p| Param::parse(p)).collect::<Result<Vec<_>>>()?;
+    let ext_path = crate::get_extension_path(opts.path)?;
+    let lib_rs = ext_path.join("src").join("lib.rs");
+    let mut src = fs::read_to_string(&lib_rs).with_context(|| format!("Failed to read {}", lib_rs.display()))?;
+
+    println!("{}", format!("➕ Adding command '{}'", opts.name).green().bold());
+    let description = opts.description.unwrap_or_else(|| format!("TODO: describe {}", opts.name));
+    let skipped = add_command(&mut src, &opts.name, &description, &params)?;
+    fs::write(&lib_rs, &src).with_context(|| format!("Failed to write {}", lib_rs.display()))?;
+
+    println!("  {} commands(): ExtensionCommand descriptor", "✓".green());
+    if skipped.is_empty() {
+        println!("  {} execute_command(): stub arm", "✓".green());
+    }
+    for note in &skipped {
+        println!("  {} {}", "⚠".yellow(), note);
+    }
+    if ext_path.join("frontend").exists() {
+        println!("\n{}", "Call it from the frontend with:".cyan());
+        println!("  {}", frontend_call(&opts.name, &params));
+    }
+    println!("\n{} Updated {}", "✅".green(), lib_rs.display());
+    Ok(())
+}
+
+pub fn cmd_add_metric(opts: AddMetricOptions) -> Result<()> {
+    check_identifier(&opts.name)?;
+    let value_type = ValueType::parse(&opts.data_type)?;
+    let ext_path = crate::get_extension_path(opts.path)?;
+    let lib_rs = ext_path.join("src").join("lib.rs");
+    let mut src = fs::read_to_string(&lib_rs).with_context(|| format!("Failed to read {}", lib_rs.display()))?;
+
+    println!("{}", format!("➕ Adding metric '{}'", opts.name).green().bold());
+    let skipped = add_metric(&mut src, &opts.name, value_type, &opts.unit)?;
+    fs::write(&lib_rs, &src).with_context(|| format!("Failed to write {}", lib_rs.display()))?;
+
+    println!("  {} metrics(): MetricDescriptor", "✓".green());
+    match skipped {
+        Some(note) => println!("  {} {}", "⚠".yellow(), note),
+        None => println!("  {} produce_metrics(): placeholder value", "✓".green()),
+    }
+    println!("\n{} Updated {}", "✅".green(), lib_rs.display());
+    Ok(())
+}
+
+/// Insert the descriptor and `execute_command` arms; returns notes for
+/// `execute_command` bodies that could not be edited.
+fn add_command(src: &mut String, name: &str, description: &str, params: &[Param]) -> Result<Vec<String>> {
+    let list = returned_vec(src, "commands")?;
+    if src[list.clone()].contains(&format!("name: \"{}\"", name)) {
+        anyhow::bail!("Command '{}' already exists in commands()", name);
+    }
+    let bodies = dispatch_bodies(src);
+    if bodies.is_empty() {
+        anyhow::bail!("No execute_command() found in src/lib.rs");
+    }
+    if bodies.iter().any(|b| src[b.clone()].contains(&format!("\"{}\" =>", name))) {
+        anyhow::bail!("Command '{}' already has an arm in execute_command()", name);
+    }
+
+    let indent = format!("{}    ", indent_at(src, list.start));
+    let descriptor = command_descriptor(src, name, description, params, &indent);
+    append_to_list(src, list, &descriptor);
+
+    // Later bodies first so earlier offsets stay valid
+    let mut skipped = Vec::new();
+    for (i, body) in dispatch_bodies(src).into_iter().enumerate().rev() {
+        let Some(arm_at) = match_block(src, body.clone(), "command").and_then(|b| wildcard_arm(src, b)) else {
+            skipped.push(i);
+            continue;
+        };
+        let arm = command_arm(src, name, params, indent_at(src, arm_at));
+        src.insert_str(arm_at, &arm);
+        if !params.is_empty() {
+            use_args_parameter(src, body.start);
+        }
+    }
+
+    // Line numbers in the edited file
+    let bodies = dispatch_bodies(src);
+    Ok(skipped
+        .into_iter()
+        .rev()
+        .map(|i| {
+            format!(
+                "Dispatch function at line {} has no `match command {{ .. _ => .. }}`; add the \"{}\" arm by hand",
+                line_number(src, bodies[i].start),
+                name
+            )
+        })
+        .collect())
+}
+
+/// Insert the descriptor and a `produce_metrics` value; returns a note when
+/// `produce_metrics` does not build its values with `vec![...]`.
+fn add_metric(src: &mut String, name: &str, value_type: ValueType, unit: &str) -> Result<Option<String>> {
+    let list = returned_vec(src, "metrics")?;
+    if src[list.clone()].contains(&format!("\"{}\"", name)) {
+        anyhow::bail!("Metric '{}' already exists in metrics()", name);
+    }
+
+    let descriptor = metric_descriptor(src, name, value_type, unit, &format!("{}    ", indent_at(src, list.start)));
+    let produce = fn_bodies(src, "produce_metrics")
+        .into_iter()
+        .next()
+        .and_then(|body| macro_brackets(src, body.clone(), "vec").map(|values| (body, values)));
+    let Some((body, values)) = produce else {
+        append_to_list(src, list, &descriptor);
+        return Ok(Some(format!(
+            "produce_metrics() does not return a vec![...]; report '{}' there by hand",
+            name
+        )));
+    };
+
+    let now = if src[body].contains("let now") { "now" } else { "chrono::Utc::now().timestamp_millis()" };
+    let value = metric_value(src, name, value_type, now, &format!("{}    ", indent_at(src, values.start)));
+    // Edit the later list first so the earlier range stays valid
+    if values.start > list.start {
+        append_to_list(src, values, &value);
+        append_to_list(src, list, &descriptor);
+    } else {
+        append_to_list(src, list, &descriptor);
+        append_to_list(src, values, &value);
+    }
+    Ok(None)
+}
+
+/// Bodies of `execute_command` and `execute_command_sync`, in file order.
+fn dispatch_bodies(src: &str) -> Vec<Range<usize>> {
+    let mut bodies = fn_bodies(src, "execute_command");
+    bodies.extend(fn_bodies(src, "execute_command_sync"));
+    bodies.sort_by_key(|b| b.start);
+    bodies
+}
+
+/// The `vec![...]` returned by `fn <name>(`.
+fn returned_vec(src: &str, fn_name: &str) -> Result<Range<usize>> {
+    let body = fn_bodies(src, fn_name)
+        .into_iter()
+        .next()
+        .with_context(|| format!("No {}() found in src/lib.rs", fn_name))?;
+    macro_brackets(src, body, "vec").with_context(|| format!("{}() does not return a vec![...]", fn_name))
+}
+
+fn line_number(src: &str, pos: usize) -> usize {
+    src[..pos].matches('\n').count() + 1
+}
+
+/// Whether `ident` is brought into scope by one of the file's `use` items.
+fn imported(src: &str, ident: &str) -> bool {
+    let mut rest = src;
+    while let Some(start) = rest.find("use ") {
+        let item = &rest[start..];
+        let end = item.find(';').unwrap_or(item.len());
+        let is_item_start = start == 0 || rest[..start].ends_with(['\n', ' ']);
+        if is_item_start
+            && item[..end]
+                .split(|c: char| !c.is_alphanumeric() && c != '_')
+                .any(|word| word == ident)
+        {
+            return true;
+        }
+        rest = &item[end..];
+    }
+    false
+}
+
+/// `ident` if imported, otherwise its full path.
+fn path_to<'a>(src: &str, ident: &'a str, full_path: &'a str) -> &'a str {
+    if imported(src, ident) {
+        ident
+    } else {
+        full_path
+    }
+}
+
+fn command_descriptor(src: &str, name: &str, description: &str, params: &[Param], indent: &str) -> String {
+    let data_type = path_to(src, "MetricDataType", "neomind_extension_sdk::MetricDataType");
+    let param_def = path_to(src, "ParameterDefinition", "neomind_extension_sdk::ParameterDefinition");
+    let hash_map = path_to(src, "HashMap", "std::collections::HashMap");
+    let json = path_to(src, "json", "serde_json::json");
+
+    let parameters = if params.is_empty() {
+        "vec![]".to_string()
+    } else {
+        let mut out = String::from("vec![\n");
+        for p in params {
+            out.push_str(&format!(
+                "{i}        {param_def} {{\n\
+                 {i}            name: \"{name}\".to_string(),\n\
+                 {i}            display_name: \"{display}\".to_string(),\n\
+                 {i}            description: String::new(),\n\
+                 {i}            param_type: {data_type}::{variant},\n\
+                 {i}            required: {required},\n\
+                 {i}            default_value: None,\n\
+                 {i}            min: None,\n\
+                 {i}            max: None,\n\
+                 {i}            options: Vec::new(),\n\
+                 {i}        }},\n",
+                i = indent,
+                name = p.name,
+                display = to_display_name(&p.name),
+                variant = p.value_type.variant(),
+                required = p.required,
+            ));
+        }
+        out.push_str(&format!("{}    ]", indent));
+        out
+    };
+    let sample = params
+        .iter()
+        .map(|p| format!("\"{}\": {}", p.name, p.value_type.sample()))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!(
+        "{i}ExtensionCommand {{\n\
+         {i}    name: \"{name}\".to_string(),\n\
+         {i}    display_name: \"{display}\".to_string(),\n\
+         {i}    description: \"{description}\".to_string(),\n\
+         {i}    payload_template: String::new(),\n\
+         {i}    parameters: {parameters},\n\
+         {i}    fixed_values: {hash_map}::new(),\n\
+         {i}    samples: vec![{json}!({{{sample}}})],\n\
+         {i}    parameter_groups: Vec::new(),\n\
+         {i}}},\n",
+        i = indent,
+        display = to_display_name(name),
+        description = description.replace('\\', "\\\\").replace('"', "\\\""),
+    )
+}
+
+/// Stub arm reading each parameter and echoing it back.
+fn command_arm(src: &str, name: &str, params: &[Param], indent: &str) -> String {
+    let json = path_to(src, "json", "serde_json::json");
+    let mut arm = format!("\"{}\" => {{\n", name);
+    let mut fields = vec!["\"success\": true".to_string()];
+    for p in params {
+        let read = format!("args.get(\"{}\")\n    .and_then(|v| v.{}())", p.name, p.value_type.accessor());
+        if p.required {
+            arm.push_str(&format!(
+                "    let {} = {}\n        .ok_or_else(|| ExtensionError::InvalidArguments(\"Missing {}\".to_string()))?;\n",
+                p.name,
+                read.replace('\n', "\n    "),
+                p.name
+            ));
+        } else {
+            arm.push_str(&format!("    let {} = {};\n", p.name, read.replace('\n', "\n    ")));
+        }
+        fields.push(format!("\"{0}\": {0}", p.name));
+    }
+    arm.push_str(&format!("    // TODO: implement {}\n", name));
+    arm.push_str(&format!("    Ok({}!({{{}}}))\n}}\n", json, fields.join(", ")));
+
+    arm.lines()
+        .map(|line| format!("{}{}\n", indent, line))
+        .collect()
+}
+
+/// `_args` becomes `args` once an arm reads it.
+fn use_args_parameter(src: &mut String, body_start: usize) {
+    let Some(sig) = src[..body_start].rfind("fn execute_command") else {
+        return;
+    };
+    if let Some(at) = src[sig..body_start].find("_args:") {
+        src.replace_range(sig + at..sig + at + 1, "");
+    }
+}
+
+fn metric_descriptor(src: &str, name: &str, value_type: ValueType, unit: &str, indent: &str) -> String {
+    let data_type = path_to(src, "MetricDataType", "neomind_extension_sdk::MetricDataType");
+    let unit = if unit.is_empty() {
+        "String::new()".to_string()
+    } else {
+        format!("\"{}\".to_string()", unit)
+    };
+    format!(
+        "{i}MetricDescriptor {{\n\
+         {i}    name: \"{name}\".to_string(),\n\
+         {i}    display_name: \"{display}\".to_string(),\n\
+         {i}    data_type: {data_type}::{variant},\n\
+         {i}    unit: {unit},\n\
+         {i}    min: None,\n\
+         {i}    max: None,\n\
+         {i}    required: false,\n\
+         {i}}},\n",
+        i = indent,
+        display = to_display_name(name),
+        variant = value_type.variant(),
+    )
+}
+
+fn metric_value(src: &str, name: &str, value_type: ValueType, now: &str, indent: &str) -> String {
+    let value = path_to(src, "ParamMetricValue", "neomind_extension_sdk::ParamMetricValue");
+    format!(
+        "{i}// TODO: report the real {name} value\n\
+         {i}ExtensionMetricValue {{\n\
+         {i}    name: \"{name}\".to_string(),\n\
+         {i}    value: {value}::{placeholder},\n\
+         {i}    timestamp: {now},\n\
+         {i}}},\n",
+        i = indent,
+        placeholder = value_type.placeholder(),
+    )
+}
+
+fn frontend_call(name: &str, params: &[Param]) -> String {
+    let args = params
+        .iter()
+        .map(|p| format!("{}: {}", p.name, p.value_type.sample().replace('"', "'")))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if args.is_empty() {
+        format!("await executeCommand('{}', {{}})", name)
+    } else {
+        format!("await executeCommand('{}', {{ {} }})", name, args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::new::{copy_dir, TEMPLATES};
+    use crate::utils::rust_source::matching_close;
+    use std::path::Path;
+    use std::process::Command;
+
+    fn template_lib_rs(template: &str) -> String {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("templates")
+            .join(template)
+            .join("src")
+            .join("lib.rs");
+        fs::read_to_string(path).unwrap().replace("{{EXTENSION_STRUCT_NAME}}", "DoorSensor")
+    }
+
+    fn assert_balanced(src: &str) {
+        let wrapped = format!("{{{}}}", src);
+        assert_eq!(matching_close(&wrapped, 0), Some(wrapped.len()), "unbalanced:\n{}", src);
+    }
+
+    #[test]
+    fn test_parse_params() {
+        let param = Param::parse("label:string?").unwrap();
+        assert_eq!(param.value_type, ValueType::String);
+        assert!(!param.required);
+        assert!(Param::parse("threshold:float").unwrap().required);
+        assert!(Param::parse("threshold").is_err());
+        assert!(Param::parse("Threshold:float").is_err());
+        assert!(Param::parse("threshold:decimal").is_err());
+    }
+
+    #[test]
+    fn test_add_command_to_basic_template() {
+        let mut src = template_lib_rs("basic");
+        let params = vec![Param::parse("threshold:float").unwrap(), Param::parse("label:string?").unwrap()];
+        let skipped = add_command(&mut src, "set_threshold", "Change the threshold", &params).unwrap();
+        assert!(skipped.is_empty());
+        assert_balanced(&src);
+
+        // Descriptor after ping, arm before the wildcard, `_args` now used
+        let descriptor = src.find("name: \"set_threshold\".to_string()").unwrap();
+        assert!(descriptor > src.find("name: \"ping\"").unwrap());
+        assert!(src.contains("param_type: MetricDataType::Float,\n                        required: true,"));
+        assert!(src.contains("samples: vec![json!({\"threshold\": 0.0, \"label\": \"\"})],"));
+        let arm = src.find("            \"set_threshold\" => {\n").unwrap();
+        assert!(arm < src.find("_ => Err(ExtensionError::CommandNotFound").unwrap());
+        assert!(src.contains("Missing threshold"));
+        assert!(src.contains("command: &str, args: &serde_json::Value"));
+
+        let err = add_command(&mut src, "set_threshold", "", &[]).unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+    }
+
+    #[test]
+    fn test_add_command_without_params_keeps_unused_args() {
+        let mut src = template_lib_rs("push-stream");
+        add_command(&mut src, "reset", "Reset counters", &[]).unwrap();
+        assert_balanced(&src);
+        assert!(src.contains("_args: &Value"));
+        assert!(src.contains("fixed_values: HashMap::new(),\n                samples: vec![json!({})],"));
+    }
+
+    #[test]
+    fn test_add_metric_uses_existing_timestamp() {
+        let mut src = template_lib_rs("wasm");
+        assert_eq!(add_metric(&mut src, "last_value", ValueType::Float, "°C").unwrap(), None);
+        assert_balanced(&src);
+        assert!(src.contains("unit: \"°C\".to_string(),"));
+        assert!(src.contains("value: ParamMetricValue::Float(0.0),\n                timestamp: now,"));
+        assert!(add_metric(&mut src, "last_value", ValueType::Float, "").is_err());
+    }
+
+    #[test]
+    fn test_add_metric_after_pushed_values() {
+        // The first vec![...] in produce_metrics() gets the value even when more are pushed later
+        let mut src = template_lib_rs("http-bridge");
+        assert_eq!(add_metric(&mut src, "queue_depth", ValueType::Integer, "").unwrap(), None);
+        assert!(src.contains("data_type: MetricDataType::Integer,\n                unit: String::new(),"));
+        assert!(src.contains("value: ParamMetricValue::Integer(0),\n                timestamp: now,"));
+        assert_balanced(&src);
+    }
+
+    #[test]
+    fn test_add_metric_without_values_vec() {
+        let mut src = "fn metrics(&self) -> Vec<MetricDescriptor> {\n    vec![]\n}\n\n\
+                       fn produce_metrics(&self) -> Result<Vec<ExtensionMetricValue>> {\n    \
+                       Ok(self.values.iter().map(to_value).collect())\n}\n"
+            .to_string();
+        let note = add_metric(&mut src, "queue_depth", ValueType::Integer, "").unwrap();
+        assert!(note.unwrap().contains("by hand"));
+        assert!(src.contains("name: \"queue_depth\".to_string()"));
+        assert_balanced(&src);
+    }
+
+    /// Render every template, run `add command` and `add metric` on it and
+    /// `cargo check` the results as one workspace sharing the repo's
+    /// dependencies and lock file.
+    #[test]
+    fn test_added_items_compile_on_every_template() {
+        let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let repo_root = manifest_dir.parent().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut members = Vec::new();
+        for template in TEMPLATES {
+            let name = format!("add-{}", template.name);
+            let ext_dir = dir.path().join(&name);
+            copy_dir(&manifest_dir.join("templates").join(template.name), &ext_dir, &name, &mut Vec::new()).unwrap();
+            let path = Some(ext_dir.to_string_lossy().into_owned());
+
+            cmd_add_command(AddCommandOptions {
+                path: path.clone(),
+                name: "calibrate".to_string(),
+                params: vec!["threshold:float".to_string(), "label:string?".to_string()],
+                description: None,
+            })
+            .unwrap();
+            cmd_add_metric(AddMetricOptions {
+                path,
+                name: "queue_depth".to_string(),
+                data_type: "integer".to_string(),
+                unit: "count".to_string(),
+            })
+            .unwrap();
+
+            // Descriptor plus produce_metrics() value, descriptor plus dispatch arm
+            let src = fs::read_to_string(ext_dir.join("src").join("lib.rs")).unwrap();
+            assert_eq!(src.matches("name: \"queue_depth\".to_string()").count(), 2, "{}", template.name);
+            assert!(src.contains("name: \"calibrate\".to_string()"), "{}", template.name);
+            assert!(src.contains("\"calibrate\" => {"), "{}", template.name);
+            members.push(name);
+        }
+
+        let root: toml::Table = toml::from_str(&fs::read_to_string(repo_root.join("Cargo.toml")).unwrap()).unwrap();
+        let mut workspace = toml::Table::new();
+        workspace.insert("resolver".to_string(), "2".into());
+        workspace.insert("members".to_string(), members.into());
+        workspace.insert("dependencies".to_string(), root["workspace"]["dependencies"].clone());
+        let mut manifest = toml::Table::new();
+        manifest.insert("workspace".to_string(), workspace.into());
+        fs::write(dir.path().join("Cargo.toml"), toml::to_string(&manifest).unwrap()).unwrap();
+        fs::copy(repo_root.join("Cargo.lock"), dir.path().join("Cargo.lock")).unwrap();
+
+        let status = Command::new("cargo")
+            .args(["check", "--offline", "--workspace", "--all-targets"])
+            .current_dir(dir.path())
+            .env("CARGO_TARGET_DIR", manifest_dir.join("target").join("template-check"))
+            .status()
+            .unwrap();
+        assert!(status.success(), "generated extensions do not compile");
+    }
+
+    #[test]
+    fn test_imported() {
+        let src = "use std::collections::HashMap;\nuse neomind_extension_sdk::{\n    json, Extension,\n};\nfn f() { let x: Option<ParameterDefinition> = None; }";
+        assert!(imported(src, "HashMap"));
+        assert!(imported(src, "json"));
+        assert!(!imported(src, "ParameterDefinition"));
+    }
+}
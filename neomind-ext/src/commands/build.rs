@@ -0,0 +1,432 @@
+//! `neomind-ext build` - cargo build for the host or a matrix of targets.
+//!
+//! With `--targets linux-x86_64,linux-aarch64,wasm32` every target is built in
+//! turn (platforms are parsed like `package --platform`, so build keys,
+//! package keys and triples all work). Targets this host cannot build - a
+//! missing rustup target, a macOS or MSVC target from another OS, or a Linux
+//! cross target without a linker - are skipped with the reason instead of
+//! failing the run. Each built library is copied to
+//! `<target dir>/builds/<profile>/<id>-<version>-<build key>.<ext>`, using the
+//! same platform keys as `builds` in metadata.json.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+use crate::commands::package;
+use crate::utils::cargo::{self, CrateInfo};
+use crate::utils::exit::{fail, FailureClass};
+use crate::utils::manifest::ExtensionMetadata;
+use crate::utils::platform::{Platform, NATIVE_PLATFORMS};
+
+pub struct BuildOptions {
+    pub path: Option<String>,
+    pub release: bool,
+    /// Platforms to build; empty builds for the host only
+    pub targets: Vec<String>,
+    pub json: bool,
+}
+
+/// What the host can build, detected once per run.
+pub struct HostToolchain {
+    pub platform: Option<Platform>,
+    /// `std::env::consts::OS` of the host
+    pub os: &'static str,
+    /// Targets from `rustup target list --installed`; `None` without rustup
+    pub installed_targets: Option<Vec<String>>,
+    /// Linux triples with a usable cross linker
+    pub cross_linkers: Vec<&'static str>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+enum Outcome {
+    Built { artifact: PathBuf },
+    Skipped { reason: String },
+    Failed { reason: String },
+}
+
+#[derive(Serialize)]
+struct TargetResult {
+    #[serde(rename = "target", serialize_with = "serialize_build_key")]
+    platform: Platform,
+    #[serde(flatten)]
+    outcome: Outcome,
+    #[serde(rename = "elapsed_secs", serialize_with = "serialize_secs")]
+    elapsed: Duration,
+}
+
+/// `--json` result of `build`, for the host build and for `--targets`.
+#[derive(Serialize)]
+struct BuildReport {
+    extension: String,
+    profile: &'static str,
+    targets: Vec<TargetResult>,
+}
+
+pub fn cmd_build(opts: BuildOptions) -> Result<()> {
+    let ext_path = crate::get_extension_path(opts.path)?;
+    let profile = if opts.release { "release" } else { "debug" };
+    if !ext_path.join("Cargo.toml").exists() {
+        return Err(fail(
+            FailureClass::MissingFile,
+            format!("Cargo.toml not found at: {}", ext_path.display()),
+        ));
+    }
+    let crate_info = CrateInfo::load(&ext_path)?;
+
+    if opts.targets.is_empty() {
+        return build_host(&ext_path, &crate_info, opts.release, opts.json);
+    }
+
+    // Reject typos before building anything
+    let platforms = opts
+        .targets
+        .iter()
+        .map(|name| Platform::parse(name).ok_or_else(|| anyhow::anyhow!("Unknown platform: {}", name)))
+        .collect::<Result<Vec<_>>>()?;
+    let ext_id = extension_id(&ext_path)?;
+
+    if !opts.json {
+        println!(
+            "{}",
+            format!("🔨 Building {} for {} target(s) ({})", ext_id, platforms.len(), profile).green().bold()
+        );
+    }
+    let host = HostToolchain::detect(&ext_path);
+    let out_dir = cargo::target_dir(&ext_path).join("builds").join(profile);
+
+    let mut results = Vec::new();
+    for platform in platforms {
+        if !opts.json {
+            println!("\n{} {} ({})", "──".cyan(), platform.build_key.bold(), platform.triple);
+        }
+        let started = Instant::now();
+        let outcome = match host.skip_reason(&platform) {
+            Some(reason) => {
+                if !opts.json {
+                    println!("  {} {}", "⏭".yellow(), reason);
+                }
+                Outcome::Skipped { reason }
+            }
+            None => build_target(&ext_path, &crate_info, &ext_id, &platform, &host, opts.release, &out_dir),
+        };
+        results.push(TargetResult {
+            platform,
+            outcome,
+            elapsed: started.elapsed(),
+        });
+    }
+
+    let failed = results.iter().filter(|r| matches!(r.outcome, Outcome::Failed { .. })).count();
+    if opts.json {
+        let report = BuildReport {
+            extension: ext_id,
+            profile,
+            targets: results,
+        };
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_summary(&results);
+    }
+    if failed > 0 {
+        return Err(fail(FailureClass::CompileFailure, format!("{} target(s) failed to build", failed)));
+    }
+    Ok(())
+}
+
+/// Plain `cargo build` for the host, as before `--targets` existed.
+fn build_host(ext_path: &Path, crate_info: &CrateInfo, release: bool, json: bool) -> Result<()> {
+    let profile = if release { "release" } else { "debug" };
+    if json {
+        return build_host_json(ext_path, crate_info, release);
+    }
+    println!("{}", format!("🔨 Building extension ({})", profile).green().bold());
+    println!("{}", "Running cargo build...".yellow());
+
+    let status = cargo_build(ext_path, &crate_info.package_name, release, None)?;
+    if !status.success() {
+        return Err(fail(
+            FailureClass::CompileFailure,
+            format!("Build failed with exit code: {:?}", status.code()),
+        ));
+    }
+    println!("{}", "✅ Build completed successfully!".green().bold());
+
+    if let Some(host) = Platform::host() {
+        let lib_path = crate_info.artifact_path(&host, profile);
+        if lib_path.exists() {
+            println!("\n{}: {}", "Output".cyan(), lib_path.display());
+        }
+    }
+    Ok(())
+}
+
+/// The host build reported like a one-entry `--targets` run.
+fn build_host_json(ext_path: &Path, crate_info: &CrateInfo, release: bool) -> Result<()> {
+    let platform = Platform::host().ok_or_else(|| anyhow::anyhow!("Unsupported host platform"))?;
+    let profile = if release { "release" } else { "debug" };
+    let started = Instant::now();
+    let status = cargo_build(ext_path, &crate_info.package_name, release, None)?;
+    let outcome = if status.success() {
+        Outcome::Built {
+            artifact: crate_info.artifact_path(&platform, profile),
+        }
+    } else {
+        Outcome::Failed {
+            reason: format!("cargo build exited with {:?}", status.code()),
+        }
+    };
+    let failed = matches!(outcome, Outcome::Failed { .. });
+    let report = BuildReport {
+        extension: extension_id(ext_path)?,
+        profile,
+        targets: vec![TargetResult {
+            platform,
+            outcome,
+            elapsed: started.elapsed(),
+        }],
+    };
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    if failed {
+        return Err(fail(
+            FailureClass::CompileFailure,
+            format!("Build failed with exit code: {:?}", status.code()),
+        ));
+    }
+    Ok(())
+}
+
+fn build_target(
+    ext_path: &Path,
+    crate_info: &CrateInfo,
+    ext_id: &str,
+    platform: &Platform,
+    host: &HostToolchain,
+    release: bool,
+    out_dir: &Path,
+) -> Outcome {
+    let profile = if release { "release" } else { "debug" };
+    // Host builds stay in target/<profile>/ where `package` and `lint` look first
+    let triple = (host.platform != Some(*platform)).then_some(platform.triple);
+    let status = match cargo_build(ext_path, &crate_info.package_name, release, triple) {
+        Ok(status) => status,
+        Err(e) => return Outcome::Failed { reason: e.to_string() },
+    };
+    if !status.success() {
+        return Outcome::Failed {
+            reason: format!("cargo build exited with {:?}", status.code()),
+        };
+    }
+
+    let built = crate_info.artifact_path(platform, profile);
+    if !built.exists() {
+        return Outcome::Failed {
+            reason: format!("{} not found after build", built.display()),
+        };
+    }
+    let artifact = out_dir.join(artifact_file_name(ext_id, &crate_info.version, platform));
+    let copied = fs::create_dir_all(out_dir)
+        .and_then(|_| fs::copy(&built, &artifact))
+        .with_context(|| format!("Failed to copy {} to {}", built.display(), artifact.display()));
+    match copied {
+        Ok(_) => Outcome::Built { artifact },
+        Err(e) => Outcome::Failed { reason: format!("{:#}", e) },
+    }
+}
+
+pub fn cargo_build(ext_path: &Path, package_name: &str, release: bool, triple: Option<&str>) -> Result<std::process::ExitStatus> {
+    let mut cmd = Command::new("cargo");
+    cmd.arg("build").arg("-p").arg(package_name);
+    if release {
+        cmd.arg("--release");
+    }
+    if let Some(triple) = triple {
+        cmd.arg("--target").arg(triple);
+    }
+    cmd.current_dir(ext_path).status().context("Failed to run cargo build")
+}
+
+/// `<id>-<version>-<build key>.<ext>`, e.g. `yolo-device-inference-2.7.0-linux-aarch64.so`
+pub fn artifact_file_name(ext_id: &str, version: &str, platform: &Platform) -> String {
+    format!("{}-{}-{}.{}", ext_id, version, platform.build_key, platform.lib_ext)
+}
+
+impl HostToolchain {
+    /// Run in the extension directory so rust-toolchain.toml overrides apply.
+    pub fn detect(ext_path: &Path) -> Self {
+        let installed_targets = Command::new("rustup")
+            .args(["target", "list", "--installed"])
+            .current_dir(ext_path)
+            .output()
+            .ok()
+            .filter(|out| out.status.success())
+            .map(|out| String::from_utf8_lossy(&out.stdout).lines().map(|l| l.trim().to_string()).collect());
+        let cross_linkers = NATIVE_PLATFORMS
+            .iter()
+            .filter(|p| p.triple.contains("-linux-") && has_cross_linker(p.triple))
+            .map(|p| p.triple)
+            .collect();
+        Self {
+            platform: Platform::host(),
+            os: std::env::consts::OS,
+            installed_targets,
+            cross_linkers,
+        }
+    }
+
+    /// Why `platform` cannot be built here, or `None` if it should be attempted.
+    pub fn skip_reason(&self, platform: &Platform) -> Option<String> {
+        if self.platform == Some(*platform) {
+            return None;
+        }
+        if platform.triple.ends_with("-apple-darwin") && self.os != "macos" {
+            return Some("macOS targets need a macOS host with the Apple SDK".to_string());
+        }
+        if platform.triple.ends_with("-windows-msvc") && self.os != "windows" {
+            return Some("MSVC targets need a Windows host".to_string());
+        }
+        if let Some(installed) = &self.installed_targets {
+            if !installed.iter().any(|t| t == platform.triple) {
+                return Some(format!(
+                    "Rust target {} is not installed (rustup target add {})",
+                    platform.triple, platform.triple
+                ));
+            }
+        }
+        // Apple's toolchain links both architectures; Linux cross builds need a linker
+        if platform.is_wasm() || !platform.triple.contains("-linux-") {
+            return None;
+        }
+        (!self.cross_linkers.contains(&platform.triple)).then(|| {
+            format!(
+                "No cross linker for {} (install {} or set {})",
+                platform.triple,
+                gcc_linker(platform.triple),
+                linker_env_var(platform.triple)
+            )
+        })
+    }
+}
+
+/// `aarch64-unknown-linux-gnu` -> `aarch64-linux-gnu-gcc`
+fn gcc_linker(triple: &str) -> String {
+    let arch = triple.split('-').next().unwrap_or(triple);
+    format!("{}-linux-gnu-gcc", arch)
+}
+
+/// `CARGO_TARGET_AARCH64_UNKNOWN_LINUX_GNU_LINKER`
+fn linker_env_var(triple: &str) -> String {
+    format!("CARGO_TARGET_{}_LINKER", triple.to_uppercase().replace('-', "_"))
+}
+
+fn has_cross_linker(triple: &str) -> bool {
+    if std::env::var_os(linker_env_var(triple)).is_some() {
+        return true;
+    }
+    let linker = gcc_linker(triple);
+    std::env::var_os("PATH")
+        .map(|paths| std::env::split_paths(&paths).any(|dir| dir.join(&linker).is_file()))
+        .unwrap_or(false)
+}
+
+fn extension_id(ext_path: &Path) -> Result<String> {
+    match ExtensionMetadata::load_optional(ext_path)? {
+        Some(metadata) => Ok(metadata.id),
+        None => package::dir_name(ext_path),
+    }
+}
+
+fn serialize_build_key<S: serde::Serializer>(platform: &Platform, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(platform.build_key)
+}
+
+fn serialize_secs<S: serde::Serializer>(elapsed: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_f64(elapsed.as_secs_f64())
+}
+
+fn print_summary(results: &[TargetResult]) {
+    println!("\n{}", "Build summary".bold());
+    println!("  {:<16} {:<8} {:>8}  ARTIFACT / REASON", "TARGET", "STATUS", "TIME");
+    for result in results {
+        let (status, detail) = match &result.outcome {
+            // Pad before colouring so escape codes do not skew the columns
+            Outcome::Built { artifact } => (format!("{:<8}", "built").green(), artifact.display().to_string()),
+            Outcome::Skipped { reason } => (format!("{:<8}", "skipped").yellow(), reason.clone()),
+            Outcome::Failed { reason } => (format!("{:<8}", "failed").red(), reason.clone()),
+        };
+        println!(
+            "  {:<16} {} {:>7.1}s  {}",
+            result.platform.build_key,
+            status,
+            result.elapsed.as_secs_f64(),
+            detail
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::platform::WASM;
+
+    fn linux_host(installed: &[&str], cross_linkers: Vec<&'static str>) -> HostToolchain {
+        HostToolchain {
+            platform: Platform::parse("linux-x86_64"),
+            os: "linux",
+            installed_targets: Some(installed.iter().map(|t| t.to_string()).collect()),
+            cross_linkers,
+        }
+    }
+
+    #[test]
+    fn test_skip_reasons_on_linux_host() {
+        let host = linux_host(&["x86_64-unknown-linux-gnu", "wasm32-unknown-unknown"], vec![]);
+        let platform = |key| Platform::parse(key).unwrap();
+
+        assert_eq!(host.skip_reason(&platform("linux-x86_64")), None);
+        assert_eq!(host.skip_reason(&WASM), None);
+        assert!(host.skip_reason(&platform("linux-aarch64")).unwrap().contains("rustup target add aarch64-unknown-linux-gnu"));
+        assert!(host.skip_reason(&platform("darwin-aarch64")).unwrap().contains("macOS host"));
+    }
+
+    #[test]
+    fn test_linux_cross_target_needs_linker() {
+        let without = linux_host(&["aarch64-unknown-linux-gnu"], vec![]);
+        let reason = without.skip_reason(&Platform::parse("linux-aarch64").unwrap()).unwrap();
+        assert!(reason.contains("aarch64-linux-gnu-gcc"));
+        assert!(reason.contains("CARGO_TARGET_AARCH64_UNKNOWN_LINUX_GNU_LINKER"));
+
+        let with = linux_host(&["aarch64-unknown-linux-gnu"], vec!["aarch64-unknown-linux-gnu"]);
+        assert_eq!(with.skip_reason(&Platform::parse("linux-aarch64").unwrap()), None);
+    }
+
+    #[test]
+    fn test_json_target_result() {
+        let result = TargetResult {
+            platform: Platform::parse("linux_arm64").unwrap(),
+            outcome: Outcome::Skipped {
+                reason: "No cross linker".to_string(),
+            },
+            elapsed: Duration::from_millis(1500),
+        };
+        assert_eq!(
+            serde_json::to_value(&result).unwrap(),
+            serde_json::json!({ "target": "linux-aarch64", "status": "skipped", "reason": "No cross linker", "elapsed_secs": 1.5 })
+        );
+    }
+
+    #[test]
+    fn test_artifact_names_use_build_keys() {
+        let linux = Platform::parse("linux_arm64").unwrap();
+        assert_eq!(
+            artifact_file_name("yolo-device-inference", "2.7.0", &linux),
+            "yolo-device-inference-2.7.0-linux-aarch64.so"
+        );
+        assert_eq!(artifact_file_name("wasm-demo", "1.0.0", &WASM), "wasm-demo-1.0.0-wasm32.wasm");
+    }
+}
@@ -0,0 +1,393 @@
+//! `neomind-ext dev` - watch an extension, rebuild on change and hot-reload
+//! it into the `run` harness.
+//!
+//! The REPL accepts the same actions as `run`. When `src/`, `Cargo.toml` or
+//! `build.rs` change the crate is rebuilt; on success a fresh copy of the
+//! library is loaded (a new file name per reload, since `dlopen` hands back the
+//! already-loaded image for a path it has seen), a new instance is created with
+//! the same config, and the last command or event is replayed with a diff
+//! against its previous response. Changes under `frontend/` run
+//! `npm run build` instead. A failed build keeps the previous library loaded.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde_json::{json, Value};
+use std::fs;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::Duration;
+
+use crate::commands::build;
+use crate::commands::run::{self, Action};
+use crate::utils::cargo::{self, CrateInfo};
+use crate::utils::ffi::{ExtensionInstance, ExtensionLibrary};
+use crate::utils::host::{self, CapabilityCall};
+use crate::utils::json_diff;
+use crate::utils::platform::Platform;
+use crate::utils::watch::Snapshot;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub struct DevOptions {
+    pub path: Option<String>,
+    pub release: bool,
+    /// Config passed to create on every reload, as JSON or @file
+    pub config: Option<String>,
+    /// Action to run after the first successful load
+    pub action: Vec<String>,
+}
+
+enum DevEvent {
+    Line(String),
+    Changed(Vec<PathBuf>),
+    Eof,
+}
+
+enum Next {
+    Reload,
+    Quit,
+}
+
+/// The action replayed after each reload and the response it produced last time.
+#[derive(Default)]
+struct LastAction {
+    action: Option<Action>,
+    response: Option<Value>,
+}
+
+struct DevContext {
+    ext_path: PathBuf,
+    crate_info: CrateInfo,
+    host: Platform,
+    release: bool,
+    config: Value,
+    /// Directory holding one library copy per reload
+    reload_dir: PathBuf,
+}
+
+pub fn cmd_dev(opts: DevOptions) -> Result<()> {
+    let ext_path = crate::get_extension_path(opts.path)?;
+    let crate_info = CrateInfo::load(&ext_path)?;
+    let host = Platform::host().ok_or_else(|| anyhow::anyhow!("Unsupported host platform"))?;
+    let config = match &opts.config {
+        Some(config) => run::parse_json_arg(config)?,
+        None => Value::Object(Default::default()),
+    };
+    let reload_dir = cargo::target_dir(&ext_path).join("neomind-dev").join(&crate_info.lib_name);
+    // Copies from an earlier session are no longer loaded by anything
+    let _ = fs::remove_dir_all(&reload_dir);
+    fs::create_dir_all(&reload_dir)?;
+    let ctx = DevContext {
+        ext_path,
+        crate_info,
+        host,
+        release: opts.release,
+        config,
+        reload_dir,
+    };
+
+    let mut last = LastAction::default();
+    if !opts.action.is_empty() {
+        last.action = Some(Action::parse(&opts.action.join(" "))?);
+    }
+
+    println!("{}", "👀 Watching src/ and frontend/ (type `help` for actions, `quit` to exit)".green().bold());
+    let (tx, rx) = mpsc::channel();
+    spawn_stdin_reader(tx.clone());
+    spawn_watcher(tx, &ctx.ext_path);
+
+    let mut built = rebuild(&ctx);
+    let mut generation = 0;
+    loop {
+        let library = if built {
+            generation += 1;
+            load_generation(&ctx, generation)
+                .map_err(|e| println!("{} {:#}", "✗".red(), e))
+                .ok()
+        } else {
+            None
+        };
+        let instance = library.as_ref().and_then(|library| {
+            let created = library.create(&ctx.config);
+            run::print_created_calls();
+            created.map_err(|e| println!("{} {:#}", "✗".red(), e)).ok()
+        });
+        if let Some(instance) = &instance {
+            replay_last(instance, &mut last);
+        }
+
+        match serve(instance.as_ref(), &rx, &ctx, &mut last)? {
+            Next::Reload => built = true,
+            Next::Quit => break,
+        }
+    }
+    Ok(())
+}
+
+/// Handle REPL lines and file changes until a rebuild succeeds or the user quits.
+fn serve(
+    instance: Option<&ExtensionInstance>,
+    events: &Receiver<DevEvent>,
+    ctx: &DevContext,
+    last: &mut LastAction,
+) -> Result<Next> {
+    loop {
+        let event = events.recv().context("watcher stopped")?;
+        match event {
+            DevEvent::Eof => return Ok(Next::Quit),
+            DevEvent::Line(line) if line.trim().is_empty() => {}
+            DevEvent::Line(line) => match Action::parse(&line) {
+                Ok(Action::Quit) => return Ok(Next::Quit),
+                Ok(Action::Help) => run::print_help(),
+                Ok(action) => match instance {
+                    Some(instance) => {
+                        let response = invoke(instance, &action);
+                        *last = LastAction {
+                            action: Some(action),
+                            response: Some(response),
+                        };
+                    }
+                    None => println!("{} no library loaded; fix the build first", "✗".red()),
+                },
+                Err(e) => println!("{} {:#}", "✗".red(), e),
+            },
+            DevEvent::Changed(paths) => {
+                let frontend = ctx.ext_path.join("frontend");
+                let (frontend_changes, crate_changes): (Vec<&PathBuf>, Vec<&PathBuf>) =
+                    paths.iter().partition(|p| p.starts_with(&frontend));
+                if !frontend_changes.is_empty() {
+                    print_changed(&ctx.ext_path, &frontend_changes);
+                    rebuild_frontend(&frontend);
+                }
+                if !crate_changes.is_empty() {
+                    print_changed(&ctx.ext_path, &crate_changes);
+                    if rebuild(ctx) {
+                        return Ok(Next::Reload);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Run `action`, print its result and capability calls, and return both as one JSON value.
+fn invoke(instance: &ExtensionInstance, action: &Action) -> Value {
+    let (result, calls) = run::perform(instance, action);
+    match &result {
+        Ok(Some(value)) => println!("{}", serde_json::to_string_pretty(value).unwrap_or_default()),
+        Ok(None) => println!("{}", "ok".green()),
+        Err(e) => println!("{} {:#}", "✗".red(), e),
+    }
+    run::print_calls(&calls);
+    response_value(result, &calls)
+}
+
+fn response_value(result: Result<Option<Value>>, calls: &[CapabilityCall]) -> Value {
+    let mut response = match result {
+        Ok(value) => json!({ "result": value }),
+        Err(e) => json!({ "error": format!("{:#}", e) }),
+    };
+    if !calls.is_empty() {
+        response["capability_calls"] = serde_json::to_value(calls).unwrap_or_default();
+    }
+    response
+}
+
+fn replay_last(instance: &ExtensionInstance, last: &mut LastAction) {
+    let Some(action) = &last.action else {
+        return;
+    };
+    println!("{} {}", "↻ Replaying".cyan().bold(), describe(action));
+    let response = invoke(instance, action);
+    if let Some(previous) = &last.response {
+        let changes = json_diff::diff(previous, &response);
+        if changes.is_empty() {
+            println!("{}", "Response unchanged".dimmed());
+        } else {
+            println!("{}", format!("Response changed ({} difference(s)):", changes.len()).yellow());
+            for change in &changes {
+                let line = change.to_string();
+                let line = match change {
+                    json_diff::Change::Added { .. } => line.green(),
+                    json_diff::Change::Removed { .. } => line.red(),
+                    json_diff::Change::Changed { .. } => line.yellow(),
+                };
+                println!("  {}", line);
+            }
+        }
+    }
+    last.response = Some(response);
+}
+
+fn describe(action: &Action) -> String {
+    match action {
+        Action::Invoke { command, .. } => format!("invoke {}", command),
+        Action::Event { event_type, .. } => format!("event {}", event_type),
+        Action::Configure(_) => "configure".to_string(),
+        Action::Metrics => "metrics".to_string(),
+        Action::Help | Action::Quit => String::new(),
+    }
+}
+
+/// `cargo build` for the host; prints the outcome and returns whether it succeeded.
+fn rebuild(ctx: &DevContext) -> bool {
+    println!("{}", "🔨 Building...".yellow());
+    match build::cargo_build(&ctx.ext_path, &ctx.crate_info.package_name, ctx.release, None) {
+        Ok(status) if status.success() => true,
+        Ok(status) => {
+            println!("{} build failed ({}); keeping the previous library", "✗".red(), status);
+            false
+        }
+        Err(e) => {
+            println!("{} {:#}", "✗".red(), e);
+            false
+        }
+    }
+}
+
+fn rebuild_frontend(frontend: &Path) {
+    if !frontend.join("package.json").exists() {
+        return;
+    }
+    println!("{}", "🎨 npm run build".yellow());
+    match Command::new("npm").args(["run", "build"]).current_dir(frontend).status() {
+        Ok(status) if status.success() => println!("{} frontend/dist updated", "✓".green()),
+        Ok(status) => println!("{} frontend build failed ({})", "✗".red(), status),
+        Err(e) => println!("{} Failed to run npm: {}", "✗".red(), e),
+    }
+}
+
+/// Copy the freshly built library to a per-reload file name and load it.
+fn load_generation(ctx: &DevContext, generation: usize) -> Result<ExtensionLibrary> {
+    let profile = if ctx.release { "release" } else { "debug" };
+    let built = ctx.crate_info.artifact_path(&ctx.host, profile);
+    let copy = ctx.reload_dir.join(reload_file_name(&ctx.host, &ctx.crate_info.lib_name, generation));
+    fs::copy(&built, &copy).with_context(|| format!("Failed to copy {}", built.display()))?;
+
+    let library = ExtensionLibrary::load(&copy)?;
+    host::install(&library)?;
+    let descriptor = library.descriptor()?;
+    let metadata = &descriptor["metadata"];
+    println!(
+        "{} {} v{} (reload #{})",
+        "▶ Loaded".green().bold(),
+        metadata["id"].as_str().unwrap_or_default(),
+        metadata["version"].as_str().unwrap_or_default(),
+        generation
+    );
+    Ok(library)
+}
+
+/// `libfoo.so` -> `libfoo-3.so`
+fn reload_file_name(platform: &Platform, lib_name: &str, generation: usize) -> String {
+    platform.library_file_name(&format!("{}-{}", lib_name, generation))
+}
+
+fn print_changed(ext_path: &Path, paths: &[&PathBuf]) {
+    let shown: Vec<String> = paths
+        .iter()
+        .take(3)
+        .map(|p| p.strip_prefix(ext_path).unwrap_or(p).display().to_string())
+        .collect();
+    let more = paths.len().saturating_sub(shown.len());
+    let suffix = if more > 0 { format!(" (+{} more)", more) } else { String::new() };
+    println!("\n{} {}{}", "✎ Changed".cyan(), shown.join(", "), suffix);
+}
+
+fn spawn_stdin_reader(tx: Sender<DevEvent>) {
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lock().lines() {
+            let Ok(line) = line else { break };
+            if tx.send(DevEvent::Line(line)).is_err() {
+                return;
+            }
+        }
+        let _ = tx.send(DevEvent::Eof);
+    });
+}
+
+/// Poll the watched paths; a change is reported once the tree has been
+/// stable for one interval, so an editor's save-and-rename counts once.
+fn spawn_watcher(tx: Sender<DevEvent>, ext_path: &Path) {
+    let roots = watched_paths(ext_path);
+    std::thread::spawn(move || {
+        let mut reported = Snapshot::take(&roots);
+        let mut previous = reported.clone();
+        loop {
+            std::thread::sleep(POLL_INTERVAL);
+            let current = Snapshot::take(&roots);
+            if current == previous && current != reported {
+                if tx.send(DevEvent::Changed(current.changes_since(&reported))).is_err() {
+                    return;
+                }
+                reported = current.clone();
+            }
+            previous = current;
+        }
+    });
+}
+
+fn watched_paths(ext_path: &Path) -> Vec<PathBuf> {
+    ["src", "frontend", "Cargo.toml", "build.rs"]
+        .iter()
+        .map(|p| ext_path.join(p))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reload_file_names_are_unique_per_generation() {
+        let linux = Platform::parse("linux_amd64").unwrap();
+        assert_eq!(reload_file_name(&linux, "neomind_extension_foo", 3), "libneomind_extension_foo-3.so");
+        assert_ne!(reload_file_name(&linux, "x", 1), reload_file_name(&linux, "x", 2));
+    }
+
+    #[test]
+    fn test_response_value_includes_errors_and_calls() {
+        let calls = [CapabilityCall {
+            capability: "device_metrics_write".to_string(),
+            params: json!({ "metric": "count", "value": 2 }),
+        }];
+        let ok = response_value(Ok(Some(json!({ "count": 2 }))), &calls);
+        assert_eq!(ok["result"]["count"], 2);
+        assert_eq!(ok["capability_calls"][0]["params"]["metric"], "count");
+
+        let failed = response_value(Err(anyhow::anyhow!("model not loaded")), &[]);
+        assert_eq!(failed, json!({ "error": "model not loaded" }));
+    }
+
+    #[test]
+    fn test_reload_replays_last_action() {
+        let dir = tempfile::tempdir().unwrap();
+        let ext_path = PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/../extensions/wasm-demo"));
+        // Makes sure the debug build load_generation copies from exists
+        crate::utils::ffi::demo_library(dir.path());
+        let ctx = DevContext {
+            crate_info: CrateInfo::load(&ext_path).unwrap(),
+            ext_path,
+            host: Platform::host().unwrap(),
+            release: false,
+            config: json!({}),
+            reload_dir: dir.path().join("reload"),
+        };
+        fs::create_dir_all(&ctx.reload_dir).unwrap();
+
+        let mut last = LastAction {
+            action: Some(Action::parse("invoke increment {\"amount\": 2}").unwrap()),
+            response: None,
+        };
+        for generation in 1..=2 {
+            let library = load_generation(&ctx, generation).unwrap();
+            let instance = library.create(&ctx.config).unwrap();
+            replay_last(&instance, &mut last);
+            // Every reload starts from a fresh instance
+            assert_eq!(last.response.as_ref().unwrap()["result"]["counter"], 2);
+        }
+        assert!(ctx.reload_dir.join(reload_file_name(&ctx.host, &ctx.crate_info.lib_name, 2)).exists());
+    }
+}
@@ -0,0 +1,500 @@
+//! `neomind-ext diff old.nep new.nep` - what changed in the shipped surface
+//! between two package versions.
+//!
+//! Commands and metrics come from the runtime descriptor of each package's
+//! host-platform binary (loaded like `lint` does); everything else from the
+//! archives themselves. Changes that break existing dashboards and rules are
+//! flagged: removed commands or metrics, changed parameter or metric types,
+//! and parameters that callers must now pass.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::utils::checksum;
+use crate::utils::exit::{fail, FailureClass};
+use crate::utils::ffi::ExtensionLibrary;
+use crate::utils::json_diff::{self, Change};
+use crate::utils::nep::NepPackage;
+use crate::utils::platform::Platform;
+
+/// Manifest fields compared in their own sections instead of field by field.
+const SECTIONED_FIELDS: [&str; 2] = ["binaries", "checksums"];
+
+pub struct DiffOptions {
+    pub old: String,
+    pub new: String,
+    /// Compare archives only; do not load the binaries
+    pub no_runtime: bool,
+    /// Exit non-zero when a breaking change is found
+    pub fail_on_breaking: bool,
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiffEntry {
+    /// commands, metrics, manifest, binaries, models or frontend
+    pub section: &'static str,
+    pub change: ChangeKind,
+    pub name: String,
+    pub detail: String,
+    pub breaking: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PackageDiff {
+    pub old: String,
+    pub new: String,
+    pub old_version: String,
+    pub new_version: String,
+    pub breaking: usize,
+    pub entries: Vec<DiffEntry>,
+    /// Parts that could not be compared, e.g. no host binary to load
+    pub notes: Vec<String>,
+}
+
+/// What is compared from one package.
+struct PackageSurface {
+    manifest: Value,
+    /// Archive path -> (size, SHA-256)
+    files: BTreeMap<String, (u64, String)>,
+    descriptor: Option<Value>,
+}
+
+pub fn cmd_diff(opts: DiffOptions) -> Result<()> {
+    let mut notes = Vec::new();
+    let scratch = std::env::temp_dir().join(format!("neomind-ext-diff-{}", std::process::id()));
+    let old = load_surface(Path::new(&opts.old), &scratch.join("old"), !opts.no_runtime, &mut notes);
+    let new = load_surface(Path::new(&opts.new), &scratch.join("new"), !opts.no_runtime, &mut notes);
+    let _ = fs::remove_dir_all(&scratch);
+    let (old, new) = (old?, new?);
+
+    let mut entries = Vec::new();
+    if let (Some(a), Some(b)) = (&old.descriptor, &new.descriptor) {
+        entries.extend(diff_descriptors(a, b));
+    }
+    entries.extend(diff_manifests(&old.manifest, &new.manifest));
+    entries.extend(diff_binaries(&old, &new));
+    entries.extend(diff_files("models", "models/", &old.files, &new.files));
+    entries.extend(diff_files("frontend", "frontend/", &old.files, &new.files));
+
+    let version = |m: &Value| m["version"].as_str().unwrap_or("?").to_string();
+    let report = PackageDiff {
+        old: opts.old.clone(),
+        new: opts.new.clone(),
+        old_version: version(&old.manifest),
+        new_version: version(&new.manifest),
+        breaking: entries.iter().filter(|e| e.breaking).count(),
+        entries,
+        notes,
+    };
+
+    if opts.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_human(&report);
+    }
+    if opts.fail_on_breaking && report.breaking > 0 {
+        return Err(fail(FailureClass::ValidationFailure, format!("{} breaking change(s) between {} and {}", report.breaking, report.old_version, report.new_version)));
+    }
+    Ok(())
+}
+
+fn load_surface(nep: &Path, scratch: &Path, load_runtime: bool, notes: &mut Vec<String>) -> Result<PackageSurface> {
+    let mut package = NepPackage::open(nep)?;
+    let manifest = package
+        .manifest()
+        .with_context(|| format!("Failed to read manifest of {}", nep.display()))?;
+    let mut files = BTreeMap::new();
+    for name in package.file_names() {
+        let data = package.read(&name)?;
+        files.insert(name, (data.len() as u64, checksum::sha256_hex(&data)));
+    }
+
+    let descriptor = if load_runtime {
+        match host_descriptor(&mut package, &manifest, scratch) {
+            Ok(descriptor) => Some(descriptor),
+            Err(e) => {
+                notes.push(format!("commands and metrics not compared for {}: {:#}", nep.display(), e));
+                None
+            }
+        }
+    } else {
+        None
+    };
+    Ok(PackageSurface {
+        manifest,
+        files,
+        descriptor,
+    })
+}
+
+/// Extract the host-platform binary and read its descriptor.
+fn host_descriptor(package: &mut NepPackage, manifest: &Value, scratch: &Path) -> Result<Value> {
+    let host = Platform::host().ok_or_else(|| anyhow::anyhow!("unsupported host platform"))?;
+    let entry = manifest["binaries"][host.package_key]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("no {} binary in the package", host.package_key))?;
+    let file_name = Path::new(entry)
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("invalid binary path {}", entry))?;
+    fs::create_dir_all(scratch)?;
+    let lib_path: PathBuf = scratch.join(file_name);
+    fs::write(&lib_path, package.read(entry)?)?;
+    ExtensionLibrary::load(&lib_path)?.descriptor()
+}
+
+/// Commands, their parameters and metric descriptors.
+pub fn diff_descriptors(old: &Value, new: &Value) -> Vec<DiffEntry> {
+    let mut entries = Vec::new();
+    let old_commands = by_name(&old["commands"]);
+    let new_commands = by_name(&new["commands"]);
+    for (name, command) in &old_commands {
+        match new_commands.get(name) {
+            None => entries.push(entry("commands", ChangeKind::Removed, name, "command removed", true)),
+            Some(updated) => entries.extend(diff_parameters(name, command, updated)),
+        }
+    }
+    for name in new_commands.keys().filter(|n| !old_commands.contains_key(*n)) {
+        entries.push(entry("commands", ChangeKind::Added, name, "new command", false));
+    }
+
+    let old_metrics = by_name(&old["metrics"]);
+    let new_metrics = by_name(&new["metrics"]);
+    for (name, metric) in &old_metrics {
+        match new_metrics.get(name) {
+            None => entries.push(entry("metrics", ChangeKind::Removed, name, "metric removed", true)),
+            Some(updated) if updated != metric => {
+                let type_changed = metric["data_type"] != updated["data_type"];
+                entries.push(entry(
+                    "metrics",
+                    ChangeKind::Changed,
+                    name,
+                    &describe_changes(&json_diff::diff(metric, updated)),
+                    type_changed,
+                ));
+            }
+            Some(_) => {}
+        }
+    }
+    for name in new_metrics.keys().filter(|n| !old_metrics.contains_key(*n)) {
+        entries.push(entry("metrics", ChangeKind::Added, name, "new metric", false));
+    }
+    entries
+}
+
+fn diff_parameters(command: &str, old: &Value, new: &Value) -> Vec<DiffEntry> {
+    let mut entries = Vec::new();
+    let old_params = by_name(&old["parameters"]);
+    let new_params = by_name(&new["parameters"]);
+    let required = |p: &Value| p["required"].as_bool().unwrap_or(false);
+
+    for (name, param) in &old_params {
+        let label = format!("{}({})", command, name);
+        match new_params.get(name) {
+            None => entries.push(entry("commands", ChangeKind::Removed, &label, "parameter removed", false)),
+            Some(updated) if updated != param => {
+                let type_changed = param["param_type"] != updated["param_type"];
+                let now_required = required(updated) && !required(param);
+                entries.push(entry(
+                    "commands",
+                    ChangeKind::Changed,
+                    &label,
+                    &describe_changes(&json_diff::diff(param, updated)),
+                    type_changed || now_required,
+                ));
+            }
+            Some(_) => {}
+        }
+    }
+    for (name, param) in new_params.iter().filter(|(n, _)| !old_params.contains_key(*n)) {
+        let label = format!("{}({})", command, name);
+        if required(param) {
+            entries.push(entry("commands", ChangeKind::Added, &label, "new required parameter", true));
+        } else {
+            entries.push(entry("commands", ChangeKind::Added, &label, "new optional parameter", false));
+        }
+    }
+
+    let rest = |c: &Value| -> Value {
+        let mut c = c.as_object().cloned().unwrap_or_default();
+        c.remove("parameters");
+        Value::Object(c)
+    };
+    let other = json_diff::diff(&rest(old), &rest(new));
+    if !other.is_empty() {
+        entries.push(entry("commands", ChangeKind::Changed, command, &describe_changes(&other), false));
+    }
+    entries
+}
+
+/// Manifest fields other than binaries and checksums, which get their own sections.
+pub fn diff_manifests(old: &Value, new: &Value) -> Vec<DiffEntry> {
+    let strip = |m: &Value| -> Value {
+        let mut fields: Map<String, Value> = m.as_object().cloned().unwrap_or_default();
+        for field in SECTIONED_FIELDS {
+            fields.remove(field);
+        }
+        Value::Object(fields)
+    };
+    json_diff::diff(&strip(old), &strip(new))
+        .into_iter()
+        .map(|change| {
+            let (kind, path) = match &change {
+                Change::Added { path, .. } => (ChangeKind::Added, path),
+                Change::Removed { path, .. } => (ChangeKind::Removed, path),
+                Change::Changed { path, .. } => (ChangeKind::Changed, path),
+            };
+            let detail = change.to_string();
+            let detail = detail.split_once(": ").map(|(_, d)| d).unwrap_or(&detail);
+            entry("manifest", kind, path.trim_start_matches('/'), detail, false)
+        })
+        .collect()
+}
+
+/// Binary size per platform key.
+fn diff_binaries(old: &PackageSurface, new: &PackageSurface) -> Vec<DiffEntry> {
+    let binaries = |s: &PackageSurface| -> BTreeMap<String, u64> {
+        s.manifest["binaries"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter_map(|(platform, path)| {
+                let size = s.files.get(path.as_str()?).map(|(size, _)| *size)?;
+                Some((platform.clone(), size))
+            })
+            .collect()
+    };
+    let (a, b) = (binaries(old), binaries(new));
+    let mut entries = Vec::new();
+    for (platform, old_size) in &a {
+        match b.get(platform) {
+            None => entries.push(entry("binaries", ChangeKind::Removed, platform, "platform dropped", true)),
+            Some(new_size) if new_size != old_size => {
+                entries.push(entry("binaries", ChangeKind::Changed, platform, &size_change(*old_size, *new_size), false))
+            }
+            Some(_) => {}
+        }
+    }
+    for (platform, size) in b.iter().filter(|(p, _)| !a.contains_key(*p)) {
+        entries.push(entry("binaries", ChangeKind::Added, platform, &format_size(*size), false));
+    }
+    entries
+}
+
+/// Files under `prefix`, compared by content.
+fn diff_files(
+    section: &'static str,
+    prefix: &str,
+    old: &BTreeMap<String, (u64, String)>,
+    new: &BTreeMap<String, (u64, String)>,
+) -> Vec<DiffEntry> {
+    let under = |files: &BTreeMap<String, (u64, String)>| -> BTreeMap<String, (u64, String)> {
+        files
+            .iter()
+            .filter_map(|(name, info)| Some((name.strip_prefix(prefix)?.to_string(), info.clone())))
+            .collect()
+    };
+    let (a, b) = (under(old), under(new));
+    let mut entries = Vec::new();
+    for (name, (old_size, old_hash)) in &a {
+        match b.get(name) {
+            None => entries.push(entry(section, ChangeKind::Removed, name, &format_size(*old_size), false)),
+            Some((new_size, new_hash)) if new_hash != old_hash => {
+                entries.push(entry(section, ChangeKind::Changed, name, &size_change(*old_size, *new_size), false))
+            }
+            Some(_) => {}
+        }
+    }
+    for (name, (size, _)) in b.iter().filter(|(n, _)| !a.contains_key(*n)) {
+        entries.push(entry(section, ChangeKind::Added, name, &format_size(*size), false));
+    }
+    entries
+}
+
+fn by_name(list: &Value) -> BTreeMap<String, Value> {
+    list.as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|item| Some((item["name"].as_str()?.to_string(), item.clone())))
+        .collect()
+}
+
+fn entry(section: &'static str, change: ChangeKind, name: &str, detail: &str, breaking: bool) -> DiffEntry {
+    DiffEntry {
+        section,
+        change,
+        name: name.to_string(),
+        detail: detail.to_string(),
+        breaking,
+    }
+}
+
+fn describe_changes(changes: &[Change]) -> String {
+    changes.iter().map(|c| c.to_string()).collect::<Vec<_>>().join("; ")
+}
+
+fn format_size(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.2} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    }
+}
+
+fn size_change(old: u64, new: u64) -> String {
+    let delta = new as f64 - old as f64;
+    let percent = if old > 0 { delta / old as f64 * 100.0 } else { 0.0 };
+    format!("{} -> {} ({:+.1}%)", format_size(old), format_size(new), percent)
+}
+
+fn print_human(report: &PackageDiff) {
+    println!(
+        "{} {} ({}) -> {} ({})",
+        "📦 Comparing".green().bold(),
+        report.old,
+        report.old_version,
+        report.new,
+        report.new_version
+    );
+    for section in ["commands", "metrics", "manifest", "binaries", "models", "frontend"] {
+        let entries: Vec<&DiffEntry> = report.entries.iter().filter(|e| e.section == section).collect();
+        if entries.is_empty() {
+            continue;
+        }
+        println!("\n{}", section.cyan().bold());
+        for e in entries {
+            let mark = match e.change {
+                ChangeKind::Added => "+".green(),
+                ChangeKind::Removed => "-".red(),
+                ChangeKind::Changed => "~".yellow(),
+            };
+            let breaking = if e.breaking { format!(" {}", "BREAKING".red().bold()) } else { String::new() };
+            println!("  {} {:<28} {}{}", mark, e.name, e.detail, breaking);
+        }
+    }
+    for note in &report.notes {
+        println!("\n{} {}", "Note:".yellow(), note);
+    }
+    println!();
+    if report.entries.is_empty() {
+        println!("{}", "No differences".green().bold());
+    } else if report.breaking > 0 {
+        println!("{}", format!("⚠ {} breaking change(s)", report.breaking).red().bold());
+    } else {
+        println!("{}", format!("{} change(s), none breaking", report.entries.len()).green().bold());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    fn write_nep(path: &Path, version: &str, files: &[(&str, &[u8])]) {
+        let manifest = serde_json::to_vec(&json!({
+            "format": "neomind-extension-package",
+            "id": "demo",
+            "version": version,
+            "binaries": { "linux_amd64": "binaries/linux_amd64/extension.so" },
+        }))
+        .unwrap();
+        let mut zip = zip::ZipWriter::new(fs::File::create(path).unwrap());
+        zip.start_file("manifest.json", SimpleFileOptions::default()).unwrap();
+        zip.write_all(&manifest).unwrap();
+        for (name, data) in files {
+            zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(data).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    fn find<'a>(entries: &'a [DiffEntry], section: &str, name: &str) -> &'a DiffEntry {
+        entries
+            .iter()
+            .find(|e| e.section == section && e.name == name)
+            .unwrap_or_else(|| panic!("no {} entry for {}", section, name))
+    }
+
+    #[test]
+    fn test_breaking_command_and_metric_changes() {
+        let old = json!({
+            "commands": [
+                {"name": "detect", "parameters": [{"name": "image", "param_type": "string", "required": true},
+                                                  {"name": "threshold", "param_type": "float"}]},
+                {"name": "reset", "parameters": []},
+            ],
+            "metrics": [{"name": "fps", "data_type": "float", "unit": "fps"}],
+        });
+        let new = json!({
+            "commands": [
+                {"name": "detect", "parameters": [{"name": "image", "param_type": "string", "required": true},
+                                                  {"name": "threshold", "param_type": "integer"},
+                                                  {"name": "roi", "param_type": "string", "required": true}]},
+                {"name": "get_status", "parameters": []},
+            ],
+            "metrics": [{"name": "fps", "data_type": "float", "unit": "frames/s"}],
+        });
+        let entries = diff_descriptors(&old, &new);
+
+        assert!(find(&entries, "commands", "reset").breaking);
+        assert!(find(&entries, "commands", "detect(threshold)").breaking);
+        assert!(find(&entries, "commands", "detect(roi)").breaking);
+        assert!(!find(&entries, "commands", "get_status").breaking);
+        let fps = find(&entries, "metrics", "fps");
+        assert!(!fps.breaking);
+        assert_eq!(fps.detail, "~ /unit: \"fps\" -> \"frames/s\"");
+        assert_eq!(entries.iter().filter(|e| e.breaking).count(), 3);
+    }
+
+    #[test]
+    fn test_archive_sections() {
+        let dir = tempfile::tempdir().unwrap();
+        let (old_path, new_path) = (dir.path().join("old.nep"), dir.path().join("new.nep"));
+        write_nep(
+            &old_path,
+            "2.6.0",
+            &[
+                ("binaries/linux_amd64/extension.so", &[0u8; 2048]),
+                ("models/yolov8n.onnx", b"v8"),
+                ("frontend/index.js", b"a"),
+            ],
+        );
+        write_nep(
+            &new_path,
+            "2.7.0",
+            &[
+                ("binaries/linux_amd64/extension.so", &[0u8; 4096]),
+                ("models/yolo11n.onnx", b"v11"),
+                ("frontend/index.js", b"b"),
+            ],
+        );
+        let mut notes = Vec::new();
+        let scratch = dir.path().join("scratch");
+        let old = load_surface(&old_path, &scratch, false, &mut notes).unwrap();
+        let new = load_surface(&new_path, &scratch, false, &mut notes).unwrap();
+
+        assert_eq!(find(&diff_manifests(&old.manifest, &new.manifest), "manifest", "version").detail, "\"2.6.0\" -> \"2.7.0\"");
+        assert_eq!(find(&diff_binaries(&old, &new), "binaries", "linux_amd64").detail, "2.0 KB -> 4.0 KB (+100.0%)");
+        let models = diff_files("models", "models/", &old.files, &new.files);
+        assert_eq!(find(&models, "models", "yolov8n.onnx").change, ChangeKind::Removed);
+        assert_eq!(find(&models, "models", "yolo11n.onnx").change, ChangeKind::Added);
+        let frontend = diff_files("frontend", "frontend/", &old.files, &new.files);
+        assert_eq!(find(&frontend, "frontend", "index.js").change, ChangeKind::Changed);
+        assert!(notes.is_empty());
+    }
+}
@@ -0,0 +1,246 @@
+//! `neomind-ext doctor` - report the native prerequisites the vision and
+//! streaming extensions only discover at their first inference or frame.
+//!
+//! Libraries are resolved the way `setup_native_lib_paths` resolves them (see
+//! [`crate::utils::native`]); `--ext-dir` stands in for `NEOMIND_EXTENSION_DIR`
+//! so an installed extension's bundled `binaries/<platform>/` can be checked.
+
+use anyhow::Result;
+use colored::Colorize;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::commands::{models, package};
+use crate::utils::cargo;
+use crate::utils::exit::{fail, FailureClass};
+use crate::utils::models::ModelsManifest;
+use crate::utils::native::{self, Resolved, FFMPEG_LIBS, ORT_API_VERSION};
+use crate::utils::report::{CheckStatus, Checks};
+
+/// Font the repo ships in `fonts/`, looked up again in the usual system locations.
+const FONT_FILE: &str = "DejaVuSans.ttf";
+const SYSTEM_FONT_DIRS: [&str; 4] = [
+    "/usr/share/fonts/truetype/dejavu",
+    "/usr/share/fonts/dejavu",
+    "/Library/Fonts",
+    "C:\\Windows\\Fonts",
+];
+
+pub struct DoctorOptions {
+    /// Installed extension directory to resolve bundled libraries from
+    pub ext_dir: Option<String>,
+    pub json: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DoctorReport {
+    pub search_path: Vec<String>,
+    pub checks: Checks,
+}
+
+pub fn cmd_doctor(opts: DoctorOptions) -> Result<()> {
+    let ext_dir = match opts.ext_dir {
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => std::env::var_os("NEOMIND_EXTENSION_DIR").map(PathBuf::from),
+    };
+    let repo_root = crate::get_repo_root().ok();
+    let report = diagnose(ext_dir.as_deref(), repo_root.as_deref())?;
+
+    if opts.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("{}", "🩺 Checking native runtime prerequisites".green().bold());
+        println!("{}: {}", "Search path".cyan(), report.search_path.join(", "));
+        report.checks.print();
+        println!();
+    }
+
+    if report.checks.has_failures() {
+        return Err(fail(FailureClass::MissingFile, format!("Missing prerequisites: {}", report.checks.failed_names().join(", "))));
+    }
+    if !opts.json {
+        println!("{}", "✅ Native runtime prerequisites found".green().bold());
+    }
+    Ok(())
+}
+
+pub fn diagnose(ext_dir: Option<&Path>, repo_root: Option<&Path>) -> Result<DoctorReport> {
+    let dirs = native::search_dirs(ext_dir);
+    let mut checks = Checks::default();
+
+    check_ort(&mut checks, &dirs);
+    for (name, major) in FFMPEG_LIBS {
+        let versioned = native::ffmpeg_file_name(name, Some(major));
+        let unversioned = native::ffmpeg_file_name(name, None);
+        match native::resolve(&dirs, &[&versioned, &unversioned]) {
+            Some(resolved) => checks.pass(&format!("ffmpeg:{}", name), resolved.describe()),
+            None => checks.fail(
+                &format!("ffmpeg:{}", name),
+                format!("{} not found (needed by stream-player and yolo-video-v2)", versioned),
+            ),
+        }
+    }
+    check_font(&mut checks, repo_root);
+    if let Some(root) = repo_root {
+        check_models(&mut checks, root)?;
+    }
+
+    Ok(DoctorReport {
+        search_path: dirs.iter().map(|d| d.display().to_string()).collect(),
+        checks,
+    })
+}
+
+fn check_ort(checks: &mut Checks, dirs: &[PathBuf]) {
+    let Some(resolved) = native::resolve_ort(dirs) else {
+        checks.fail("onnxruntime", format!("{} not found; set ORT_DYLIB_PATH", native::ort_file_name()));
+        return;
+    };
+    match native::probe_ort(&resolved) {
+        Ok(probe) if probe.supports_api => checks.pass(
+            "onnxruntime",
+            format!("{} {} (API {})", resolved.describe(), probe.version, ORT_API_VERSION),
+        ),
+        Ok(probe) => checks.fail(
+            "onnxruntime",
+            format!(
+                "{} is ORT {}, which does not provide API {} (need ONNX Runtime 1.22+)",
+                resolved.describe(),
+                probe.version,
+                ORT_API_VERSION
+            ),
+        ),
+        Err(e) if matches!(resolved, Resolved::Env(_)) => {
+            checks.fail("onnxruntime", format!("ORT_DYLIB_PATH is set but unusable: {:#}", e))
+        }
+        Err(e) => checks.fail("onnxruntime", format!("{:#}", e)),
+    }
+}
+
+fn check_font(checks: &mut Checks, repo_root: Option<&Path>) {
+    let candidates = repo_root
+        .map(|root| root.join("fonts"))
+        .into_iter()
+        .chain(SYSTEM_FONT_DIRS.iter().map(PathBuf::from))
+        .map(|dir| dir.join(FONT_FILE));
+    let found: Vec<String> = candidates.filter(|p| p.is_file()).map(|p| p.display().to_string()).collect();
+    checks.expect(
+        "font",
+        !found.is_empty(),
+        found.join(", "),
+        format!("{} not found in fonts/ or the system font directories", FONT_FILE),
+    );
+}
+
+/// Each workspace member that declares a `models.toml` or depends on ONNX Runtime.
+fn check_models(checks: &mut Checks, repo_root: &Path) -> Result<()> {
+    for ext_dir in cargo::workspace_members(repo_root)? {
+        if !ext_dir.join("Cargo.toml").is_file() {
+            continue;
+        }
+        let name = format!("models:{}", package::dir_name(&ext_dir)?);
+        if let Some(manifest) = ModelsManifest::load_optional(&ext_dir)? {
+            let model_checks = models::verify(&ext_dir, &manifest)?;
+            let problems: Vec<String> = model_checks
+                .iter()
+                .filter(|c| c.status != CheckStatus::Pass)
+                .map(|c| format!("{} {}", c.name, c.message))
+                .collect();
+            let status = if model_checks.has_failures() {
+                CheckStatus::Fail
+            } else if problems.is_empty() {
+                CheckStatus::Pass
+            } else {
+                CheckStatus::Warn
+            };
+            let message = if problems.is_empty() {
+                format!("{} declared models verified", manifest.models.len())
+            } else {
+                problems.join("; ")
+            };
+            checks.record(&name, status, message);
+        } else if cargo::depends_on(&ext_dir, "ort")? {
+            let onnx = onnx_files(&ext_dir.join("models"))?;
+            checks.expect(
+                &name,
+                !onnx.is_empty(),
+                onnx.join(", "),
+                "no .onnx files in models/ and no models.toml to fetch them from",
+            );
+        }
+    }
+    Ok(())
+}
+
+fn onnx_files(models_dir: &Path) -> Result<Vec<String>> {
+    if !models_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut files = Vec::new();
+    for entry in fs::read_dir(models_dir)? {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) == Some("onnx") {
+            files.push(package::dir_name(&path)?);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_repo(root: &Path) {
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"extensions/detector\", \"extensions/weather\"]\n",
+        )
+        .unwrap();
+        let detector = root.join("extensions/detector");
+        fs::create_dir_all(detector.join("models")).unwrap();
+        fs::write(
+            detector.join("Cargo.toml"),
+            "[package]\nname = \"detector\"\n\n[target.'cfg(not(target_arch = \"wasm32\"))'.dependencies]\nort = \"2\"\n",
+        )
+        .unwrap();
+        let weather = root.join("extensions/weather");
+        fs::create_dir_all(&weather).unwrap();
+        fs::write(weather.join("Cargo.toml"), "[package]\nname = \"weather\"\n").unwrap();
+        fs::create_dir_all(root.join("fonts")).unwrap();
+        fs::write(root.join("fonts").join(FONT_FILE), "").unwrap();
+    }
+
+    fn status(report: &DoctorReport, name: &str) -> Option<CheckStatus> {
+        report.checks.iter().find(|c| c.name == name).map(|c| c.status)
+    }
+
+    #[test]
+    fn test_models_reported_for_onnx_extensions_only() {
+        let dir = tempfile::tempdir().unwrap();
+        write_repo(dir.path());
+
+        let report = diagnose(None, Some(dir.path())).unwrap();
+        assert_eq!(status(&report, "models:detector"), Some(CheckStatus::Fail));
+        assert_eq!(status(&report, "models:weather"), None);
+        assert_eq!(status(&report, "font"), Some(CheckStatus::Pass));
+
+        fs::write(dir.path().join("extensions/detector/models/yolo11n.onnx"), "").unwrap();
+        let report = diagnose(None, Some(dir.path())).unwrap();
+        assert_eq!(status(&report, "models:detector"), Some(CheckStatus::Pass));
+    }
+
+    #[test]
+    fn test_bundled_non_ort_library_fails_probe() {
+        let dir = tempfile::tempdir().unwrap();
+        let platform_dir = dir.path().join("binaries").join("linux_amd64");
+        fs::create_dir_all(&platform_dir).unwrap();
+        fs::write(platform_dir.join(native::ort_file_name()), "not a library").unwrap();
+
+        let mut checks = Checks::default();
+        check_ort(&mut checks, &native::search_dirs(Some(dir.path())));
+        let check = checks.iter().next().unwrap();
+        assert_eq!(check.status, CheckStatus::Fail);
+    }
+}
@@ -0,0 +1,281 @@
+//! `neomind-ext index` - regenerate extensions/index.json from the workspace
+//! members' metadata.json and the repo-wide VERSION file.
+//!
+//! Replaces the index half of scripts/update-versions.sh. Entries are the
+//! member's metadata.json with `builds` recomputed for the release layout and
+//! `metadata_url` appended. Any drift between metadata.json and its sources
+//! (see `lint::lint_metadata_sources`) fails the command before anything is
+//! written; `--check` additionally fails when index.json is out of date.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::commands::lint::{self, RepoContext};
+use crate::utils::cargo;
+use crate::utils::manifest::ExtensionMetadata;
+use crate::utils::market;
+use crate::utils::report::{CheckStatus, Checks};
+
+pub struct IndexOptions {
+    /// Compare with the existing index.json instead of writing it
+    pub check: bool,
+}
+
+/// Generated index plus the drift checks of every extension that has any.
+pub struct GeneratedIndex {
+    pub index: Value,
+    pub drift: Vec<(String, Checks)>,
+}
+
+pub fn cmd_index(opts: IndexOptions) -> Result<()> {
+    let repo_root = crate::get_repo_root()?;
+    let index_path = repo_root.join("extensions").join("index.json");
+
+    println!("{}", "🗂  Generating marketplace index".green().bold());
+    let generated = generate(&repo_root)?;
+
+    for (ext_id, checks) in &generated.drift {
+        println!("{}: {}", "Drift".red(), ext_id.bold());
+        checks.print();
+    }
+    if !generated.drift.is_empty() {
+        let ids: Vec<&str> = generated.drift.iter().map(|(id, _)| id.as_str()).collect();
+        anyhow::bail!("metadata.json is out of sync for: {}", ids.join(", "));
+    }
+
+    let count = generated.index["extensions"].as_array().map_or(0, |e| e.len());
+    if opts.check {
+        let existing = load_index(&index_path)?;
+        let differences = diff_index(&existing, &generated.index);
+        if !differences.is_empty() {
+            for difference in &differences {
+                println!("  {} {}", "✗".red(), difference);
+            }
+            anyhow::bail!(
+                "{} is out of date; run `neomind-ext index` to regenerate it",
+                index_path.display()
+            );
+        }
+        println!("{} {} extension(s) up to date", "✅".green(), count);
+        return Ok(());
+    }
+
+    let content = serde_json::to_string_pretty(&generated.index)? + "\n";
+    fs::write(&index_path, content).with_context(|| format!("Failed to write {}", index_path.display()))?;
+    println!("{} Wrote {} extension(s) to {}", "✅".green(), count, index_path.display());
+    Ok(())
+}
+
+/// Build the index for the workspace rooted at `repo_root`.
+pub fn generate(repo_root: &Path) -> Result<GeneratedIndex> {
+    let market_version = market::market_version(repo_root)?;
+    let repo = RepoContext {
+        market_version: Some(market_version.clone()),
+        index: None,
+    };
+
+    let mut members: Vec<PathBuf> = cargo::workspace_members(repo_root)?
+        .into_iter()
+        .filter(|dir| dir.parent().and_then(|p| p.file_name()) == Some("extensions".as_ref()))
+        .collect();
+    members.sort();
+
+    let mut entries = Vec::new();
+    let mut drift = Vec::new();
+    for ext_dir in &members {
+        let dir_name = ext_dir
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut checks = Checks::default();
+
+        let metadata_path = ext_dir.join("metadata.json");
+        if !metadata_path.exists() {
+            checks.fail("metadata.json", "workspace member has no metadata.json");
+            drift.push((dir_name, checks));
+            continue;
+        }
+        let content = fs::read_to_string(&metadata_path)
+            .with_context(|| format!("Failed to read {}", metadata_path.display()))?;
+        let raw: Map<String, Value> = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", metadata_path.display()))?;
+        let metadata: ExtensionMetadata = serde_json::from_value(Value::Object(raw.clone()))
+            .with_context(|| format!("Invalid {}", metadata_path.display()))?;
+
+        lint::lint_metadata_sources(&mut checks, ext_dir, &metadata, &repo)?;
+        if checks.has_failures() {
+            drift.push((metadata.id.clone(), only_problems(checks)));
+        }
+        entries.push(index_entry(raw, &metadata, &market_version));
+    }
+
+    Ok(GeneratedIndex {
+        index: json!({
+            "version": market_version,
+            "market_version": market_version,
+            "extensions": entries,
+        }),
+        drift,
+    })
+}
+
+/// metadata.json with `builds` recomputed and `metadata_url` appended.
+fn index_entry(mut raw: Map<String, Value>, metadata: &ExtensionMetadata, market_version: &str) -> Value {
+    let builds: Map<String, Value> = market::build_urls(market_version, &metadata.id, &metadata.version)
+        .into_iter()
+        .map(|(key, url)| (key.to_string(), json!({ "url": url })))
+        .collect();
+    raw.insert("builds".to_string(), Value::Object(builds));
+    raw.insert("metadata_url".to_string(), json!(market::metadata_url(&metadata.id)));
+    Value::Object(raw)
+}
+
+fn only_problems(checks: Checks) -> Checks {
+    let mut problems = Checks::default();
+    for check in checks.iter().filter(|c| c.status != CheckStatus::Pass) {
+        problems.record(&check.name, check.status, check.message.clone());
+    }
+    problems
+}
+
+fn load_index(path: &Path) -> Result<Value> {
+    let content = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+/// Human-readable differences between the committed and the generated index.
+fn diff_index(existing: &Value, generated: &Value) -> Vec<String> {
+    let mut differences = Vec::new();
+    for field in ["version", "market_version"] {
+        if existing[field] != generated[field] {
+            differences.push(format!("{}: {} -> {}", field, existing[field], generated[field]));
+        }
+    }
+
+    let entries = |index: &Value| -> Vec<Value> { index["extensions"].as_array().cloned().unwrap_or_default() };
+    let existing_entries = entries(existing);
+    let generated_entries = entries(generated);
+    let find = |list: &[Value], id: &Value| list.iter().find(|e| e["id"] == *id).cloned();
+    let id_of = |entry: &Value| entry["id"].as_str().unwrap_or_default().to_string();
+
+    for entry in &generated_entries {
+        match find(&existing_entries, &entry["id"]) {
+            None => differences.push(format!("{}: missing from index.json", id_of(entry))),
+            Some(old) if old != *entry => {
+                let fields: Vec<&str> = entry
+                    .as_object()
+                    .into_iter()
+                    .flatten()
+                    .filter(|(key, value)| old.get(key.as_str()) != Some(value))
+                    .map(|(key, _)| key.as_str())
+                    .chain(
+                        old.as_object()
+                            .into_iter()
+                            .flatten()
+                            .filter(|(key, _)| entry.get(key.as_str()).is_none())
+                            .map(|(key, _)| key.as_str()),
+                    )
+                    .collect();
+                differences.push(format!("{}: stale {}", id_of(entry), fields.join(", ")));
+            }
+            Some(_) => {}
+        }
+    }
+    for entry in &existing_entries {
+        if find(&generated_entries, &entry["id"]).is_none() {
+            differences.push(format!("{}: not a workspace member", id_of(entry)));
+        }
+    }
+    differences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_repo(root: &Path, ext_version: &str, metadata_version: &str) {
+        fs::write(root.join("VERSION"), "2.7.0\n").unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\n    \"extensions/demo\",\n    # \"extensions/old\",\n]\n",
+        )
+        .unwrap();
+        let ext_dir = root.join("extensions").join("demo");
+        fs::create_dir_all(&ext_dir).unwrap();
+        fs::write(
+            ext_dir.join("Cargo.toml"),
+            format!("[package]\nname = \"demo\"\nversion = \"{}\"\n", ext_version),
+        )
+        .unwrap();
+        let builds: Map<String, Value> = market::build_urls("2.7.0", "demo", metadata_version)
+            .into_iter()
+            .map(|(key, url)| (key.to_string(), json!({ "url": url })))
+            .collect();
+        fs::write(
+            ext_dir.join("metadata.json"),
+            json!({
+                "id": "demo",
+                "name": "demo",
+                "version": metadata_version,
+                "categories": ["utility"],
+                "builds": builds,
+            })
+            .to_string(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_generate_builds_entries_from_metadata() {
+        let dir = tempfile::tempdir().unwrap();
+        write_repo(dir.path(), "1.0.0", "1.0.0");
+
+        let generated = generate(dir.path()).unwrap();
+        assert!(generated.drift.is_empty());
+        assert_eq!(generated.index["market_version"], "2.7.0");
+        let entry = &generated.index["extensions"][0];
+        let keys: Vec<&str> = entry.as_object().unwrap().keys().map(String::as_str).collect();
+        assert_eq!(keys, ["id", "name", "version", "categories", "builds", "metadata_url"]);
+        assert_eq!(
+            entry["builds"]["linux-aarch64"]["url"],
+            "https://github.com/camthink-ai/NeoMind-Extensions/releases/download/v2.7.0/demo-1.0.0-linux_arm64.nep"
+        );
+    }
+
+    #[test]
+    fn test_version_mismatch_is_drift() {
+        let dir = tempfile::tempdir().unwrap();
+        write_repo(dir.path(), "1.1.0", "1.0.0");
+
+        let generated = generate(dir.path()).unwrap();
+        assert_eq!(generated.drift.len(), 1);
+        let (id, checks) = &generated.drift[0];
+        assert_eq!(id, "demo");
+        assert_eq!(checks.failed_names(), ["version"]);
+    }
+
+    #[test]
+    fn test_diff_index_reports_stale_and_missing_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        write_repo(dir.path(), "1.0.0", "1.0.0");
+        let generated = generate(dir.path()).unwrap().index;
+        assert!(diff_index(&generated, &generated).is_empty());
+
+        let mut stale = generated.clone();
+        stale["extensions"][0]["builds"]
+            .as_object_mut()
+            .unwrap()
+            .remove("windows-x86_64");
+        stale["extensions"]
+            .as_array_mut()
+            .unwrap()
+            .push(json!({ "id": "removed" }));
+        assert_eq!(
+            diff_index(&stale, &generated),
+            ["demo: stale builds", "removed: not a workspace member"]
+        );
+    }
+}
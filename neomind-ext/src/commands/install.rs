@@ -0,0 +1,460 @@
+//! `neomind-ext install` / `uninstall` - manage extensions in a local NeoMind
+//! extensions directory (the runtime's `data/extensions/`).
+//!
+//! Each extension is unpacked to `<into>/<id>/`, which the runtime exposes to
+//! the extension as `NEOMIND_EXTENSION_DIR`. Besides the package contents that
+//! directory holds state the extension writes itself (config.json,
+//! faces.json, downloaded models). An install receipt (`.neomind-install.json`)
+//! records which files came from the package, so everything else is treated
+//! as state: it is carried over on upgrade and rollback and kept by
+//! `uninstall --keep-data`. The replaced version is parked in
+//! `<into>/.neomind-ext/previous/<id>/` for `install --rollback <id>`.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::commands::{package, validate};
+use crate::utils::nep::NepPackage;
+use crate::utils::report::CheckStatus;
+
+/// Install receipt, stored inside the installed extension directory
+const RECEIPT_FILE: &str = ".neomind-install.json";
+
+/// CLI bookkeeping under the extensions directory; not an extension itself
+const CLI_DIR: &str = ".neomind-ext";
+
+/// Package-owned paths when an install has no receipt (unpacked by hand)
+const PACKAGE_FILES: [&str; 3] = ["manifest.json", "frontend.json", "signature.json"];
+const PACKAGE_DIRS: [&str; 2] = ["binaries/", "frontend/"];
+
+pub struct InstallOptions {
+    /// .nep file or extension directory; `None` with `rollback`
+    pub source: Option<String>,
+    pub into: String,
+    /// Extension ID to roll back to its previous version
+    pub rollback: Option<String>,
+}
+
+pub struct UninstallOptions {
+    pub id: String,
+    pub into: String,
+    pub keep_data: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Receipt {
+    id: String,
+    version: String,
+    /// File name of the installed .nep
+    package: String,
+    sha256: String,
+    /// Unix seconds
+    installed_at: u64,
+    /// Files unpacked from the package, relative to the extension directory
+    files: Vec<String>,
+}
+
+impl Receipt {
+    fn load(ext_dir: &Path) -> Option<Self> {
+        let content = fs::read_to_string(ext_dir.join(RECEIPT_FILE)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+}
+
+pub fn cmd_install(opts: InstallOptions) -> Result<()> {
+    let into = PathBuf::from(&opts.into);
+    if let Some(id) = opts.rollback {
+        return rollback(&into, &id);
+    }
+    let source = opts
+        .source
+        .ok_or_else(|| anyhow::anyhow!("Pass a .nep file or extension directory to install"))?;
+    let source = PathBuf::from(source);
+
+    let nep = if source.is_dir() {
+        // Package the release build first, like `neomind-ext package`
+        package::cmd_package(package::PackageOptions {
+            path: Some(source.display().to_string()),
+            with_frontend: false,
+            debug: false,
+            platform: None,
+            output: None,
+            json: false,
+        })?
+    } else {
+        source
+    };
+
+    println!("{}", "📥 Installing extension".green().bold());
+    let receipt = install_package(&nep, &into)?;
+    println!(
+        "\n{} Installed {} v{} into {}",
+        "✅".green(),
+        receipt.id,
+        receipt.version,
+        into.join(&receipt.id).display()
+    );
+    Ok(())
+}
+
+pub fn cmd_uninstall(opts: UninstallOptions) -> Result<()> {
+    ensure_valid_id(&opts.id)?;
+    let into = PathBuf::from(&opts.into);
+    let ext_dir = into.join(&opts.id);
+    if !ext_dir.is_dir() {
+        anyhow::bail!("{} is not installed in {}", opts.id, into.display());
+    }
+
+    println!("{}", format!("🗑  Uninstalling {}", opts.id).green().bold());
+    let kept = uninstall(&into, &opts.id, opts.keep_data)?;
+    for file in &kept {
+        println!("  {} kept {}", "•".cyan(), file);
+    }
+    println!("{} Uninstalled {}", "✅".green(), opts.id);
+    Ok(())
+}
+
+/// Validate `nep` and unpack it to `<into>/<id>/`, carrying over state files
+/// and parking the replaced version for rollback.
+fn install_package(nep: &Path, into: &Path) -> Result<Receipt> {
+    let report = validate::validate_nep_package(nep)?;
+    for check in report.checks.iter().filter(|c| c.status != CheckStatus::Pass) {
+        println!("  {} {}: {}", "⚠".yellow(), check.name, check.message);
+    }
+    if !report.valid {
+        anyhow::bail!(
+            "Refusing to install {}: {}",
+            nep.display(),
+            report.checks.failed_names().join(", ")
+        );
+    }
+    let identity = report.extension.context("Package has no extension identity")?;
+    ensure_valid_id(&identity.id)?;
+    println!("{}: {} v{}", "Package".cyan(), identity.id, identity.version);
+
+    let mut package = NepPackage::open(nep)?;
+    let files = package.file_names();
+    let receipt = Receipt {
+        id: identity.id.clone(),
+        version: identity.version.clone(),
+        package: nep.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default(),
+        sha256: report.sha256.unwrap_or_default(),
+        installed_at: SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0),
+        files,
+    };
+
+    // Unpack next to the final location so the swap is a rename
+    fs::create_dir_all(into).with_context(|| format!("Failed to create {}", into.display()))?;
+    let staging = cli_dir(into).join("staging").join(&identity.id);
+    remove_if_exists(&staging)?;
+    fs::create_dir_all(&staging)?;
+    package.extract_to(&staging)?;
+    write_receipt(&staging, &receipt)?;
+
+    let ext_dir = into.join(&identity.id);
+    if ext_dir.exists() {
+        let previous = Receipt::load(&ext_dir);
+        let carried = carry_state(&ext_dir, &staging, &receipt.files)?;
+        if !carried.is_empty() {
+            println!("{}: {}", "Kept".cyan(), carried.join(", "));
+        }
+        let slot = previous_slot(into, &identity.id);
+        remove_if_exists(&slot)?;
+        fs::create_dir_all(slot.parent().unwrap_or(into))?;
+        fs::rename(&ext_dir, &slot).with_context(|| format!("Failed to move aside {}", ext_dir.display()))?;
+        if let Some(previous) = previous {
+            println!("{}: v{} (roll back with --rollback {})", "Replaced".cyan(), previous.version, identity.id);
+        }
+    }
+    fs::rename(&staging, &ext_dir).with_context(|| format!("Failed to move into {}", ext_dir.display()))?;
+    Ok(receipt)
+}
+
+/// Swap the installed version with the one parked by the last upgrade.
+fn rollback(into: &Path, id: &str) -> Result<()> {
+    ensure_valid_id(id)?;
+    let ext_dir = into.join(id);
+    let slot = previous_slot(into, id);
+    if !slot.is_dir() {
+        anyhow::bail!("No previous version of {} to roll back to", id);
+    }
+    let restored = Receipt::load(&slot);
+    let restored_files = restored.as_ref().map(|r| r.files.clone()).unwrap_or_default();
+
+    println!("{}", format!("⏪ Rolling back {}", id).green().bold());
+    if ext_dir.exists() {
+        let carried = carry_state(&ext_dir, &slot, &restored_files)?;
+        if !carried.is_empty() {
+            println!("{}: {}", "Kept".cyan(), carried.join(", "));
+        }
+        // The current version becomes the new rollback target
+        let parked = cli_dir(into).join("staging").join(id);
+        remove_if_exists(&parked)?;
+        fs::create_dir_all(parked.parent().unwrap_or(into))?;
+        fs::rename(&ext_dir, &parked)?;
+        fs::rename(&slot, &ext_dir)?;
+        fs::rename(&parked, &slot)?;
+    } else {
+        fs::rename(&slot, &ext_dir)?;
+    }
+
+    let version = restored.map(|r| format!("v{}", r.version)).unwrap_or_else(|| "previous version".to_string());
+    println!("{} {} rolled back to {}", "✅".green(), id, version);
+    Ok(())
+}
+
+/// Remove an installed extension; returns the state files kept with `keep_data`.
+fn uninstall(into: &Path, id: &str, keep_data: bool) -> Result<Vec<String>> {
+    ensure_valid_id(id)?;
+    let ext_dir = into.join(id);
+    remove_if_exists(&previous_slot(into, id))?;
+    if !keep_data {
+        fs::remove_dir_all(&ext_dir).with_context(|| format!("Failed to remove {}", ext_dir.display()))?;
+        return Ok(Vec::new());
+    }
+
+    let state = state_files(&ext_dir)?;
+    for file in list_files(&ext_dir)? {
+        if !state.contains(&file) {
+            fs::remove_file(ext_dir.join(&file))?;
+        }
+    }
+    prune_empty_dirs(&ext_dir)?;
+    if state.is_empty() {
+        remove_if_exists(&ext_dir)?;
+    }
+    Ok(state)
+}
+
+/// Copy the state files of `from` into `to`, skipping paths in `package_files`.
+fn carry_state(from: &Path, to: &Path, package_files: &[String]) -> Result<Vec<String>> {
+    let mut carried = Vec::new();
+    for file in state_files(from)? {
+        if package_files.contains(&file) {
+            continue;
+        }
+        let target = to.join(&file);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(from.join(&file), &target).with_context(|| format!("Failed to keep {}", file))?;
+        carried.push(file);
+    }
+    Ok(carried)
+}
+
+/// Files in an installed extension that did not come from its package.
+fn state_files(ext_dir: &Path) -> Result<Vec<String>> {
+    let receipt = Receipt::load(ext_dir);
+    Ok(list_files(ext_dir)?
+        .into_iter()
+        .filter(|f| f != RECEIPT_FILE)
+        .filter(|f| match &receipt {
+            Some(receipt) => !receipt.files.contains(f),
+            None => !PACKAGE_FILES.contains(&f.as_str()) && !PACKAGE_DIRS.iter().any(|d| f.starts_with(d)),
+        })
+        .collect())
+}
+
+/// Every file under `dir`, as sorted `/`-separated relative paths.
+fn list_files(dir: &Path) -> Result<Vec<String>> {
+    fn walk(root: &Path, dir: &Path, files: &mut Vec<String>) -> Result<()> {
+        for entry in fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+            let path = entry?.path();
+            if path.is_dir() {
+                walk(root, &path, files)?;
+            } else if let Ok(relative) = path.strip_prefix(root) {
+                let parts: Vec<String> = relative.components().map(|c| c.as_os_str().to_string_lossy().into_owned()).collect();
+                files.push(parts.join("/"));
+            }
+        }
+        Ok(())
+    }
+    let mut files = Vec::new();
+    walk(dir, dir, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+fn prune_empty_dirs(dir: &Path) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            prune_empty_dirs(&path)?;
+            if fs::read_dir(&path)?.next().is_none() {
+                fs::remove_dir(&path)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_receipt(ext_dir: &Path, receipt: &Receipt) -> Result<()> {
+    let content = serde_json::to_string_pretty(receipt)? + "\n";
+    fs::write(ext_dir.join(RECEIPT_FILE), content).context("Failed to write install receipt")
+}
+
+/// Extension IDs are joined onto the extensions directory; refuse anything
+/// that could name a path outside it.
+fn ensure_valid_id(id: &str) -> Result<()> {
+    if !validate::is_valid_extension_id(id) {
+        anyhow::bail!("Invalid extension id '{}': only a-z, 0-9, '-' and '_' are allowed", id);
+    }
+    Ok(())
+}
+
+fn cli_dir(into: &Path) -> PathBuf {
+    into.join(CLI_DIR)
+}
+
+fn previous_slot(into: &Path, id: &str) -> PathBuf {
+    cli_dir(into).join("previous").join(id)
+}
+
+fn remove_if_exists(path: &Path) -> Result<()> {
+    if path.exists() {
+        fs::remove_dir_all(path).with_context(|| format!("Failed to remove {}", path.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::checksum;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    /// A valid package for `demo` whose binary contents are `binary`
+    fn write_nep(path: &Path, version: &str, binary: &[u8]) {
+        write_nep_with_id(path, "demo", version, binary);
+    }
+
+    fn write_nep_with_id(path: &Path, id: &str, version: &str, binary: &[u8]) {
+        let manifest = serde_json::to_vec(&serde_json::json!({
+            "format": "neomind-extension-package",
+            "format_version": "2.0",
+            "id": id,
+            "name": "demo",
+            "version": version,
+            "binaries": { "linux_amd64": "binaries/linux_amd64/extension.so" },
+            "checksums": { "binaries/linux_amd64/extension.so": checksum::sha256_hex(binary) }
+        }))
+        .unwrap();
+        let mut zip = zip::ZipWriter::new(fs::File::create(path).unwrap());
+        for (name, data) in [("manifest.json", manifest.as_slice()), ("binaries/linux_amd64/extension.so", binary)] {
+            zip.start_file(name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(data).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn test_upgrade_keeps_state_and_rolls_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let into = dir.path().join("extensions");
+        let (v1, v2) = (dir.path().join("demo-1.nep"), dir.path().join("demo-2.nep"));
+        write_nep(&v1, "1.0.0", b"v1");
+        write_nep(&v2, "2.0.0", b"v2");
+
+        install_package(&v1, &into).unwrap();
+        let ext_dir = into.join("demo");
+        fs::write(ext_dir.join("config.json"), "{\"bindings\": []}").unwrap();
+        fs::create_dir_all(ext_dir.join("models")).unwrap();
+        fs::write(ext_dir.join("models").join("yolo.onnx"), "weights").unwrap();
+
+        install_package(&v2, &into).unwrap();
+        assert_eq!(fs::read(ext_dir.join("binaries/linux_amd64/extension.so")).unwrap(), b"v2");
+        assert!(ext_dir.join("config.json").exists());
+        assert!(ext_dir.join("models").join("yolo.onnx").exists());
+        assert_eq!(Receipt::load(&ext_dir).unwrap().version, "2.0.0");
+
+        // State written after the upgrade survives the rollback
+        fs::write(ext_dir.join("faces.json"), "[]").unwrap();
+        rollback(&into, "demo").unwrap();
+        assert_eq!(fs::read(ext_dir.join("binaries/linux_amd64/extension.so")).unwrap(), b"v1");
+        assert!(ext_dir.join("faces.json").exists());
+        assert_eq!(Receipt::load(&ext_dir).unwrap().version, "1.0.0");
+
+        // Rolling back again returns to 2.0.0
+        rollback(&into, "demo").unwrap();
+        assert_eq!(Receipt::load(&ext_dir).unwrap().version, "2.0.0");
+    }
+
+    #[test]
+    fn test_corrupt_package_is_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let nep = dir.path().join("demo.nep");
+        write_nep(&nep, "1.0.0", b"v1");
+        // Rewrite the binary without updating its checksum
+        let mut package = NepPackage::open(&nep).unwrap();
+        let manifest = package.read("manifest.json").unwrap();
+        let mut zip = zip::ZipWriter::new(fs::File::create(&nep).unwrap());
+        for (name, data) in [("manifest.json", manifest.as_slice()), ("binaries/linux_amd64/extension.so", b"evil".as_slice())] {
+            zip.start_file(name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(data).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let into = dir.path().join("extensions");
+        let err = install_package(&nep, &into).unwrap_err();
+        assert!(err.to_string().contains("checksums"), "{}", err);
+        assert!(!into.join("demo").exists());
+    }
+
+    #[test]
+    fn test_traversal_id_is_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let into = dir.path().join("data").join("extensions");
+        let victim = dir.path().join("home");
+        fs::create_dir_all(victim.join("user")).unwrap();
+        fs::write(victim.join("user").join("notes.txt"), "keep me").unwrap();
+
+        let nep = dir.path().join("evil.nep");
+        write_nep_with_id(&nep, "../../home/user", "1.0.0", b"v1");
+        let report = validate::validate_nep_package(&nep).unwrap();
+        assert!(!report.valid && report.extension.is_none());
+        assert!(install_package(&nep, &into).is_err());
+        assert!(uninstall(&into, "../../home/user", false).is_err());
+        assert!(rollback(&into, "../../home/user").is_err());
+        assert!(victim.join("user").join("notes.txt").exists());
+        assert!(!into.join(CLI_DIR).exists());
+
+        for id in ["", ".", "..", "a/b", "a\\b", "Demo"] {
+            assert!(!validate::is_valid_extension_id(id), "{id}");
+        }
+        assert!(validate::is_valid_extension_id("yolo-device_inference2"));
+    }
+
+    #[test]
+    fn test_uninstall_keep_data() {
+        let dir = tempfile::tempdir().unwrap();
+        let into = dir.path().join("extensions");
+        let nep = dir.path().join("demo.nep");
+        write_nep(&nep, "1.0.0", b"v1");
+        install_package(&nep, &into).unwrap();
+        fs::write(into.join("demo").join("config.json"), "{}").unwrap();
+
+        let kept = uninstall(&into, "demo", true).unwrap();
+        assert_eq!(kept, vec!["config.json"]);
+        assert_eq!(list_files(&into.join("demo")).unwrap(), vec!["config.json"]);
+
+        uninstall(&into, "demo", false).unwrap();
+        assert!(!into.join("demo").exists());
+    }
+
+    #[test]
+    fn test_state_files_without_receipt() {
+        let dir = tempfile::tempdir().unwrap();
+        for file in ["manifest.json", "binaries/linux_amd64/extension.so", "frontend/a.js", "faces.json"] {
+            let path = dir.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "x").unwrap();
+        }
+        assert_eq!(state_files(dir.path()).unwrap(), vec!["faces.json"]);
+    }
+}
@@ -0,0 +1,600 @@
+//! `neomind-ext lint` - cross-check an extension's runtime descriptor against
+//! metadata.json, manifest.json, frontend/frontend.json and extensions/index.json.
+//!
+//! The runtime side comes from loading the built cdylib and calling
+//! `neomind_extension_descriptor_json` (metadata(), commands() and metrics()).
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::utils::cargo::CrateInfo;
+use crate::utils::exit::{fail, FailureClass};
+use crate::utils::ffi::{self, ExtensionLibrary};
+use crate::utils::manifest::{self, ExtensionMetadata, FrontendMetadata};
+use crate::utils::market;
+use crate::utils::report::Checks;
+
+pub struct LintOptions {
+    pub path: Option<String>,
+    pub all: bool,
+    pub lib: Option<String>,
+    pub no_runtime: bool,
+    pub json: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LintReport {
+    pub extension: String,
+    pub path: String,
+    pub checks: Checks,
+}
+
+/// Repo-level inputs shared by every linted extension.
+pub struct RepoContext {
+    pub market_version: Option<String>,
+    pub index: Option<Vec<Value>>,
+}
+
+impl RepoContext {
+    pub fn load(repo_root: Option<&Path>) -> Result<Self> {
+        let Some(root) = repo_root else {
+            return Ok(Self {
+                market_version: None,
+                index: None,
+            });
+        };
+        let index_path = root.join("extensions").join("index.json");
+        let index = if index_path.exists() {
+            let content = fs::read_to_string(&index_path)
+                .with_context(|| format!("Failed to read {}", index_path.display()))?;
+            let index: Value = serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", index_path.display()))?;
+            Some(index["extensions"].as_array().cloned().unwrap_or_default())
+        } else {
+            None
+        };
+        Ok(Self {
+            market_version: market::market_version(root).ok(),
+            index,
+        })
+    }
+}
+
+pub fn cmd_lint(opts: LintOptions) -> Result<()> {
+    let ext_dirs = if opts.all {
+        let repo_root = crate::get_repo_root()?;
+        extension_dirs(&repo_root.join("extensions"))?
+    } else {
+        vec![crate::get_extension_path(opts.path.clone())?]
+    };
+    if opts.lib.is_some() && ext_dirs.len() > 1 {
+        anyhow::bail!("--lib can only be used when linting a single extension");
+    }
+
+    let mut reports = Vec::new();
+    for ext_dir in &ext_dirs {
+        let repo_root = market::find_repo_root(ext_dir);
+        let repo = RepoContext::load(repo_root.as_deref())?;
+        let mut checks = Checks::default();
+        let descriptor = if opts.no_runtime {
+            None
+        } else {
+            load_descriptor(ext_dir, opts.lib.as_deref(), &mut checks)
+        };
+        reports.push(lint_extension(ext_dir, &repo, descriptor.as_ref(), checks)?);
+    }
+
+    if opts.json {
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+    } else {
+        for report in &reports {
+            println!("{}: {}", "Linting".cyan(), report.extension.bold());
+            report.checks.print();
+            println!();
+        }
+    }
+
+    let failed: Vec<&str> = reports
+        .iter()
+        .filter(|r| r.checks.has_failures())
+        .map(|r| r.extension.as_str())
+        .collect();
+    if !failed.is_empty() {
+        return Err(fail(FailureClass::ValidationFailure, format!("Lint failed for: {}", failed.join(", "))));
+    }
+    if !opts.json {
+        println!("{}", "✅ Metadata is consistent".green().bold());
+    }
+    Ok(())
+}
+
+/// Extension directories under `extensions/` that have a Cargo.toml and metadata.json.
+fn extension_dirs(extensions_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut dirs = Vec::new();
+    for entry in fs::read_dir(extensions_dir)
+        .with_context(|| format!("Failed to read {}", extensions_dir.display()))?
+    {
+        let path = entry?.path();
+        if path.join("Cargo.toml").exists() && path.join("metadata.json").exists() {
+            dirs.push(path);
+        }
+    }
+    dirs.sort();
+    Ok(dirs)
+}
+
+/// Load the built library and fetch its descriptor, recording a `runtime` check.
+fn load_descriptor(ext_dir: &Path, lib: Option<&str>, checks: &mut Checks) -> Option<Value> {
+    let lib_path = match lib {
+        Some(lib) => PathBuf::from(lib),
+        None => match ffi::find_built_library(ext_dir) {
+            Ok(Some(path)) => path,
+            Ok(None) => {
+                checks.fail(
+                    "runtime",
+                    "No built library found; run `neomind-ext build` first or pass --no-runtime",
+                );
+                return None;
+            }
+            Err(e) => {
+                checks.warn("runtime", format!("Skipped runtime checks: {:#}", e));
+                return None;
+            }
+        },
+    };
+
+    match ExtensionLibrary::load(&lib_path).and_then(|l| l.descriptor()) {
+        Ok(descriptor) => {
+            checks.pass("runtime", format!("Loaded {}", lib_path.display()));
+            Some(descriptor)
+        }
+        Err(e) => {
+            checks.fail("runtime", format!("{:#}", e));
+            None
+        }
+    }
+}
+
+fn lint_extension(
+    ext_dir: &Path,
+    repo: &RepoContext,
+    descriptor: Option<&Value>,
+    mut checks: Checks,
+) -> Result<LintReport> {
+    let dir_name = ext_dir
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let Some(metadata) = ExtensionMetadata::load_optional(ext_dir)? else {
+        checks.fail("metadata.json", "metadata.json not found");
+        return Ok(LintReport {
+            extension: dir_name,
+            path: ext_dir.display().to_string(),
+            checks,
+        });
+    };
+
+    lint_metadata_sources(&mut checks, ext_dir, &metadata, repo)?;
+    if let Some(descriptor) = descriptor {
+        lint_runtime(&mut checks, &metadata, descriptor);
+        lint_frontend_commands(&mut checks, ext_dir, descriptor);
+    }
+    lint_manifest_json(&mut checks, ext_dir, &metadata)?;
+    lint_index(&mut checks, &metadata, repo);
+
+    Ok(LintReport {
+        extension: metadata.id,
+        path: ext_dir.display().to_string(),
+        checks,
+    })
+}
+
+/// metadata.json vs the files it is derived from: directory name, Cargo.toml
+/// version, frontend/frontend.json and the release URL layout.
+pub fn lint_metadata_sources(
+    checks: &mut Checks,
+    ext_dir: &Path,
+    metadata: &ExtensionMetadata,
+    repo: &RepoContext,
+) -> Result<()> {
+    let dir_name = ext_dir
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    checks.expect(
+        "id",
+        metadata.id == dir_name,
+        format!("metadata.json id '{}' matches directory", metadata.id),
+        format!("metadata.json id '{}' != directory name '{}'", metadata.id, dir_name),
+    );
+    if let Ok(crate_info) = CrateInfo::load(ext_dir) {
+        checks.expect(
+            "version",
+            metadata.version == crate_info.version,
+            format!("metadata.json and Cargo.toml agree on {}", crate_info.version),
+            format!(
+                "metadata.json version {} != Cargo.toml version {}",
+                metadata.version, crate_info.version
+            ),
+        );
+    }
+    lint_frontend(checks, ext_dir, metadata)?;
+    lint_builds(checks, "builds", metadata.builds.as_ref(), metadata, repo);
+    Ok(())
+}
+
+fn str_field<'a>(value: &'a Value, field: &str) -> &'a str {
+    value.get(field).and_then(|v| v.as_str()).unwrap_or_default()
+}
+
+/// metadata(), commands() and metrics() vs metadata.json.
+fn lint_runtime(checks: &mut Checks, metadata: &ExtensionMetadata, descriptor: &Value) {
+    let runtime = &descriptor["metadata"];
+    let runtime_id = str_field(runtime, "id");
+    let runtime_version = str_field(runtime, "version");
+    let runtime_name = str_field(runtime, "name");
+
+    checks.expect(
+        "runtime.id",
+        runtime_id == metadata.id,
+        format!("metadata().id is '{}'", runtime_id),
+        format!("metadata().id '{}' != metadata.json id '{}'", runtime_id, metadata.id),
+    );
+    checks.expect(
+        "runtime.version",
+        runtime_version == metadata.version,
+        format!("metadata().version is {}", runtime_version),
+        format!(
+            "metadata().version {} != metadata.json version {}",
+            runtime_version, metadata.version
+        ),
+    );
+    if !runtime_name.eq_ignore_ascii_case(&metadata.name) {
+        checks.warn(
+            "runtime.name",
+            format!("metadata().name '{}' != metadata.json name '{}'", runtime_name, metadata.name),
+        );
+    }
+    let runtime_description = runtime.get("description").and_then(|d| d.as_str());
+    if runtime_description.is_some() && runtime_description != metadata.description.as_deref() {
+        checks.warn("runtime.description", "metadata().description differs from metadata.json");
+    }
+
+    for (kind, field) in [("commands", "commands"), ("metrics", "metrics")] {
+        let names: Vec<&str> = descriptor[field]
+            .as_array()
+            .map(|items| items.iter().map(|i| str_field(i, "name")).collect())
+            .unwrap_or_default();
+        let unique: BTreeSet<&str> = names.iter().copied().collect();
+        let check = format!("runtime.{}", kind);
+        if names.iter().any(|n| n.is_empty()) {
+            checks.fail(&check, format!("{}() has an entry without a name", kind));
+        } else if unique.len() != names.len() {
+            checks.fail(&check, format!("{}() has duplicate names", kind));
+        } else {
+            checks.pass(&check, format!("{} {}", names.len(), kind));
+        }
+    }
+}
+
+/// Commands the frontend sends with `executeCommand(..., 'name', ...)` must exist in commands().
+fn lint_frontend_commands(checks: &mut Checks, ext_dir: &Path, descriptor: &Value) {
+    let used = frontend_command_calls(&ext_dir.join("frontend").join("src"));
+    if used.is_empty() {
+        return;
+    }
+    let declared: BTreeSet<&str> = descriptor["commands"]
+        .as_array()
+        .map(|c| c.iter().map(|c| str_field(c, "name")).collect())
+        .unwrap_or_default();
+    let unknown: Vec<&str> = used
+        .iter()
+        .map(String::as_str)
+        .filter(|c| !declared.contains(c))
+        .collect();
+    checks.expect(
+        "frontend.commands",
+        unknown.is_empty(),
+        format!("{} command(s) used by the frontend are declared", used.len()),
+        format!("Frontend calls undeclared command(s): {}", unknown.join(", ")),
+    );
+}
+
+/// Command names passed as string literals to `executeCommand(` in frontend sources.
+fn frontend_command_calls(src_dir: &Path) -> BTreeSet<String> {
+    let mut commands = BTreeSet::new();
+    let Ok(entries) = fs::read_dir(src_dir) else {
+        return commands;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            commands.extend(frontend_command_calls(&path));
+            continue;
+        }
+        let is_source = matches!(
+            path.extension().and_then(|e| e.to_str()),
+            Some("ts" | "tsx" | "js" | "jsx")
+        );
+        if let (true, Ok(source)) = (is_source, fs::read_to_string(&path)) {
+            commands.extend(command_literals(&source));
+        }
+    }
+    commands
+}
+
+fn command_literals(source: &str) -> Vec<String> {
+    const CALL: &str = "executeCommand(";
+    let mut found = Vec::new();
+    let mut rest = source;
+    while let Some(start) = rest.find(CALL) {
+        rest = &rest[start + CALL.len()..];
+        // First string literal before the argument list closes
+        let args = &rest[..rest.find(')').unwrap_or(rest.len())];
+        if let Some(open) = args.find(['\'', '"']) {
+            let quote = args[open..].chars().next().unwrap_or('\'');
+            let literal = &args[open + 1..];
+            if let Some(end) = literal.find(quote) {
+                found.push(literal[..end].to_string());
+            }
+        }
+    }
+    found
+}
+
+/// Legacy manifest.json next to metadata.json (yolo-video-v2).
+fn lint_manifest_json(checks: &mut Checks, ext_dir: &Path, metadata: &ExtensionMetadata) -> Result<()> {
+    let path = ext_dir.join("manifest.json");
+    if !path.exists() {
+        return Ok(());
+    }
+    let content = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let manifest: Value =
+        serde_json::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    let id = str_field(&manifest, "id");
+    checks.expect(
+        "manifest.json",
+        id == metadata.id,
+        "manifest.json id matches metadata.json",
+        format!("manifest.json id '{}' != metadata.json id '{}'", id, metadata.id),
+    );
+    let version = str_field(&manifest, "version");
+    if !version.is_empty() && version != metadata.version {
+        checks.warn(
+            "manifest.json",
+            format!("manifest.json version {} != metadata.json version {}", version, metadata.version),
+        );
+    }
+    Ok(())
+}
+
+/// metadata.json `frontend` vs frontend/frontend.json.
+fn lint_frontend(checks: &mut Checks, ext_dir: &Path, metadata: &ExtensionMetadata) -> Result<()> {
+    let declared = manifest::frontend_metadata(ext_dir)?;
+    match (&metadata.frontend, &declared) {
+        (None, None) => {}
+        (Some(_), None) => checks.fail("frontend", "metadata.json declares a frontend but frontend/frontend.json is missing"),
+        (None, Some(_)) => checks.fail("frontend", "frontend/frontend.json exists but metadata.json has no frontend section"),
+        (Some(meta), Some(frontend)) => {
+            checks.expect(
+                "frontend",
+                frontend_matches(meta, frontend),
+                format!("Components: {}", frontend.components.join(", ")),
+                format!(
+                    "metadata.json frontend {} != frontend.json {}",
+                    describe_frontend(meta),
+                    describe_frontend(frontend)
+                ),
+            );
+        }
+    }
+    Ok(())
+}
+
+fn frontend_matches(a: &FrontendMetadata, b: &FrontendMetadata) -> bool {
+    a.components == b.components && a.entrypoint == b.entrypoint
+}
+
+fn describe_frontend(frontend: &FrontendMetadata) -> String {
+    format!(
+        "[{}] ({})",
+        frontend.components.join(", "),
+        frontend.entrypoint.as_deref().unwrap_or("no entrypoint")
+    )
+}
+
+/// Every build URL must point at `<id>-<version>-<platform>.nep` under the market release tag.
+fn lint_builds(
+    checks: &mut Checks,
+    name: &str,
+    builds: Option<&Value>,
+    metadata: &ExtensionMetadata,
+    repo: &RepoContext,
+) {
+    let Some(market_version) = &repo.market_version else {
+        checks.warn(name, "VERSION file not found; build URLs not checked");
+        return;
+    };
+    let Some(builds) = builds.and_then(|b| b.as_object()) else {
+        checks.fail(name, "No builds declared");
+        return;
+    };
+
+    let mut problems = Vec::new();
+    for (key, expected) in market::build_urls(market_version, &metadata.id, &metadata.version) {
+        match builds.get(key).map(|b| str_field(b, "url")) {
+            Some(url) if url == expected => {}
+            Some(url) => problems.push(format!("{}: {}", key, url)),
+            None => problems.push(format!("{}: missing", key)),
+        }
+    }
+    checks.expect(
+        name,
+        problems.is_empty(),
+        format!("{} build URLs match v{} / {}", builds.len(), market_version, metadata.version),
+        format!("Build URLs do not match version {}: {}", metadata.version, problems.join("; ")),
+    );
+}
+
+/// The extension's entry in extensions/index.json vs metadata.json.
+fn lint_index(checks: &mut Checks, metadata: &ExtensionMetadata, repo: &RepoContext) {
+    let Some(index) = &repo.index else {
+        checks.warn("index", "extensions/index.json not found");
+        return;
+    };
+    let Some(entry) = index.iter().find(|e| str_field(e, "id") == metadata.id) else {
+        checks.fail("index", format!("No entry for '{}' in extensions/index.json", metadata.id));
+        return;
+    };
+
+    let version = str_field(entry, "version");
+    checks.expect(
+        "index.version",
+        version == metadata.version,
+        format!("index.json lists {}", version),
+        format!("index.json version {} != metadata.json version {}", version, metadata.version),
+    );
+
+    let index_frontend: Option<FrontendMetadata> = entry
+        .get("frontend")
+        .and_then(|f| serde_json::from_value(f.clone()).ok());
+    let same_frontend = match (&metadata.frontend, &index_frontend) {
+        (None, None) => true,
+        (Some(a), Some(b)) => frontend_matches(a, b),
+        _ => false,
+    };
+    checks.expect(
+        "index.frontend",
+        same_frontend,
+        "index.json frontend components match metadata.json",
+        format!(
+            "index.json frontend {} != metadata.json frontend {}",
+            index_frontend.as_ref().map(describe_frontend).unwrap_or_else(|| "none".into()),
+            metadata.frontend.as_ref().map(describe_frontend).unwrap_or_else(|| "none".into()),
+        ),
+    );
+
+    lint_builds(checks, "index.builds", entry.get("builds"), metadata, repo);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::report::CheckStatus;
+    use serde_json::json;
+
+    fn write_extension(root: &Path, version: &str) -> PathBuf {
+        fs::write(root.join("VERSION"), "2.7.0\n").unwrap();
+        let ext_dir = root.join("extensions").join("demo");
+        fs::create_dir_all(ext_dir.join("frontend").join("src")).unwrap();
+        fs::write(
+            ext_dir.join("Cargo.toml"),
+            format!("[package]\nname = \"demo\"\nversion = \"{}\"\n", version),
+        )
+        .unwrap();
+
+        let builds: serde_json::Map<String, Value> = market::build_urls("2.7.0", "demo", version)
+            .into_iter()
+            .map(|(key, url)| (key.to_string(), json!({ "url": url })))
+            .collect();
+        let metadata = json!({
+            "id": "demo",
+            "name": "demo",
+            "version": version,
+            "builds": builds,
+            "frontend": { "components": ["DemoCard"], "entrypoint": "demo-components.umd.cjs" },
+        });
+        fs::write(ext_dir.join("metadata.json"), metadata.to_string()).unwrap();
+        fs::write(
+            ext_dir.join("frontend").join("frontend.json"),
+            json!({
+                "entrypoint": "demo-components.umd.cjs",
+                "components": [{ "name": "DemoCard" }],
+            })
+            .to_string(),
+        )
+        .unwrap();
+        fs::write(
+            ext_dir.join("frontend").join("src").join("index.tsx"),
+            "async function executeCommand(\n  extensionId: string,\n  command: string\n) {}\n\
+             executeCommand(extensionId, 'get_status', {})\n\
+             executeCommand(extensionId, \"reset\")\n",
+        )
+        .unwrap();
+
+        let mut entry = metadata;
+        entry["metadata_url"] = json!("https://example.invalid/metadata.json");
+        fs::write(
+            root.join("extensions").join("index.json"),
+            json!({ "version": "2.7.0", "extensions": [entry] }).to_string(),
+        )
+        .unwrap();
+        ext_dir
+    }
+
+    fn descriptor(version: &str, commands: &[&str]) -> Value {
+        json!({
+            "metadata": { "id": "demo", "name": "Demo", "version": version },
+            "commands": commands.iter().map(|c| json!({ "name": c })).collect::<Vec<_>>(),
+            "metrics": [{ "name": "fps" }],
+        })
+    }
+
+    fn lint(ext_dir: &Path, descriptor: Option<&Value>) -> LintReport {
+        let repo = RepoContext::load(market::find_repo_root(ext_dir).as_deref()).unwrap();
+        lint_extension(ext_dir, &repo, descriptor, Checks::default()).unwrap()
+    }
+
+    fn status(report: &LintReport, name: &str) -> CheckStatus {
+        report.checks.iter().find(|c| c.name == name).unwrap().status
+    }
+
+    #[test]
+    fn test_consistent_extension_passes() {
+        let dir = tempfile::tempdir().unwrap();
+        let ext_dir = write_extension(dir.path(), "1.2.0");
+
+        let report = lint(&ext_dir, Some(&descriptor("1.2.0", &["get_status", "reset"])));
+        assert!(!report.checks.has_failures(), "{:?}", report.checks);
+        assert_eq!(status(&report, "index.builds"), CheckStatus::Pass);
+        assert_eq!(status(&report, "frontend.commands"), CheckStatus::Pass);
+    }
+
+    #[test]
+    fn test_runtime_version_and_undeclared_command_fail() {
+        let dir = tempfile::tempdir().unwrap();
+        let ext_dir = write_extension(dir.path(), "1.2.0");
+
+        let report = lint(&ext_dir, Some(&descriptor("1.0.0", &["get_status"])));
+        assert_eq!(status(&report, "runtime.version"), CheckStatus::Fail);
+        assert_eq!(status(&report, "frontend.commands"), CheckStatus::Fail);
+    }
+
+    #[test]
+    fn test_stale_index_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let ext_dir = write_extension(dir.path(), "1.2.0");
+        // Bump the crate and metadata.json without regenerating index.json
+        let metadata_path = ext_dir.join("metadata.json");
+        let bumped = fs::read_to_string(&metadata_path).unwrap().replace("1.2.0", "1.3.0");
+        fs::write(&metadata_path, bumped).unwrap();
+        fs::write(ext_dir.join("Cargo.toml"), "[package]\nname = \"demo\"\nversion = \"1.3.0\"\n").unwrap();
+
+        let report = lint(&ext_dir, None);
+        assert_eq!(status(&report, "builds"), CheckStatus::Pass);
+        assert_eq!(status(&report, "index.version"), CheckStatus::Fail);
+        assert_eq!(status(&report, "index.builds"), CheckStatus::Fail);
+    }
+
+    #[test]
+    fn test_command_literals() {
+        let source = "executeCommand('bind_device', { id })\nawait executeCommand(extensionId, \"list\")";
+        assert_eq!(command_literals(source), vec!["bind_device", "list"]);
+    }
+}
@@ -0,0 +1,19 @@
+//! Subcommand implementations that outgrew main.rs.
+
+pub mod abi;
+pub mod add;
+pub mod build;
+pub mod dev;
+pub mod diff;
+pub mod doctor;
+pub mod index;
+pub mod install;
+pub mod lint;
+pub mod models;
+pub mod new;
+pub mod package;
+pub mod replay;
+pub mod run;
+pub mod sign;
+pub mod test;
+pub mod validate;
@@ -0,0 +1,251 @@
+//! `neomind-ext models fetch|verify|list` - manage the model files an
+//! extension declares in `models.toml`.
+//!
+//! `fetch --mirror <dir>` copies from a staged directory instead of the
+//! network (`<dir>/<id>/<file>` or `<dir>/<file>`), so air-gapped sites can
+//! prepare every extension's models once. Downloads go through `curl` to a
+//! `.part` file and only replace the model after its size and SHA-256 check out.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::commands::package;
+use crate::utils::checksum;
+use crate::utils::manifest::ExtensionMetadata;
+use crate::utils::models::{ModelEntry, ModelStatus, ModelsManifest, MODELS_TOML};
+use crate::utils::report::Checks;
+
+pub struct FetchOptions {
+    pub path: Option<String>,
+    /// Local directory to copy models from instead of downloading
+    pub mirror: Option<String>,
+}
+
+pub fn cmd_fetch(opts: FetchOptions) -> Result<()> {
+    let ext_path = crate::get_extension_path(opts.path)?;
+    let manifest = load(&ext_path)?;
+    let ext_id = extension_id(&ext_path)?;
+    let mirror = opts.mirror.map(PathBuf::from);
+
+    println!("{}", "📥 Fetching models".green().bold());
+    fs::create_dir_all(ext_path.join("models"))?;
+
+    let mut failed = Vec::new();
+    for model in &manifest.models {
+        let target = model.path(&ext_path);
+        if model.check(&target)?.is_usable() {
+            println!("  {} {} (already present)", "✓".green(), model.file);
+            continue;
+        }
+        match fetch_model(model, &target, &ext_id, mirror.as_deref()) {
+            Ok(status) => {
+                println!("  {} {} ({})", "✓".green(), model.file, status.describe());
+                if status == ModelStatus::Unpinned {
+                    println!(
+                        "    {} pin it in {}: sha256 = \"{}\", size = {}",
+                        "→".blue(),
+                        MODELS_TOML,
+                        checksum::sha256_file(&target)?,
+                        fs::metadata(&target)?.len()
+                    );
+                }
+            }
+            Err(e) => {
+                println!("  {} {}: {:#}", "✗".red(), model.file, e);
+                failed.push(model.file.as_str());
+            }
+        }
+    }
+
+    if !failed.is_empty() {
+        anyhow::bail!("Failed to fetch: {}", failed.join(", "));
+    }
+    println!("{}", "✅ Models ready".green().bold());
+    Ok(())
+}
+
+pub fn cmd_verify(path: Option<String>) -> Result<()> {
+    let ext_path = crate::get_extension_path(path)?;
+    let manifest = load(&ext_path)?;
+
+    println!("{}", "🔍 Verifying models".green().bold());
+    let checks = verify(&ext_path, &manifest)?;
+    checks.print();
+
+    if checks.has_failures() {
+        anyhow::bail!("Model verification failed: {}", checks.failed_names().join(", "));
+    }
+    println!("{}", "✅ All models verified".green().bold());
+    Ok(())
+}
+
+pub fn cmd_list(path: Option<String>) -> Result<()> {
+    let ext_path = crate::get_extension_path(path)?;
+    let manifest = load(&ext_path)?;
+
+    println!(
+        "{:<24} {:>12}  {:<8} {:<8} {}",
+        "FILE".bold(),
+        "SIZE".bold(),
+        "BUNDLE".bold(),
+        "STATUS".bold(),
+        "SOURCE".bold()
+    );
+    for model in &manifest.models {
+        let status = model.check(&model.path(&ext_path))?;
+        let state = match status {
+            ModelStatus::Verified => "ok".green(),
+            ModelStatus::Unpinned => "unpinned".yellow(),
+            ModelStatus::Missing => "missing".yellow(),
+            _ => "invalid".red(),
+        };
+        println!(
+            "{:<24} {:>12}  {:<8} {:<8} {}",
+            model.file,
+            model.size.map(|s| s.to_string()).unwrap_or_else(|| "-".to_string()),
+            if model.bundle { "yes" } else { "no" },
+            state,
+            model.url.as_deref().unwrap_or("(repo or --mirror)")
+        );
+    }
+    Ok(())
+}
+
+/// One check per declared model: missing or mismatched files fail, unpinned ones warn.
+pub fn verify(ext_path: &Path, manifest: &ModelsManifest) -> Result<Checks> {
+    let mut checks = Checks::default();
+    for model in &manifest.models {
+        let status = model.check(&model.path(ext_path))?;
+        match status {
+            ModelStatus::Verified => checks.pass(&model.file, status.describe()),
+            ModelStatus::Unpinned => checks.warn(&model.file, status.describe()),
+            ModelStatus::Missing if model.url.is_some() => {
+                checks.fail(&model.file, "missing; run `neomind-ext models fetch`")
+            }
+            _ => checks.fail(&model.file, status.describe()),
+        }
+    }
+    Ok(checks)
+}
+
+fn load(ext_path: &Path) -> Result<ModelsManifest> {
+    ModelsManifest::load_optional(ext_path)?
+        .ok_or_else(|| anyhow::anyhow!("No {} in {}", MODELS_TOML, ext_path.display()))
+}
+
+fn extension_id(ext_path: &Path) -> Result<String> {
+    match ExtensionMetadata::load_optional(ext_path)? {
+        Some(m) => Ok(m.id),
+        None => package::dir_name(ext_path),
+    }
+}
+
+/// Stage the model next to its target, check it, then move it into place.
+fn fetch_model(model: &ModelEntry, target: &Path, ext_id: &str, mirror: Option<&Path>) -> Result<ModelStatus> {
+    let part = target.with_file_name(format!(".{}.part", model.file));
+    match mirror {
+        Some(mirror) => {
+            let source = [mirror.join(ext_id).join(&model.file), mirror.join(&model.file)]
+                .into_iter()
+                .find(|p| p.is_file())
+                .ok_or_else(|| anyhow::anyhow!("not found in mirror {}", mirror.display()))?;
+            fs::copy(&source, &part).with_context(|| format!("Failed to copy {}", source.display()))?;
+        }
+        None => {
+            let url = model
+                .url
+                .as_deref()
+                .ok_or_else(|| anyhow::anyhow!("missing and no url declared"))?;
+            download(url, &part)?;
+        }
+    }
+
+    let status = model.check(&part)?;
+    if !status.is_usable() {
+        let _ = fs::remove_file(&part);
+        anyhow::bail!("{}", status.describe());
+    }
+    fs::rename(&part, target)?;
+    Ok(status)
+}
+
+fn download(url: &str, dest: &Path) -> Result<()> {
+    println!("  {} {}", "→".blue(), format!("Downloading {}", url).dimmed());
+    let status = Command::new("curl")
+        .args(["-fL", "--retry", "3", "-o"])
+        .arg(dest)
+        .arg(url)
+        .status()
+        .context("Failed to run curl")?;
+    if !status.success() {
+        let _ = fs::remove_file(dest);
+        anyhow::bail!("download failed ({})", status);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extension_with_manifest(root: &Path) -> PathBuf {
+        let ext = root.join("demo-ext");
+        fs::create_dir_all(&ext).unwrap();
+        fs::write(ext.join("metadata.json"), r#"{"id": "demo-ext", "name": "Demo", "version": "1.0.0"}"#).unwrap();
+        fs::write(
+            ext.join(MODELS_TOML),
+            format!(
+                "[[model]]\nfile = \"det.onnx\"\nurl = \"https://example.invalid/det.onnx\"\nsha256 = \"{}\"\nsize = 4\n\n\
+                 [[model]]\nfile = \"vocab.txt\"\nurl = \"https://example.invalid/vocab.txt\"\n",
+                checksum::sha256_hex(b"onnx")
+            ),
+        )
+        .unwrap();
+        ext
+    }
+
+    #[test]
+    fn test_fetch_from_mirror_then_verify() {
+        let dir = tempfile::tempdir().unwrap();
+        let ext = extension_with_manifest(dir.path());
+        let mirror = dir.path().join("mirror");
+        fs::create_dir_all(mirror.join("demo-ext")).unwrap();
+        fs::write(mirror.join("demo-ext").join("det.onnx"), "onnx").unwrap();
+        fs::write(mirror.join("vocab.txt"), "a\nb\n").unwrap();
+
+        cmd_fetch(FetchOptions {
+            path: Some(ext.to_string_lossy().to_string()),
+            mirror: Some(mirror.to_string_lossy().to_string()),
+        })
+        .unwrap();
+        assert_eq!(fs::read_to_string(ext.join("models/det.onnx")).unwrap(), "onnx");
+
+        let manifest = load(&ext).unwrap();
+        let checks = verify(&ext, &manifest).unwrap();
+        assert!(!checks.has_failures());
+        // vocab.txt has no pinned sha256
+        assert!(checks.iter().any(|c| c.name == "vocab.txt" && c.message.contains("no sha256")));
+    }
+
+    #[test]
+    fn test_mismatched_mirror_file_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let ext = extension_with_manifest(dir.path());
+        let mirror = dir.path().join("mirror");
+        fs::create_dir_all(&mirror).unwrap();
+        fs::write(mirror.join("det.onnx"), "ONNX").unwrap();
+        fs::write(mirror.join("vocab.txt"), "a\n").unwrap();
+
+        let result = cmd_fetch(FetchOptions {
+            path: Some(ext.to_string_lossy().to_string()),
+            mirror: Some(mirror.to_string_lossy().to_string()),
+        });
+        assert!(result.is_err());
+        assert!(!ext.join("models/det.onnx").exists());
+        assert!(!ext.join("models/.det.onnx.part").exists());
+        assert!(ext.join("models/vocab.txt").exists());
+    }
+}
@@ -0,0 +1,409 @@
+//! `neomind-ext new` - scaffold an extension from one of the templates under
+//! neomind-ext/templates, register it as a workspace member and write its
+//! metadata.json so `lint` and `index` accept it straight away.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::utils::cargo::CrateInfo;
+use crate::utils::exit::{fail, FailureClass};
+use crate::utils::market;
+
+pub struct NewOptions {
+    pub name: String,
+    pub template: String,
+    pub with_frontend: bool,
+    pub json: bool,
+}
+
+/// `--json` result of `new`.
+#[derive(Debug, Serialize)]
+pub struct NewReport {
+    pub name: String,
+    pub template: &'static str,
+    pub path: PathBuf,
+    /// Files written, template files first, then metadata.json
+    pub files: Vec<PathBuf>,
+    /// Whether the extension was added to the workspace members
+    pub workspace_updated: bool,
+}
+
+/// A scaffolding template: `templates/<name>/` plus what goes into metadata.json.
+pub struct Template {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// metadata.json `type`
+    pub ext_type: &'static str,
+    /// metadata.json `categories`
+    pub categories: &'static [&'static str],
+}
+
+pub const TEMPLATES: &[Template] = &[
+    Template {
+        name: "basic",
+        description: "Minimal extension with one metric and a ping command",
+        ext_type: "native",
+        categories: &["utility"],
+    },
+    Template {
+        name: "device-inference",
+        description: "Bind devices and process their metric updates (DeviceBinding, handle_event, persisted config)",
+        ext_type: "native",
+        categories: &["ai", "computer-vision", "device-integration"],
+    },
+    Template {
+        name: "push-stream",
+        description: "Push-mode stream sessions (StreamCapability, init_session, start_push)",
+        ext_type: "native",
+        categories: &["video", "streaming"],
+    },
+    Template {
+        name: "wasm",
+        description: "Sandboxed extension for wasm32-unknown-unknown without native dependencies",
+        ext_type: "wasm",
+        categories: &["utility"],
+    },
+    Template {
+        name: "http-bridge",
+        description: "Poll an external HTTP API and expose it through commands and metrics",
+        ext_type: "native",
+        categories: &["utility", "integration"],
+    },
+];
+
+pub fn find_template(name: &str) -> Option<&'static Template> {
+    TEMPLATES.iter().find(|t| t.name == name)
+}
+
+pub fn cmd_list_templates(json: bool) -> Result<()> {
+    if json {
+        let templates: Vec<Value> = TEMPLATES
+            .iter()
+            .map(|t| json!({ "name": t.name, "description": t.description, "type": t.ext_type }))
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&templates)?);
+        return Ok(());
+    }
+    println!("{}", "Available templates:".green().bold());
+    for template in TEMPLATES {
+        println!("  {:<18} {}", template.name.cyan(), template.description);
+    }
+    Ok(())
+}
+
+pub fn cmd_new(opts: NewOptions) -> Result<()> {
+    let name = opts.name;
+    let Some(template) = find_template(&opts.template) else {
+        let names: Vec<&str> = TEMPLATES.iter().map(|t| t.name).collect();
+        return Err(fail(
+            FailureClass::ValidationFailure,
+            format!("Unknown template '{}'. Available: {}", opts.template, names.join(", ")),
+        ));
+    };
+
+    if !opts.json {
+        println!("{}", "🚀 Creating new NeoMind extension".green().bold());
+        println!("{}: {}", "Name".cyan(), name);
+        println!("{}: {}", "Template".cyan(), template.name);
+        println!("{}: {}", "Frontend".cyan(), if opts.with_frontend { "Yes" } else { "No" });
+    }
+
+    // Validate extension name
+    if !name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+        return Err(fail(
+            FailureClass::ValidationFailure,
+            format!("Invalid extension name '{}'. Use kebab-case (e.g., my-cool-extension)", name),
+        ));
+    }
+
+    // Get repository root
+    let repo_root = crate::get_repo_root()?;
+    let extensions_dir = repo_root.join("extensions");
+    let new_ext_dir = extensions_dir.join(&name);
+
+    // Check if extension already exists
+    if new_ext_dir.exists() {
+        return Err(fail(
+            FailureClass::ValidationFailure,
+            format!("Extension '{}' already exists at: {}", name, new_ext_dir.display()),
+        ));
+    }
+
+    let template_dir = templates_dir(&repo_root).join(template.name);
+    if !template_dir.exists() {
+        return Err(fail(
+            FailureClass::MissingFile,
+            format!("Template directory not found: {}", template_dir.display()),
+        ));
+    }
+
+    // Copy template
+    let mut files = Vec::new();
+    copy_dir(&template_dir, &new_ext_dir, &name, &mut files)?;
+    files.push(write_metadata(&repo_root, &new_ext_dir, &name, template)?);
+
+    // Update workspace Cargo.toml
+    let workspace_updated = update_workspace_cargo_toml(&repo_root, &name)?;
+
+    if opts.json {
+        let report = NewReport {
+            name,
+            template: template.name,
+            path: new_ext_dir,
+            files,
+            workspace_updated,
+        };
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!("\n{}", "Creating files...".yellow());
+    for file in &files {
+        println!("  {}", file.display().to_string().dimmed());
+    }
+    if workspace_updated {
+        println!("  {}", "✓ Updated workspace Cargo.toml".green());
+    }
+
+    println!("\n{}", "✅ Extension created successfully!".green().bold());
+    println!("\n{}", "Next steps:".yellow().bold());
+    println!("  1. cd extensions/{}", name);
+    println!("  2. {} your code", "edit".cyan());
+    println!("  3. {} build the extension", "neomind-ext build".cyan());
+    println!("  4. {} run tests", "neomind-ext test".cyan());
+    println!("  5. {} package as .nep", "neomind-ext package".cyan());
+
+    Ok(())
+}
+
+fn templates_dir(repo_root: &Path) -> PathBuf {
+    repo_root.join("neomind-ext").join("templates")
+}
+
+/// Copy `src` to `dst` with template variables replaced, recording each file written.
+pub(crate) fn copy_dir(src: &Path, dst: &Path, ext_name: &str, written: &mut Vec<PathBuf>) -> Result<()> {
+    // Create destination directory
+    fs::create_dir_all(dst).context("Failed to create directory")?;
+
+    // Read source directory
+    for entry in fs::read_dir(src).context("Failed to read template directory")? {
+        let entry = entry?;
+        let ty = entry.file_type()?;
+        let src_path = entry.path();
+        let file_name = entry.file_name();
+        let dst_path = dst.join(&file_name);
+
+        if ty.is_dir() {
+            // Recursively copy directory
+            copy_dir(&src_path, &dst_path, ext_name, written)?;
+        } else {
+            // Read file content
+            let content = fs::read_to_string(&src_path)?;
+
+            // Replace template variables
+            let content = replace_template_variables(&content, ext_name);
+
+            // Write to destination
+            fs::write(&dst_path, content).context("Failed to write file")?;
+            written.push(dst_path);
+        }
+    }
+
+    Ok(())
+}
+
+/// metadata.json in the layout scripts/update-versions.sh generates.
+fn write_metadata(repo_root: &Path, ext_dir: &Path, ext_id: &str, template: &Template) -> Result<PathBuf> {
+    let market_version = market::market_version(repo_root)?;
+    let crate_info = CrateInfo::load(ext_dir)?;
+    let metadata = metadata_json(ext_id, &crate_info.version, &market_version, template);
+
+    let path = ext_dir.join("metadata.json");
+    fs::write(&path, serde_json::to_string_pretty(&metadata)? + "\n")
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+fn metadata_json(ext_id: &str, version: &str, market_version: &str, template: &Template) -> Value {
+    let builds: Map<String, Value> = market::build_urls(market_version, ext_id, version)
+        .into_iter()
+        .map(|(key, url)| (key.to_string(), json!({ "url": url })))
+        .collect();
+    json!({
+        "id": ext_id,
+        "name": ext_id.replace('-', " "),
+        "version": version,
+        "description": format!("A NeoMind extension for {}", to_display_name(ext_id)),
+        "author": "Your Name",
+        "license": "MIT",
+        "type": template.ext_type,
+        "categories": template.categories,
+        "homepage": format!("https://github.com/{}/tree/main/extensions/{}", market::GITHUB_REPO, ext_id),
+        "builds": builds,
+    })
+}
+
+fn replace_template_variables(content: &str, ext_name: &str) -> String {
+    let mut result = content.to_string();
+
+    // Extension name transformations
+    let ext_id = ext_name;
+    let ext_struct_name = to_pascal_case(ext_name);
+    let ext_display_name = to_display_name(ext_name);
+    let ext_name_underscored = ext_name.replace('-', "_");
+    let ext_package_name = format!("neomind_extension_{}", ext_name_underscored);
+
+    // Replace variables
+    result = result.replace("{{EXTENSION_NAME}}", ext_name);
+    result = result.replace("{{EXTENSION_ID}}", ext_id);
+    result = result.replace("{{EXTENSION_STRUCT_NAME}}", &ext_struct_name);
+    result = result.replace("{{EXTENSION_DISPLAY_NAME}}", &ext_display_name);
+    result = result.replace("{{EXTENSION_NAME_UNDERSCORED}}", &ext_name_underscored);
+    result = result.replace("{{EXTENSION_PACKAGE_NAME}}", &ext_package_name);
+    result = result.replace("{{EXTENSION_AUTHOR}}", "Your Name");
+    result = result.replace("{{EXTENSION_DESCRIPTION}}", &format!("A NeoMind extension for {}", ext_display_name));
+
+    result
+}
+
+fn to_pascal_case(s: &str) -> String {
+    s.split('-')
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                None => String::new(),
+                Some(first) => {
+                    first.to_uppercase().collect::<String>() + chars.as_str()
+                }
+            }
+        })
+        .collect()
+}
+
+/// `door-sensor` / `set_threshold` -> `Door Sensor` / `Set Threshold`
+pub fn to_display_name(s: &str) -> String {
+    s.split(['-', '_'])
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                None => String::new(),
+                Some(first) => {
+                    first.to_uppercase().collect::<String>() + chars.as_str()
+                }
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Add `extensions/<name>` to the workspace members; false if there was nothing to do.
+fn update_workspace_cargo_toml(repo_root: &Path, ext_name: &str) -> Result<bool> {
+    let workspace_cargo = repo_root.join("Cargo.toml");
+
+    if !workspace_cargo.exists() {
+        return Ok(false);
+    }
+
+    let ext_path = format!("extensions/{}", ext_name);
+    let content = fs::read_to_string(&workspace_cargo)?;
+
+    if content.contains(&ext_path) {
+        return Ok(false);
+    }
+
+    let lines: Vec<&str> = content.lines().collect();
+    let mut result = Vec::new();
+    let mut in_members = false;
+    let mut inserted = false;
+
+    for (i, line) in lines.iter().enumerate() {
+        result.push(line.to_string());
+
+        if line.contains("members = [") {
+            in_members = true;
+        } else if in_members && !inserted {
+            if line.trim().starts_with('"') {
+                if i + 1 < lines.len() {
+                    let next_line = lines[i + 1].trim();
+                    if next_line == "]" {
+                        result.push(format!("    \"{}\",", ext_path));
+                        inserted = true;
+                    }
+                }
+            } else if line.trim() == "]" {
+                result.push(format!("    \"{}\",", ext_path));
+                inserted = true;
+            }
+        }
+    }
+
+    fs::write(&workspace_cargo, result.join("\n"))?;
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundled_templates() -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR")).join("templates")
+    }
+
+    #[test]
+    fn test_every_template_is_listed() {
+        let mut on_disk: Vec<String> = fs::read_dir(bundled_templates())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        on_disk.sort();
+        let mut listed: Vec<&str> = TEMPLATES.iter().map(|t| t.name).collect();
+        listed.sort();
+        assert_eq!(on_disk, listed);
+    }
+
+    #[test]
+    fn test_templates_render_to_valid_crates() {
+        for template in TEMPLATES {
+            let dir = tempfile::tempdir().unwrap();
+            let ext_dir = dir.path().join("door-sensor");
+            copy_dir(&bundled_templates().join(template.name), &ext_dir, "door-sensor", &mut Vec::new()).unwrap();
+
+            let crate_info = CrateInfo::load(&ext_dir).unwrap();
+            assert_eq!(crate_info.lib_name, "neomind_extension_door_sensor", "{}", template.name);
+            let lib_rs = fs::read_to_string(ext_dir.join("src").join("lib.rs")).unwrap();
+            assert!(lib_rs.contains("neomind_export!(DoorSensor)"), "{}", template.name);
+            assert!(!lib_rs.contains("{{"), "{} leaves a placeholder", template.name);
+        }
+    }
+
+    #[test]
+    fn test_metadata_matches_lint_expectations() {
+        let template = find_template("wasm").unwrap();
+        let metadata = metadata_json("door-sensor", "1.0.0", "2.7.0", template);
+        assert_eq!(metadata["name"], "door sensor");
+        assert_eq!(metadata["type"], "wasm");
+        assert_eq!(
+            metadata["builds"]["linux-x86_64"]["url"],
+            "https://github.com/camthink-ai/NeoMind-Extensions/releases/download/v2.7.0/door-sensor-1.0.0-linux_amd64.nep"
+        );
+    }
+
+    #[test]
+    fn test_workspace_member_is_appended() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\n    \"extensions/a\",\n]\n",
+        )
+        .unwrap();
+        assert!(update_workspace_cargo_toml(dir.path(), "door-sensor").unwrap());
+        let content = fs::read_to_string(dir.path().join("Cargo.toml")).unwrap();
+        assert!(content.contains("    \"extensions/a\",\n    \"extensions/door-sensor\",\n]"));
+        assert!(!update_workspace_cargo_toml(dir.path(), "door-sensor").unwrap());
+    }
+}
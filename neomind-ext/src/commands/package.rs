@@ -0,0 +1,764 @@
+//! `neomind-ext package` - build a .nep package without external scripts.
+//!
+//! Package layout (same as build.sh):
+//!
+//! ```text
+//! manifest.json
+//! frontend.json                     (if frontend/frontend.json exists)
+//! binaries/<platform>/extension.so  (native) or binaries/extension.wasm
+//! binaries/<platform>/<native libs> (ONNX Runtime; FFmpeg DLLs on Windows)
+//! frontend/...                      (contents of frontend/dist)
+//! models/...                        (models.toml entries, or *.onnx, *.bin, *.txt from models/)
+//! ```
+//!
+//! Unlike build.sh, Linux and macOS packages do not get the binary's other
+//! shared library dependencies (the `ldd` walk and `install_name_tool`
+//! rewrites); an FFmpeg extension packaged for those platforms relies on the
+//! host's FFmpeg and a warning says so.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+
+use crate::commands::models;
+use crate::utils::cargo::{self, CrateInfo};
+use crate::utils::checksum;
+use crate::utils::exit::{fail, FailureClass};
+use crate::utils::manifest::{self, ExtensionMetadata, PackageFrontend, PackageManifest};
+use crate::utils::models::ModelsManifest;
+use crate::utils::platform::{self, Platform};
+
+/// Model file extensions bundled into `models/`
+const MODEL_EXTENSIONS: [&str; 3] = ["onnx", "bin", "txt"];
+
+/// Dependency DLLs build.sh bundles from `FFMPEG_DIR` for Windows packages:
+/// FFmpeg itself and the codec/TLS libraries of BtbN shared builds.
+const WINDOWS_DLLS: [&str; 57] = [
+    "avcodec", "avformat", "avutil", "swscale", "swresample", "avdevice", "avfilter",
+    "x264", "x265", "vpx", "opus", "vorbis", "ogg", "speex", "soxr",
+    "srt", "ssh", "rist", "zmq", "sodium",
+    "ssl", "crypto", "gmp", "hogtle", "nettle",
+    "brotlicommon", "brotlidec", "brotlienc",
+    "zstd", "lzma", "png", "jpeg", "webp", "sharpyuv",
+    "fontconfig", "freetype", "fribidi",
+    "unistring", "idn2", "intl", "tasn1", "p11-kit", "gnutls",
+    "bluray", "aom", "dav1d", "rav1e", "jxl", "jxl_cms", "jxl_threads", "snappy",
+    "openjp2", "mp3lame", "vmaf", "theora", "theoraenc", "theoradec",
+];
+
+pub struct PackageOptions {
+    pub path: Option<String>,
+    pub with_frontend: bool,
+    pub debug: bool,
+    pub platform: Option<String>,
+    pub output: Option<String>,
+    pub json: bool,
+}
+
+/// `--json` result of `package`.
+#[derive(Debug, Serialize)]
+pub struct PackageReport {
+    pub extension: String,
+    pub version: String,
+    pub platform: &'static str,
+    pub output: PathBuf,
+    pub sha256: String,
+    /// Archive entries besides manifest.json
+    pub files: Vec<String>,
+}
+
+pub fn cmd_package(opts: PackageOptions) -> Result<PathBuf> {
+    let ext_path = crate::get_extension_path(opts.path)?;
+
+    if !opts.json {
+        println!("{}", "📦 Packaging extension as .nep".green().bold());
+    }
+
+    let crate_info = CrateInfo::load(&ext_path)?;
+    let metadata = ExtensionMetadata::load_optional(&ext_path)?;
+    let ext_id = match &metadata {
+        Some(m) => m.id.clone(),
+        None => dir_name(&ext_path)?,
+    };
+    let profile = if opts.debug { "debug" } else { "release" };
+
+    let platform = resolve_platform(&crate_info, opts.platform.as_deref(), profile)?;
+    let artifact = crate_info.artifact_path(&platform, profile);
+    if !artifact.exists() {
+        return Err(fail(
+            FailureClass::MissingFile,
+            format!(
+                "Built library not found: {}\nRun `neomind-ext build{}` first",
+                artifact.display(),
+                if opts.debug { "" } else { " --release" }
+            ),
+        ));
+    }
+
+    if !opts.json {
+        println!("{}: {}", "Extension".cyan(), ext_id);
+        println!("{}: {}", "Version".cyan(), crate_info.version);
+        println!("{}: {}", "Platform".cyan(), platform.package_key);
+    }
+
+    let files = collect_package_files(&ext_path, &platform, &artifact, opts.with_frontend, !opts.json)?;
+
+    let manifest = build_manifest(&ext_path, &crate_info, metadata.as_ref(), &ext_id, &platform, &files)?;
+    let manifest_json = serde_json::to_vec_pretty(&manifest)?;
+
+    let output_dir = match opts.output {
+        Some(dir) => PathBuf::from(dir),
+        None => cargo::find_workspace_root(&ext_path)
+            .unwrap_or_else(|| ext_path.clone())
+            .join("dist"),
+    };
+    fs::create_dir_all(&output_dir)
+        .with_context(|| format!("Failed to create {}", output_dir.display()))?;
+
+    let package_version = std::env::var("MARKET_VERSION").unwrap_or_else(|_| crate_info.version.clone());
+    let output_path = output_dir.join(package_file_name(&ext_id, &package_version, &platform));
+
+    write_package(&output_path, &manifest_json, &files)?;
+    verify_package(&output_path)?;
+
+    let checksum = checksum::sha256_file(&output_path)?;
+    record_checksum(&output_dir.join("checksums.txt"), &output_path, &checksum)?;
+
+    if opts.json {
+        let report = PackageReport {
+            extension: ext_id,
+            version: package_version,
+            platform: platform.package_key,
+            output: output_path.clone(),
+            sha256: checksum,
+            files: files.into_keys().collect(),
+        };
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("{}", "✅ Package created successfully!".green().bold());
+        println!("\n{}: {}", "Output".cyan(), output_path.display());
+        println!("{}: {}", "SHA-256".cyan(), checksum);
+    }
+
+    Ok(output_path)
+}
+
+/// Pick the platform to package: explicit `--platform`, a built WASM module, or the host.
+fn resolve_platform(crate_info: &CrateInfo, requested: Option<&str>, profile: &str) -> Result<Platform> {
+    if let Some(name) = requested {
+        return Platform::parse(name).ok_or_else(|| anyhow::anyhow!("Unknown platform: {}", name));
+    }
+
+    if crate_info.artifact_path(&platform::WASM, profile).exists() {
+        return Ok(platform::WASM);
+    }
+
+    Platform::host().ok_or_else(|| {
+        anyhow::anyhow!(
+            "Unsupported host platform {}-{}; pass --platform",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        )
+    })
+}
+
+/// `<id>-<version>-<platform>.nep` for native packages, `<id>-<version>.nep` for WASM.
+pub fn package_file_name(ext_id: &str, version: &str, platform: &Platform) -> String {
+    if platform.is_wasm() {
+        format!("{}-{}.nep", ext_id, version)
+    } else {
+        format!("{}-{}-{}.nep", ext_id, version, platform.package_key)
+    }
+}
+
+/// Path of the extension binary inside the package.
+pub fn binary_entry(platform: &Platform) -> String {
+    if platform.is_wasm() {
+        "binaries/extension.wasm".to_string()
+    } else {
+        format!("binaries/{}/extension.{}", platform.package_key, platform.lib_ext)
+    }
+}
+
+/// Archive path -> source file on disk
+fn collect_package_files(
+    ext_path: &Path,
+    platform: &Platform,
+    artifact: &Path,
+    require_frontend: bool,
+    verbose: bool,
+) -> Result<BTreeMap<String, PathBuf>> {
+    let mut files = BTreeMap::new();
+    files.insert(binary_entry(platform), artifact.to_path_buf());
+
+    let frontend_dist = ext_path.join("frontend").join("dist");
+    if frontend_dist.is_dir() {
+        for file in walk_files(&frontend_dist)? {
+            let relative = file.strip_prefix(&frontend_dist)?;
+            files.insert(format!("frontend/{}", archive_path(relative)), file);
+        }
+    } else if require_frontend {
+        return Err(fail(
+            FailureClass::MissingFile,
+            format!(
+                "--with-frontend given but {} does not exist. Build the frontend first (npm run build)",
+                frontend_dist.display()
+            ),
+        ));
+    }
+
+    let frontend_json = ext_path.join("frontend").join("frontend.json");
+    if frontend_json.exists() {
+        files.insert("frontend.json".to_string(), frontend_json);
+    }
+
+    for (entry, path) in collect_native_libs(ext_path, platform, verbose)? {
+        files.insert(entry, path);
+    }
+
+    for (entry, path) in collect_models(ext_path, verbose)? {
+        if verbose {
+            println!("  {} {}", "→".blue(), format!("Including {}", path.display()).dimmed());
+        }
+        files.insert(entry, path);
+    }
+
+    Ok(files)
+}
+
+/// Native libraries the extension loads at runtime, bundled next to it in
+/// `binaries/<platform>/`: ONNX Runtime for `ort`/`usls` extensions, and on
+/// Windows the FFmpeg DLLs from `FFMPEG_DIR`.
+fn collect_native_libs(ext_path: &Path, platform: &Platform, verbose: bool) -> Result<Vec<(String, PathBuf)>> {
+    if platform.is_wasm() {
+        return Ok(Vec::new());
+    }
+    let warn = |message: String| {
+        if verbose {
+            println!("  {} {}", "⚠".yellow(), message);
+        }
+    };
+    let binaries_dir = format!("binaries/{}", platform.package_key);
+    let mut bundled = Vec::new();
+
+    if cargo::depends_on(ext_path, "ort")? || cargo::depends_on(ext_path, "usls")? {
+        match find_ort(&ort_search_dirs(platform), platform)? {
+            Some(path) => bundled.push(path),
+            None => warn(format!(
+                "ONNX Runtime not found for {}; set ORT_LIB_PATH to bundle it, \
+                 otherwise the host must provide it",
+                platform.package_key
+            )),
+        }
+    }
+
+    let uses_ffmpeg = cargo::depends_on(ext_path, "ffmpeg-next")?;
+    if platform.lib_ext == "dll" {
+        let dirs: Vec<PathBuf> = std::env::var_os("FFMPEG_DIR")
+            .map(PathBuf::from)
+            .map(|dir| vec![dir.join("bin"), dir.join("lib")])
+            .unwrap_or_default();
+        let dlls = find_dlls(&dirs, &WINDOWS_DLLS)?;
+        if dlls.is_empty() && uses_ffmpeg {
+            warn("No FFmpeg DLLs found; set FFMPEG_DIR to bundle them".to_string());
+        }
+        bundled.extend(dlls);
+    } else if uses_ffmpeg {
+        warn(format!(
+            "FFmpeg libraries are not bundled for {}; the host must provide them (use build.sh to bundle them)",
+            platform.package_key
+        ));
+    }
+
+    if !bundled.is_empty() && Platform::host() != Some(*platform) {
+        warn(format!(
+            "Native libraries come from this host; make sure they are built for {}",
+            platform.triple
+        ));
+    }
+
+    bundled
+        .into_iter()
+        .map(|path| {
+            if verbose {
+                println!("  {} {}", "→".blue(), format!("Bundling {}", path.display()).dimmed());
+            }
+            Ok((format!("{}/{}", binaries_dir, file_name(&path)?), path))
+        })
+        .collect()
+}
+
+/// Directories build.sh looks in for ONNX Runtime: `ORT_LIB_PATH`, then
+/// `LD_LIBRARY_PATH` for Linux targets. The directory of `ORT_DYLIB_PATH` is
+/// tried in between, since that is what the extensions load at runtime.
+fn ort_search_dirs(platform: &Platform) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(dir) = std::env::var_os("ORT_LIB_PATH") {
+        dirs.push(PathBuf::from(dir));
+    }
+    if let Some(dir) = std::env::var_os("ORT_DYLIB_PATH").and_then(|p| PathBuf::from(p).parent().map(Path::to_path_buf)) {
+        dirs.push(dir);
+    }
+    if platform.lib_ext == "so" {
+        if let Some(paths) = std::env::var_os("LD_LIBRARY_PATH") {
+            dirs.extend(std::env::split_paths(&paths));
+        }
+    }
+    dirs
+}
+
+/// First ONNX Runtime library for `platform` in `dirs`, preferring the
+/// unversioned name the extensions point `ORT_DYLIB_PATH` at.
+fn find_ort(dirs: &[PathBuf], platform: &Platform) -> Result<Option<PathBuf>> {
+    let (exact, prefix, suffix) = match platform.lib_ext {
+        "dylib" => ("libonnxruntime.dylib", "libonnxruntime", ".dylib"),
+        "dll" => ("onnxruntime.dll", "onnxruntime", ".dll"),
+        _ => ("libonnxruntime.so", "libonnxruntime.so", ""),
+    };
+    for dir in dirs.iter().filter(|d| d.is_dir()) {
+        if dir.join(exact).is_file() {
+            return Ok(Some(dir.join(exact)));
+        }
+        let mut candidates: Vec<PathBuf> = fs::read_dir(dir)?
+            .map(|entry| Ok(entry?.path()))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .filter(|path| {
+                path.is_file()
+                    && path
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .map(|n| n.starts_with(prefix) && n.ends_with(suffix))
+                        .unwrap_or(false)
+            })
+            .collect();
+        candidates.sort();
+        if let Some(path) = candidates.into_iter().next() {
+            return Ok(Some(path));
+        }
+    }
+    Ok(None)
+}
+
+/// The first `<name>*.dll` in `dirs` for each of `names`, skipping DLLs
+/// already picked up under an earlier, shorter name (`theora` vs `theoraenc`).
+fn find_dlls(dirs: &[PathBuf], names: &[&str]) -> Result<Vec<PathBuf>> {
+    let mut available = Vec::new();
+    for dir in dirs.iter().filter(|d| d.is_dir()) {
+        let mut dlls: Vec<PathBuf> = fs::read_dir(dir)?
+            .map(|entry| Ok(entry?.path()))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .filter(|path| path.is_file() && path.extension().and_then(|e| e.to_str()) == Some("dll"))
+            .collect();
+        dlls.sort();
+        available.extend(dlls);
+    }
+
+    let mut found: Vec<PathBuf> = Vec::new();
+    for name in names {
+        let matching = available.iter().find(|path| {
+            let file = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+            file.starts_with(name) && !found.iter().any(|f| f.file_name() == path.file_name())
+        });
+        if let Some(path) = matching {
+            found.push(path.clone());
+        }
+    }
+    Ok(found)
+}
+
+/// Model files to bundle. With a `models.toml` only declared `bundle = true`
+/// models go in, and each must be pinned and verify; otherwise every
+/// model-like file in `models/`.
+fn collect_models(ext_path: &Path, verbose: bool) -> Result<Vec<(String, PathBuf)>> {
+    let models_dir = ext_path.join("models");
+    let mut bundled = Vec::new();
+
+    if let Some(declared) = ModelsManifest::load_optional(ext_path)? {
+        let checks = models::verify(ext_path, &declared)?;
+        if checks.has_failures() {
+            if verbose {
+                checks.print();
+            }
+            return Err(fail(
+                FailureClass::ValidationFailure,
+                format!(
+                    "Declared models failed verification: {}\nRun `neomind-ext models fetch` first",
+                    checks.failed_names().join(", ")
+                ),
+            ));
+        }
+        let unpinned: Vec<&str> = declared
+            .models
+            .iter()
+            .filter(|m| m.bundle && m.sha256.is_none())
+            .map(|m| m.file.as_str())
+            .collect();
+        if !unpinned.is_empty() {
+            return Err(fail(
+                FailureClass::ValidationFailure,
+                format!(
+                    "Bundled models have no sha256 pinned in models.toml: {}
+                     Run `neomind-ext models fetch` and pin the sha256/size it prints, or set `bundle = false`",
+                    unpinned.join(", ")
+                ),
+            ));
+        }
+        for model in declared.models.iter().filter(|m| m.bundle) {
+            bundled.push((format!("models/{}", model.file), model.path(ext_path)));
+        }
+        if models_dir.is_dir() {
+            for entry in fs::read_dir(&models_dir)? {
+                let name = file_name(&entry?.path())?;
+                if verbose && !name.starts_with('.') && !declared.models.iter().any(|m| m.file == name) {
+                    println!("  {} {}", "→".blue(), format!("Skipping models/{} (not in models.toml)", name).dimmed());
+                }
+            }
+        }
+        return Ok(bundled);
+    }
+
+    if models_dir.is_dir() {
+        for entry in fs::read_dir(&models_dir)? {
+            let path = entry?.path();
+            let is_model = path
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(|e| MODEL_EXTENSIONS.contains(&e))
+                .unwrap_or(false);
+            if path.is_file() && is_model {
+                bundled.push((format!("models/{}", file_name(&path)?), path));
+            }
+        }
+    }
+    Ok(bundled)
+}
+
+fn build_manifest(
+    ext_path: &Path,
+    crate_info: &CrateInfo,
+    metadata: Option<&ExtensionMetadata>,
+    ext_id: &str,
+    platform: &Platform,
+    files: &BTreeMap<String, PathBuf>,
+) -> Result<PackageManifest> {
+    let name = metadata
+        .map(|m| m.name.clone())
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| manifest::default_display_name(ext_id));
+
+    let has_models = files.keys().any(|entry| entry.starts_with("models/"));
+
+    let mut binaries = BTreeMap::new();
+    binaries.insert(platform.package_key.to_string(), binary_entry(platform));
+
+    let checksums = files
+        .iter()
+        .map(|(entry, source)| Ok((entry.clone(), checksum::sha256_file(source)?)))
+        .collect::<Result<BTreeMap<_, _>>>()?;
+
+    Ok(PackageManifest {
+        format: manifest::PACKAGE_FORMAT.to_string(),
+        format_version: manifest::PACKAGE_FORMAT_VERSION.to_string(),
+        abi_version: manifest::PACKAGE_ABI_VERSION,
+        id: ext_id.to_string(),
+        name,
+        version: crate_info.version.clone(),
+        sdk_version: manifest::PACKAGE_SDK_VERSION.to_string(),
+        ext_type: if platform.is_wasm() { "wasm" } else { "native" }.to_string(),
+        binaries,
+        frontend: PackageFrontend {
+            components: manifest::dashboard_components(ext_path, ext_id)?,
+        },
+        models: has_models.then(|| "models/".to_string()),
+        checksums,
+    })
+}
+
+fn write_package(output: &Path, manifest_json: &[u8], files: &BTreeMap<String, PathBuf>) -> Result<()> {
+    let file = fs::File::create(output)
+        .with_context(|| format!("Failed to create {}", output.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .unix_permissions(0o644);
+
+    zip.start_file("manifest.json", options)?;
+    zip.write_all(manifest_json)?;
+
+    let mut dirs: Vec<String> = Vec::new();
+    for (entry, source) in files {
+        // Emit parent directory entries the way build.sh does
+        let parts: Vec<&str> = entry.split('/').collect();
+        for depth in 1..parts.len() {
+            let dir = format!("{}/", parts[..depth].join("/"));
+            if !dirs.contains(&dir) {
+                zip.add_directory(dir.as_str(), options)?;
+                dirs.push(dir);
+            }
+        }
+
+        let data = fs::read(source).with_context(|| format!("Failed to read {}", source.display()))?;
+        zip.start_file(entry.as_str(), options)?;
+        zip.write_all(&data)?;
+    }
+
+    zip.finish()?;
+    Ok(())
+}
+
+/// Re-open the archive and read every entry so CRC errors surface now, not on install.
+fn verify_package(path: &Path) -> Result<()> {
+    let file = fs::File::open(path)?;
+    let mut archive = zip::ZipArchive::new(file)
+        .with_context(|| format!("Package is not a valid ZIP: {}", path.display()))?;
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        let name = entry.name().to_string();
+        let mut sink = Vec::new();
+        entry
+            .read_to_end(&mut sink)
+            .with_context(|| format!("CRC check failed for: {}", name))?;
+    }
+    Ok(())
+}
+
+/// Add or replace the package's line in `checksums.txt` (sha256sum format).
+fn record_checksum(checksums: &Path, package: &Path, checksum: &str) -> Result<()> {
+    let name = file_name(package)?;
+    let existing = fs::read_to_string(checksums).unwrap_or_default();
+    let mut lines: Vec<String> = existing
+        .lines()
+        .filter(|line| line.split_whitespace().nth(1) != Some(name.as_str()))
+        .map(String::from)
+        .collect();
+    lines.push(format!("{}  {}", checksum, name));
+    fs::write(checksums, lines.join("\n") + "\n")?;
+    Ok(())
+}
+
+fn walk_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(walk_files(&path)?);
+        } else {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Forward-slash path for ZIP entries regardless of host OS
+fn archive_path(relative: &Path) -> String {
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn file_name(path: &Path) -> Result<String> {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .map(String::from)
+        .ok_or_else(|| anyhow::anyhow!("Invalid file name: {}", path.display()))
+}
+
+pub fn dir_name(path: &Path) -> Result<String> {
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    file_name(&canonical)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fake_extension(root: &Path) -> PathBuf {
+        let ext = root.join("demo-ext");
+        fs::create_dir_all(ext.join("frontend/dist/assets")).unwrap();
+        fs::create_dir_all(ext.join("models")).unwrap();
+        fs::write(
+            ext.join("Cargo.toml"),
+            "[package]\nname = \"demo-ext\"\nversion = \"1.2.3\"\n\n[lib]\nname = \"neomind_extension_demo_ext\"\n",
+        )
+        .unwrap();
+        fs::write(
+            ext.join("metadata.json"),
+            r#"{"id": "demo-ext", "name": "demo ext", "version": "1.2.3"}"#,
+        )
+        .unwrap();
+        fs::write(ext.join("frontend/dist/demo.umd.cjs"), "module.exports = {}").unwrap();
+        fs::write(ext.join("frontend/dist/assets/style.css"), "body {}").unwrap();
+        fs::write(ext.join("models/model.onnx"), [0u8; 16]).unwrap();
+        fs::write(ext.join("models/README.md"), "not bundled").unwrap();
+
+        let platform = Platform::parse("linux_amd64").unwrap();
+        let lib_dir = ext.join("target").join(platform.triple).join("release");
+        fs::create_dir_all(&lib_dir).unwrap();
+        fs::write(lib_dir.join("libneomind_extension_demo_ext.so"), b"\x7fELF").unwrap();
+        ext
+    }
+
+    #[test]
+    fn test_package_layout_and_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let ext = fake_extension(dir.path());
+        let out = dir.path().join("out");
+
+        let path = cmd_package(PackageOptions {
+            path: Some(ext.to_string_lossy().to_string()),
+            with_frontend: true,
+            debug: false,
+            platform: Some("linux-x86_64".to_string()),
+            output: Some(out.to_string_lossy().to_string()),
+            json: false,
+        })
+        .unwrap();
+
+        assert_eq!(path, out.join("demo-ext-1.2.3-linux_amd64.nep"));
+
+        let mut archive = zip::ZipArchive::new(fs::File::open(&path).unwrap()).unwrap();
+        let names: Vec<String> = archive.file_names().map(String::from).collect();
+        for expected in [
+            "manifest.json",
+            "binaries/linux_amd64/extension.so",
+            "frontend/demo.umd.cjs",
+            "frontend/assets/style.css",
+            "models/model.onnx",
+        ] {
+            assert!(names.iter().any(|n| n == expected), "missing {expected} in {names:?}");
+        }
+        assert!(!names.iter().any(|n| n == "models/README.md"));
+
+        let mut manifest = String::new();
+        archive.by_name("manifest.json").unwrap().read_to_string(&mut manifest).unwrap();
+        let manifest: serde_json::Value = serde_json::from_str(&manifest).unwrap();
+        assert_eq!(manifest["format"], "neomind-extension-package");
+        assert_eq!(manifest["id"], "demo-ext");
+        assert_eq!(manifest["version"], "1.2.3");
+        assert_eq!(manifest["type"], "native");
+        assert_eq!(manifest["binaries"]["linux_amd64"], "binaries/linux_amd64/extension.so");
+        assert_eq!(manifest["models"], "models/");
+        assert_eq!(
+            manifest["checksums"]["models/model.onnx"],
+            checksum::sha256_hex(&[0u8; 16])
+        );
+
+        let checksums = fs::read_to_string(out.join("checksums.txt")).unwrap();
+        assert!(checksums.contains("demo-ext-1.2.3-linux_amd64.nep"));
+    }
+
+    #[test]
+    fn test_missing_frontend_is_an_error_with_flag() {
+        let dir = tempfile::tempdir().unwrap();
+        let ext = fake_extension(dir.path());
+        fs::remove_dir_all(ext.join("frontend")).unwrap();
+
+        let result = cmd_package(PackageOptions {
+            path: Some(ext.to_string_lossy().to_string()),
+            with_frontend: true,
+            debug: false,
+            platform: Some("linux_amd64".to_string()),
+            output: Some(dir.path().join("out").to_string_lossy().to_string()),
+            json: false,
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_models_toml_limits_bundled_models() {
+        let dir = tempfile::tempdir().unwrap();
+        let ext = fake_extension(dir.path());
+        fs::write(ext.join("models/vocab.txt"), "a\nb\n").unwrap();
+        fs::write(
+            ext.join("models.toml"),
+            format!(
+                "[[model]]\nfile = \"model.onnx\"\nsha256 = \"{}\"\n\n\
+                 [[model]]\nfile = \"vocab.txt\"\nbundle = false\n",
+                checksum::sha256_hex(&[0u8; 16])
+            ),
+        )
+        .unwrap();
+        let options = |out: &str| PackageOptions {
+            path: Some(ext.to_string_lossy().to_string()),
+            with_frontend: false,
+            debug: false,
+            platform: Some("linux_amd64".to_string()),
+            output: Some(dir.path().join(out).to_string_lossy().to_string()),
+            json: false,
+        };
+
+        let path = cmd_package(options("out")).unwrap();
+        let archive = zip::ZipArchive::new(fs::File::open(&path).unwrap()).unwrap();
+        let models: Vec<&str> = archive
+            .file_names()
+            .filter(|n| n.starts_with("models/") && !n.ends_with('/'))
+            .collect();
+        assert_eq!(models, ["models/model.onnx"]);
+
+        fs::write(ext.join("models/model.onnx"), [1u8; 16]).unwrap();
+        assert!(cmd_package(options("out2")).is_err());
+
+        // A bundled model without a pinned sha256 is refused even though it is present
+        fs::write(ext.join("models.toml"), "[[model]]\nfile = \"model.onnx\"\n").unwrap();
+        let err = cmd_package(options("out3")).unwrap_err();
+        assert!(format!("{:#}", err).contains("no sha256 pinned"));
+        assert!(!dir.path().join("out3").exists());
+    }
+
+    #[test]
+    fn test_find_ort_prefers_unversioned_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let linux = Platform::parse("linux_amd64").unwrap();
+        let dirs = [dir.path().join("missing"), dir.path().to_path_buf()];
+        assert_eq!(find_ort(&dirs, &linux).unwrap(), None);
+
+        fs::write(dir.path().join("libonnxruntime.so.1.22.0"), "").unwrap();
+        fs::write(dir.path().join("libonnxruntime.1.22.0.dylib"), "").unwrap();
+        assert_eq!(find_ort(&dirs, &linux).unwrap(), Some(dir.path().join("libonnxruntime.so.1.22.0")));
+
+        fs::write(dir.path().join("libonnxruntime.so"), "").unwrap();
+        assert_eq!(find_ort(&dirs, &linux).unwrap(), Some(dir.path().join("libonnxruntime.so")));
+
+        let mac = Platform::parse("darwin_aarch64").unwrap();
+        assert_eq!(find_ort(&dirs, &mac).unwrap(), Some(dir.path().join("libonnxruntime.1.22.0.dylib")));
+    }
+
+    #[test]
+    fn test_find_dlls_takes_first_match_per_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let bin = dir.path().join("bin");
+        fs::create_dir_all(&bin).unwrap();
+        for dll in ["avcodec-61.dll", "avutil-59.dll", "theoradec.dll", "theoraenc.dll", "avcodec-61.lib"] {
+            fs::write(bin.join(dll), "").unwrap();
+        }
+
+        let found = find_dlls(&[bin.clone(), dir.path().join("lib")], &WINDOWS_DLLS).unwrap();
+        let names: Vec<String> = found.iter().map(|p| file_name(p).unwrap()).collect();
+        assert_eq!(names, ["avcodec-61.dll", "avutil-59.dll", "theoradec.dll", "theoraenc.dll"]);
+    }
+
+    #[test]
+    fn test_native_libs_skipped_without_native_dependencies() {
+        let dir = tempfile::tempdir().unwrap();
+        let ext = fake_extension(dir.path());
+        let platform = Platform::parse("linux_amd64").unwrap();
+        assert!(collect_native_libs(&ext, &platform, false).unwrap().is_empty());
+        assert!(collect_native_libs(&ext, &platform::WASM, false).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_wasm_package_names() {
+        assert_eq!(package_file_name("wasm-demo", "2.7.0", &platform::WASM), "wasm-demo-2.7.0.nep");
+        assert_eq!(binary_entry(&platform::WASM), "binaries/extension.wasm");
+    }
+}
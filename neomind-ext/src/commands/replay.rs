@@ -0,0 +1,296 @@
+//! `neomind-ext replay` - feed a JSONL recording (see `utils::recording`) to a
+//! loaded extension and report capability calls, errors and latency per record.
+
+use anyhow::Result;
+use colored::Colorize;
+use serde::Serialize;
+use serde_json::Value;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::commands::run::parse_json_arg;
+use crate::utils::exit::{fail, FailureClass};
+use crate::utils::ffi::{self, ExtensionInstance, ExtensionLibrary};
+use crate::utils::host::{self, CapabilityCall};
+use crate::utils::recording::{self, Record};
+
+pub struct ReplayOptions {
+    pub file: String,
+    pub path: Option<String>,
+    pub lib: Option<String>,
+    pub config: Option<String>,
+    /// Timing factor: 1.0 replays at the recorded pace, 0 without any delay
+    pub speed: f64,
+    pub json: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecordReport {
+    /// 1-based position in the recording (comments and blank lines excluded)
+    pub index: usize,
+    /// `command` or the event type
+    pub kind: String,
+    pub name: Option<String>,
+    pub device_id: Option<String>,
+    pub metric: Option<String>,
+    pub latency_ms: f64,
+    pub capability_calls: Vec<CapabilityCall>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReplayReport {
+    pub recording: String,
+    pub events: usize,
+    pub commands: usize,
+    pub failed: usize,
+    pub mean_event_latency_ms: f64,
+    pub max_event_latency_ms: f64,
+    pub records: Vec<RecordReport>,
+}
+
+pub fn cmd_replay(opts: ReplayOptions) -> Result<()> {
+    if !opts.speed.is_finite() || opts.speed < 0.0 {
+        anyhow::bail!("--speed must be a non-negative number");
+    }
+    let records = recording::load(Path::new(&opts.file))?;
+    let ext_path = crate::get_extension_path(opts.path)?;
+    let lib_path = ffi::resolve_library(&ext_path, opts.lib.as_deref())?;
+    let config = match &opts.config {
+        Some(config) => parse_json_arg(config)?,
+        None => Value::Object(Default::default()),
+    };
+
+    let library = ExtensionLibrary::load(&lib_path)?;
+    host::install(&library)?;
+    let instance = library.create(&config)?;
+    host::take_calls();
+
+    if !opts.json {
+        println!(
+            "{} {} record(s) from {} (speed {})",
+            "⏵ Replaying".green().bold(),
+            records.len(),
+            opts.file,
+            if opts.speed == 0.0 { "unthrottled".to_string() } else { format!("{}x", opts.speed) }
+        );
+    }
+
+    let mut reports = Vec::with_capacity(records.len());
+    let mut last_timestamp = None;
+    for (i, record) in records.iter().enumerate() {
+        if let Record::Event(event) = record {
+            std::thread::sleep(replay_delay(last_timestamp, event.timestamp, opts.speed));
+            last_timestamp = event.timestamp.or(last_timestamp);
+        }
+        let report = replay_record(&instance, i + 1, record);
+        if !opts.json {
+            print_record(&report);
+        }
+        reports.push(report);
+    }
+
+    let report = summarize(&opts.file, reports);
+    if opts.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!();
+        println!(
+            "{}: {} event(s), {} command(s), {} failed",
+            "Summary".cyan(),
+            report.events,
+            report.commands,
+            report.failed
+        );
+        println!(
+            "{}: mean {:.1} ms, max {:.1} ms",
+            "Event latency".cyan(),
+            report.mean_event_latency_ms,
+            report.max_event_latency_ms
+        );
+    }
+
+    if report.failed > 0 {
+        return Err(fail(FailureClass::TestFailure, format!("{} record(s) failed during replay", report.failed)));
+    }
+    Ok(())
+}
+
+/// Time to wait before an event captured at `current`, given the previous event at `previous`.
+fn replay_delay(previous: Option<i64>, current: Option<i64>, speed: f64) -> Duration {
+    match (previous, current) {
+        (Some(previous), Some(current)) if speed > 0.0 && current > previous => {
+            Duration::from_secs_f64((current - previous) as f64 / 1000.0 / speed)
+        }
+        _ => Duration::ZERO,
+    }
+}
+
+fn replay_record(instance: &ExtensionInstance, index: usize, record: &Record) -> RecordReport {
+    host::take_calls();
+    let started = Instant::now();
+    let (result, mut report) = match record {
+        Record::Command { command, args } => (
+            instance.execute_command(command, args).map(|_| ()),
+            RecordReport {
+                index,
+                kind: "command".to_string(),
+                name: Some(command.clone()),
+                device_id: args.get("device_id").and_then(|v| v.as_str()).map(String::from),
+                metric: None,
+                latency_ms: 0.0,
+                capability_calls: Vec::new(),
+                error: None,
+            },
+        ),
+        Record::Event(event) => (
+            instance.handle_event(&event.event_type, &event.payload),
+            RecordReport {
+                index,
+                kind: event.event_type.clone(),
+                name: None,
+                device_id: event.device_id().map(String::from),
+                metric: event.metric().map(String::from),
+                latency_ms: 0.0,
+                capability_calls: Vec::new(),
+                error: None,
+            },
+        ),
+    };
+    report.latency_ms = started.elapsed().as_secs_f64() * 1000.0;
+    report.capability_calls = host::take_calls();
+    report.error = result.err().map(|e| format!("{:#}", e));
+    report
+}
+
+fn summarize(recording: &str, records: Vec<RecordReport>) -> ReplayReport {
+    let latencies: Vec<f64> = records
+        .iter()
+        .filter(|r| r.kind != "command")
+        .map(|r| r.latency_ms)
+        .collect();
+    let mean = if latencies.is_empty() {
+        0.0
+    } else {
+        latencies.iter().sum::<f64>() / latencies.len() as f64
+    };
+    ReplayReport {
+        recording: recording.to_string(),
+        events: latencies.len(),
+        commands: records.len() - latencies.len(),
+        failed: records.iter().filter(|r| r.error.is_some()).count(),
+        mean_event_latency_ms: mean,
+        max_event_latency_ms: latencies.iter().copied().fold(0.0, f64::max),
+        records,
+    }
+}
+
+fn print_record(report: &RecordReport) {
+    let mark = if report.error.is_some() { "✗".red() } else { "✓".green() };
+    let subject = match (&report.name, &report.device_id, &report.metric) {
+        (Some(name), _, _) => name.clone(),
+        (None, Some(device), Some(metric)) => format!("{}/{}", device, metric),
+        (None, Some(device), None) => device.clone(),
+        _ => String::new(),
+    };
+    println!(
+        "  {} #{:<4} {:<12} {:<32} {:>8.1} ms  {} call(s)",
+        mark,
+        report.index,
+        report.kind,
+        subject,
+        report.latency_ms,
+        report.capability_calls.len()
+    );
+    for call in &report.capability_calls {
+        println!("      {} {}", "→".yellow(), call.summary());
+    }
+    if let Some(error) = &report.error {
+        println!("      {}", error.red());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(kind: &str, latency_ms: f64, error: Option<&str>) -> RecordReport {
+        RecordReport {
+            index: 1,
+            kind: kind.to_string(),
+            name: None,
+            device_id: None,
+            metric: None,
+            latency_ms,
+            capability_calls: Vec::new(),
+            error: error.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_replay_delay_scales_with_speed() {
+        assert_eq!(replay_delay(Some(1000), Some(1500), 1.0), Duration::from_millis(500));
+        assert_eq!(replay_delay(Some(1000), Some(1500), 10.0), Duration::from_millis(50));
+        assert_eq!(replay_delay(Some(1000), Some(1500), 0.0), Duration::ZERO);
+        assert_eq!(replay_delay(None, Some(1500), 1.0), Duration::ZERO);
+        // Out-of-order captures do not stall the replay
+        assert_eq!(replay_delay(Some(2000), Some(1500), 1.0), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_summary_excludes_commands_from_latency() {
+        let report = summarize(
+            "frames.jsonl",
+            vec![
+                record("command", 100.0, None),
+                record("DeviceMetric", 10.0, None),
+                record("DeviceMetric", 30.0, Some("decode failed")),
+            ],
+        );
+        assert_eq!(report.events, 2);
+        assert_eq!(report.commands, 1);
+        assert_eq!(report.failed, 1);
+        assert_eq!(report.mean_event_latency_ms, 20.0);
+        assert_eq!(report.max_event_latency_ms, 30.0);
+    }
+
+    #[test]
+    fn test_replay_against_built_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        let lib = ffi::demo_library(dir.path());
+        let recording = dir.path().join("session.jsonl");
+        std::fs::write(
+            &recording,
+            "{\"command\": \"increment\", \"args\": {\"amount\": 3}}\n\
+             {\"timestamp\": 1000, \"payload\": {\"device_id\": \"cam-1\", \"metric\": \"image\", \"value\": \"aGVsbG8=\"}}\n\
+             {\"timestamp\": 1010, \"payload\": {\"device_id\": \"cam-1\", \"metric\": \"image\", \"value\": \"aGVsbG8=\"}}\n",
+        )
+        .unwrap();
+        let options = |file: &Path| ReplayOptions {
+            file: file.to_string_lossy().to_string(),
+            path: Some(concat!(env!("CARGO_MANIFEST_DIR"), "/../extensions/wasm-demo").to_string()),
+            lib: Some(lib.to_string_lossy().to_string()),
+            config: None,
+            speed: 0.0,
+            json: true,
+        };
+        cmd_replay(options(&recording)).unwrap();
+
+        let library = ExtensionLibrary::load(&lib).unwrap();
+        host::install(&library).unwrap();
+        let instance = library.create(&Value::Object(Default::default())).unwrap();
+        let records = recording::load(&recording).unwrap();
+        let reports: Vec<RecordReport> =
+            records.iter().enumerate().map(|(i, r)| replay_record(&instance, i + 1, r)).collect();
+        assert!(reports.iter().all(|r| r.error.is_none()), "{:?}", reports);
+        assert_eq!(reports[1].kind, "DeviceMetric");
+        assert_eq!(reports[1].device_id.as_deref(), Some("cam-1"));
+        let get = instance.execute_command("get", &serde_json::json!({})).unwrap();
+        assert_eq!(get["counter"], 3);
+
+        // Commands the extension rejects fail the replay
+        let broken = dir.path().join("broken.jsonl");
+        std::fs::write(&broken, "{\"command\": \"no_such_command\"}\n").unwrap();
+        assert!(cmd_replay(options(&broken)).is_err());
+    }
+}
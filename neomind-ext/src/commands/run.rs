@@ -0,0 +1,259 @@
+//! `neomind-ext run` - load a built extension into a stub host and drive it
+//! from a REPL or a single command line.
+//!
+//! ```text
+//! invoke <command> [json-args]
+//! metrics
+//! configure <json>
+//! event <EventType> <json>
+//! ```
+//!
+//! JSON arguments may be given inline or as `@path/to/file.json`. Capability
+//! calls made while handling an action (e.g. `device_metrics_write`) are
+//! recorded by the stub host and printed after the result.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde_json::Value;
+use std::fs;
+use std::io::{BufRead, Write};
+
+use crate::utils::ffi::{self, ExtensionInstance, ExtensionLibrary};
+use crate::utils::host;
+
+pub struct RunOptions {
+    pub path: Option<String>,
+    pub lib: Option<String>,
+    pub config: Option<String>,
+    /// One-shot action; the REPL starts when empty
+    pub action: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    Invoke { command: String, args: Value },
+    Metrics,
+    Configure(Value),
+    Event { event_type: String, payload: Value },
+    Help,
+    Quit,
+}
+
+impl Action {
+    pub fn parse(line: &str) -> Result<Self> {
+        let line = line.trim();
+        let (verb, rest) = split_word(line);
+        match verb {
+            "invoke" => {
+                let (command, args) = split_word(rest);
+                if command.is_empty() {
+                    anyhow::bail!("usage: invoke <command> [json-args]");
+                }
+                let args = if args.is_empty() {
+                    Value::Object(Default::default())
+                } else {
+                    parse_json_arg(args)?
+                };
+                Ok(Action::Invoke {
+                    command: command.to_string(),
+                    args,
+                })
+            }
+            "metrics" => Ok(Action::Metrics),
+            "configure" => {
+                if rest.is_empty() {
+                    anyhow::bail!("usage: configure <json>");
+                }
+                Ok(Action::Configure(parse_json_arg(rest)?))
+            }
+            "event" => {
+                let (event_type, payload) = split_word(rest);
+                if event_type.is_empty() || payload.is_empty() {
+                    anyhow::bail!("usage: event <EventType> <json>");
+                }
+                Ok(Action::Event {
+                    event_type: event_type.to_string(),
+                    payload: parse_json_arg(payload)?,
+                })
+            }
+            "help" | "?" => Ok(Action::Help),
+            "quit" | "exit" => Ok(Action::Quit),
+            other => anyhow::bail!("Unknown action '{}' (try `help`)", other),
+        }
+    }
+}
+
+fn split_word(s: &str) -> (&str, &str) {
+    match s.split_once(char::is_whitespace) {
+        Some((word, rest)) => (word, rest.trim()),
+        None => (s, ""),
+    }
+}
+
+/// Inline JSON, or `@file` to read it from disk.
+pub fn parse_json_arg(arg: &str) -> Result<Value> {
+    match arg.strip_prefix('@') {
+        Some(path) => {
+            let content = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path))?;
+            serde_json::from_str(&content).with_context(|| format!("Failed to parse {}", path))
+        }
+        None => serde_json::from_str(arg).with_context(|| format!("Invalid JSON: {}", arg)),
+    }
+}
+
+pub fn cmd_run(opts: RunOptions) -> Result<()> {
+    let ext_path = crate::get_extension_path(opts.path)?;
+    let lib_path = ffi::resolve_library(&ext_path, opts.lib.as_deref())?;
+    let config = match &opts.config {
+        Some(config) => parse_json_arg(config)?,
+        None => Value::Object(Default::default()),
+    };
+
+    let library = ExtensionLibrary::load(&lib_path)?;
+    host::install(&library)?;
+    let descriptor = library.descriptor()?;
+    let instance = library.create(&config)?;
+    print_created_calls();
+
+    if !opts.action.is_empty() {
+        let action = Action::parse(&opts.action.join(" "))?;
+        return execute(&instance, action).map(|_| ());
+    }
+
+    let metadata = &descriptor["metadata"];
+    println!(
+        "{} {} v{}",
+        "▶ Loaded".green().bold(),
+        metadata["id"].as_str().unwrap_or_default(),
+        metadata["version"].as_str().unwrap_or_default()
+    );
+    println!("{}: {}", "Library".cyan(), lib_path.display());
+    println!("Type `help` for available actions, `quit` to exit.");
+    repl(&instance)
+}
+
+fn repl(instance: &ExtensionInstance) -> Result<()> {
+    let stdin = std::io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("{} ", "neomind>".cyan());
+        std::io::stdout().flush()?;
+        let Some(line) = lines.next().transpose()? else {
+            break;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let result = Action::parse(&line).and_then(|action| execute(instance, action));
+        match result {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => println!("{} {:#}", "✗".red(), e),
+        }
+    }
+    Ok(())
+}
+
+/// Run one action; returns `false` when the REPL should exit.
+fn execute(instance: &ExtensionInstance, action: Action) -> Result<bool> {
+    match action {
+        Action::Help => {
+            print_help();
+            return Ok(true);
+        }
+        Action::Quit => return Ok(false),
+        _ => {}
+    }
+    let (result, calls) = perform(instance, &action);
+
+    let outcome = result.and_then(|value| {
+        match value {
+            Some(value) => println!("{}", serde_json::to_string_pretty(&value)?),
+            None => println!("{}", "ok".green()),
+        }
+        Ok(())
+    });
+    // Capability calls are shown even when the action itself failed
+    print_calls(&calls);
+    outcome.map(|_| true)
+}
+
+/// Call into the extension for `action` and collect the capability calls it made.
+/// `Help` and `Quit` are handled by the caller and do nothing here.
+pub fn perform(instance: &ExtensionInstance, action: &Action) -> (Result<Option<Value>>, Vec<host::CapabilityCall>) {
+    host::take_calls();
+    let result = match action {
+        Action::Invoke { command, args } => instance.execute_command(command, args).map(Some),
+        Action::Metrics => instance.produce_metrics().map(Some),
+        Action::Configure(config) => instance.configure(config).map(|_| None),
+        Action::Event { event_type, payload } => instance.handle_event(event_type, payload).map(|_| None),
+        Action::Help | Action::Quit => Ok(None),
+    };
+    (result, host::take_calls())
+}
+
+pub fn print_created_calls() {
+    let calls = host::take_calls();
+    if !calls.is_empty() {
+        println!("{}", "Capability calls during create:".dimmed());
+        print_calls(&calls);
+    }
+}
+
+pub fn print_calls(calls: &[host::CapabilityCall]) {
+    for call in calls {
+        println!("  {} {}", "→".yellow(), call.summary());
+    }
+}
+
+pub fn print_help() {
+    println!("  invoke <command> [json-args]   execute_command");
+    println!("  metrics                        produce_metrics");
+    println!("  configure <json>               configure");
+    println!("  event <EventType> <json>       handle_event (e.g. event DeviceMetric @frame.json)");
+    println!("  quit");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_actions() {
+        assert_eq!(
+            Action::parse("invoke get_status").unwrap(),
+            Action::Invoke {
+                command: "get_status".into(),
+                args: json!({})
+            }
+        );
+        assert_eq!(
+            Action::parse("invoke bind_device {\"device_id\": \"cam-1\"}").unwrap(),
+            Action::Invoke {
+                command: "bind_device".into(),
+                args: json!({ "device_id": "cam-1" })
+            }
+        );
+        assert_eq!(
+            Action::parse("  event DeviceMetric {\"metric\": \"image\"}").unwrap(),
+            Action::Event {
+                event_type: "DeviceMetric".into(),
+                payload: json!({ "metric": "image" })
+            }
+        );
+        assert_eq!(Action::parse("metrics").unwrap(), Action::Metrics);
+        assert!(Action::parse("configure").is_err());
+        assert!(Action::parse("invoke x {not json").is_err());
+        assert!(Action::parse("frobnicate").is_err());
+    }
+
+    #[test]
+    fn test_json_arg_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        fs::write(&path, "{\"confidence\": 0.5}").unwrap();
+        let value = parse_json_arg(&format!("@{}", path.display())).unwrap();
+        assert_eq!(value, json!({ "confidence": 0.5 }));
+    }
+}
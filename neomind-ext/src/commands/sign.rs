@@ -0,0 +1,54 @@
+//! `neomind-ext keygen`, `sign` and `verify` - ed25519 signatures for .nep
+//! packages (see `utils::signing` for what is signed).
+
+use anyhow::Result;
+use colored::Colorize;
+use std::path::Path;
+
+use crate::utils::nep::NepPackage;
+use crate::utils::exit::{fail, FailureClass};
+use crate::utils::signing::{self, SignatureState};
+
+pub fn cmd_keygen(out: &str) -> Result<()> {
+    let key = signing::generate_key()?;
+    let (secret, public) = signing::write_keypair(&key, Path::new(out))?;
+
+    println!("{}", "🔑 Generated ed25519 signing key".green().bold());
+    println!("{}: {}", "Key ID".cyan(), signing::key_id(&key.verifying_key()));
+    println!("{}: {}", "Secret key".cyan(), secret.display());
+    println!("{}: {}", "Public key".cyan(), public.display());
+    println!("\nKeep {} out of version control; ship {} to the devices.", secret.display(), public.display());
+    Ok(())
+}
+
+pub fn cmd_sign(package: &str, key: &str) -> Result<()> {
+    let key = signing::read_signing_key(Path::new(key))?;
+    let signature = signing::sign_package(Path::new(package), &key)?;
+
+    println!("{}", "✍️  Package signed".green().bold());
+    println!("{}: {}", "Package".cyan(), package);
+    println!("{}: {}", "Key ID".cyan(), signature.key_id);
+    Ok(())
+}
+
+pub fn cmd_verify(package: &str, pubkey: &str, json: bool) -> Result<()> {
+    let trusted = signing::read_verifying_key(Path::new(pubkey))?;
+    let state = signing::check_package(&mut NepPackage::open(Path::new(package))?, Some(&trusted));
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&state)?);
+    }
+    match state {
+        SignatureState::Signed { key_id } => {
+            if !json {
+                println!("{} {} is signed by {}", "✅".green(), package, key_id);
+            }
+            Ok(())
+        }
+        SignatureState::Unsigned => Err(fail(FailureClass::ValidationFailure, format!("{} is not signed", package))),
+        SignatureState::Tampered { reason } => Err(fail(
+            FailureClass::ValidationFailure,
+            format!("{} failed verification: {}", package, reason),
+        )),
+    }
+}
@@ -0,0 +1,153 @@
+//! `neomind-ext test` - cargo test for one extension.
+//!
+//! Tests are compiled first (`cargo test --no-run`) so a compile error and a
+//! failing test exit with different codes.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::utils::exit::{fail, FailureClass};
+
+pub struct TestOptions {
+    pub path: Option<String>,
+    pub verbose: bool,
+    pub json: bool,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct TestCounts {
+    pub passed: u64,
+    pub failed: u64,
+    pub ignored: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TestReport {
+    pub extension: String,
+    pub passed: bool,
+    pub exit_code: Option<i32>,
+    /// Summed over every `test result:` line (unit, integration and doc tests)
+    pub tests: TestCounts,
+}
+
+pub fn cmd_test(opts: TestOptions) -> Result<()> {
+    let ext_path = crate::get_extension_path(opts.path)?;
+
+    if !opts.json {
+        println!("{}", "🧪 Running extension tests".green().bold());
+        if !ext_path.join("tests").exists() {
+            println!("{} No tests directory found. Running cargo test...", "Note:".yellow());
+        }
+        println!("{}", "Compiling tests...".yellow());
+    }
+
+    let mut compile = cargo_test(&ext_path, false);
+    compile.arg("--no-run");
+    if opts.json {
+        compile.stdout(std::io::stderr());
+    }
+    let status = compile.status().context("Failed to run cargo test")?;
+    if !status.success() {
+        return Err(fail(
+            FailureClass::CompileFailure,
+            format!("Tests failed to compile (cargo exited with {:?})", status.code()),
+        ));
+    }
+
+    if !opts.json {
+        println!("{}", "Running cargo test...".yellow());
+        let status = cargo_test(&ext_path, opts.verbose).status().context("Failed to run cargo test")?;
+        if !status.success() {
+            return Err(fail(
+                FailureClass::TestFailure,
+                format!("Tests failed with exit code: {:?}", status.code()),
+            ));
+        }
+        println!("{}", "✅ All tests passed!".green().bold());
+        return Ok(());
+    }
+
+    // Keep stdout for the report; the libtest output still reaches the terminal on stderr
+    let output = cargo_test(&ext_path, opts.verbose)
+        .stdout(Stdio::piped())
+        .output()
+        .context("Failed to run cargo test")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    eprint!("{}", stdout);
+
+    let report = TestReport {
+        extension: ext_path.display().to_string(),
+        passed: output.status.success(),
+        exit_code: output.status.code(),
+        tests: count_results(&stdout),
+    };
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    if !report.passed {
+        return Err(fail(
+            FailureClass::TestFailure,
+            format!("{} test(s) failed", report.tests.failed),
+        ));
+    }
+    Ok(())
+}
+
+fn cargo_test(ext_path: &Path, verbose: bool) -> Command {
+    let mut cmd = Command::new("cargo");
+    cmd.arg("test").current_dir(ext_path);
+    if verbose {
+        cmd.arg("--").arg("--nocapture");
+    }
+    cmd
+}
+
+/// Sum `test result: ok. 3 passed; 1 failed; 0 ignored; ...` lines.
+fn count_results(stdout: &str) -> TestCounts {
+    let mut counts = TestCounts::default();
+    for line in stdout.lines().filter_map(|l| l.trim().strip_prefix("test result: ")) {
+        for part in line.split(';') {
+            let mut words = part.split_whitespace().rev();
+            let (Some(label), Some(count)) = (words.next(), words.next()) else {
+                continue;
+            };
+            let Ok(count) = count.parse::<u64>() else {
+                continue;
+            };
+            match label {
+                "passed" => counts.passed += count,
+                "failed" => counts.failed += count,
+                "ignored" => counts.ignored += count,
+                _ => {}
+            }
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_results() {
+        let stdout = "\
+running 4 tests
+test tests::test_parse ... ok
+test result: FAILED. 2 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.01s
+
+running 3 tests
+test result: ok. 3 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+";
+        assert_eq!(
+            count_results(stdout),
+            TestCounts {
+                passed: 5,
+                failed: 1,
+                ignored: 1,
+            }
+        );
+        assert_eq!(count_results("error: could not compile"), TestCounts::default());
+    }
+}
@@ -0,0 +1,458 @@
+//! `neomind-ext validate <file.nep>` - structural checks on a built package.
+//!
+//! Replaces scripts/test_nep.py. Every check is recorded in a
+//! [`ValidationReport`] so CI can consume the result with `--json`.
+
+use anyhow::Result;
+use colored::Colorize;
+use serde::Serialize;
+use serde_json::Value;
+use std::path::Path;
+
+use crate::utils::checksum;
+use crate::utils::exit::{fail, FailureClass};
+use crate::utils::manifest::PACKAGE_FORMAT;
+use crate::utils::nep::NepPackage;
+use crate::utils::report::Checks;
+use crate::utils::signing::{self, SignatureState};
+
+/// Manifest fields every package must carry
+pub const REQUIRED_MANIFEST_FIELDS: [&str; 5] = ["format", "format_version", "id", "name", "version"];
+
+/// Packages above this size get a warning
+const LARGE_PACKAGE_BYTES: u64 = 10 * 1024 * 1024;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PackageIdentity {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationReport {
+    pub package: String,
+    pub valid: bool,
+    pub size_bytes: u64,
+    pub sha256: Option<String>,
+    pub extension: Option<PackageIdentity>,
+    /// `None` when the package could not be opened
+    pub signature: Option<SignatureState>,
+    pub checks: Checks,
+}
+
+impl ValidationReport {
+    fn new(path: &Path) -> Self {
+        Self {
+            package: path.display().to_string(),
+            valid: true,
+            size_bytes: std::fs::metadata(path).map(|m| m.len()).unwrap_or(0),
+            sha256: checksum::sha256_file(path).ok(),
+            extension: None,
+            signature: None,
+            checks: Checks::default(),
+        }
+    }
+
+    pub fn print_human(&self) {
+        println!("{}: {}", "Validating".cyan(), self.package);
+        if let Some(ext) = &self.extension {
+            println!("{}: {} (v{})", "Extension".cyan(), ext.name, ext.version);
+            println!("{}: {}", "ID".cyan(), ext.id);
+        }
+        println!();
+        self.checks.print();
+        println!();
+        println!(
+            "{}: {} bytes ({:.2} MB)",
+            "Size".cyan(),
+            self.size_bytes,
+            self.size_bytes as f64 / (1024.0 * 1024.0)
+        );
+        if let Some(sha256) = &self.sha256 {
+            println!("{}: {}", "SHA-256".cyan(), sha256);
+        }
+    }
+}
+
+/// Run every check against a .nep file. Only I/O problems with the file itself
+/// return `Err`; anything wrong with its contents is a failed check.
+pub fn validate_nep_package(nep_path: &Path) -> Result<ValidationReport> {
+    let mut report = ValidationReport::new(nep_path);
+    run_checks(&mut report, nep_path);
+    report.valid = !report.checks.has_failures();
+    Ok(report)
+}
+
+fn run_checks(report: &mut ValidationReport, nep_path: &Path) {
+    // 1. ZIP integrity
+    let mut package = match NepPackage::open(nep_path) {
+        Ok(package) => package,
+        Err(e) => {
+            report.checks.fail("zip", format!("{:#}", e));
+            return;
+        }
+    };
+    let files = package.file_names();
+    let corrupt: Vec<String> = files.iter().filter(|f| package.read(f).is_err()).cloned().collect();
+    if corrupt.is_empty() {
+        report.checks.pass("zip", format!("Valid ZIP archive with {} files", files.len()));
+    } else {
+        report.checks.fail("zip", format!("CRC check failed for: {}", corrupt.join(", ")));
+    }
+
+    // 2. manifest.json
+    let manifest = match package.manifest() {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            report.checks.fail("manifest", format!("{:#}", e));
+            return;
+        }
+    };
+    report.checks.pass("manifest", "manifest.json found at package root");
+
+    check_required_fields(report, &manifest);
+    check_version(report, &manifest);
+    check_binaries(report, &mut package, &manifest);
+    check_frontend(report, &mut package, &manifest);
+    check_checksums(report, &mut package, &manifest);
+    check_signature(report, &mut package);
+
+    if report.size_bytes > LARGE_PACKAGE_BYTES {
+        report.checks.warn(
+            "size",
+            format!("Package is large ({:.2} MB)", report.size_bytes as f64 / (1024.0 * 1024.0)),
+        );
+    }
+}
+
+fn check_required_fields(report: &mut ValidationReport, manifest: &Value) {
+    let missing: Vec<&str> = REQUIRED_MANIFEST_FIELDS
+        .iter()
+        .filter(|f| manifest.get(**f).and_then(|v| v.as_str()).map(|s| s.is_empty()).unwrap_or(true))
+        .copied()
+        .collect();
+
+    if !missing.is_empty() {
+        report.checks.fail("required_fields", format!("Missing required field(s): {}", missing.join(", ")));
+        return;
+    }
+
+    let field = |name: &str| manifest[name].as_str().unwrap_or_default().to_string();
+    if !is_valid_extension_id(&field("id")) {
+        // The ID becomes a directory name on install; don't report an identity for it
+        report.checks.fail(
+            "required_fields",
+            format!("id '{}' must only contain a-z, 0-9, '-' and '_'", field("id")),
+        );
+        return;
+    }
+    report.extension = Some(PackageIdentity {
+        id: field("id"),
+        name: field("name"),
+        version: field("version"),
+    });
+
+    if field("format") != PACKAGE_FORMAT {
+        report.checks.fail(
+            "required_fields",
+            format!("format is '{}', expected '{}'", field("format"), PACKAGE_FORMAT),
+        );
+    } else {
+        report.checks.pass(
+            "required_fields",
+            format!("{} v{}", field("format"), field("format_version")),
+        );
+    }
+}
+
+/// Whether `id` is safe to use as a directory name under the extensions
+/// directory: a non-empty slug of `a-z`, `0-9`, `-` and `_`.
+pub fn is_valid_extension_id(id: &str) -> bool {
+    !id.is_empty() && id.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
+
+fn check_version(report: &mut ValidationReport, manifest: &Value) {
+    let Some(version) = manifest.get("version").and_then(|v| v.as_str()) else {
+        return;
+    };
+    match semver::Version::parse(version) {
+        Ok(_) => report.checks.pass("semver", format!("{} is valid semver", version)),
+        Err(e) => report.checks.fail("semver", format!("'{}' is not valid semver: {}", version, e)),
+    }
+}
+
+fn check_binaries(report: &mut ValidationReport, package: &mut NepPackage, manifest: &Value) {
+    let binaries = manifest.get("binaries").and_then(|b| b.as_object());
+    let Some(binaries) = binaries.filter(|b| !b.is_empty()) else {
+        report.checks.fail("binaries", "manifest declares no binaries");
+        return;
+    };
+
+    for (platform, path) in binaries {
+        let name = format!("binary:{}", platform);
+        match path.as_str() {
+            Some(path) => match package.size_of(path) {
+                Some(0) => report.checks.fail(&name, format!("{} is empty", path)),
+                Some(size) => report.checks.pass(&name, format!("{} ({} bytes)", path, size)),
+                None => report.checks.fail(&name, format!("{} referenced in manifest but missing", path)),
+            },
+            None => report.checks.fail(&name, "binary path is not a string"),
+        }
+    }
+}
+
+fn check_frontend(report: &mut ValidationReport, package: &mut NepPackage, manifest: &Value) {
+    let components = manifest
+        .get("frontend")
+        .and_then(|f| f.get("components"))
+        .and_then(|c| c.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    // Entrypoint declared by the bundled frontend.json
+    let frontend_json = package
+        .read("frontend.json")
+        .ok()
+        .and_then(|data| serde_json::from_slice::<Value>(&data).ok());
+    let entrypoint = frontend_json
+        .as_ref()
+        .and_then(|f| f.get("entrypoint"))
+        .and_then(|e| e.as_str())
+        .map(String::from);
+
+    if components.is_empty() && entrypoint.is_none() {
+        report.checks.pass("frontend", "No frontend components");
+        return;
+    }
+
+    let mut missing = Vec::new();
+    for component in &components {
+        if let Some(bundle) = component.get("bundle_path").and_then(|b| b.as_str()) {
+            if !package.contains(bundle) {
+                missing.push(bundle.to_string());
+            }
+        }
+    }
+    if let Some(entrypoint) = &entrypoint {
+        let cjs = entrypoint
+            .strip_suffix(".umd.js")
+            .map(|stem| format!("frontend/{}.umd.cjs", stem));
+        let found = package.contains(&format!("frontend/{}", entrypoint))
+            || cjs.map(|p| package.contains(&p)).unwrap_or(false);
+        if !found {
+            missing.push(format!("frontend/{}", entrypoint));
+        }
+    }
+
+    if missing.is_empty() {
+        let frontend_files = package
+            .file_names()
+            .iter()
+            .filter(|f| f.starts_with("frontend/"))
+            .count();
+        report.checks.pass(
+            "frontend",
+            format!("{} component(s), {} frontend file(s)", components.len(), frontend_files),
+        );
+    } else {
+        missing.dedup();
+        report.checks.fail("frontend", format!("Missing frontend entrypoint: {}", missing.join(", ")));
+    }
+}
+
+fn check_checksums(report: &mut ValidationReport, package: &mut NepPackage, manifest: &Value) {
+    let Some(checksums) = manifest.get("checksums").and_then(|c| c.as_object()) else {
+        report.checks.warn("checksums", "manifest has no checksums (package built by an older packager)");
+        return;
+    };
+
+    let mut mismatched = Vec::new();
+    for (path, expected) in checksums {
+        let expected = expected.as_str().unwrap_or_default();
+        match package.read(path) {
+            Ok(data) if checksum::sha256_hex(&data) == expected => {}
+            Ok(_) => mismatched.push(format!("{} (hash mismatch)", path)),
+            Err(_) => mismatched.push(format!("{} (missing)", path)),
+        }
+    }
+
+    let unlisted: Vec<String> = package
+        .file_names()
+        .into_iter()
+        .filter(|f| f != "manifest.json" && f != signing::SIGNATURE_ENTRY && !checksums.contains_key(f))
+        .collect();
+
+    if !mismatched.is_empty() {
+        report.checks.fail("checksums", mismatched.join(", "));
+    } else if !unlisted.is_empty() {
+        report.checks.warn("checksums", format!("Files without checksum: {}", unlisted.join(", ")));
+    } else {
+        report.checks.pass("checksums", format!("{} file(s) verified", checksums.len()));
+    }
+}
+
+/// Self-consistency of signature.json; trust in the key is `verify --pubkey`'s job.
+fn check_signature(report: &mut ValidationReport, package: &mut NepPackage) {
+    let state = signing::check_package(package, None);
+    match &state {
+        SignatureState::Unsigned => report.checks.warn("signature", "Package is unsigned"),
+        SignatureState::Signed { key_id } => report.checks.pass("signature", format!("Signed with key {}", key_id)),
+        SignatureState::Tampered { reason } => report.checks.fail("signature", format!("Tampered: {}", reason)),
+    }
+    report.signature = Some(state);
+}
+
+/// `validate` entry point for .nep files: prints the report and fails if invalid.
+pub fn run(nep_path: &Path, json: bool) -> Result<()> {
+    let report = validate_nep_package(nep_path)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        report.print_human();
+    }
+
+    if !report.valid {
+        return Err(fail(FailureClass::ValidationFailure, format!("Package validation failed: {}", report.checks.failed_names().join(", "))));
+    }
+
+    if !json {
+        println!("{}", "✅ Package is valid!".green().bold());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::report::CheckStatus;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    fn write_nep(path: &Path, entries: &[(&str, &[u8])]) {
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(path).unwrap());
+        for (name, data) in entries {
+            zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(data).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    fn manifest(binary_hash: &str, version: &str) -> Vec<u8> {
+        serde_json::to_vec(&serde_json::json!({
+            "format": "neomind-extension-package",
+            "format_version": "2.0",
+            "id": "demo",
+            "name": "demo",
+            "version": version,
+            "binaries": { "linux_amd64": "binaries/linux_amd64/extension.so" },
+            "frontend": { "components": [{ "bundle_path": "frontend/demo.umd.cjs" }] },
+            "checksums": {
+                "binaries/linux_amd64/extension.so": binary_hash,
+                "frontend/demo.umd.cjs": checksum::sha256_hex(b"js"),
+            }
+        }))
+        .unwrap()
+    }
+
+    fn status_of<'a>(report: &'a ValidationReport, name: &str) -> &'a CheckStatus {
+        &report.checks.iter().find(|c| c.name == name).unwrap().status
+    }
+
+    #[test]
+    fn test_valid_package_passes() {
+        let dir = tempfile::tempdir().unwrap();
+        let nep = dir.path().join("demo.nep");
+        let manifest = manifest(&checksum::sha256_hex(b"ELF"), "1.0.0");
+        write_nep(
+            &nep,
+            &[
+                ("manifest.json", &manifest),
+                ("binaries/linux_amd64/extension.so", b"ELF"),
+                ("frontend/demo.umd.cjs", b"js"),
+            ],
+        );
+
+        let report = validate_nep_package(&nep).unwrap();
+        assert!(report.valid, "{:?}", report.checks);
+        assert_eq!(report.extension.as_ref().unwrap().id, "demo");
+        assert_eq!(status_of(&report, "checksums"), &CheckStatus::Pass);
+        assert_eq!(report.signature, Some(SignatureState::Unsigned));
+    }
+
+    #[test]
+    fn test_signed_package_reports_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let nep = dir.path().join("demo.nep");
+        let manifest = manifest(&checksum::sha256_hex(b"ELF"), "1.0.0");
+        write_nep(
+            &nep,
+            &[
+                ("manifest.json", &manifest),
+                ("binaries/linux_amd64/extension.so", b"ELF"),
+                ("frontend/demo.umd.cjs", b"js"),
+            ],
+        );
+        let signature = signing::sign_package(&nep, &signing::generate_key().unwrap()).unwrap();
+
+        let report = validate_nep_package(&nep).unwrap();
+        assert!(report.valid, "{:?}", report.checks);
+        assert_eq!(status_of(&report, "checksums"), &CheckStatus::Pass);
+        assert_eq!(
+            report.signature,
+            Some(SignatureState::Signed {
+                key_id: signature.key_id
+            })
+        );
+    }
+
+    #[test]
+    fn test_tampered_binary_and_bad_version_fail() {
+        let dir = tempfile::tempdir().unwrap();
+        let nep = dir.path().join("demo.nep");
+        let manifest = manifest(&checksum::sha256_hex(b"ELF"), "1.0");
+        write_nep(
+            &nep,
+            &[
+                ("manifest.json", &manifest),
+                ("binaries/linux_amd64/extension.so", b"PATCHED"),
+                ("frontend/demo.umd.cjs", b"js"),
+            ],
+        );
+
+        let report = validate_nep_package(&nep).unwrap();
+        assert!(!report.valid);
+        assert_eq!(status_of(&report, "checksums"), &CheckStatus::Fail);
+        assert_eq!(status_of(&report, "semver"), &CheckStatus::Fail);
+    }
+
+    #[test]
+    fn test_missing_binary_and_fields_fail() {
+        let dir = tempfile::tempdir().unwrap();
+        let nep = dir.path().join("demo.nep");
+        let manifest = serde_json::to_vec(&serde_json::json!({
+            "format": "neomind-extension-package",
+            "id": "demo",
+            "binaries": { "linux_amd64": "binaries/linux_amd64/extension.so" },
+        }))
+        .unwrap();
+        write_nep(&nep, &[("manifest.json", &manifest)]);
+
+        let report = validate_nep_package(&nep).unwrap();
+        assert!(!report.valid);
+        assert_eq!(status_of(&report, "required_fields"), &CheckStatus::Fail);
+        assert_eq!(status_of(&report, "binary:linux_amd64"), &CheckStatus::Fail);
+    }
+
+    #[test]
+    fn test_not_a_zip_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let nep = dir.path().join("demo.nep");
+        std::fs::write(&nep, b"not a zip").unwrap();
+
+        let report = validate_nep_package(&nep).unwrap();
+        assert!(!report.valid);
+        assert_eq!(status_of(&report, "zip"), &CheckStatus::Fail);
+    }
+}
@@ -1,15 +1,25 @@
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand};
 use colored::Colorize;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+mod commands;
+mod utils;
+
+use utils::exit::{fail, FailureClass};
+use utils::report::Checks;
+
 #[derive(Parser)]
 #[command(name = "neomind-ext")]
 #[command(about = "CLI tool for developing NeoMind extensions", long_about = None)]
 #[command(version = "0.1.0")]
 struct Cli {
+    /// Print the result as JSON on stdout and errors as JSON on stderr (new, build, package, validate, test and the report commands)
+    #[arg(long, global = true)]
+    json: bool,
     #[command(subcommand)]
     command: Commands,
 }
@@ -19,11 +29,23 @@ enum Commands {
     /// Create a new extension project
     New {
         /// Extension name (kebab-case, e.g., my-cool-extension)
-        name: String,
+        #[arg(required_unless_present = "list_templates")]
+        name: Option<String>,
+        /// Template to scaffold from (see --list-templates)
+        #[arg(short, long, default_value = "basic")]
+        template: String,
+        /// List the available templates and exit
+        #[arg(long)]
+        list_templates: bool,
         /// Include frontend component
         #[arg(long)]
         with_frontend: bool,
     },
+    /// Add a command or metric to an existing extension's src/lib.rs
+    Add {
+        #[command(subcommand)]
+        item: AddItem,
+    },
     /// Build extension
     Build {
         /// Extension directory (default: current directory)
@@ -32,15 +54,61 @@ enum Commands {
         /// Release build
         #[arg(long)]
         release: bool,
+        /// Comma-separated platforms to build (e.g. linux-x86_64,linux-aarch64,wasm32; default: host)
+        #[arg(long, value_delimiter = ',')]
+        targets: Vec<String>,
     },
     /// Package extension as .nep file
     Package {
         /// Extension directory
         #[arg(short, long)]
         path: Option<String>,
-        /// Include frontend
+        /// Require frontend/dist to be present and bundle it
         #[arg(long)]
         with_frontend: bool,
+        /// Package the debug build instead of the release build
+        #[arg(long)]
+        debug: bool,
+        /// Target platform (e.g., linux-x86_64, linux_arm64, wasm32; default: host or built WASM)
+        #[arg(long)]
+        platform: Option<String>,
+        /// Output directory (default: <workspace root>/dist)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Install a .nep (or an extension directory, packaged first) into a NeoMind extensions directory
+    Install {
+        /// .nep file or extension directory
+        #[arg(required_unless_present = "rollback")]
+        source: Option<String>,
+        /// NeoMind extensions directory (e.g. NeoMind/data/extensions)
+        #[arg(long)]
+        into: String,
+        /// Restore the version replaced by the last install of this extension ID
+        #[arg(long, value_name = "ID", conflicts_with = "source")]
+        rollback: Option<String>,
+    },
+    /// Remove an installed extension from a NeoMind extensions directory
+    Uninstall {
+        /// Extension ID
+        id: String,
+        /// NeoMind extensions directory
+        #[arg(long)]
+        into: String,
+        /// Keep state files the extension wrote (config.json, faces.json, ...)
+        #[arg(long)]
+        keep_data: bool,
+    },
+    /// Fetch, verify or list the model files declared in models.toml
+    Models {
+        #[command(subcommand)]
+        action: ModelsAction,
+    },
+    /// Check ONNX Runtime, FFmpeg, fonts and model files the native extensions need at runtime
+    Doctor {
+        /// Installed extension directory to resolve bundled libraries from (default: $NEOMIND_EXTENSION_DIR)
+        #[arg(long)]
+        ext_dir: Option<String>,
     },
     /// Validate extension or .nep package
     Validate {
@@ -48,6 +116,121 @@ enum Commands {
         #[arg(short, long)]
         path: Option<String>,
     },
+    /// Compare two .nep packages: commands, metrics, manifest, binary sizes, models and frontend
+    Diff {
+        /// Older .nep file
+        old: String,
+        /// Newer .nep file
+        new: String,
+        /// Compare the archives only; do not load the host-platform binaries
+        #[arg(long)]
+        no_runtime: bool,
+        /// Exit non-zero when a breaking change is found (for CI)
+        #[arg(long)]
+        fail_on_breaking: bool,
+    },
+    /// Generate an ed25519 key pair for signing packages
+    Keygen {
+        /// Output path prefix; writes <OUT>.key and <OUT>.pub
+        #[arg(short, long, default_value = "neomind-signing")]
+        out: String,
+    },
+    /// Sign a .nep package in place
+    Sign {
+        /// .nep file
+        package: String,
+        /// Secret key written by `keygen`
+        #[arg(long)]
+        key: String,
+    },
+    /// Verify a .nep package's signature against a trusted public key
+    Verify {
+        /// .nep file
+        package: String,
+        /// Public key written by `keygen`
+        #[arg(long)]
+        pubkey: String,
+    },
+    /// Cross-check runtime metadata against metadata.json and extensions/index.json
+    Lint {
+        /// Extension directory (default: current directory)
+        #[arg(short, long)]
+        path: Option<String>,
+        /// Lint every extension under extensions/
+        #[arg(long, conflicts_with = "path")]
+        all: bool,
+        /// Built library to load (default: target/release, then target/debug)
+        #[arg(long)]
+        lib: Option<String>,
+        /// Only check the JSON files; do not load the built library
+        #[arg(long)]
+        no_runtime: bool,
+    },
+    /// Check a built library's SDK version, serde_json features, panic strategy and exports
+    CheckAbi {
+        /// Extension directory (default: current directory)
+        #[arg(short, long)]
+        path: Option<String>,
+        /// Built library to check (default: target/release, then target/debug)
+        #[arg(long)]
+        lib: Option<String>,
+        /// Supported neomind-extension-sdk versions (default: the range the runner loads)
+        #[arg(long)]
+        sdk_range: Option<String>,
+    },
+    /// Regenerate extensions/index.json from each member's metadata.json
+    Index {
+        /// Fail if index.json is out of date instead of writing it (for CI)
+        #[arg(long)]
+        check: bool,
+    },
+    /// Load the built extension into a stub host and invoke it (REPL when no action is given)
+    Run {
+        /// Extension directory (default: current directory)
+        #[arg(short, long)]
+        path: Option<String>,
+        /// Built library to load (default: target/release, then target/debug)
+        #[arg(long)]
+        lib: Option<String>,
+        /// Config passed to create, as JSON or @file
+        #[arg(long)]
+        config: Option<String>,
+        /// One-shot action, e.g. `invoke get_status '{}'` or `event DeviceMetric @frame.json`
+        #[arg(trailing_var_arg = true)]
+        action: Vec<String>,
+    },
+    /// Watch src/ and frontend/, rebuild on change and hot-reload into the run harness
+    Dev {
+        /// Extension directory (default: current directory)
+        #[arg(short, long)]
+        path: Option<String>,
+        /// Build and load the release profile
+        #[arg(long)]
+        release: bool,
+        /// Config passed to create on every reload, as JSON or @file
+        #[arg(long)]
+        config: Option<String>,
+        /// Action to run after the first load and replay after each reload, e.g. `invoke process_image @img.json`
+        #[arg(trailing_var_arg = true)]
+        action: Vec<String>,
+    },
+    /// Replay a JSONL recording of events against the built extension
+    Replay {
+        /// Recording file (one JSON event or command per line)
+        file: String,
+        /// Extension directory (default: current directory)
+        #[arg(short, long)]
+        path: Option<String>,
+        /// Built library to load (default: target/release, then target/debug)
+        #[arg(long)]
+        lib: Option<String>,
+        /// Config passed to create, as JSON or @file
+        #[arg(long)]
+        config: Option<String>,
+        /// Timing factor relative to the recording (2 = twice as fast, 0 = no delay)
+        #[arg(long, default_value_t = 1.0)]
+        speed: f64,
+    },
     /// Test extension
     Test {
         /// Extension directory
@@ -59,191 +242,255 @@ enum Commands {
     },
 }
 
-fn main() -> Result<()> {
-    let cli = Cli::parse();
-
-    match cli.command {
-        Commands::New { name, with_frontend } => {
-            cmd_new(name, with_frontend)?;
-        }
-        Commands::Build { path, release } => {
-            cmd_build(path, release)?;
-        }
-        Commands::Package { path, with_frontend } => {
-            cmd_package(path, with_frontend)?;
-        }
-        Commands::Validate { path } => {
-            cmd_validate(path)?;
-        }
-        Commands::Test { path, verbose } => {
-            cmd_test(path, verbose)?;
-        }
-    }
-
-    Ok(())
+#[derive(Subcommand)]
+enum AddItem {
+    /// Add a command descriptor and a stub execute_command arm
+    Command {
+        /// Command name (snake_case, e.g. set_threshold)
+        name: String,
+        /// Parameter as name:type, type one of string|integer|float|boolean; suffix `?` for optional
+        #[arg(long = "param", value_name = "NAME:TYPE")]
+        params: Vec<String>,
+        /// Description shown to users and the LLM
+        #[arg(short, long)]
+        description: Option<String>,
+        /// Extension directory (default: current directory)
+        #[arg(short, long)]
+        path: Option<String>,
+    },
+    /// Add a metric descriptor and a placeholder value in produce_metrics
+    Metric {
+        /// Metric name (snake_case, e.g. last_latency_ms)
+        name: String,
+        /// Data type: string|integer|float|boolean
+        #[arg(short = 't', long = "type", default_value = "float")]
+        data_type: String,
+        /// Unit, e.g. ms or count
+        #[arg(short, long, default_value = "")]
+        unit: String,
+        /// Extension directory (default: current directory)
+        #[arg(short, long)]
+        path: Option<String>,
+    },
 }
 
-fn cmd_new(name: String, with_frontend: bool) -> Result<()> {
-    println!("{}", "🚀 Creating new NeoMind extension".green().bold());
-    println!("{}: {}", "Name".cyan(), name);
-    println!("{}: {}", "Frontend".cyan(), if with_frontend { "Yes" } else { "No" });
-
-    // Validate extension name
-    if !name.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
-        anyhow::bail!("Invalid extension name '{}'. Use kebab-case (e.g., my-cool-extension)", name);
-    }
-
-    // Get repository root
-    let repo_root = get_repo_root()?;
-    let extensions_dir = repo_root.join("extensions");
-    let new_ext_dir = extensions_dir.join(&name);
-
-    // Check if extension already exists
-    if new_ext_dir.exists() {
-        anyhow::bail!("Extension '{}' already exists at: {}", name, new_ext_dir.display());
-    }
-
-    // Create template
-    let template_dir = repo_root.join("neomind-ext").join("templates").join("basic");
-    if !template_dir.exists() {
-        anyhow::bail!("Template directory not found: {}", template_dir.display());
-    }
-
-    println!("\n{}", "Creating files...".yellow());
-
-    // Copy template
-    copy_dir(&template_dir, &new_ext_dir, &name)?;
-
-    // Update workspace Cargo.toml
-    update_workspace_cargo_toml(&repo_root, &name)?;
-
-    println!("\n{}", "✅ Extension created successfully!".green().bold());
-    println!("\n{}", "Next steps:".yellow().bold());
-    println!("  1. cd {}", name);
-    println!("  2. {} your code", "edit".cyan());
-    println!("  3. {} build the extension", "neomind-ext build".cyan());
-    println!("  4. {} run tests", "neomind-ext test".cyan());
-    println!("  5. {} package as .nep", "neomind-ext package".cyan());
-
-    Ok(())
+#[derive(Subcommand)]
+enum ModelsAction {
+    /// Download (or copy from --mirror) models that are missing or fail verification
+    Fetch {
+        /// Directory to copy models from instead of downloading (<dir>/<id>/<file> or <dir>/<file>)
+        #[arg(long)]
+        mirror: Option<String>,
+        /// Extension directory (default: current directory)
+        #[arg(short, long)]
+        path: Option<String>,
+    },
+    /// Check every declared model's size and SHA-256
+    Verify {
+        /// Extension directory (default: current directory)
+        #[arg(short, long)]
+        path: Option<String>,
+    },
+    /// List declared models and whether they are present
+    List {
+        /// Extension directory (default: current directory)
+        #[arg(short, long)]
+        path: Option<String>,
+    },
 }
 
-fn cmd_build(path: Option<String>, release: bool) -> Result<()> {
-    let ext_path = get_extension_path(path)?;
-    let build_type = if release { "release" } else { "debug" };
-
-    println!("{}", format!("🔨 Building extension ({})", build_type).green().bold());
-
-    // Check if Cargo.toml exists
-    let cargo_toml = ext_path.join("Cargo.toml");
-    if !cargo_toml.exists() {
-        anyhow::bail!("Cargo.toml not found at: {}", ext_path.display());
+impl Commands {
+    /// Subcommands with a structured `--json` result.
+    fn supports_json(&self) -> bool {
+        matches!(
+            self,
+            Commands::New { .. }
+                | Commands::Build { .. }
+                | Commands::Package { .. }
+                | Commands::Validate { .. }
+                | Commands::Test { .. }
+                | Commands::Doctor { .. }
+                | Commands::Diff { .. }
+                | Commands::Verify { .. }
+                | Commands::Lint { .. }
+                | Commands::CheckAbi { .. }
+                | Commands::Replay { .. }
+        )
     }
-
-    // Get package name from Cargo.toml
-    let package_name = extract_package_name(&cargo_toml)?;
-
-    // Build with cargo
-    println!("{}", "Running cargo build...".yellow());
-
-    let mut cmd = Command::new("cargo");
-    cmd.arg("build");
-
-    if release {
-        cmd.arg("--release");
-    }
-
-    cmd.arg("-p").arg(&package_name);
-
-    let status = cmd
-        .current_dir(&ext_path)
-        .status()
-        .context("Failed to run cargo build")?;
-
-    if !status.success() {
-        anyhow::bail!("Build failed with exit code: {:?}", status.code());
-    }
-
-    println!("{}", "✅ Build completed successfully!".green().bold());
-
-    // Show output path
-    let target_dir = ext_path.join("target").parallel_join(build_type);
-    let lib_name = format!("lib{}.dylib", package_name);
-    let lib_path = target_dir.join(&lib_name);
-
-    if lib_path.exists() {
-        println!("\n{}: {}", "Output".cyan(), lib_path.display());
-    }
-
-    Ok(())
 }
 
-fn cmd_package(path: Option<String>, with_frontend: bool) -> Result<()> {
-    let ext_path = get_extension_path(path)?;
-
-    println!("{}", "📦 Packaging extension as .nep".green().bold());
-
-    // Get package name from Cargo.toml
-    let cargo_toml = ext_path.join("Cargo.toml");
-    let package_name = extract_package_name(&cargo_toml)?;
-
-    // Find package.sh script
-    let repo_root = get_repo_root()?;
-    let package_script = repo_root.join("build-package.sh");
-
-    if !package_script.exists() {
-        anyhow::bail!("Package script not found: {}", package_script.display());
+fn main() {
+    let cli = Cli::parse();
+    if cli.json && !cli.command.supports_json() {
+        Cli::command()
+            .error(ErrorKind::ArgumentConflict, "--json is not supported by this subcommand")
+            .exit();
     }
 
-    println!("{}", "Running package.sh...".yellow());
-
-    // Build command - use package name
-    let ext_dir_name = ext_path
-        .file_name()
-        .and_then(|n| n.to_str())
-        .ok_or_else(|| anyhow::anyhow!("Invalid extension path"))?;
-
-    let mut cmd = Command::new("bash");
-    cmd.arg(&package_script);
-    cmd.arg(&ext_dir_name);
-
-    let status = cmd
-        .current_dir(&repo_root)
-        .status()
-        .context("Failed to run package.sh")?;
-
-    if !status.success() {
-        anyhow::bail!("Packaging failed with exit code: {:?}", status.code());
+    let json = cli.json;
+    if let Err(err) = run(cli) {
+        if json {
+            eprintln!("{}", utils::exit::error_json(&err));
+        } else {
+            eprintln!("Error: {:?}", err);
+        }
+        std::process::exit(utils::exit::exit_code(&err));
     }
+}
 
-    println!("{}", "✅ Package created successfully!".green().bold());
-
-    // Show output path
-    let dist_dir = repo_root.join("dist");
-    if dist_dir.exists() {
-        let mut entries: Vec<_> = fs::read_dir(&dist_dir)?
-            .filter_map(|e| e.ok())
-            .filter(|e| {
-                e.path()
-                    .extension()
-                    .and_then(|s| s.to_str())
-                    .map(|ext| ext == "nep")
-                    .unwrap_or(false)
-            })
-            .collect();
-
-        entries.sort_by_key(|e| e.metadata().ok().and_then(|m| m.modified().ok()));
-
-        if let Some(last_entry) = entries.last() {
-            println!("\n{}: {}", "Output".cyan(), last_entry.path().display());
+fn run(cli: Cli) -> Result<()> {
+    let json = cli.json;
+    match cli.command {
+        Commands::New { name, template, list_templates, with_frontend } => match name {
+            Some(name) if !list_templates => {
+                commands::new::cmd_new(commands::new::NewOptions {
+                    name,
+                    template,
+                    with_frontend,
+                    json,
+                })?;
+            }
+            _ => commands::new::cmd_list_templates(json)?,
+        },
+        Commands::Add { item } => match item {
+            AddItem::Command { name, params, description, path } => {
+                commands::add::cmd_add_command(commands::add::AddCommandOptions {
+                    path,
+                    name,
+                    params,
+                    description,
+                })?;
+            }
+            AddItem::Metric { name, data_type, unit, path } => {
+                commands::add::cmd_add_metric(commands::add::AddMetricOptions {
+                    path,
+                    name,
+                    data_type,
+                    unit,
+                })?;
+            }
+        },
+        Commands::Build { path, release, targets } => {
+            commands::build::cmd_build(commands::build::BuildOptions {
+                path,
+                release,
+                targets,
+                json,
+            })?;
+        }
+        Commands::Package { path, with_frontend, debug, platform, output } => {
+            commands::package::cmd_package(commands::package::PackageOptions {
+                path,
+                with_frontend,
+                debug,
+                platform,
+                output,
+                json,
+            })?;
+        }
+        Commands::Install { source, into, rollback } => {
+            commands::install::cmd_install(commands::install::InstallOptions {
+                source,
+                into,
+                rollback,
+            })?;
+        }
+        Commands::Uninstall { id, into, keep_data } => {
+            commands::install::cmd_uninstall(commands::install::UninstallOptions {
+                id,
+                into,
+                keep_data,
+            })?;
+        }
+        Commands::Models { action } => match action {
+            ModelsAction::Fetch { mirror, path } => {
+                commands::models::cmd_fetch(commands::models::FetchOptions { path, mirror })?;
+            }
+            ModelsAction::Verify { path } => commands::models::cmd_verify(path)?,
+            ModelsAction::List { path } => commands::models::cmd_list(path)?,
+        },
+        Commands::Doctor { ext_dir } => {
+            commands::doctor::cmd_doctor(commands::doctor::DoctorOptions { ext_dir, json })?;
+        }
+        Commands::Validate { path } => {
+            cmd_validate(path, json)?;
+        }
+        Commands::Diff { old, new, no_runtime, fail_on_breaking } => {
+            commands::diff::cmd_diff(commands::diff::DiffOptions {
+                old,
+                new,
+                no_runtime,
+                fail_on_breaking,
+                json,
+            })?;
+        }
+        Commands::Keygen { out } => {
+            commands::sign::cmd_keygen(&out)?;
+        }
+        Commands::Sign { package, key } => {
+            commands::sign::cmd_sign(&package, &key)?;
+        }
+        Commands::Verify { package, pubkey } => {
+            commands::sign::cmd_verify(&package, &pubkey, json)?;
+        }
+        Commands::Lint { path, all, lib, no_runtime } => {
+            commands::lint::cmd_lint(commands::lint::LintOptions {
+                path,
+                all,
+                lib,
+                no_runtime,
+                json,
+            })?;
+        }
+        Commands::CheckAbi { path, lib, sdk_range } => {
+            commands::abi::cmd_check_abi(commands::abi::CheckAbiOptions {
+                path,
+                lib,
+                sdk_range,
+                json,
+            })?;
+        }
+        Commands::Index { check } => {
+            commands::index::cmd_index(commands::index::IndexOptions { check })?;
+        }
+        Commands::Run { path, lib, config, action } => {
+            commands::run::cmd_run(commands::run::RunOptions {
+                path,
+                lib,
+                config,
+                action,
+            })?;
+        }
+        Commands::Dev { path, release, config, action } => {
+            commands::dev::cmd_dev(commands::dev::DevOptions {
+                path,
+                release,
+                config,
+                action,
+            })?;
+        }
+        Commands::Replay { file, path, lib, config, speed } => {
+            commands::replay::cmd_replay(commands::replay::ReplayOptions {
+                file,
+                path,
+                lib,
+                config,
+                speed,
+                json,
+            })?;
+        }
+        Commands::Test { path, verbose } => {
+            commands::test::cmd_test(commands::test::TestOptions { path, verbose, json })?;
         }
     }
 
     Ok(())
 }
 
-fn cmd_validate(path: Option<String>) -> Result<()> {
-    println!("{}", "✅ Validating extension".green().bold());
+fn cmd_validate(path: Option<String>, json: bool) -> Result<()> {
+    if !json {
+        println!("{}", "✅ Validating extension".green().bold());
+    }
 
     let target_path = if let Some(p) = path {
         PathBuf::from(p)
@@ -251,6 +498,12 @@ fn cmd_validate(path: Option<String>) -> Result<()> {
         // Current directory
         std::env::current_dir()?
     };
+    if !target_path.exists() {
+        return Err(fail(
+            FailureClass::MissingFile,
+            format!("No such extension or package: {}", target_path.display()),
+        ));
+    }
 
     // Check if it's a .nep file
     let is_nep_file = target_path
@@ -261,45 +514,26 @@ fn cmd_validate(path: Option<String>) -> Result<()> {
 
     if is_nep_file {
         // Validate .nep file
-        validate_nep_package(&target_path)?;
+        commands::validate::run(&target_path, json)?;
     } else {
         // Validate extension directory
-        validate_extension_directory(&target_path)?;
+        validate_extension_directory(&target_path, json)?;
     }
 
     Ok(())
 }
 
-fn validate_nep_package(nep_path: &PathBuf) -> Result<()> {
-    println!("{}: {}", "Validating".cyan(), nep_path.display());
-
-    // Find test_nep.py script
-    let repo_root = get_repo_root()?;
-    let test_script = repo_root.join("scripts").join("test_nep.py");
-
-    if !test_script.exists() {
-        anyhow::bail!("Test script not found: {}", test_script.display());
-    }
-
-    // Run Python script
-    let mut cmd = Command::new("python3");
-    cmd.arg(&test_script);
-    cmd.arg(nep_path);
-
-    let status = cmd
-        .status()
-        .context("Failed to run test_nep.py")?;
-
-    if !status.success() {
-        anyhow::bail!("Validation failed with exit code: {:?}", status.code());
-    }
-
-    println!("{}", "✅ Package is valid!".green().bold());
-    Ok(())
+#[derive(serde::Serialize)]
+struct DirectoryReport {
+    path: String,
+    valid: bool,
+    checks: Checks,
 }
 
-fn validate_extension_directory(ext_path: &PathBuf) -> Result<()> {
-    println!("{}: {}", "Validating".cyan(), ext_path.display());
+fn validate_extension_directory(ext_path: &Path, json: bool) -> Result<()> {
+    if !json {
+        println!("{}: {}", "Validating".cyan(), ext_path.display());
+    }
 
     // Check for required files
     let required_files = vec!["Cargo.toml", "src/lib.rs"];
@@ -307,62 +541,49 @@ fn validate_extension_directory(ext_path: &PathBuf) -> Result<()> {
     for file in &required_files {
         let file_path = ext_path.join(file);
         if !file_path.exists() {
-            anyhow::bail!("Missing required file: {}", file);
+            return Err(fail(FailureClass::MissingFile, format!("Missing required file: {}", file)));
         }
     }
 
     // Try to parse Cargo.toml
     let cargo_toml = ext_path.join("Cargo.toml");
-    let _package_name = extract_package_name(&cargo_toml)?;
+    let package_name = extract_package_name(&cargo_toml)?;
 
     // Check if it builds
-    println!("{}", "Running cargo check...".yellow());
-
-    let status = Command::new("cargo")
-        .arg("check")
-        .current_dir(ext_path)
-        .status()
-        .context("Failed to run cargo check")?;
-
-    if !status.success() {
-        anyhow::bail!("Cargo check failed. Extension has compilation errors.");
-    }
-
-    println!("{}", "✅ Extension is valid!".green().bold());
-    Ok(())
-}
-
-fn cmd_test(path: Option<String>, verbose: bool) -> Result<()> {
-    let ext_path = get_extension_path(path)?;
-
-    println!("{}", "🧪 Running extension tests".green().bold());
-
-    // Check if tests exist
-    let tests_dir = ext_path.join("tests");
-    if !tests_dir.exists() {
-        println!("{} No tests directory found. Running cargo test...", "Note:".yellow());
+    if !json {
+        println!("{}", "Running cargo check...".yellow());
     }
 
-    // Run cargo test
-    println!("{}", "Running cargo test...".yellow());
-
     let mut cmd = Command::new("cargo");
-    cmd.arg("test");
-
-    if verbose {
-        cmd.arg("--").arg("--nocapture");
+    cmd.arg("check").current_dir(ext_path);
+    if json {
+        cmd.stdout(std::io::stderr());
+    }
+    let status = cmd.status().context("Failed to run cargo check")?;
+
+    if json {
+        let mut checks = Checks::default();
+        checks.pass("files", format!("{} present", required_files.join(", ")));
+        checks.pass("package", format!("Cargo package {}", package_name));
+        checks.expect("cargo check", status.success(), "Compiles", format!("cargo check exited with {:?}", status.code()));
+        let report = DirectoryReport {
+            path: ext_path.display().to_string(),
+            valid: !checks.has_failures(),
+            checks,
+        };
+        println!("{}", serde_json::to_string_pretty(&report)?);
     }
-
-    let status = cmd
-        .current_dir(&ext_path)
-        .status()
-        .context("Failed to run cargo test")?;
 
     if !status.success() {
-        anyhow::bail!("Tests failed with exit code: {:?}", status.code());
+        return Err(fail(
+            FailureClass::CompileFailure,
+            "Cargo check failed. Extension has compilation errors.",
+        ));
     }
 
-    println!("{}", "✅ All tests passed!".green().bold());
+    if !json {
+        println!("{}", "✅ Extension is valid!".green().bold());
+    }
     Ok(())
 }
 
@@ -407,97 +628,16 @@ fn get_extension_path(path: Option<String>) -> Result<PathBuf> {
     };
 
     if !ext_path.exists() {
-        anyhow::bail!("Extension path does not exist: {}", ext_path.display());
+        return Err(fail(
+            FailureClass::MissingFile,
+            format!("Extension path does not exist: {}", ext_path.display()),
+        ));
     }
 
     Ok(ext_path)
 }
 
-fn copy_dir(src: &PathBuf, dst: &PathBuf, ext_name: &str) -> Result<()> {
-    // Create destination directory
-    fs::create_dir_all(dst).context("Failed to create directory")?;
-
-    // Read source directory
-    for entry in fs::read_dir(src).context("Failed to read template directory")? {
-        let entry = entry?;
-        let ty = entry.file_type()?;
-        let src_path = entry.path();
-        let file_name = entry.file_name();
-        let dst_path = dst.join(&file_name);
-
-        if ty.is_dir() {
-            // Recursively copy directory
-            copy_dir(&src_path, &dst_path, ext_name)?;
-        } else {
-            // Read file content
-            let content = fs::read_to_string(&src_path)?;
-
-            // Replace template variables
-            let content = replace_template_variables(&content, ext_name);
-
-            // Write to destination
-            fs::write(&dst_path, content).context("Failed to write file")?;
-
-            println!("  {}", dst_path.display().to_string().dimmed());
-        }
-    }
-
-    Ok(())
-}
-
-fn replace_template_variables(content: &str, ext_name: &str) -> String {
-    let mut result = content.to_string();
-
-    // Extension name transformations
-    let ext_id = ext_name;
-    let ext_struct_name = to_pascal_case(ext_name);
-    let ext_display_name = to_display_name(ext_name);
-    let ext_name_underscored = ext_name.replace('-', "_");
-    let ext_package_name = format!("neomind_extension_{}", ext_name_underscored);
-
-    // Replace variables
-    result = result.replace("{{EXTENSION_NAME}}", ext_name);
-    result = result.replace("{{EXTENSION_ID}}", ext_id);
-    result = result.replace("{{EXTENSION_STRUCT_NAME}}", &ext_struct_name);
-    result = result.replace("{{EXTENSION_DISPLAY_NAME}}", &ext_display_name);
-    result = result.replace("{{EXTENSION_NAME_UNDERSCORED}}", &ext_name_underscored);
-    result = result.replace("{{EXTENSION_PACKAGE_NAME}}", &ext_package_name);
-    result = result.replace("{{EXTENSION_AUTHOR}}", "Your Name");
-    result = result.replace("{{EXTENSION_DESCRIPTION}}", &format!("A NeoMind extension for {}", ext_display_name));
-
-    result
-}
-
-fn to_pascal_case(s: &str) -> String {
-    s.split('-')
-        .map(|word| {
-            let mut chars = word.chars();
-            match chars.next() {
-                None => String::new(),
-                Some(first) => {
-                    first.to_uppercase().collect::<String>() + chars.as_str()
-                }
-            }
-        })
-        .collect()
-}
-
-fn to_display_name(s: &str) -> String {
-    s.split('-')
-        .map(|word| {
-            let mut chars = word.chars();
-            match chars.next() {
-                None => String::new(),
-                Some(first) => {
-                    first.to_uppercase().collect::<String>() + chars.as_str()
-                }
-            }
-        })
-        .collect::<Vec<_>>()
-        .join(" ")
-}
-
-fn extract_package_name(cargo_toml: &PathBuf) -> Result<String> {
+fn extract_package_name(cargo_toml: &Path) -> Result<String> {
     let content = fs::read_to_string(cargo_toml)?;
 
     // Find name = "..." line
@@ -514,61 +654,3 @@ fn extract_package_name(cargo_toml: &PathBuf) -> Result<String> {
 
     anyhow::bail!("Could not find package name in Cargo.toml");
 }
-
-fn update_workspace_cargo_toml(repo_root: &PathBuf, ext_name: &str) -> Result<()> {
-    let workspace_cargo = repo_root.join("Cargo.toml");
-
-    if !workspace_cargo.exists() {
-        return Ok(());
-    }
-
-    let ext_path = format!("extensions/{}", ext_name);
-    let content = fs::read_to_string(&workspace_cargo)?;
-    
-    if content.contains(&ext_path) {
-        return Ok(());
-    }
-
-    let lines: Vec<&str> = content.lines().collect();
-    let mut result = Vec::new();
-    let mut in_members = false;
-    let mut inserted = false;
-
-    for (i, line) in lines.iter().enumerate() {
-        result.push(line.to_string());
-        
-        if line.contains("members = [") {
-            in_members = true;
-        } else if in_members && !inserted {
-            if line.trim().starts_with('"') {
-                if i + 1 < lines.len() {
-                    let next_line = lines[i + 1].trim();
-                    if next_line == "]" {
-                        result.push(format!("    \"{}\",", ext_path));
-                        inserted = true;
-                    }
-                }
-            } else if line.trim() == "]" {
-                result.push(format!("    \"{}\",", ext_path));
-                inserted = true;
-            }
-        }
-    }
-
-    fs::write(&workspace_cargo, result.join("\n"))?;
-    
-    println!("  {}", "✓ Updated workspace Cargo.toml".green());
-
-    Ok(())
-}
-
-
-trait PathBufExt {
-    fn parallel_join(&self, other: &str) -> PathBuf;
-}
-
-impl PathBufExt for PathBuf {
-    fn parallel_join(&self, other: &str) -> PathBuf {
-        self.join(other)
-    }
-}
@@ -0,0 +1,216 @@
+//! Cargo manifest and target directory helpers.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::platform::Platform;
+
+/// The parts of an extension's Cargo.toml the CLI cares about.
+#[derive(Debug, Clone)]
+pub struct CrateInfo {
+    /// `[package] name`, as passed to `cargo build -p`
+    pub package_name: String,
+    /// `[package] version`, resolved through `version.workspace = true`
+    pub version: String,
+    /// `[lib] name`, defaulting to the package name with `-` replaced by `_`
+    pub lib_name: String,
+    /// Directory containing the Cargo.toml
+    pub manifest_dir: PathBuf,
+}
+
+impl CrateInfo {
+    pub fn load(ext_dir: &Path) -> Result<Self> {
+        let cargo_toml = ext_dir.join("Cargo.toml");
+        let doc = read_toml(&cargo_toml)?;
+
+        let package = doc
+            .get("package")
+            .and_then(|p| p.as_table())
+            .ok_or_else(|| anyhow::anyhow!("No [package] section in {}", cargo_toml.display()))?;
+
+        let package_name = package
+            .get("name")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Could not find package name in Cargo.toml"))?
+            .to_string();
+
+        let version = match package.get("version") {
+            Some(toml::Value::String(v)) => v.clone(),
+            Some(toml::Value::Table(t)) if t.get("workspace").and_then(|w| w.as_bool()) == Some(true) => {
+                workspace_package_version(ext_dir)?
+            }
+            _ => "0.1.0".to_string(),
+        };
+
+        let lib_name = doc
+            .get("lib")
+            .and_then(|l| l.get("name"))
+            .and_then(|v| v.as_str())
+            .map(String::from)
+            .unwrap_or_else(|| package_name.replace('-', "_"));
+
+        Ok(Self {
+            package_name,
+            version,
+            lib_name,
+            manifest_dir: ext_dir.to_path_buf(),
+        })
+    }
+
+    /// Path of the compiled library for `platform` and `profile` (`release` or `debug`).
+    ///
+    /// Host builds land in `target/<profile>/`, cross builds in
+    /// `target/<triple>/<profile>/`; the host location is preferred when both exist.
+    pub fn artifact_path(&self, platform: &Platform, profile: &str) -> PathBuf {
+        let target_dir = target_dir(&self.manifest_dir);
+        let file_name = platform.library_file_name(&self.lib_name);
+
+        let host_path = target_dir.join(profile).join(&file_name);
+        let is_host = Platform::host().map(|h| h == *platform).unwrap_or(false);
+        if is_host && host_path.exists() {
+            return host_path;
+        }
+
+        target_dir.join(platform.triple).join(profile).join(&file_name)
+    }
+}
+
+/// Cargo's target directory for a crate: `CARGO_TARGET_DIR`, the enclosing
+/// workspace's `target/`, or the crate's own `target/`.
+pub fn target_dir(ext_dir: &Path) -> PathBuf {
+    if let Ok(dir) = std::env::var("CARGO_TARGET_DIR") {
+        return PathBuf::from(dir);
+    }
+
+    match find_workspace_root(ext_dir) {
+        Some(root) => root.join("target"),
+        None => ext_dir.join("target"),
+    }
+}
+
+/// Nearest ancestor (including `ext_dir` itself) whose Cargo.toml declares `[workspace]`.
+pub fn find_workspace_root(ext_dir: &Path) -> Option<PathBuf> {
+    let start = ext_dir.canonicalize().unwrap_or_else(|_| ext_dir.to_path_buf());
+    start
+        .ancestors()
+        .find(|dir| {
+            read_toml(&dir.join("Cargo.toml"))
+                .map(|doc| doc.get("workspace").is_some())
+                .unwrap_or(false)
+        })
+        .map(Path::to_path_buf)
+}
+
+/// Directories listed in `[workspace] members` of `<root>/Cargo.toml`.
+pub fn workspace_members(root: &Path) -> Result<Vec<PathBuf>> {
+    let cargo_toml = root.join("Cargo.toml");
+    let doc = read_toml(&cargo_toml)?;
+    let members = doc
+        .get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(|m| m.as_array())
+        .ok_or_else(|| anyhow::anyhow!("No [workspace] members in {}", cargo_toml.display()))?;
+    Ok(members
+        .iter()
+        .filter_map(|m| m.as_str())
+        .map(|m| root.join(m))
+        .collect())
+}
+
+/// `cargo metadata` for the crate at `ext_dir`, with the dependency graph
+/// resolved for `triple` only.
+pub fn metadata(ext_dir: &Path, triple: &str) -> Result<serde_json::Value> {
+    let output = Command::new("cargo")
+        .args(["metadata", "--format-version", "1", "--filter-platform", triple, "--manifest-path"])
+        .arg(ext_dir.join("Cargo.toml"))
+        .output()
+        .context("Failed to run cargo metadata")?;
+    if !output.status.success() {
+        anyhow::bail!("cargo metadata failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    serde_json::from_slice(&output.stdout).context("cargo metadata returned invalid JSON")
+}
+
+/// Whether the crate at `ext_dir` lists `dependency` under `[dependencies]`
+/// or any `[target.<cfg>.dependencies]` table.
+pub fn depends_on(ext_dir: &Path, dependency: &str) -> Result<bool> {
+    let doc = read_toml(&ext_dir.join("Cargo.toml"))?;
+    let has = |table: Option<&toml::Value>| {
+        table
+            .and_then(|t| t.as_table())
+            .map(|t| t.contains_key(dependency))
+            .unwrap_or(false)
+    };
+    let in_targets = doc
+        .get("target")
+        .and_then(|t| t.as_table())
+        .map(|targets| targets.values().any(|cfg| has(cfg.get("dependencies"))))
+        .unwrap_or(false);
+    Ok(has(doc.get("dependencies")) || in_targets)
+}
+
+/// `[profile.<profile>] <key>` as Cargo applies it to `ext_dir`: a
+/// `CARGO_PROFILE_<PROFILE>_<KEY>` override, else the workspace root's
+/// Cargo.toml (profiles in member manifests are ignored by Cargo).
+pub fn profile_setting(ext_dir: &Path, profile: &str, key: &str) -> Result<Option<String>> {
+    let env = format!("CARGO_PROFILE_{}_{}", profile, key).to_uppercase().replace('-', "_");
+    if let Ok(value) = std::env::var(env) {
+        return Ok(Some(value));
+    }
+    let root = find_workspace_root(ext_dir).unwrap_or_else(|| ext_dir.to_path_buf());
+    let doc = read_toml(&root.join("Cargo.toml"))?;
+    Ok(doc
+        .get("profile")
+        .and_then(|p| p.get(profile))
+        .and_then(|p| p.get(key))
+        .and_then(|v| v.as_str())
+        .map(String::from))
+}
+
+fn workspace_package_version(ext_dir: &Path) -> Result<String> {
+    let root = find_workspace_root(ext_dir)
+        .ok_or_else(|| anyhow::anyhow!("version.workspace = true but no workspace root found"))?;
+    let doc = read_toml(&root.join("Cargo.toml"))?;
+    doc.get("workspace")
+        .and_then(|w| w.get("package"))
+        .and_then(|p| p.get("version"))
+        .and_then(|v| v.as_str())
+        .map(String::from)
+        .ok_or_else(|| anyhow::anyhow!("No [workspace.package] version in {}", root.display()))
+}
+
+fn read_toml(path: &Path) -> Result<toml::Table> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    content
+        .parse::<toml::Table>()
+        .with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_resolves_lib_name_and_workspace_version() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"ext\"]\n\n[workspace.package]\nversion = \"3.1.0\"\n",
+        )
+        .unwrap();
+        let ext_dir = dir.path().join("ext");
+        fs::create_dir(&ext_dir).unwrap();
+        fs::write(
+            ext_dir.join("Cargo.toml"),
+            "[package]\nname = \"my-ext\"\nversion.workspace = true\n",
+        )
+        .unwrap();
+
+        let info = CrateInfo::load(&ext_dir).unwrap();
+        assert_eq!(info.version, "3.1.0");
+        assert_eq!(info.lib_name, "my_ext");
+    }
+}
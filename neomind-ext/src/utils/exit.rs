@@ -0,0 +1,108 @@
+//! Exit codes for scripts and CI.
+//!
+//! Commands tag errors that belong to a failure class with [`fail`]; `main`
+//! maps the class to a stable exit code. Untagged errors exit with 1, and
+//! clap's usage errors keep their own code 2.
+
+use serde::Serialize;
+use serde_json::json;
+use std::fmt;
+
+/// Any error without a failure class.
+pub const GENERAL_ERROR: i32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureClass {
+    /// An input path, Cargo.toml, built library or package does not exist
+    MissingFile,
+    /// cargo build, check or test compilation failed
+    CompileFailure,
+    /// A check report (validate, lint, check-abi, verify, doctor, diff) has failures
+    ValidationFailure,
+    /// cargo test ran and tests failed
+    TestFailure,
+}
+
+impl FailureClass {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            FailureClass::MissingFile => 3,
+            FailureClass::CompileFailure => 4,
+            FailureClass::ValidationFailure => 5,
+            FailureClass::TestFailure => 6,
+        }
+    }
+}
+
+/// An error tagged with its failure class.
+#[derive(Debug)]
+pub struct Failure {
+    pub class: FailureClass,
+    pub message: String,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for Failure {}
+
+/// `return Err(fail(FailureClass::CompileFailure, "..."))` in place of `bail!`.
+pub fn fail(class: FailureClass, message: impl Into<String>) -> anyhow::Error {
+    Failure {
+        class,
+        message: message.into(),
+    }
+    .into()
+}
+
+/// The class of `err`: an explicit [`Failure`] anywhere in the chain, else a
+/// file-not-found I/O error (a `.nep` or config file that does not exist).
+pub fn classify(err: &anyhow::Error) -> Option<FailureClass> {
+    if let Some(failure) = err.chain().find_map(|e| e.downcast_ref::<Failure>()) {
+        return Some(failure.class);
+    }
+    err.chain()
+        .filter_map(|e| e.downcast_ref::<std::io::Error>())
+        .any(|e| e.kind() == std::io::ErrorKind::NotFound)
+        .then_some(FailureClass::MissingFile)
+}
+
+pub fn exit_code(err: &anyhow::Error) -> i32 {
+    classify(err).map(FailureClass::exit_code).unwrap_or(GENERAL_ERROR)
+}
+
+/// The error document written to stderr under `--json`.
+pub fn error_json(err: &anyhow::Error) -> serde_json::Value {
+    json!({
+        "error": {
+            "class": classify(err),
+            "exit_code": exit_code(err),
+            "message": format!("{:#}", err),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_classify_through_context() {
+        let err = Err::<(), _>(fail(FailureClass::CompileFailure, "cargo build exited with Some(101)"))
+            .context("Building yolo-video-v2")
+            .unwrap_err();
+        assert_eq!(exit_code(&err), 4);
+        assert_eq!(error_json(&err)["error"]["class"], "compile_failure");
+        assert_eq!(error_json(&err)["error"]["message"], "Building yolo-video-v2: cargo build exited with Some(101)");
+
+        let missing = std::fs::read("/nonexistent/demo.nep").context("Failed to open demo.nep").unwrap_err();
+        assert_eq!(classify(&missing), Some(FailureClass::MissingFile));
+        assert_eq!(exit_code(&anyhow::anyhow!("anything else")), GENERAL_ERROR);
+        assert_eq!(error_json(&anyhow::anyhow!("x"))["error"]["class"], serde_json::Value::Null);
+    }
+}
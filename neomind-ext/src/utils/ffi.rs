@@ -0,0 +1,258 @@
+//! Loading a built extension library through the SDK's JSON FFI exports.
+//!
+//! `neomind_export!` generates `neomind_extension_*_json` functions that take
+//! a UTF-8 JSON buffer and return a heap-allocated C string of the form
+//! `{"success": bool, "error"?: string, ...}`. Every returned string must be
+//! released with `neomind_extension_free_string`.
+//!
+//! The library holds a single extension instance, created on the first call
+//! and dropped by `neomind_extension_reset_instance`; the JSON exports act
+//! on that instance. Capabilities requested through the SDK's
+//! `CapabilityContext` are routed to the bridge installed with
+//! `neomind_extension_set_capability_bridge`, which can be set only once per
+//! loaded library.
+
+use anyhow::{Context, Result};
+use libloading::{Library, Symbol};
+use serde_json::Value;
+use std::ffi::{c_void, CStr};
+use std::os::raw::c_char;
+use std::path::{Path, PathBuf};
+
+use super::cargo::CrateInfo;
+use super::manifest::ExtensionMetadata;
+use super::platform::Platform;
+
+type AbiVersionFn = unsafe extern "C" fn() -> u32;
+type JsonFn = unsafe extern "C" fn() -> *mut c_char;
+type JsonInputFn = unsafe extern "C" fn(*const u8, usize) -> *mut c_char;
+type FreeStringFn = unsafe extern "C" fn(*mut c_char);
+type ResetInstanceFn = unsafe extern "C" fn();
+
+/// Host side of `CapabilityContext::invoke_capability`: receives
+/// `{"capability", "params"}` and returns a response the extension releases
+/// through the paired [`CapabilityFreeFn`].
+pub type CapabilityHandlerFn = extern "C" fn(*const u8, usize) -> *mut c_char;
+pub type CapabilityFreeFn = extern "C" fn(*mut c_char);
+type SetCapabilityBridgeFn = unsafe extern "C" fn(CapabilityHandlerFn, CapabilityFreeFn);
+
+/// A dlopen'ed extension cdylib.
+pub struct ExtensionLibrary {
+    path: PathBuf,
+    library: Library,
+}
+
+impl ExtensionLibrary {
+    pub fn load(path: &Path) -> Result<Self> {
+        // SAFETY: loading runs the library's initialisers; extensions built with
+        // the SDK have none beyond Rust statics.
+        let library = unsafe { Library::new(path) }
+            .with_context(|| format!("Failed to load extension library {}", path.display()))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            library,
+        })
+    }
+
+    /// `{metadata, commands, metrics}` as returned by `neomind_extension_descriptor_json`.
+    pub fn descriptor(&self) -> Result<Value> {
+        let mut response = self.call(b"neomind_extension_descriptor_json\0")?;
+        Ok(response["descriptor"].take())
+    }
+
+    /// Route the library's capability calls to `handler`.
+    pub fn set_capability_bridge(&self, handler: CapabilityHandlerFn, free: CapabilityFreeFn) -> Result<()> {
+        let func: Symbol<SetCapabilityBridgeFn> = self.symbol(b"neomind_extension_set_capability_bridge\0")?;
+        unsafe { func(handler, free) };
+        Ok(())
+    }
+
+    /// Start from a fresh extension instance and apply `config` to it; an
+    /// empty object skips `configure`. The instance is reset again on drop.
+    pub fn create(&self, config: &Value) -> Result<ExtensionInstance<'_>> {
+        self.reset_instance();
+        let instance = ExtensionInstance { library: self };
+        if config.as_object().map(|c| !c.is_empty()).unwrap_or(!config.is_null()) {
+            instance.configure(config)?;
+        }
+        Ok(instance)
+    }
+
+    /// Drop the library's extension instance; the next call creates a new one.
+    fn reset_instance(&self) {
+        // Older SDKs can't reset; their instance lives as long as the library
+        if let Ok(reset) = self.symbol::<ResetInstanceFn>(b"neomind_extension_reset_instance\0") {
+            unsafe { reset() };
+        }
+    }
+
+    /// Whether the library exports `symbol` (NUL-terminated), without calling it.
+    pub fn exports(&self, symbol: &[u8]) -> bool {
+        // SAFETY: the symbol is only looked up, never called.
+        unsafe { self.library.get::<*const c_void>(symbol) }.is_ok()
+    }
+
+    /// `neomind_extension_abi_version()`, the `SDK_ABI_VERSION` the library was built against.
+    pub fn abi_version(&self) -> Result<u32> {
+        let func: Symbol<AbiVersionFn> = self.symbol(b"neomind_extension_abi_version\0")?;
+        Ok(unsafe { func() })
+    }
+
+    /// Call a no-argument JSON export and unwrap its `success` envelope.
+    fn call(&self, symbol: &[u8]) -> Result<Value> {
+        let func: Symbol<JsonFn> = self.symbol(symbol)?;
+        let ptr = unsafe { func() };
+        self.take_response(symbol, ptr)
+    }
+
+    fn take_response(&self, symbol: &[u8], ptr: *mut c_char) -> Result<Value> {
+        let name = symbol_name(symbol);
+        if ptr.is_null() {
+            anyhow::bail!("{} returned null", name);
+        }
+
+        let text = unsafe { CStr::from_ptr(ptr) }.to_string_lossy().into_owned();
+        let free: Symbol<FreeStringFn> = self.symbol(b"neomind_extension_free_string\0")?;
+        unsafe { free(ptr) };
+
+        let response: Value =
+            serde_json::from_str(&text).with_context(|| format!("{} returned invalid JSON", name))?;
+        if response.get("success").and_then(|s| s.as_bool()) == Some(false) {
+            let error = response.get("error").and_then(|e| e.as_str()).unwrap_or("unknown error");
+            anyhow::bail!("{}: {}", name, error);
+        }
+        Ok(response)
+    }
+
+    fn symbol<T>(&self, symbol: &[u8]) -> Result<Symbol<'_, T>> {
+        // SAFETY: the type parameter matches the signature generated by `neomind_export!`.
+        unsafe { self.library.get(symbol) }.with_context(|| {
+            format!(
+                "{} does not export {} (not built with neomind_export!?)",
+                self.path.display(),
+                symbol_name(symbol)
+            )
+        })
+    }
+}
+
+/// The library's extension instance, as set up by [`ExtensionLibrary::create`].
+pub struct ExtensionInstance<'lib> {
+    library: &'lib ExtensionLibrary,
+}
+
+impl ExtensionInstance<'_> {
+    /// `execute_command(command, args)`; returns the command's result value.
+    pub fn execute_command(&self, command: &str, args: &Value) -> Result<Value> {
+        let request = serde_json::json!({ "command": command, "args": args });
+        let mut response = self.call(b"neomind_extension_execute_command_json\0", &request)?;
+        Ok(response["result"].take())
+    }
+
+    /// `produce_metrics()`; returns the list of metric values.
+    pub fn produce_metrics(&self) -> Result<Value> {
+        let mut response = self.library.call(b"neomind_extension_produce_metrics_json\0")?;
+        Ok(response["metrics"].take())
+    }
+
+    pub fn configure(&self, config: &Value) -> Result<()> {
+        self.call(b"neomind_extension_configure_json\0", config)?;
+        Ok(())
+    }
+
+    /// `handle_event(event_type, payload)`.
+    pub fn handle_event(&self, event_type: &str, payload: &Value) -> Result<()> {
+        let request = serde_json::json!({ "event_type": event_type, "payload": payload });
+        self.call(b"neomind_extension_handle_event_json\0", &request)?;
+        Ok(())
+    }
+
+    fn call(&self, symbol: &[u8], request: &Value) -> Result<Value> {
+        let func: Symbol<JsonInputFn> = self.library.symbol(symbol)?;
+        let request = serde_json::to_vec(request)?;
+        let ptr = unsafe { func(request.as_ptr(), request.len()) };
+        self.library.take_response(symbol, ptr)
+    }
+}
+
+impl Drop for ExtensionInstance<'_> {
+    fn drop(&mut self) {
+        self.library.reset_instance();
+    }
+}
+
+/// Host release build of the extension, falling back to the debug build.
+pub fn find_built_library(ext_dir: &Path) -> Result<Option<PathBuf>> {
+    let metadata = ExtensionMetadata::load_optional(ext_dir)?;
+    if metadata.and_then(|m| m.ext_type).as_deref() == Some("wasm") {
+        anyhow::bail!("only native extensions can be loaded");
+    }
+    let info = CrateInfo::load(ext_dir)?;
+    let host = Platform::host().ok_or_else(|| anyhow::anyhow!("Unsupported host platform"))?;
+    Ok(["release", "debug"]
+        .iter()
+        .map(|profile| info.artifact_path(&host, profile))
+        .find(|p| p.exists()))
+}
+
+/// `--lib` if given, otherwise the built library of `ext_dir`.
+pub fn resolve_library(ext_dir: &Path, lib: Option<&str>) -> Result<PathBuf> {
+    if let Some(lib) = lib {
+        return Ok(PathBuf::from(lib));
+    }
+    find_built_library(ext_dir)?
+        .ok_or_else(|| anyhow::anyhow!("No built library found; run `neomind-ext build` first or pass --lib"))
+}
+
+fn symbol_name(symbol: &[u8]) -> &str {
+    std::str::from_utf8(symbol).unwrap_or_default().trim_end_matches('\0')
+}
+
+/// A private copy of the wasm-demo extension's native library, built on first use.
+#[cfg(test)]
+pub(crate) fn demo_library(dir: &Path) -> PathBuf {
+    static BUILT: std::sync::OnceLock<PathBuf> = std::sync::OnceLock::new();
+    let built = BUILT.get_or_init(|| {
+        let ext_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../extensions/wasm-demo");
+        let status = std::process::Command::new("cargo")
+            .args(["build", "-p", "wasm-demo"])
+            .current_dir(&ext_dir)
+            .status()
+            .expect("Failed to run cargo build");
+        assert!(status.success(), "building wasm-demo failed");
+        let host = Platform::host().expect("unsupported host");
+        CrateInfo::load(&ext_dir).unwrap().artifact_path(&host, "debug")
+    });
+    // Each test loads its own file so the library's singleton isn't shared
+    let copy = dir.join(built.file_name().unwrap());
+    std::fs::copy(built, &copy).unwrap();
+    copy
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_load_and_invoke_built_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        let library = ExtensionLibrary::load(&demo_library(dir.path())).unwrap();
+        crate::utils::host::install(&library).unwrap();
+        assert_eq!(library.descriptor().unwrap()["metadata"]["id"], "wasm-demo");
+
+        let instance = library.create(&json!({"log_level": "debug"})).unwrap();
+        let result = instance.execute_command("increment", &json!({"amount": 2})).unwrap();
+        assert_eq!(result["counter"], 2);
+        let metrics = instance.produce_metrics().unwrap();
+        assert_eq!(metrics[0]["name"], "counter");
+        instance.handle_event("DeviceMetric", &json!({"device_id": "cam-1"})).unwrap();
+        let error = instance.execute_command("nope", &json!({})).unwrap_err();
+        assert!(format!("{:#}", error).contains("nope"));
+        drop(instance);
+
+        // A new instance starts from scratch
+        let instance = library.create(&json!({})).unwrap();
+        assert_eq!(instance.execute_command("get", &json!({})).unwrap()["counter"], 0);
+    }
+}
@@ -0,0 +1,114 @@
+//! Stub NeoMind host used by `run` and `replay`.
+//!
+//! Capability calls made by a loaded extension are recorded instead of being
+//! forwarded to a server, so the CLI can show exactly which virtual metrics,
+//! device registrations, etc. an extension would have written.
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::sync::Mutex;
+
+use super::ffi::ExtensionLibrary;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CapabilityCall {
+    pub capability: String,
+    pub params: Value,
+}
+
+impl CapabilityCall {
+    /// One-line summary; `device_metrics_write` is shown as `device.metric = value`.
+    pub fn summary(&self) -> String {
+        if self.capability == "device_metrics_write" {
+            let field = |name: &str| self.params.get(name).cloned().unwrap_or(Value::Null);
+            return format!(
+                "{} {}.{} = {}",
+                self.capability,
+                field("device_id").as_str().unwrap_or("?"),
+                field("metric").as_str().unwrap_or("?"),
+                field("value")
+            );
+        }
+        format!("{} {}", self.capability, self.params)
+    }
+}
+
+static CALLS: Mutex<Vec<CapabilityCall>> = Mutex::new(Vec::new());
+
+/// Route `library`'s capability calls to the recording stub.
+pub fn install(library: &ExtensionLibrary) -> Result<()> {
+    library.set_capability_bridge(handle_capability, free_response)
+}
+
+/// Capability calls recorded since the last call to `take_calls`.
+pub fn take_calls() -> Vec<CapabilityCall> {
+    std::mem::take(&mut *CALLS.lock().unwrap_or_else(|e| e.into_inner()))
+}
+
+/// Every capability succeeds; writes echo back what was written.
+fn stub_response(call: &CapabilityCall) -> Value {
+    match call.capability.as_str() {
+        "device_metrics_write" => json!({ "success": true, "result": { "written": call.params } }),
+        _ => json!({ "success": true, "result": {} }),
+    }
+}
+
+extern "C" fn handle_capability(request: *const u8, len: usize) -> *mut c_char {
+    let bytes = if request.is_null() {
+        &[][..]
+    } else {
+        // SAFETY: the extension passes a buffer of `len` bytes it owns for the call.
+        unsafe { std::slice::from_raw_parts(request, len) }
+    };
+    let request: Value = serde_json::from_slice(bytes).unwrap_or(Value::Null);
+    let call = CapabilityCall {
+        capability: request["capability"].as_str().unwrap_or_default().to_string(),
+        params: request.get("params").cloned().unwrap_or(Value::Null),
+    };
+
+    let response = stub_response(&call);
+    CALLS.lock().unwrap_or_else(|e| e.into_inner()).push(call);
+    CString::new(response.to_string())
+        .map(CString::into_raw)
+        .unwrap_or(std::ptr::null_mut())
+}
+
+extern "C" fn free_response(ptr: *mut c_char) {
+    if !ptr.is_null() {
+        // SAFETY: `ptr` was produced by `CString::into_raw` in `handle_capability`.
+        drop(unsafe { CString::from_raw(ptr) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    #[test]
+    fn test_handler_records_metric_writes() {
+        take_calls();
+        let request = json!({
+            "capability": "device_metrics_write",
+            "params": { "device_id": "cam-1", "metric": "virtual.yolo.detections", "value": 3 },
+        })
+        .to_string();
+
+        let ptr = handle_capability(request.as_ptr(), request.len());
+        let response: Value =
+            serde_json::from_str(unsafe { CStr::from_ptr(ptr) }.to_str().unwrap()).unwrap();
+        free_response(ptr);
+
+        assert_eq!(response["success"], true);
+        let calls = take_calls();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(
+            calls[0].summary(),
+            "device_metrics_write cam-1.virtual.yolo.detections = 3"
+        );
+        assert!(take_calls().is_empty());
+    }
+}
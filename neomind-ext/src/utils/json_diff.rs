@@ -0,0 +1,133 @@
+//! Structural diff of two JSON values, reported per leaf path.
+
+use serde_json::Value;
+use std::fmt;
+
+/// Longest value shown in a change line; base64 images are cut down to this.
+const MAX_SHOWN: usize = 80;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    Added { path: String, value: Value },
+    Removed { path: String, value: Value },
+    Changed { path: String, old: Value, new: Value },
+}
+
+/// Differences from `old` to `new`. Objects are compared by key, arrays by
+/// index; anything else (including a type change) is one `Changed` entry.
+pub fn diff(old: &Value, new: &Value) -> Vec<Change> {
+    let mut changes = Vec::new();
+    diff_at(String::new(), old, new, &mut changes);
+    changes
+}
+
+fn diff_at(path: String, old: &Value, new: &Value, changes: &mut Vec<Change>) {
+    match (old, new) {
+        (Value::Object(a), Value::Object(b)) => {
+            for (key, old_value) in a {
+                let child = format!("{}/{}", path, key);
+                match b.get(key) {
+                    Some(new_value) => diff_at(child, old_value, new_value, changes),
+                    None => changes.push(Change::Removed {
+                        path: child,
+                        value: old_value.clone(),
+                    }),
+                }
+            }
+            for (key, new_value) in b.iter().filter(|(key, _)| !a.contains_key(*key)) {
+                changes.push(Change::Added {
+                    path: format!("{}/{}", path, key),
+                    value: new_value.clone(),
+                });
+            }
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            for (i, old_value) in a.iter().enumerate() {
+                let child = format!("{}/{}", path, i);
+                match b.get(i) {
+                    Some(new_value) => diff_at(child, old_value, new_value, changes),
+                    None => changes.push(Change::Removed {
+                        path: child,
+                        value: old_value.clone(),
+                    }),
+                }
+            }
+            for (i, new_value) in b.iter().enumerate().skip(a.len()) {
+                changes.push(Change::Added {
+                    path: format!("{}/{}", path, i),
+                    value: new_value.clone(),
+                });
+            }
+        }
+        _ if old != new => changes.push(Change::Changed {
+            path,
+            old: old.clone(),
+            new: new.clone(),
+        }),
+        _ => {}
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Added { path, value } => write!(f, "+ {}: {}", display_path(path), shorten(value)),
+            Change::Removed { path, value } => write!(f, "- {}: {}", display_path(path), shorten(value)),
+            Change::Changed { path, old, new } => {
+                write!(f, "~ {}: {} -> {}", display_path(path), shorten(old), shorten(new))
+            }
+        }
+    }
+}
+
+fn display_path(path: &str) -> &str {
+    if path.is_empty() {
+        "/"
+    } else {
+        path
+    }
+}
+
+fn shorten(value: &Value) -> String {
+    let text = value.to_string();
+    if text.chars().count() <= MAX_SHOWN {
+        return text;
+    }
+    let head: String = text.chars().take(MAX_SHOWN).collect();
+    format!("{}… ({} chars)", head, text.chars().count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_reports_leaf_paths() {
+        let old = json!({"detections": [{"label": "person", "confidence": 0.81}], "latency_ms": 12});
+        let new = json!({"detections": [{"label": "person", "confidence": 0.9}, {"label": "car"}], "image": "x"});
+        let lines: Vec<String> = diff(&old, &new).iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            lines,
+            [
+                "~ /detections/0/confidence: 0.81 -> 0.9",
+                "+ /detections/1: {\"label\":\"car\"}",
+                "- /latency_ms: 12",
+                "+ /image: \"x\"",
+            ]
+        );
+        assert!(diff(&old, &old).is_empty());
+    }
+
+    #[test]
+    fn test_long_values_are_shortened() {
+        let change = Change::Changed {
+            path: String::new(),
+            old: Value::Null,
+            new: Value::String("A".repeat(500)),
+        };
+        let line = change.to_string();
+        assert!(line.starts_with("~ /: null -> \"AAA"));
+        assert!(line.ends_with("(502 chars)"));
+    }
+}
@@ -0,0 +1,363 @@
+//! metadata.json, frontend.json and .nep manifest.json models.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// `format` value of every .nep manifest
+pub const PACKAGE_FORMAT: &str = "neomind-extension-package";
+/// Current .nep `format_version`
+pub const PACKAGE_FORMAT_VERSION: &str = "2.0";
+/// Extension ABI version expected by the NeoMind runner
+pub const PACKAGE_ABI_VERSION: u32 = 3;
+/// SDK version recorded in generated manifests
+pub const PACKAGE_SDK_VERSION: &str = "2.0.0";
+
+/// Per-extension metadata.json (the source of the marketplace index entry).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExtensionMetadata {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub license: Option<String>,
+    #[serde(rename = "type", default)]
+    pub ext_type: Option<String>,
+    /// Build key (`linux-x86_64`, ...) -> `{"url": ...}`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub builds: Option<Value>,
+    #[serde(default)]
+    pub frontend: Option<FrontendMetadata>,
+}
+
+/// `frontend` section of metadata.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FrontendMetadata {
+    #[serde(default)]
+    pub components: Vec<String>,
+    #[serde(default)]
+    pub entrypoint: Option<String>,
+}
+
+impl ExtensionMetadata {
+    /// Load `<ext_dir>/metadata.json`, returning `None` if the file does not exist.
+    pub fn load_optional(ext_dir: &Path) -> Result<Option<Self>> {
+        let path = ext_dir.join("metadata.json");
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let metadata = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        Ok(Some(metadata))
+    }
+}
+
+/// manifest.json at the root of a .nep package.
+///
+/// Field order matches the manifests produced by build.sh.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageManifest {
+    pub format: String,
+    pub format_version: String,
+    pub abi_version: u32,
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    pub sdk_version: String,
+    #[serde(rename = "type")]
+    pub ext_type: String,
+    /// Platform key (`linux_amd64`, `wasm`, ...) -> path inside the package
+    pub binaries: BTreeMap<String, String>,
+    pub frontend: PackageFrontend,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub models: Option<String>,
+    /// Package path -> SHA-256 of every file except manifest.json
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub checksums: BTreeMap<String, String>,
+}
+
+/// `frontend` section of manifest.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PackageFrontend {
+    #[serde(default)]
+    pub components: Vec<Value>,
+}
+
+/// Display name used when metadata.json has none: `yolo-video-v2` -> `yolo video`
+pub fn default_display_name(ext_id: &str) -> String {
+    ext_id.trim_end_matches("-v2").replace('-', " ")
+}
+
+/// Dashboard component type derived from the extension ID:
+/// `yolo-device-inference` -> `yolo-device-inference-card`, `yolo-video-v2` -> `yolo-video-card`
+fn component_type(ext_id: &str) -> String {
+    let base = ext_id.strip_suffix("-v2").or_else(|| ext_id.strip_suffix("-v1")).unwrap_or(ext_id);
+    format!("{}-card", base)
+}
+
+/// Read the UMD global name (`build.lib.name`) from frontend/vite.config.ts.
+fn vite_global_name(frontend_dir: &Path) -> Option<String> {
+    let content = fs::read_to_string(frontend_dir.join("vite.config.ts")).ok()?;
+    content.lines().find_map(|line| {
+        let rest = line.trim().strip_prefix("name:")?.trim();
+        let quote = rest.chars().next().filter(|c| *c == '\'' || *c == '"')?;
+        let inner = &rest[1..];
+        inner.find(quote).map(|end| inner[..end].to_string())
+    })
+}
+
+/// Resolve the built frontend entrypoint, accepting `.umd.cjs` when `.umd.js` is declared.
+pub fn resolve_frontend_entrypoint(frontend_dist: &Path, entrypoint: &str) -> String {
+    if !frontend_dist.join(entrypoint).exists() {
+        if let Some(stem) = entrypoint.strip_suffix(".umd.js") {
+            let cjs = format!("{}.umd.cjs", stem);
+            if frontend_dist.join(&cjs).exists() {
+                return cjs;
+            }
+        }
+    }
+    entrypoint.to_string()
+}
+
+/// Parsed `<ext_dir>/frontend/frontend.json`, or `None` if the extension has no frontend.
+pub fn load_frontend_json(ext_dir: &Path) -> Result<Option<Value>> {
+    let path = ext_dir.join("frontend").join("frontend.json");
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let frontend = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    Ok(Some(frontend))
+}
+
+/// The metadata.json `frontend` section implied by frontend.json: component
+/// names plus entrypoint (the same reduction scripts/update-versions.sh does).
+pub fn frontend_metadata(ext_dir: &Path) -> Result<Option<FrontendMetadata>> {
+    let Some(frontend) = load_frontend_json(ext_dir)? else {
+        return Ok(None);
+    };
+    let components = frontend
+        .get("components")
+        .and_then(|c| c.as_array())
+        .map(|c| {
+            c.iter()
+                .filter_map(|c| c.get("name").and_then(|n| n.as_str()).map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+    let entrypoint = frontend.get("entrypoint").and_then(|e| e.as_str()).map(String::from);
+    Ok(Some(FrontendMetadata { components, entrypoint }))
+}
+
+/// Convert frontend/frontend.json into the manifest's `dashboard_components` list.
+pub fn dashboard_components(ext_dir: &Path, ext_id: &str) -> Result<Vec<Value>> {
+    let frontend_dir = ext_dir.join("frontend");
+    let Some(frontend) = load_frontend_json(ext_dir)? else {
+        return Ok(Vec::new());
+    };
+
+    let entrypoint = frontend.get("entrypoint").and_then(|v| v.as_str()).unwrap_or_default();
+    let entrypoint = resolve_frontend_entrypoint(&frontend_dir.join("dist"), entrypoint);
+    let global_name = vite_global_name(&frontend_dir);
+    let component_type = component_type(ext_id);
+
+    let components = frontend
+        .get("components")
+        .and_then(|c| c.as_array())
+        .map(|c| c.as_slice())
+        .unwrap_or_default();
+
+    Ok(components
+        .iter()
+        .map(|c| dashboard_component(c, &component_type, &entrypoint, global_name.as_deref()))
+        .collect())
+}
+
+fn dashboard_component(
+    component: &Value,
+    component_type: &str,
+    entrypoint: &str,
+    global_name: Option<&str>,
+) -> Value {
+    let size = |key: &str, dim: &str, default: u64| {
+        component
+            .get(key)
+            .and_then(|s| s.get(dim))
+            .cloned()
+            .unwrap_or_else(|| json!(default))
+    };
+    // Category must be one of: chart, metric, table, control, media, custom, other
+    let category = match component.get("type").and_then(|t| t.as_str()) {
+        Some("card") | Some("widget") | Some("panel") => "custom",
+        Some(t @ ("chart" | "metric" | "table" | "control" | "media")) => t,
+        _ => "other",
+    };
+    let has_data_source = component
+        .get("hasDataSource")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let config_schema = component.get("configSchema").and_then(|s| s.as_object());
+
+    let mut result = json!({
+        "type": component_type,
+        "name": component.get("displayName"),
+        "description": component.get("description"),
+        "category": category,
+        "icon": component.get("icon"),
+        "bundle_path": format!("frontend/{}", entrypoint),
+        "export_name": component.get("name"),
+    });
+    if let Some(global_name) = global_name {
+        result["global_name"] = json!(global_name);
+    }
+
+    let extra = json!({
+        "size_constraints": {
+            "min_w": size("minSize", "width", 200),
+            "min_h": size("minSize", "height", 150),
+            "default_w": size("defaultSize", "width", 300),
+            "default_h": size("defaultSize", "height", 200),
+            "max_w": size("maxSize", "width", 800),
+            "max_h": size("maxSize", "height", 600),
+        },
+        "has_data_source": has_data_source,
+        "has_display_config": true,
+        "has_actions": false,
+        "max_data_sources": if has_data_source { 1 } else { 0 },
+        "data_source_allowed_types": component.get("dataSourceAllowedTypes"),
+        "config_schema": config_schema.map(|schema| config_schema_json(schema, component.get("uiHints"))),
+        "default_config": config_schema.map(|schema| {
+            schema
+                .iter()
+                .filter_map(|(key, field)| {
+                    field.get("default").filter(|d| !d.is_null()).map(|d| (key.clone(), d.clone()))
+                })
+                .collect::<serde_json::Map<_, _>>()
+        }),
+        "variants": [],
+    });
+    if let (Some(result), Some(extra)) = (result.as_object_mut(), extra.as_object()) {
+        result.extend(extra.clone());
+    }
+    result
+}
+
+fn config_schema_json(schema: &serde_json::Map<String, Value>, ui_hints: Option<&Value>) -> Value {
+    let properties: serde_json::Map<String, Value> = schema
+        .iter()
+        .map(|(key, field)| {
+            let field_type = field
+                .get("type")
+                .and_then(|t| t.as_str())
+                .filter(|t| matches!(*t, "string" | "number" | "boolean"))
+                .unwrap_or("string");
+            (
+                key.clone(),
+                json!({
+                    "type": field_type,
+                    "title": field.get("title"),
+                    "description": field.get("description"),
+                    "default": field.get("default"),
+                    "enum": field.get("enum"),
+                    "enumTitles": field.get("enumTitles"),
+                }),
+            )
+        })
+        .collect();
+
+    let ui_hints = ui_hints.map(|hints| {
+        let rules: Vec<Value> = hints
+            .get("visibilityRules")
+            .and_then(|r| r.as_array())
+            .map(|rules| {
+                rules
+                    .iter()
+                    .map(|rule| {
+                        json!({
+                            "field": rule.get("field"),
+                            "condition": rule.get("condition"),
+                            "value": rule.get("value"),
+                            "then_show": rule.get("thenShow"),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        json!({
+            "field_order": hints.get("fieldOrder"),
+            "visibility_rules": rules,
+        })
+    });
+
+    json!({
+        "type": "object",
+        "properties": properties,
+        "ui_hints": ui_hints,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_component_type_strips_version_suffix() {
+        assert_eq!(component_type("yolo-video-v2"), "yolo-video-card");
+        assert_eq!(component_type("yolo-device-inference"), "yolo-device-inference-card");
+    }
+
+    #[test]
+    fn test_dashboard_components_from_frontend_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let frontend = dir.path().join("frontend");
+        fs::create_dir_all(frontend.join("dist")).unwrap();
+        fs::write(frontend.join("dist/demo-components.umd.cjs"), "").unwrap();
+        fs::write(
+            frontend.join("vite.config.ts"),
+            "export default { build: { lib: { name: 'DemoComponents' } } }\n    name: 'DemoComponents',\n",
+        )
+        .unwrap();
+        fs::write(
+            frontend.join("frontend.json"),
+            r#"{
+                "entrypoint": "demo-components.umd.js",
+                "components": [{
+                    "name": "DemoCard",
+                    "type": "widget",
+                    "displayName": "Demo",
+                    "minSize": { "width": 320 },
+                    "hasDataSource": true,
+                    "configSchema": { "confidence": { "type": "number", "default": 0.25 } }
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        let components = dashboard_components(dir.path(), "demo-v2").unwrap();
+        assert_eq!(components.len(), 1);
+        let c = &components[0];
+        assert_eq!(c["type"], "demo-card");
+        assert_eq!(c["category"], "custom");
+        assert_eq!(c["bundle_path"], "frontend/demo-components.umd.cjs");
+        assert_eq!(c["export_name"], "DemoCard");
+        assert_eq!(c["global_name"], "DemoComponents");
+        assert_eq!(c["size_constraints"]["min_w"], 320);
+        assert_eq!(c["size_constraints"]["min_h"], 150);
+        assert_eq!(c["max_data_sources"], 1);
+        assert_eq!(c["config_schema"]["properties"]["confidence"]["type"], "number");
+        assert_eq!(c["default_config"]["confidence"], 0.25);
+    }
+}
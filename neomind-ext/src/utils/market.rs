@@ -0,0 +1,72 @@
+//! Marketplace conventions: release URLs and the repo-wide VERSION file.
+//!
+//! Mirrors scripts/update-versions.sh: release assets live under the
+//! `v<market version>` tag and are named `<id>-<extension version>-<platform>.nep`.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::platform::{Platform, NATIVE_PLATFORMS};
+
+pub const GITHUB_REPO: &str = "camthink-ai/NeoMind-Extensions";
+
+/// Market version from `<repo root>/VERSION`.
+pub fn market_version(repo_root: &Path) -> Result<String> {
+    let path = repo_root.join("VERSION");
+    let content = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let version = content.trim();
+    if version.is_empty() {
+        anyhow::bail!("{} is empty", path.display());
+    }
+    Ok(version.to_string())
+}
+
+/// Nearest ancestor of `start` holding the repo-wide VERSION file and `extensions/`.
+pub fn find_repo_root(start: &Path) -> Option<PathBuf> {
+    let start = start.canonicalize().unwrap_or_else(|_| start.to_path_buf());
+    start
+        .ancestors()
+        .find(|dir| dir.join("VERSION").is_file() && dir.join("extensions").is_dir())
+        .map(Path::to_path_buf)
+}
+
+/// Download URL of the .nep for `platform`.
+pub fn build_url(market_version: &str, ext_id: &str, ext_version: &str, platform: &Platform) -> String {
+    format!(
+        "https://github.com/{}/releases/download/v{}/{}-{}-{}.nep",
+        GITHUB_REPO, market_version, ext_id, ext_version, platform.package_key
+    )
+}
+
+/// Raw metadata.json URL referenced from the marketplace index.
+pub fn metadata_url(ext_id: &str) -> String {
+    format!(
+        "https://raw.githubusercontent.com/{}/main/extensions/{}/metadata.json",
+        GITHUB_REPO, ext_id
+    )
+}
+
+/// `(build key, url)` for every native platform, in index order.
+pub fn build_urls(market_version: &str, ext_id: &str, ext_version: &str) -> Vec<(&'static str, String)> {
+    NATIVE_PLATFORMS
+        .iter()
+        .map(|p| (p.build_key, build_url(market_version, ext_id, ext_version, p)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_urls_follow_release_layout() {
+        let urls = build_urls("2.7.0", "uink-rms-bridge", "0.1.0");
+        assert_eq!(urls.len(), 5);
+        assert_eq!(urls[2].0, "linux-x86_64");
+        assert_eq!(
+            urls[2].1,
+            "https://github.com/camthink-ai/NeoMind-Extensions/releases/download/v2.7.0/uink-rms-bridge-0.1.0-linux_amd64.nep"
+        );
+    }
+}
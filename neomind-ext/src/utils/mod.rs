@@ -0,0 +1,19 @@
+//! Helpers shared by several subcommands.
+
+pub mod cargo;
+pub mod checksum;
+pub mod exit;
+pub mod ffi;
+pub mod host;
+pub mod json_diff;
+pub mod manifest;
+pub mod market;
+pub mod models;
+pub mod native;
+pub mod nep;
+pub mod platform;
+pub mod recording;
+pub mod report;
+pub mod rust_source;
+pub mod signing;
+pub mod watch;
@@ -0,0 +1,164 @@
+//! Per-extension model manifests (`models.toml`).
+//!
+//! ```toml
+//! [[model]]
+//! file = "det_mv3_db.onnx"
+//! url = "https://github.com/jamjamjon/assets/releases/download/db/ppocr-v4-ch.onnx"
+//! sha256 = "..."
+//! size = 4748928
+//! ```
+//!
+//! Files live in the extension's `models/` directory, where packaging picks
+//! them up and the runtime finds them under `NEOMIND_EXTENSION_DIR/models/`.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::checksum;
+
+pub const MODELS_TOML: &str = "models.toml";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModelsManifest {
+    #[serde(default, rename = "model")]
+    pub models: Vec<ModelEntry>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModelEntry {
+    /// File name inside `models/`
+    pub file: String,
+    /// Download source; entries without one are committed to the repo or staged via `--mirror`
+    pub url: Option<String>,
+    /// Expected SHA-256 (lowercase hex)
+    pub sha256: Option<String>,
+    /// Expected size in bytes
+    pub size: Option<u64>,
+    /// Whether `package` bundles the file into the .nep
+    #[serde(default = "default_bundle")]
+    pub bundle: bool,
+}
+
+fn default_bundle() -> bool {
+    true
+}
+
+/// State of one model file on disk.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModelStatus {
+    /// Present and matching every pinned value
+    Verified,
+    /// Present, but the manifest pins no SHA-256 to compare against
+    Unpinned,
+    Missing,
+    SizeMismatch { expected: u64, actual: u64 },
+    ChecksumMismatch { actual: String },
+}
+
+impl ModelStatus {
+    pub fn is_usable(&self) -> bool {
+        matches!(self, Self::Verified | Self::Unpinned)
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Verified => "verified".to_string(),
+            Self::Unpinned => "present (no sha256 pinned)".to_string(),
+            Self::Missing => "missing".to_string(),
+            Self::SizeMismatch { expected, actual } => format!("size {} != expected {}", actual, expected),
+            Self::ChecksumMismatch { actual } => format!("sha256 mismatch (got {})", actual),
+        }
+    }
+}
+
+impl ModelsManifest {
+    /// `models.toml` of an extension, or `None` when it has none.
+    pub fn load_optional(ext_dir: &Path) -> Result<Option<Self>> {
+        let path = ext_dir.join(MODELS_TOML);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))
+            .map(Some)
+    }
+
+    pub fn parse(content: &str) -> Result<Self> {
+        let manifest: Self = toml::from_str(content)?;
+        for model in &manifest.models {
+            if model.file.contains(['/', '\\']) || model.file.starts_with('.') {
+                anyhow::bail!("Model file '{}' must be a plain file name inside models/", model.file);
+            }
+        }
+        Ok(manifest)
+    }
+}
+
+impl ModelEntry {
+    pub fn path(&self, ext_dir: &Path) -> PathBuf {
+        ext_dir.join("models").join(&self.file)
+    }
+
+    /// Check `path` (the model itself or a downloaded candidate) against the pinned values.
+    pub fn check(&self, path: &Path) -> Result<ModelStatus> {
+        if !path.is_file() {
+            return Ok(ModelStatus::Missing);
+        }
+        let actual_size = fs::metadata(path)?.len();
+        if let Some(expected) = self.size {
+            if expected != actual_size {
+                return Ok(ModelStatus::SizeMismatch {
+                    expected,
+                    actual: actual_size,
+                });
+            }
+        }
+        let Some(expected) = &self.sha256 else {
+            return Ok(ModelStatus::Unpinned);
+        };
+        let actual = checksum::sha256_file(path)?;
+        if actual.eq_ignore_ascii_case(expected) {
+            Ok(ModelStatus::Verified)
+        } else {
+            Ok(ModelStatus::ChecksumMismatch { actual })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_check() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("models")).unwrap();
+        fs::write(dir.path().join("models").join("vocab.txt"), "abc").unwrap();
+
+        let manifest = ModelsManifest::parse(&format!(
+            "[[model]]\nfile = \"vocab.txt\"\nsha256 = \"{}\"\nsize = 3\n\n\
+             [[model]]\nfile = \"det.onnx\"\nurl = \"https://example.com/det.onnx\"\nbundle = false\n",
+            checksum::sha256_hex(b"abc")
+        ))
+        .unwrap();
+        let (vocab, det) = (&manifest.models[0], &manifest.models[1]);
+        assert!(vocab.bundle);
+        assert!(!det.bundle);
+        assert_eq!(vocab.check(&vocab.path(dir.path())).unwrap(), ModelStatus::Verified);
+        assert_eq!(det.check(&det.path(dir.path())).unwrap(), ModelStatus::Missing);
+
+        fs::write(dir.path().join("models").join("vocab.txt"), "abd").unwrap();
+        assert!(matches!(
+            vocab.check(&vocab.path(dir.path())).unwrap(),
+            ModelStatus::ChecksumMismatch { .. }
+        ));
+    }
+
+    #[test]
+    fn test_model_paths_stay_in_models_dir() {
+        assert!(ModelsManifest::parse("[[model]]\nfile = \"../secrets.txt\"\n").is_err());
+    }
+}
@@ -0,0 +1,203 @@
+//! Native library discovery as the vision extensions perform it at runtime.
+//!
+//! `setup_native_lib_paths` in the ONNX extensions builds a search path from
+//! `NEOMIND_EXTENSION_DIR/lib/`, `NEOMIND_EXTENSION_DIR/binaries/*/`,
+//! `<cwd>/lib/`, the inherited library path variable and
+//! `/opt/homebrew/lib` + `/usr/local/lib`, then points `ORT_DYLIB_PATH` at
+//! the first ONNX Runtime found there. Anything not found falls through to the
+//! system loader's default search.
+
+use anyhow::{Context, Result};
+use libloading::Library;
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::path::{Path, PathBuf};
+
+/// ORT C API version required by `ort` 2.0.0-rc.10 (ONNX Runtime 1.22.x).
+pub const ORT_API_VERSION: u32 = 22;
+
+/// FFmpeg libraries `ffmpeg-next` 7 links with `codec`, `format` and
+/// `software-scaling`, with their FFmpeg 7 major versions.
+pub const FFMPEG_LIBS: [(&str, u32); 4] = [("avcodec", 61), ("avformat", 61), ("avutil", 59), ("swscale", 8)];
+
+/// Library path variable the extensions extend.
+pub fn library_path_var() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "DYLD_LIBRARY_PATH"
+    } else if cfg!(target_os = "windows") {
+        "PATH"
+    } else {
+        "LD_LIBRARY_PATH"
+    }
+}
+
+/// File name the extensions look for when setting `ORT_DYLIB_PATH`.
+pub fn ort_file_name() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "libonnxruntime.dylib"
+    } else if cfg!(target_os = "windows") {
+        "onnxruntime.dll"
+    } else {
+        "libonnxruntime.so"
+    }
+}
+
+/// File name of an FFmpeg library; `major` gives the versioned name the
+/// system loader resolves, `None` the unversioned development name.
+pub fn ffmpeg_file_name(name: &str, major: Option<u32>) -> String {
+    match major {
+        Some(major) if cfg!(target_os = "macos") => format!("lib{}.{}.dylib", name, major),
+        Some(major) if cfg!(target_os = "windows") => format!("{}-{}.dll", name, major),
+        Some(major) => format!("lib{}.so.{}", name, major),
+        None if cfg!(target_os = "macos") => format!("lib{}.dylib", name),
+        None if cfg!(target_os = "windows") => format!("{}.dll", name),
+        None => format!("lib{}.so", name),
+    }
+}
+
+/// Directories searched before the system loader, in the extensions' order.
+pub fn search_dirs(ext_dir: Option<&Path>) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    if let Some(ext_dir) = ext_dir {
+        dirs.push(ext_dir.join("lib"));
+        if let Ok(entries) = std::fs::read_dir(ext_dir.join("binaries")) {
+            let mut platforms: Vec<PathBuf> = entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()).collect();
+            platforms.sort();
+            dirs.extend(platforms);
+        }
+    }
+    if let Ok(cwd) = std::env::current_dir() {
+        dirs.push(cwd.join("lib"));
+    }
+    if let Some(inherited) = std::env::var_os(library_path_var()) {
+        dirs.extend(std::env::split_paths(&inherited));
+    }
+    dirs.extend(["/opt/homebrew/lib", "/usr/local/lib"].map(PathBuf::from));
+    let mut unique: Vec<PathBuf> = Vec::new();
+    for dir in dirs.into_iter().filter(|d| d.is_dir()) {
+        if !unique.contains(&dir) {
+            unique.push(dir);
+        }
+    }
+    unique
+}
+
+/// Where a library would be loaded from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Resolved {
+    /// Set explicitly through an environment variable
+    Env(PathBuf),
+    /// Found in one of the [`search_dirs`]
+    SearchPath(PathBuf),
+    /// Not in the search path, but the system loader can open it by name
+    System(String),
+}
+
+impl Resolved {
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Env(path) => format!("{} (ORT_DYLIB_PATH)", path.display()),
+            Self::SearchPath(path) => path.display().to_string(),
+            Self::System(name) => format!("{} (system loader)", name),
+        }
+    }
+
+    fn load_name(&self) -> &std::ffi::OsStr {
+        match self {
+            Self::Env(path) | Self::SearchPath(path) => path.as_os_str(),
+            Self::System(name) => name.as_ref(),
+        }
+    }
+}
+
+/// First of `file_names` in `dirs`, otherwise the first the system loader opens.
+pub fn resolve(dirs: &[PathBuf], file_names: &[&str]) -> Option<Resolved> {
+    for dir in dirs {
+        for name in file_names {
+            let path = dir.join(name);
+            if path.is_file() {
+                return Some(Resolved::SearchPath(path));
+            }
+        }
+    }
+    file_names
+        .iter()
+        // SAFETY: only system libraries are opened here; their initialisers are
+        // the same ones any extension linking them runs.
+        .find(|name| unsafe { Library::new(name) }.is_ok())
+        .map(|name| Resolved::System(name.to_string()))
+}
+
+/// The ONNX Runtime the `ort` crate would load: `ORT_DYLIB_PATH`, else the
+/// path `setup_native_lib_paths` would pick.
+pub fn resolve_ort(dirs: &[PathBuf]) -> Option<Resolved> {
+    if let Some(path) = std::env::var_os("ORT_DYLIB_PATH") {
+        return Some(Resolved::Env(PathBuf::from(path)));
+    }
+    resolve(dirs, &[ort_file_name()])
+}
+
+/// What the loaded ONNX Runtime reports about itself.
+#[derive(Debug, Clone)]
+pub struct OrtProbe {
+    pub version: String,
+    /// Whether `GetApi(ORT_API_VERSION)` returned an API table
+    pub supports_api: bool,
+}
+
+#[repr(C)]
+struct OrtApiBase {
+    get_api: unsafe extern "C" fn(u32) -> *const std::ffi::c_void,
+    get_version_string: unsafe extern "C" fn() -> *const c_char,
+}
+
+type OrtGetApiBaseFn = unsafe extern "C" fn() -> *const OrtApiBase;
+
+/// Load ONNX Runtime and ask it for its version and the required API table.
+pub fn probe_ort(resolved: &Resolved) -> Result<OrtProbe> {
+    // SAFETY: ONNX Runtime has no load-time side effects beyond its own statics.
+    let library = unsafe { Library::new(resolved.load_name()) }
+        .with_context(|| format!("Failed to load {}", resolved.describe()))?;
+    // SAFETY: `OrtGetApiBase` has this signature in every ORT release, and the
+    // returned table is static for the lifetime of the library.
+    unsafe {
+        let get_api_base = library
+            .get::<OrtGetApiBaseFn>(b"OrtGetApiBase\0")
+            .context("OrtGetApiBase not exported; not an ONNX Runtime library")?;
+        let base = get_api_base();
+        if base.is_null() {
+            anyhow::bail!("OrtGetApiBase returned null");
+        }
+        let version = CStr::from_ptr(((*base).get_version_string)())
+            .to_string_lossy()
+            .into_owned();
+        let supports_api = !((*base).get_api)(ORT_API_VERSION).is_null();
+        Ok(OrtProbe { version, supports_api })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_dirs_include_bundled_platform_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("binaries").join("linux_amd64")).unwrap();
+        std::fs::create_dir_all(dir.path().join("lib")).unwrap();
+
+        let dirs = search_dirs(Some(dir.path()));
+        assert_eq!(dirs[0], dir.path().join("lib"));
+        assert_eq!(dirs[1], dir.path().join("binaries").join("linux_amd64"));
+    }
+
+    #[test]
+    fn test_resolve_prefers_search_path() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(ort_file_name()), "").unwrap();
+
+        let resolved = resolve(&[dir.path().to_path_buf()], &[ort_file_name()]);
+        assert_eq!(resolved, Some(Resolved::SearchPath(dir.path().join(ort_file_name()))));
+        assert!(probe_ort(resolved.as_ref().unwrap()).is_err());
+    }
+}
@@ -0,0 +1,68 @@
+//! Read access to .nep packages.
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+/// An opened .nep (ZIP) package.
+pub struct NepPackage {
+    archive: zip::ZipArchive<fs::File>,
+}
+
+impl NepPackage {
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = fs::File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        let archive = zip::ZipArchive::new(file)
+            .with_context(|| format!("Not a valid ZIP archive: {}", path.display()))?;
+        Ok(Self { archive })
+    }
+
+    /// File entries only.
+    pub fn file_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .archive
+            .file_names()
+            .filter(|n| !n.ends_with('/'))
+            .map(String::from)
+            .collect();
+        names.sort();
+        names
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.archive.index_for_name(name).is_some()
+    }
+
+    /// Uncompressed size of an entry.
+    pub fn size_of(&mut self, name: &str) -> Option<u64> {
+        self.archive.by_name(name).ok().map(|e| e.size())
+    }
+
+    /// Read an entry; a CRC mismatch surfaces as an error.
+    pub fn read(&mut self, name: &str) -> Result<Vec<u8>> {
+        let mut entry = self
+            .archive
+            .by_name(name)
+            .with_context(|| format!("{} not found in package", name))?;
+        let mut data = Vec::with_capacity(entry.size() as usize);
+        entry
+            .read_to_end(&mut data)
+            .with_context(|| format!("Failed to read {} (CRC mismatch?)", name))?;
+        Ok(data)
+    }
+
+    /// Unpack every entry under `dir`; entries escaping `dir` are rejected.
+    pub fn extract_to(&mut self, dir: &Path) -> Result<()> {
+        self.archive
+            .extract(dir)
+            .with_context(|| format!("Failed to unpack into {}", dir.display()))
+    }
+
+    /// Parsed manifest.json from the package root.
+    pub fn manifest(&mut self) -> Result<Value> {
+        let data = self.read("manifest.json")?;
+        serde_json::from_slice(&data).context("manifest.json is not valid JSON")
+    }
+}
@@ -0,0 +1,124 @@
+//! Platform naming shared by packaging, the marketplace index and builds.
+//!
+//! The same platform is spelled three ways across the project:
+//! - `.nep` packages (binaries directory and file suffix): `linux_amd64`
+//! - `builds` in metadata.json / index.json: `linux-x86_64`
+//! - Rust target triples: `x86_64-unknown-linux-gnu`
+
+/// A platform an extension can be built and packaged for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Platform {
+    /// Key used inside .nep packages (`binaries/<key>/`) and in package file names
+    pub package_key: &'static str,
+    /// Key used by `builds` in metadata.json and extensions/index.json
+    pub build_key: &'static str,
+    /// Rust target triple
+    pub triple: &'static str,
+    /// Shared library file prefix (`lib` on Unix, empty on Windows and WASM)
+    pub lib_prefix: &'static str,
+    /// Shared library file extension
+    pub lib_ext: &'static str,
+}
+
+pub const WASM: Platform = Platform {
+    package_key: "wasm",
+    build_key: "wasm32",
+    triple: "wasm32-unknown-unknown",
+    lib_prefix: "",
+    lib_ext: "wasm",
+};
+
+/// Native platforms advertised by the marketplace, in index.json order.
+pub const NATIVE_PLATFORMS: [Platform; 5] = [
+    Platform {
+        package_key: "darwin_aarch64",
+        build_key: "darwin-aarch64",
+        triple: "aarch64-apple-darwin",
+        lib_prefix: "lib",
+        lib_ext: "dylib",
+    },
+    Platform {
+        package_key: "darwin_x86_64",
+        build_key: "darwin-x86_64",
+        triple: "x86_64-apple-darwin",
+        lib_prefix: "lib",
+        lib_ext: "dylib",
+    },
+    Platform {
+        package_key: "linux_amd64",
+        build_key: "linux-x86_64",
+        triple: "x86_64-unknown-linux-gnu",
+        lib_prefix: "lib",
+        lib_ext: "so",
+    },
+    Platform {
+        package_key: "linux_arm64",
+        build_key: "linux-aarch64",
+        triple: "aarch64-unknown-linux-gnu",
+        lib_prefix: "lib",
+        lib_ext: "so",
+    },
+    Platform {
+        package_key: "windows_amd64",
+        build_key: "windows-x86_64",
+        triple: "x86_64-pc-windows-msvc",
+        lib_prefix: "",
+        lib_ext: "dll",
+    },
+];
+
+impl Platform {
+    /// Platform of the machine running the CLI, if it is one we package for.
+    pub fn host() -> Option<Platform> {
+        let key = match (std::env::consts::OS, std::env::consts::ARCH) {
+            ("macos", "aarch64") => "darwin_aarch64",
+            ("macos", "x86_64") => "darwin_x86_64",
+            ("linux", "x86_64") => "linux_amd64",
+            ("linux", "aarch64") => "linux_arm64",
+            ("windows", "x86_64") => "windows_amd64",
+            _ => return None,
+        };
+        Self::parse(key)
+    }
+
+    /// Look up a platform by package key, build key, target triple or `wasm`/`wasm32`.
+    pub fn parse(name: &str) -> Option<Platform> {
+        std::iter::once(WASM)
+            .chain(NATIVE_PLATFORMS)
+            .find(|p| p.package_key == name || p.build_key == name || p.triple == name)
+    }
+
+    pub fn is_wasm(&self) -> bool {
+        self.lib_ext == "wasm"
+    }
+
+    /// File name of the compiled library for a `[lib] name`
+    pub fn library_file_name(&self, lib_name: &str) -> String {
+        format!("{}{}.{}", self.lib_prefix, lib_name, self.lib_ext)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_accepts_all_spellings() {
+        let by_package = Platform::parse("linux_amd64").unwrap();
+        let by_build = Platform::parse("linux-x86_64").unwrap();
+        let by_triple = Platform::parse("x86_64-unknown-linux-gnu").unwrap();
+        assert_eq!(by_package, by_build);
+        assert_eq!(by_build, by_triple);
+        assert_eq!(Platform::parse("wasm32"), Some(WASM));
+        assert!(Platform::parse("solaris-sparc").is_none());
+    }
+
+    #[test]
+    fn test_library_file_name() {
+        let linux = Platform::parse("linux-aarch64").unwrap();
+        let windows = Platform::parse("windows-x86_64").unwrap();
+        assert_eq!(linux.library_file_name("neomind_extension_foo"), "libneomind_extension_foo.so");
+        assert_eq!(windows.library_file_name("neomind_extension_foo"), "neomind_extension_foo.dll");
+        assert_eq!(WASM.library_file_name("neomind_extension_foo"), "neomind_extension_foo.wasm");
+    }
+}
@@ -0,0 +1,119 @@
+//! JSONL recordings of events fed to an extension by `neomind-ext replay`.
+//!
+//! One JSON object per line; blank lines and lines starting with `#` are ignored.
+//!
+//! ```text
+//! {"command": "bind_device", "args": {"device_id": "cam-1", "image_metric": "image"}}
+//! {"timestamp": 1718000000000, "event_type": "DeviceMetric", "payload": {"device_id": "cam-1", "metric": "image", "value": "<base64>"}}
+//! {"timestamp": 1718000000250, "payload": {"device_id": "cam-1", "metric": "image", "value": "<base64>"}}
+//! ```
+//!
+//! Event lines carry the `payload` exactly as the server passes it to
+//! `handle_event`; `event_type` defaults to `DeviceMetric` and `timestamp`
+//! (milliseconds) drives the replay timing. Command lines run through
+//! `execute_command` when they are reached, which is how bindings are set up
+//! before the first frame.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+pub const DEFAULT_EVENT_TYPE: &str = "DeviceMetric";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Record {
+    Command {
+        command: String,
+        #[serde(default)]
+        args: Value,
+    },
+    Event(RecordedEvent),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    /// Capture time in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<i64>,
+    #[serde(default = "default_event_type")]
+    pub event_type: String,
+    pub payload: Value,
+}
+
+fn default_event_type() -> String {
+    DEFAULT_EVENT_TYPE.to_string()
+}
+
+impl RecordedEvent {
+    /// `payload.device_id`, looking through the server's `{"payload": {...}}` envelope.
+    pub fn device_id(&self) -> Option<&str> {
+        self.inner_payload().get("device_id").and_then(|v| v.as_str())
+    }
+
+    pub fn metric(&self) -> Option<&str> {
+        self.inner_payload().get("metric").and_then(|v| v.as_str())
+    }
+
+    fn inner_payload(&self) -> &Value {
+        self.payload.get("payload").unwrap_or(&self.payload)
+    }
+}
+
+pub fn load(path: &Path) -> Result<Vec<Record>> {
+    let content = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    parse(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+pub fn parse(content: &str) -> Result<Vec<Record>> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with('#')
+        })
+        .map(|(i, line)| serde_json::from_str(line).with_context(|| format!("line {}", i + 1)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_commands_and_events() {
+        let records = parse(
+            "# captured on site\n\
+             {\"command\": \"bind_device\", \"args\": {\"device_id\": \"cam-1\"}}\n\
+             \n\
+             {\"timestamp\": 1000, \"payload\": {\"payload\": {\"device_id\": \"cam-1\", \"metric\": \"image\"}}}\n",
+        )
+        .unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(
+            records[0],
+            Record::Command {
+                command: "bind_device".into(),
+                args: json!({ "device_id": "cam-1" })
+            }
+        );
+        let Record::Event(event) = &records[1] else {
+            panic!("expected an event");
+        };
+        assert_eq!(event.event_type, "DeviceMetric");
+        assert_eq!(event.timestamp, Some(1000));
+        assert_eq!(event.device_id(), Some("cam-1"));
+        assert_eq!(event.metric(), Some("image"));
+    }
+
+    #[test]
+    fn test_parse_reports_line_number() {
+        let err = parse("{\"payload\": {}}\n{oops}\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 2");
+    }
+}
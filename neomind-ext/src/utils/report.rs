@@ -0,0 +1,86 @@
+//! Pass/warn/fail check lists shared by `validate` and `lint`.
+
+use colored::Colorize;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    pub name: String,
+    pub status: CheckStatus,
+    pub message: String,
+}
+
+/// Ordered list of check results; serializes as a plain array.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(transparent)]
+pub struct Checks(Vec<Check>);
+
+impl Checks {
+    pub fn record(&mut self, name: &str, status: CheckStatus, message: impl Into<String>) {
+        self.0.push(Check {
+            name: name.to_string(),
+            status,
+            message: message.into(),
+        });
+    }
+
+    pub fn pass(&mut self, name: &str, message: impl Into<String>) {
+        self.record(name, CheckStatus::Pass, message);
+    }
+
+    pub fn warn(&mut self, name: &str, message: impl Into<String>) {
+        self.record(name, CheckStatus::Warn, message);
+    }
+
+    pub fn fail(&mut self, name: &str, message: impl Into<String>) {
+        self.record(name, CheckStatus::Fail, message);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Check> {
+        self.0.iter()
+    }
+
+    /// Record `pass_message` when `ok`, otherwise fail with `fail_message`.
+    pub fn expect(&mut self, name: &str, ok: bool, pass_message: impl Into<String>, fail_message: impl Into<String>) {
+        if ok {
+            self.pass(name, pass_message);
+        } else {
+            self.fail(name, fail_message);
+        }
+    }
+
+    pub fn has_failures(&self) -> bool {
+        self.iter().any(|c| c.status == CheckStatus::Fail)
+    }
+
+    /// Names of failed checks, in order and without duplicates.
+    pub fn failed_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = Vec::new();
+        for check in self.iter().filter(|c| c.status == CheckStatus::Fail) {
+            if !names.contains(&check.name.as_str()) {
+                names.push(&check.name);
+            }
+        }
+        names
+    }
+
+    /// One line per check with a ✓/⚠/✗ marker.
+    pub fn print(&self) {
+        for check in self.iter() {
+            let mark = match check.status {
+                CheckStatus::Pass => "✓".green(),
+                CheckStatus::Warn => "⚠".yellow(),
+                CheckStatus::Fail => "✗".red(),
+            };
+            println!("  {} {:<18} {}", mark, check.name, check.message);
+        }
+    }
+}
@@ -0,0 +1,220 @@
+//! Just enough Rust lexing to edit an extension's src/lib.rs in place:
+//! find a function body, the `vec![...]` it returns or the arms of a `match`,
+//! and insert text before the closing delimiter. Strings, char literals and
+//! comments are skipped so brackets inside them do not count.
+
+use std::ops::Range;
+
+/// Index just past the delimiter closing the one at `open`.
+pub fn matching_close(src: &str, open: usize) -> Option<usize> {
+    let bytes = src.as_bytes();
+    let mut depth = 0usize;
+    let mut i = open;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            b'"' => i = skip_string(bytes, i + 1)?,
+            b'r' if is_raw_string_start(bytes, i) => i = skip_raw_string(bytes, i + 1)?,
+            b'\'' => i = skip_char_literal(src, i),
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i = src[i..].find('\n').map_or(bytes.len(), |n| i + n);
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = src[i + 2..].find("*/").map(|n| i + 2 + n + 1)?;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Position of the closing quote of a string whose contents start at `i`.
+fn skip_string(bytes: &[u8], mut i: usize) -> Option<usize> {
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 1,
+            b'"' => return Some(i),
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+fn is_raw_string_start(bytes: &[u8], i: usize) -> bool {
+    let prev_is_ident = i > 0 && (bytes[i - 1].is_ascii_alphanumeric() || bytes[i - 1] == b'_');
+    let mut j = i + 1;
+    while bytes.get(j) == Some(&b'#') {
+        j += 1;
+    }
+    !prev_is_ident && bytes.get(j) == Some(&b'"')
+}
+
+/// Position of the last `#` closing a raw string whose `#`s start at `i`.
+fn skip_raw_string(bytes: &[u8], mut i: usize) -> Option<usize> {
+    let hashes = bytes[i..].iter().take_while(|b| **b == b'#').count();
+    i += hashes + 1;
+    while i < bytes.len() {
+        if bytes[i] == b'"' && bytes[i + 1..].iter().take(hashes).filter(|b| **b == b'#').count() == hashes {
+            return Some(i + hashes);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Char literals are skipped; lifetimes (`'a`, `'static`) are left alone.
+fn skip_char_literal(src: &str, i: usize) -> usize {
+    let bytes = src.as_bytes();
+    if bytes.get(i + 1) == Some(&b'\\') {
+        // '\n', '\'', '\u{..}'
+        return bytes[i + 3..].iter().position(|b| *b == b'\'').map_or(i, |n| i + 3 + n);
+    }
+    // A single (possibly multi-byte) character followed by a quote
+    match src[i + 1..].chars().next() {
+        Some(c) if bytes.get(i + 1 + c.len_utf8()) == Some(&b'\'') => i + 1 + c.len_utf8(),
+        _ => i,
+    }
+}
+
+/// Body of every `fn <name>(...)`, from `{` to just past `}`.
+pub fn fn_bodies(src: &str, name: &str) -> Vec<Range<usize>> {
+    let needle = format!("fn {}(", name);
+    let mut bodies = Vec::new();
+    let mut from = 0;
+    while let Some(found) = src[from..].find(&needle).map(|n| from + n) {
+        from = found + needle.len();
+        let Some(params_close) = matching_close(src, found + needle.len() - 1) else {
+            continue;
+        };
+        // Skip the return type up to the body (or `;` for a trait declaration)
+        let Some(open) = src[params_close..].find(['{', ';']).map(|n| params_close + n) else {
+            continue;
+        };
+        if src.as_bytes()[open] == b';' {
+            continue;
+        }
+        if let Some(close) = matching_close(src, open) {
+            bodies.push(open..close);
+            from = close;
+        }
+    }
+    bodies
+}
+
+/// The first `<macro>![...]` inside `range`, from `[` to just past `]`.
+pub fn macro_brackets(src: &str, range: Range<usize>, macro_name: &str) -> Option<Range<usize>> {
+    let needle = format!("{}![", macro_name);
+    let start = src[range.clone()].find(&needle)? + range.start;
+    let open = start + needle.len() - 1;
+    let close = matching_close(src, open)?;
+    (close <= range.end).then_some(open..close)
+}
+
+/// The block of the first `match <scrutinee> {` inside `range`, from `{` to just past `}`.
+pub fn match_block(src: &str, range: Range<usize>, scrutinee: &str) -> Option<Range<usize>> {
+    let needle = format!("match {} {{", scrutinee);
+    let open = src[range.clone()].find(&needle)? + range.start + needle.len() - 1;
+    let close = matching_close(src, open)?;
+    (close <= range.end).then_some(open..close)
+}
+
+/// Start of the line holding the top-level `_ =>` arm of a match block.
+pub fn wildcard_arm(src: &str, block: Range<usize>) -> Option<usize> {
+    let mut line_start = block.start + 1;
+    while line_start < block.end {
+        let line_end = src[line_start..block.end].find('\n').map_or(block.end, |n| line_start + n);
+        if src[line_start..line_end].trim_start().starts_with("_ =>") {
+            return Some(line_start);
+        }
+        // Jump over nested blocks so arms of inner matches are not picked up
+        let mut next = line_end + 1;
+        if let Some(open) = src[line_start..line_end].rfind(['{', '(', '[']) {
+            if let Some(close) = matching_close(src, line_start + open) {
+                if close > line_end {
+                    next = src[close..block.end].find('\n').map_or(block.end, |n| close + n + 1);
+                }
+            }
+        }
+        line_start = next;
+    }
+    None
+}
+
+/// Leading whitespace of the line containing `pos`.
+pub fn indent_at(src: &str, pos: usize) -> &str {
+    let line_start = src[..pos].rfind('\n').map_or(0, |n| n + 1);
+    let line = &src[line_start..];
+    &line[..line.len() - line.trim_start().len()]
+}
+
+/// Insert `items` (already indented, each ending with a newline) as the last
+/// entries of the list delimited by `list` (`[`..`]` or `{`..`}`).
+pub fn append_to_list(src: &mut String, list: Range<usize>, items: &str) {
+    let close = list.end - 1;
+    let before_close = &src[list.start + 1..close];
+    if before_close.trim().is_empty() {
+        // `vec![]` or a list with only whitespace: open it up
+        let indent = indent_at(src, list.start).to_string();
+        src.replace_range(list.start + 1..close, &format!("\n{}{}", items, indent));
+        return;
+    }
+    // Insert after the last item, keeping a trailing comma on it
+    let last = src[..close].trim_end().len();
+    let mut insert = String::new();
+    if !src[..last].ends_with(',') {
+        insert.push(',');
+    }
+    insert.push('\n');
+    insert.push_str(items.trim_end_matches('\n'));
+    src.insert_str(last, &insert);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matching_close_skips_strings_chars_and_comments() {
+        let src = r##"vec![ "]", ']', r#"]"#, // ]
+            /* ] */ foo::<'static>(x) ]"##;
+        let open = src.find('[').unwrap();
+        assert_eq!(matching_close(src, open), Some(src.len()));
+    }
+
+    #[test]
+    fn test_fn_bodies_skips_trait_declarations() {
+        let src = "trait T { fn commands(&self) -> Vec<u8>; }\n\
+                   impl T for X { fn commands(&self) -> Vec<u8> { vec![1] } }";
+        let bodies = fn_bodies(src, "commands");
+        assert_eq!(bodies.len(), 1);
+        assert_eq!(&src[bodies[0].clone()], "{ vec![1] }");
+    }
+
+    #[test]
+    fn test_wildcard_arm_ignores_nested_matches() {
+        let src = "match command {\n    \"a\" => match x {\n        _ => 1,\n    },\n    _ => 2,\n}";
+        let block = match_block(src, 0..src.len(), "command").unwrap();
+        let arm = wildcard_arm(src, block).unwrap();
+        assert_eq!(&src[arm..arm + 10], "    _ => 2");
+    }
+
+    #[test]
+    fn test_append_to_list() {
+        let mut src = "vec![\n    a,\n    b\n]".to_string();
+        let list = 4..src.len();
+        append_to_list(&mut src, list, "    c,\n");
+        assert_eq!(src, "vec![\n    a,\n    b,\n    c,\n]");
+
+        let mut src = "    vec![]".to_string();
+        append_to_list(&mut src, 8..10, "        c,\n");
+        assert_eq!(src, "    vec![\n        c,\n    ]");
+    }
+}
@@ -0,0 +1,320 @@
+//! Ed25519 signatures embedded in .nep packages.
+//!
+//! A signed package carries `signature.json` at its root. The signature covers
+//! the SHA-256 of every other file in the archive, sorted by path:
+//!
+//! ```text
+//! neomind-nep-signature-v2
+//! binaries/linux_amd64/extension.so <sha256>
+//! frontend/index.js <sha256>
+//! manifest.json <sha256>
+//! models/model.onnx <sha256>
+//! ```
+//!
+//! so replacing, adding or removing any file (frontend bundle, models,
+//! bundled native libraries) invalidates it, whether or not manifest.json
+//! lists a checksum for that file.
+//! Keys are stored as hex: `<name>.key` (secret seed) and `<name>.pub`.
+
+use anyhow::{Context, Result};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use zip::write::SimpleFileOptions;
+
+use super::checksum;
+use super::nep::NepPackage;
+
+pub const SIGNATURE_ENTRY: &str = "signature.json";
+pub const ALGORITHM: &str = "ed25519";
+const PAYLOAD_HEADER: &str = "neomind-nep-signature-v2";
+
+/// Contents of `signature.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageSignature {
+    pub algorithm: String,
+    /// First 16 hex digits of the SHA-256 of the public key
+    pub key_id: String,
+    pub public_key: String,
+    pub signature: String,
+}
+
+/// Outcome of checking a package's signature.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "state", rename_all = "lowercase")]
+pub enum SignatureState {
+    Unsigned,
+    Signed { key_id: String },
+    Tampered { reason: String },
+}
+
+pub fn generate_key() -> Result<SigningKey> {
+    let mut seed = [0u8; 32];
+    getrandom::getrandom(&mut seed).map_err(|e| anyhow::anyhow!("No system randomness available: {}", e))?;
+    Ok(SigningKey::from_bytes(&seed))
+}
+
+pub fn key_id(key: &VerifyingKey) -> String {
+    checksum::sha256_hex(key.as_bytes())[..16].to_string()
+}
+
+/// Write `<prefix>.key` and `<prefix>.pub`; returns both paths.
+pub fn write_keypair(key: &SigningKey, prefix: &Path) -> Result<(PathBuf, PathBuf)> {
+    let secret_path = prefix.with_extension("key");
+    let public_path = prefix.with_extension("pub");
+    for path in [&secret_path, &public_path] {
+        if path.exists() {
+            anyhow::bail!("{} already exists; refusing to overwrite a key", path.display());
+        }
+    }
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut secret = options
+        .open(&secret_path)
+        .with_context(|| format!("Failed to create {}", secret_path.display()))?;
+    writeln!(secret, "{}", checksum::to_hex(&key.to_bytes()))?;
+    fs::write(&public_path, format!("{}\n", checksum::to_hex(key.verifying_key().as_bytes())))
+        .with_context(|| format!("Failed to write {}", public_path.display()))?;
+    Ok((secret_path, public_path))
+}
+
+pub fn read_signing_key(path: &Path) -> Result<SigningKey> {
+    let seed: [u8; 32] = read_key_bytes(path)?;
+    Ok(SigningKey::from_bytes(&seed))
+}
+
+pub fn read_verifying_key(path: &Path) -> Result<VerifyingKey> {
+    let bytes: [u8; 32] = read_key_bytes(path)?;
+    VerifyingKey::from_bytes(&bytes).with_context(|| format!("{} is not a valid public key", path.display()))
+}
+
+fn read_key_bytes(path: &Path) -> Result<[u8; 32]> {
+    let content = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    decode_hex(content.trim())
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| anyhow::anyhow!("{} does not contain a 32-byte hex key", path.display()))
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// The bytes that get signed: the hash of every file but the signature.
+fn signing_payload(package: &mut NepPackage) -> Result<Vec<u8>> {
+    let mut payload = format!("{}\n", PAYLOAD_HEADER);
+    for name in package.file_names() {
+        if name == SIGNATURE_ENTRY {
+            continue;
+        }
+        let data = package.read(&name)?;
+        payload.push_str(&format!("{} {}\n", name, checksum::sha256_hex(&data)));
+    }
+    Ok(payload.into_bytes())
+}
+
+/// Sign the package in place, replacing any existing signature.
+pub fn sign_package(nep_path: &Path, key: &SigningKey) -> Result<PackageSignature> {
+    let payload = signing_payload(&mut NepPackage::open(nep_path)?)?;
+    let verifying_key = key.verifying_key();
+    let signature = PackageSignature {
+        algorithm: ALGORITHM.to_string(),
+        key_id: key_id(&verifying_key),
+        public_key: checksum::to_hex(verifying_key.as_bytes()),
+        signature: checksum::to_hex(&key.sign(&payload).to_bytes()),
+    };
+
+    // Copy every entry except an old signature into a fresh archive, then swap it in
+    let tmp_path = nep_path.with_extension("nep.tmp");
+    {
+        let file = fs::File::open(nep_path).with_context(|| format!("Failed to open {}", nep_path.display()))?;
+        let mut archive = zip::ZipArchive::new(file)?;
+        let out = fs::File::create(&tmp_path).with_context(|| format!("Failed to create {}", tmp_path.display()))?;
+        let mut zip = zip::ZipWriter::new(out);
+        for i in 0..archive.len() {
+            let entry = archive.by_index_raw(i)?;
+            if entry.name() != SIGNATURE_ENTRY {
+                zip.raw_copy_file(entry)?;
+            }
+        }
+        zip.start_file(SIGNATURE_ENTRY, SimpleFileOptions::default())?;
+        zip.write_all(&serde_json::to_vec_pretty(&signature)?)?;
+        zip.finish()?;
+    }
+    fs::rename(&tmp_path, nep_path).with_context(|| format!("Failed to replace {}", nep_path.display()))?;
+    Ok(signature)
+}
+
+/// Check the embedded signature. With `trusted`, the package must be signed by
+/// that key; otherwise the embedded public key is used.
+pub fn check_package(package: &mut NepPackage, trusted: Option<&VerifyingKey>) -> SignatureState {
+    if !package.contains(SIGNATURE_ENTRY) {
+        return SignatureState::Unsigned;
+    }
+    match verify_signature(package, trusted) {
+        Ok(key_id) => SignatureState::Signed { key_id },
+        Err(e) => SignatureState::Tampered {
+            reason: format!("{:#}", e),
+        },
+    }
+}
+
+fn verify_signature(package: &mut NepPackage, trusted: Option<&VerifyingKey>) -> Result<String> {
+    let signature: PackageSignature = serde_json::from_slice(&package.read(SIGNATURE_ENTRY)?)
+        .context("signature.json is malformed")?;
+    if signature.algorithm != ALGORITHM {
+        anyhow::bail!("unsupported signature algorithm '{}'", signature.algorithm);
+    }
+
+    let embedded_bytes: [u8; 32] = decode_hex(&signature.public_key)
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| anyhow::anyhow!("signature.json has an invalid public key"))?;
+    let embedded = VerifyingKey::from_bytes(&embedded_bytes).context("signature.json has an invalid public key")?;
+    let key = match trusted {
+        Some(trusted) if trusted != &embedded => {
+            anyhow::bail!(
+                "signed by key {} but expected {}",
+                key_id(&embedded),
+                key_id(trusted)
+            )
+        }
+        Some(trusted) => trusted,
+        None => &embedded,
+    };
+
+    let signature_bytes: [u8; 64] = decode_hex(&signature.signature)
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| anyhow::anyhow!("signature.json has an invalid signature"))?;
+    let payload = signing_payload(package)?;
+    key.verify_strict(&payload, &Signature::from_bytes(&signature_bytes))
+        .map_err(|_| anyhow::anyhow!("signature does not match the package contents"))?;
+    Ok(key_id(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_nep(path: &Path, binary: &[u8]) {
+        let mut zip = zip::ZipWriter::new(fs::File::create(path).unwrap());
+        zip.start_file("manifest.json", SimpleFileOptions::default()).unwrap();
+        zip.write_all(br#"{"id": "demo", "binaries": {"linux_amd64": "binaries/linux_amd64/extension.so"}}"#)
+            .unwrap();
+        zip.start_file("binaries/linux_amd64/extension.so", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(binary).unwrap();
+        zip.finish().unwrap();
+    }
+
+    fn state(path: &Path, trusted: Option<&VerifyingKey>) -> SignatureState {
+        check_package(&mut NepPackage::open(path).unwrap(), trusted)
+    }
+
+    #[test]
+    fn test_sign_and_verify_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let nep = dir.path().join("demo.nep");
+        write_nep(&nep, b"ELF");
+        assert_eq!(state(&nep, None), SignatureState::Unsigned);
+
+        let key = generate_key().unwrap();
+        let signature = sign_package(&nep, &key).unwrap();
+        let expected = SignatureState::Signed {
+            key_id: signature.key_id.clone(),
+        };
+        assert_eq!(state(&nep, None), expected);
+        assert_eq!(state(&nep, Some(&key.verifying_key())), expected);
+
+        // Re-signing replaces the old signature instead of adding a second entry
+        sign_package(&nep, &key).unwrap();
+        assert_eq!(NepPackage::open(&nep).unwrap().file_names().len(), 3);
+
+        let other = generate_key().unwrap();
+        assert!(matches!(
+            state(&nep, Some(&other.verifying_key())),
+            SignatureState::Tampered { .. }
+        ));
+    }
+
+    #[test]
+    fn test_swapped_binary_is_tampered() {
+        let dir = tempfile::tempdir().unwrap();
+        let signed = dir.path().join("signed.nep");
+        write_nep(&signed, b"ELF");
+        sign_package(&signed, &generate_key().unwrap()).unwrap();
+
+        // Rebuild the package with a different binary but the original signature.json
+        let signature = NepPackage::open(&signed).unwrap().read(SIGNATURE_ENTRY).unwrap();
+        let tampered = dir.path().join("tampered.nep");
+        write_nep(&tampered, b"PATCHED");
+        let mut archive = zip::ZipWriter::new_append(
+            fs::OpenOptions::new().read(true).write(true).open(&tampered).unwrap(),
+        )
+        .unwrap();
+        archive.start_file(SIGNATURE_ENTRY, SimpleFileOptions::default()).unwrap();
+        archive.write_all(&signature).unwrap();
+        archive.finish().unwrap();
+
+        assert!(matches!(state(&tampered, None), SignatureState::Tampered { .. }));
+    }
+
+    #[test]
+    fn test_swapped_model_is_tampered() {
+        let dir = tempfile::tempdir().unwrap();
+        let signed = dir.path().join("signed.nep");
+        let build = |path: &Path, model: &[u8], extra: Option<&str>, signature: Option<&[u8]>| {
+            write_nep(path, b"ELF");
+            let mut zip = zip::ZipWriter::new_append(
+                fs::OpenOptions::new().read(true).write(true).open(path).unwrap(),
+            )
+            .unwrap();
+            zip.start_file("models/model.onnx", SimpleFileOptions::default()).unwrap();
+            zip.write_all(model).unwrap();
+            if let Some(extra) = extra {
+                zip.start_file(extra, SimpleFileOptions::default()).unwrap();
+                zip.write_all(b"x").unwrap();
+            }
+            if let Some(signature) = signature {
+                zip.start_file(SIGNATURE_ENTRY, SimpleFileOptions::default()).unwrap();
+                zip.write_all(signature).unwrap();
+            }
+            zip.finish().unwrap();
+        };
+        build(&signed, b"weights", None, None);
+        sign_package(&signed, &generate_key().unwrap()).unwrap();
+        assert!(matches!(state(&signed, None), SignatureState::Signed { .. }));
+        let signature = NepPackage::open(&signed).unwrap().read(SIGNATURE_ENTRY).unwrap();
+
+        // Neither file is referenced by the manifest
+        let swapped = dir.path().join("swapped.nep");
+        build(&swapped, b"backdoor", None, Some(&signature));
+        assert!(matches!(state(&swapped, None), SignatureState::Tampered { .. }));
+        let added = dir.path().join("added.nep");
+        build(&added, b"weights", Some("binaries/linux_amd64/libonnxruntime.so"), Some(&signature));
+        assert!(matches!(state(&added, None), SignatureState::Tampered { .. }));
+    }
+
+    #[test]
+    fn test_keypair_files_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = generate_key().unwrap();
+        let (secret, public) = write_keypair(&key, &dir.path().join("release")).unwrap();
+
+        assert_eq!(read_signing_key(&secret).unwrap().to_bytes(), key.to_bytes());
+        assert_eq!(read_verifying_key(&public).unwrap(), key.verifying_key());
+        assert!(write_keypair(&key, &dir.path().join("release")).is_err());
+    }
+}
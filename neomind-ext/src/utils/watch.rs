@@ -0,0 +1,89 @@
+//! Polling file watcher used by `dev`.
+//!
+//! Polling keeps the CLI free of platform notification APIs; an extension's
+//! `src/` and `frontend/` are small enough to stat every half second.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Directory names never descended into.
+const IGNORED_DIRS: [&str; 3] = ["node_modules", "dist", "target"];
+
+/// Modification time of every file under a set of roots.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Snapshot(BTreeMap<PathBuf, SystemTime>);
+
+impl Snapshot {
+    /// Files under `roots` (directories are walked, files taken as-is); missing roots are skipped.
+    pub fn take(roots: &[PathBuf]) -> Self {
+        let mut files = BTreeMap::new();
+        for root in roots {
+            collect(root, &mut files);
+        }
+        Self(files)
+    }
+
+    /// Files added, removed or modified since `earlier`.
+    pub fn changes_since(&self, earlier: &Snapshot) -> Vec<PathBuf> {
+        let mut changed: Vec<PathBuf> = self
+            .0
+            .iter()
+            .filter(|(path, mtime)| earlier.0.get(*path) != Some(*mtime))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.extend(earlier.0.keys().filter(|path| !self.0.contains_key(*path)).cloned());
+        changed.sort();
+        changed
+    }
+}
+
+fn collect(path: &Path, files: &mut BTreeMap<PathBuf, SystemTime>) {
+    let Ok(metadata) = fs::metadata(path) else {
+        return;
+    };
+    if metadata.is_file() {
+        if let Ok(mtime) = metadata.modified() {
+            files.insert(path.to_path_buf(), mtime);
+        }
+        return;
+    }
+    let Ok(entries) = fs::read_dir(path) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with('.') || IGNORED_DIRS.contains(&name.as_ref()) {
+            continue;
+        }
+        collect(&entry.path(), files);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_changes_since() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(src.join("node_modules")).unwrap();
+        fs::write(src.join("lib.rs"), "fn a() {}").unwrap();
+        fs::write(src.join("node_modules").join("dep.js"), "").unwrap();
+        let roots = [src.clone(), dir.path().join("Cargo.toml")];
+
+        let before = Snapshot::take(&roots);
+        assert_eq!(before.0.len(), 1);
+
+        fs::write(dir.path().join("Cargo.toml"), "[package]").unwrap();
+        fs::write(src.join("node_modules").join("dep.js"), "changed").unwrap();
+        let after = Snapshot::take(&roots);
+        assert_eq!(after.changes_since(&before), [dir.path().join("Cargo.toml")]);
+
+        fs::remove_file(src.join("lib.rs")).unwrap();
+        assert_eq!(Snapshot::take(&roots).changes_since(&after), [src.join("lib.rs")]);
+    }
+}
@@ -8,7 +8,6 @@ use neomind_extension_sdk::{
     MetricDescriptor, ExtensionCommand, MetricDataType, ParameterDefinition,
     ParamMetricValue, Result, prelude::*,
 };
-use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 // ============================================================================
@@ -35,7 +34,7 @@ impl Extension for {{EXTENSION_STRUCT_NAME}} {
             ExtensionMetadata {
                 id: "{{EXTENSION_ID}}".to_string(),
                 name: "{{EXTENSION_DISPLAY_NAME}}".to_string(),
-                version: "2.0.0",
+                version: "2.0.0".to_string(),
                 description: Some("{{EXTENSION_DESCRIPTION}}".to_string()),
                 author: Some("{{EXTENSION_AUTHOR}}".to_string()),
                 homepage: None,
@@ -72,7 +71,6 @@ impl Extension for {{EXTENSION_STRUCT_NAME}} {
                 fixed_values: std::collections::HashMap::new(),
                 samples: vec![json!({})],
                 description: "Check if extension is responsive".to_string(),
-                llm_hints: "Check if extension is responsive".to_string(),
                 parameter_groups: Vec::new(),
             },
         ]
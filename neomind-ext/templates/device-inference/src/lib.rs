@@ -0,0 +1,596 @@
+//! NeoMind Extension: {{EXTENSION_DISPLAY_NAME}}
+//!
+//! Device-bound processing, generated by `neomind-ext new --template device-inference`.
+//! When a bound device publishes its input metric, the extension runs
+//! `process_value` on the new value and writes the results back to the device
+//! as virtual metrics.
+//!
+//! # Event Handling
+//! - `event_subscriptions()` subscribes to `DeviceMetric` events
+//! - `handle_event()` drops events from unbound devices and other metrics
+//! - Bindings are persisted to `config.json` and restored by the `configure` command
+
+use async_trait::async_trait;
+use neomind_extension_sdk::{
+    Extension, ExtensionMetadata, ExtensionError, ExtensionMetricValue,
+    MetricDescriptor, ExtensionCommand, MetricDataType, ParameterDefinition,
+    ParamMetricValue, Result,
+};
+use neomind_extension_sdk::capabilities::CapabilityContext;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Device binding configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeviceBinding {
+    /// Device ID
+    pub device_id: String,
+    /// Device name (for display)
+    pub device_name: Option<String>,
+    /// Metric to process; `image.data` reads field `data` of metric `image`
+    pub input_metric: String,
+    /// Results are written as `virtual.<prefix><name>`
+    pub result_metric_prefix: String,
+    /// Whether the binding is active
+    pub active: bool,
+}
+
+/// Binding status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BindingStatus {
+    pub binding: DeviceBinding,
+    pub last_processed: Option<i64>,
+    pub total_processed: u64,
+    pub last_result: Option<Map<String, Value>>,
+    pub last_error: Option<String>,
+}
+
+impl BindingStatus {
+    fn new(binding: DeviceBinding) -> Self {
+        Self {
+            binding,
+            last_processed: None,
+            total_processed: 0,
+            last_result: None,
+            last_error: None,
+        }
+    }
+}
+
+/// Persisted configuration (`config.json`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct {{EXTENSION_STRUCT_NAME}}Config {
+    pub bindings: Vec<DeviceBinding>,
+}
+
+// ============================================================================
+// Extension Struct
+// ============================================================================
+
+pub struct {{EXTENSION_STRUCT_NAME}} {
+    /// Device bindings keyed by device ID
+    bindings: Arc<RwLock<HashMap<String, DeviceBinding>>>,
+    /// Binding status for tracking
+    binding_stats: Arc<RwLock<HashMap<String, BindingStatus>>>,
+
+    /// Global statistics
+    total_processed: Arc<AtomicU64>,
+    total_errors: Arc<AtomicU64>,
+}
+
+impl {{EXTENSION_STRUCT_NAME}} {
+    pub fn new() -> Self {
+        Self {
+            bindings: Arc::new(RwLock::new(HashMap::new())),
+            binding_stats: Arc::new(RwLock::new(HashMap::new())),
+            total_processed: Arc::new(AtomicU64::new(0)),
+            total_errors: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    fn invoke_capability_sync(&self, capability_name: &str, params: &Value) -> Value {
+        // Use block_in_place to safely block in an async context.
+        // This is safe because the extension runner uses multi_thread runtime.
+        tokio::task::block_in_place(|| {
+            CapabilityContext::default().invoke_capability(capability_name, params)
+        })
+    }
+
+    /// Process one value of a bound device's input metric.
+    ///
+    /// Replace this with your model or analysis. Every entry of the returned
+    /// map is written to the device as `virtual.<prefix><name>`.
+    pub fn process_value(&self, value: &Value) -> Result<Map<String, Value>> {
+        let size = match value {
+            Value::String(s) => s.len(),
+            Value::Null => {
+                return Err(ExtensionError::InvalidArguments("Metric has no value".to_string()));
+            }
+            other => other.to_string().len(),
+        };
+        let mut results = Map::new();
+        results.insert("value_size".to_string(), json!(size));
+        Ok(results)
+    }
+
+    /// Bind a device for automatic processing
+    pub fn bind_device(&self, mut binding: DeviceBinding) -> Result<()> {
+        let device_id = binding.device_id.clone();
+        binding.active = true;
+
+        self.bindings.write().insert(device_id.clone(), binding.clone());
+        self.binding_stats.write().insert(device_id.clone(), BindingStatus::new(binding));
+        self.persist_config();
+
+        tracing::info!("[{{EXTENSION_STRUCT_NAME}}] Device bound: {}", device_id);
+        Ok(())
+    }
+
+    /// Unbind a device
+    pub fn unbind_device(&self, device_id: &str) -> Result<()> {
+        if self.bindings.write().remove(device_id).is_none() {
+            return Err(ExtensionError::NotFound(format!("Device '{}' is not bound", device_id)));
+        }
+        self.binding_stats.write().remove(device_id);
+        self.persist_config();
+
+        tracing::info!("[{{EXTENSION_STRUCT_NAME}}] Device unbound: {}", device_id);
+        Ok(())
+    }
+
+    /// Enable or disable a binding without removing it
+    pub fn toggle_binding(&self, device_id: &str, active: bool) -> Result<()> {
+        {
+            let mut bindings = self.bindings.write();
+            let binding = bindings
+                .get_mut(device_id)
+                .ok_or_else(|| ExtensionError::NotFound(format!("Device '{}' is not bound", device_id)))?;
+            binding.active = active;
+            if let Some(stats) = self.binding_stats.write().get_mut(device_id) {
+                stats.binding.active = active;
+            }
+        }
+        self.persist_config();
+        Ok(())
+    }
+
+    /// Get all bindings
+    pub fn get_bindings(&self) -> Vec<BindingStatus> {
+        self.binding_stats.read().values().cloned().collect()
+    }
+
+    /// The bound device and input value an event is for, if any.
+    ///
+    /// Events arrive either as the bare metric payload or wrapped in the
+    /// server's `{"payload": {...}}` envelope.
+    fn matching_binding(&self, payload: &Value) -> Option<(DeviceBinding, Value)> {
+        let inner_payload = payload.get("payload").unwrap_or(payload);
+        let device_id = inner_payload.get("device_id")?.as_str()?;
+        let metric = inner_payload.get("metric")?.as_str()?;
+        let value = inner_payload.get("value").cloned().unwrap_or(Value::Null);
+
+        let binding = self.bindings.read().get(device_id).cloned()?;
+        if !binding.active {
+            return None;
+        }
+
+        if metric == binding.input_metric {
+            return Some((binding, value));
+        }
+        // "image.data" matches metric "image" and reads its "data" field
+        let nested = binding.input_metric.strip_prefix(metric)?.strip_prefix('.')?;
+        let value = value.pointer(&format!("/{}", nested.replace('.', "/")))?.clone();
+        Some((binding, value))
+    }
+
+    /// Write processing results as virtual metrics of the device
+    fn write_results(&self, binding: &DeviceBinding, results: &Map<String, Value>, timestamp: i64) {
+        // Virtual metrics must start with: transform., virtual., computed., derived., or aggregated.
+        for (name, value) in results {
+            let metric_name = format!("virtual.{}{}", binding.result_metric_prefix, name);
+            let params = json!({
+                "device_id": binding.device_id,
+                "metric": metric_name,
+                "value": value,
+                "timestamp": timestamp,
+            });
+            let response = self.invoke_capability_sync("device_metrics_write", &params);
+            if !response.get("success").and_then(|s| s.as_bool()).unwrap_or(false) {
+                tracing::warn!("[{{EXTENSION_STRUCT_NAME}}] Failed to write {}: {}", metric_name, response);
+            }
+        }
+    }
+
+    fn get_status(&self) -> Value {
+        json!({
+            "bound_devices": self.bindings.read().len(),
+            "total_processed": self.total_processed.load(Ordering::SeqCst),
+            "total_errors": self.total_errors.load(Ordering::SeqCst),
+        })
+    }
+
+    // ========================================================================
+    // Config persistence
+    // ========================================================================
+
+    /// Get current configuration for persistence
+    pub fn get_config(&self) -> {{EXTENSION_STRUCT_NAME}}Config {
+        {{EXTENSION_STRUCT_NAME}}Config {
+            bindings: self.bindings.read().values().cloned().collect(),
+        }
+    }
+
+    /// Load configuration from persisted state
+    pub fn load_config(&self, config: &{{EXTENSION_STRUCT_NAME}}Config) {
+        for binding in &config.bindings {
+            self.bindings.write().insert(binding.device_id.clone(), binding.clone());
+            self.binding_stats
+                .write()
+                .insert(binding.device_id.clone(), BindingStatus::new(binding.clone()));
+        }
+        tracing::info!("[{{EXTENSION_STRUCT_NAME}}] Loaded {} persisted bindings", config.bindings.len());
+    }
+
+    /// Persist configuration to `$NEOMIND_EXTENSION_DIR/config.json`
+    fn persist_config(&self) {
+        let Ok(ext_dir) = std::env::var("NEOMIND_EXTENSION_DIR") else {
+            tracing::debug!("[{{EXTENSION_STRUCT_NAME}}] NEOMIND_EXTENSION_DIR not set, skipping config persistence");
+            return;
+        };
+        let config_path = PathBuf::from(ext_dir).join("config.json");
+        if let Err(e) = write_config(&config_path, &self.get_config()) {
+            tracing::warn!("[{{EXTENSION_STRUCT_NAME}}] Failed to persist config: {}", e);
+        }
+    }
+
+    /// Load configuration from file
+    ///
+    /// The runner starts extensions in their own directory, so `config.json`
+    /// is looked up there first and in `$NEOMIND_EXTENSION_DIR` second.
+    fn load_config_from_file(&self) -> Option<{{EXTENSION_STRUCT_NAME}}Config> {
+        let mut candidates = vec![PathBuf::from("config.json")];
+        if let Ok(ext_dir) = std::env::var("NEOMIND_EXTENSION_DIR") {
+            candidates.push(PathBuf::from(ext_dir).join("config.json"));
+        }
+        candidates.iter().find(|path| path.exists()).and_then(|path| read_config(path))
+    }
+}
+
+impl Default for {{EXTENSION_STRUCT_NAME}} {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn write_config(path: &Path, config: &{{EXTENSION_STRUCT_NAME}}Config) -> std::io::Result<()> {
+    let json = serde_json::to_string_pretty(config)?;
+    std::fs::write(path, json)
+}
+
+fn read_config(path: &Path) -> Option<{{EXTENSION_STRUCT_NAME}}Config> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| tracing::warn!("[{{EXTENSION_STRUCT_NAME}}] Failed to read {:?}: {}", path, e))
+        .ok()?;
+    serde_json::from_str(&json)
+        .map_err(|e| tracing::warn!("[{{EXTENSION_STRUCT_NAME}}] Failed to parse {:?}: {}", path, e))
+        .ok()
+}
+
+fn string_param(name: &str, display_name: &str, description: &str, required: bool) -> ParameterDefinition {
+    ParameterDefinition {
+        name: name.to_string(),
+        display_name: display_name.to_string(),
+        description: description.to_string(),
+        param_type: MetricDataType::String,
+        required,
+        default_value: None,
+        min: None,
+        max: None,
+        options: Vec::new(),
+    }
+}
+
+fn command(name: &str, display_name: &str, description: &str, parameters: Vec<ParameterDefinition>, sample: Value) -> ExtensionCommand {
+    ExtensionCommand {
+        name: name.to_string(),
+        display_name: display_name.to_string(),
+        description: description.to_string(),
+        payload_template: String::new(),
+        parameters,
+        fixed_values: HashMap::new(),
+        samples: vec![sample],
+        parameter_groups: Vec::new(),
+    }
+}
+
+fn count_metric(name: &str, display_name: &str) -> MetricDescriptor {
+    MetricDescriptor {
+        name: name.to_string(),
+        display_name: display_name.to_string(),
+        data_type: MetricDataType::Integer,
+        unit: "count".to_string(),
+        min: Some(0.0),
+        max: None,
+        required: false,
+    }
+}
+
+fn count_value(name: &str, value: u64, timestamp: i64) -> ExtensionMetricValue {
+    ExtensionMetricValue {
+        name: name.to_string(),
+        value: ParamMetricValue::Integer(value as i64),
+        timestamp,
+    }
+}
+
+fn require_str<'a>(args: &'a Value, name: &str) -> Result<&'a str> {
+    args.get(name)
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| ExtensionError::InvalidArguments(format!("Missing {}", name)))
+}
+
+// ============================================================================
+// Extension Implementation
+// ============================================================================
+
+#[async_trait]
+impl Extension for {{EXTENSION_STRUCT_NAME}} {
+    fn metadata(&self) -> &ExtensionMetadata {
+        static META: std::sync::OnceLock<ExtensionMetadata> = std::sync::OnceLock::new();
+        META.get_or_init(|| {
+            ExtensionMetadata::new(
+                "{{EXTENSION_ID}}",
+                "{{EXTENSION_DISPLAY_NAME}}",
+                env!("CARGO_PKG_VERSION"),
+            )
+            .with_description("{{EXTENSION_DESCRIPTION}}")
+            .with_author("{{EXTENSION_AUTHOR}}")
+        })
+    }
+
+    fn metrics(&self) -> Vec<MetricDescriptor> {
+        vec![
+            count_metric("bound_devices", "Bound Devices"),
+            count_metric("total_processed", "Total Processed"),
+            count_metric("total_errors", "Total Errors"),
+        ]
+    }
+
+    fn commands(&self) -> Vec<ExtensionCommand> {
+        let mut active = string_param("active", "Active", "Whether the binding should be active", true);
+        active.param_type = MetricDataType::Boolean;
+
+        vec![
+            command(
+                "bind_device",
+                "Bind Device",
+                "Process a device metric automatically whenever it updates",
+                vec![
+                    string_param("device_id", "Device ID", "ID of the device to bind", true),
+                    string_param("device_name", "Device Name", "Display name for the device", false),
+                    string_param("input_metric", "Input Metric", "Metric to process (e.g. image or image.data)", true),
+                    string_param("result_metric_prefix", "Result Metric Prefix", "Prefix for the virtual result metrics", false),
+                ],
+                json!({"device_id": "camera-01", "input_metric": "image"}),
+            ),
+            command(
+                "unbind_device",
+                "Unbind Device",
+                "Stop processing a device",
+                vec![string_param("device_id", "Device ID", "ID of the bound device", true)],
+                json!({"device_id": "camera-01"}),
+            ),
+            command(
+                "toggle_binding",
+                "Toggle Binding",
+                "Toggle a device binding active state",
+                vec![string_param("device_id", "Device ID", "ID of the bound device", true), active],
+                json!({"device_id": "camera-01", "active": false}),
+            ),
+            command("get_bindings", "Get Bindings", "Get all device bindings and their status", vec![], json!({})),
+            command("get_status", "Get Status", "Get extension statistics", vec![], json!({})),
+            command("configure", "Configure", "Restore persisted bindings", vec![], json!({})),
+        ]
+    }
+
+    async fn execute_command(&self, command: &str, args: &Value) -> Result<Value> {
+        match command {
+            "bind_device" => {
+                let binding = DeviceBinding {
+                    device_id: require_str(args, "device_id")?.to_string(),
+                    device_name: args.get("device_name").and_then(|v| v.as_str()).map(String::from),
+                    input_metric: require_str(args, "input_metric")?.to_string(),
+                    result_metric_prefix: args.get("result_metric_prefix")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or("{{EXTENSION_NAME_UNDERSCORED}}_")
+                        .to_string(),
+                    active: true,
+                };
+                self.bind_device(binding)?;
+                Ok(json!({"success": true}))
+            }
+            "unbind_device" => {
+                self.unbind_device(require_str(args, "device_id")?)?;
+                Ok(json!({"success": true}))
+            }
+            "toggle_binding" => {
+                let device_id = require_str(args, "device_id")?;
+                let active = args.get("active")
+                    .and_then(|v| v.as_bool())
+                    .ok_or_else(|| ExtensionError::InvalidArguments("Missing active".to_string()))?;
+                self.toggle_binding(device_id, active)?;
+                Ok(json!({"success": true, "active": active}))
+            }
+            "get_bindings" => Ok(json!({"bindings": self.get_bindings()})),
+            "get_status" => Ok(self.get_status()),
+            "configure" => {
+                // Load config from file
+                if let Some(config) = self.load_config_from_file() {
+                    self.load_config(&config);
+                    Ok(json!({
+                        "success": true,
+                        "message": "Configuration loaded from file",
+                        "bindings_count": config.bindings.len()
+                    }))
+                } else {
+                    Ok(json!({"success": true, "message": "No persisted configuration found"}))
+                }
+            }
+            _ => Err(ExtensionError::CommandNotFound(command.to_string())),
+        }
+    }
+
+    fn produce_metrics(&self) -> Result<Vec<ExtensionMetricValue>> {
+        let now = chrono::Utc::now().timestamp_millis();
+        Ok(vec![
+            count_value("bound_devices", self.bindings.read().len() as u64, now),
+            count_value("total_processed", self.total_processed.load(Ordering::SeqCst), now),
+            count_value("total_errors", self.total_errors.load(Ordering::SeqCst), now),
+        ])
+    }
+
+    /// Handle events from the EventBus
+    ///
+    /// Called for every DeviceMetric event; only values of bound, active
+    /// devices are processed.
+    fn handle_event(&self, event_type: &str, payload: &Value) -> Result<()> {
+        if event_type != "DeviceMetric" {
+            return Ok(());
+        }
+        let Some((binding, value)) = self.matching_binding(payload) else {
+            return Ok(());
+        };
+
+        let now = chrono::Utc::now().timestamp_millis();
+        match self.process_value(&value) {
+            Ok(results) => {
+                self.total_processed.fetch_add(1, Ordering::SeqCst);
+                if let Some(stats) = self.binding_stats.write().get_mut(&binding.device_id) {
+                    stats.last_processed = Some(now);
+                    stats.total_processed += 1;
+                    stats.last_result = Some(results.clone());
+                    stats.last_error = None;
+                }
+                self.write_results(&binding, &results, now);
+            }
+            Err(e) => {
+                self.total_errors.fetch_add(1, Ordering::SeqCst);
+                tracing::warn!("[{{EXTENSION_STRUCT_NAME}}] Process failed: device={}, error={}", binding.device_id, e);
+                if let Some(stats) = self.binding_stats.write().get_mut(&binding.device_id) {
+                    stats.last_error = Some(e.to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn event_subscriptions(&self) -> &[&str] {
+        &["DeviceMetric"]
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+// ============================================================================
+// FFI Export
+// ============================================================================
+
+neomind_extension_sdk::neomind_export!({{EXTENSION_STRUCT_NAME}});
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binding(device_id: &str, input_metric: &str) -> DeviceBinding {
+        DeviceBinding {
+            device_id: device_id.to_string(),
+            device_name: None,
+            input_metric: input_metric.to_string(),
+            result_metric_prefix: "test_".to_string(),
+            active: true,
+        }
+    }
+
+    #[test]
+    fn test_extension_metadata() {
+        let ext = {{EXTENSION_STRUCT_NAME}}::new();
+        assert_eq!(ext.metadata().id, "{{EXTENSION_ID}}");
+        assert_eq!(ext.event_subscriptions(), &["DeviceMetric"]);
+    }
+
+    #[test]
+    fn test_events_for_unbound_devices_are_ignored() {
+        let ext = {{EXTENSION_STRUCT_NAME}}::new();
+        ext.bind_device(binding("cam-1", "image")).unwrap();
+
+        let event = json!({"device_id": "cam-2", "metric": "image", "value": "abc"});
+        assert!(ext.matching_binding(&event).is_none());
+        let event = json!({"device_id": "cam-1", "metric": "temperature", "value": 21.5});
+        assert!(ext.matching_binding(&event).is_none());
+
+        ext.toggle_binding("cam-1", false).unwrap();
+        let event = json!({"device_id": "cam-1", "metric": "image", "value": "abc"});
+        assert!(ext.matching_binding(&event).is_none());
+    }
+
+    #[test]
+    fn test_matching_binding_reads_envelope_and_nested_metric() {
+        let ext = {{EXTENSION_STRUCT_NAME}}::new();
+        ext.bind_device(binding("cam-1", "image.data")).unwrap();
+
+        let event = json!({"payload": {
+            "device_id": "cam-1",
+            "metric": "image",
+            "value": {"data": "abc", "format": "jpeg"}
+        }});
+        let (bound, value) = ext.matching_binding(&event).unwrap();
+        assert_eq!(bound.device_id, "cam-1");
+        assert_eq!(value, json!("abc"));
+    }
+
+    #[test]
+    fn test_process_value() {
+        let ext = {{EXTENSION_STRUCT_NAME}}::new();
+        let results = ext.process_value(&json!("abcd")).unwrap();
+        assert_eq!(results["value_size"], 4);
+        assert!(ext.process_value(&Value::Null).is_err());
+    }
+
+    #[test]
+    fn test_config_round_trip() {
+        let ext = {{EXTENSION_STRUCT_NAME}}::new();
+        ext.bind_device(binding("cam-1", "image")).unwrap();
+
+        let path = std::env::temp_dir().join(format!("{{EXTENSION_ID}}-config-{}.json", std::process::id()));
+        write_config(&path, &ext.get_config()).unwrap();
+        let config = read_config(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let restored = {{EXTENSION_STRUCT_NAME}}::new();
+        restored.load_config(&config);
+        assert_eq!(restored.get_bindings().len(), 1);
+        assert_eq!(restored.get_config().bindings, vec![binding("cam-1", "image")]);
+    }
+
+    #[test]
+    fn test_unbind_unknown_device_fails() {
+        let ext = {{EXTENSION_STRUCT_NAME}}::new();
+        assert!(ext.unbind_device("cam-1").is_err());
+    }
+}
@@ -0,0 +1,404 @@
+//! NeoMind Extension: {{EXTENSION_DISPLAY_NAME}}
+//!
+//! HTTP bridge, generated by `neomind-ext new --template http-bridge`.
+//! Forwards commands to an external REST API and polls its status endpoint
+//! from `produce_metrics`, so no background task is needed.
+//!
+//! Uses sync HTTP client (ureq) to avoid Tokio runtime issues in dynamic libraries.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use neomind_extension_sdk::{
+    Extension, ExtensionMetadata, ExtensionError, ExtensionMetricValue,
+    MetricDescriptor, ExtensionCommand, MetricDataType, ParameterDefinition,
+    ParamMetricValue, Result,
+};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Timeout for every request to the remote API
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BridgeConfig {
+    /// Base URL of the remote API, e.g. `https://api.example.com`
+    pub base_url: String,
+    /// Sent as `Authorization: Bearer <token>` when set
+    pub api_token: String,
+    /// Path polled by `produce_metrics`
+    pub status_path: String,
+    pub poll_interval_secs: u64,
+}
+
+impl Default for BridgeConfig {
+    fn default() -> Self {
+        Self {
+            base_url: String::new(),
+            api_token: String::new(),
+            status_path: "/health".to_string(),
+            poll_interval_secs: 60,
+        }
+    }
+}
+
+impl BridgeConfig {
+    fn is_configured(&self) -> bool {
+        !self.base_url.is_empty()
+    }
+
+    /// Join the base URL and an API path with exactly one `/`
+    fn endpoint_url(&self, path: &str) -> String {
+        format!("{}/{}", self.base_url.trim_end_matches('/'), path.trim_start_matches('/'))
+    }
+
+    /// Apply the fields present in a `configure` payload
+    fn apply(&mut self, args: &Value) {
+        if let Some(v) = args.get("base_url").and_then(|v| v.as_str()) { self.base_url = v.trim_end_matches('/').to_string(); }
+        if let Some(v) = args.get("api_token").and_then(|v| v.as_str()) { self.api_token = v.to_string(); }
+        if let Some(v) = args.get("status_path").and_then(|v| v.as_str()) { self.status_path = v.to_string(); }
+        if let Some(v) = args.get("poll_interval_secs").and_then(|v| v.as_u64()) { self.poll_interval_secs = v; }
+    }
+}
+
+/// Whether the status endpoint is due for another poll
+fn poll_due(now_ts: i64, last_poll_ts: i64, interval_secs: u64) -> bool {
+    last_poll_ts == 0 || now_ts - last_poll_ts >= interval_secs as i64
+}
+
+// ============================================================================
+// Extension Struct
+// ============================================================================
+
+pub struct {{EXTENSION_STRUCT_NAME}} {
+    config: RwLock<BridgeConfig>,
+    /// Whether the last status poll succeeded (-1 = never polled)
+    reachable: AtomicI64,
+    last_latency_ms: AtomicI64,
+    last_poll_ts: AtomicI64,
+    total_requests: AtomicI64,
+    total_errors: AtomicI64,
+    last_error: RwLock<Option<String>>,
+}
+
+impl {{EXTENSION_STRUCT_NAME}} {
+    pub fn new() -> Self {
+        Self {
+            config: RwLock::new(BridgeConfig::default()),
+            reachable: AtomicI64::new(-1),
+            last_latency_ms: AtomicI64::new(0),
+            last_poll_ts: AtomicI64::new(0),
+            total_requests: AtomicI64::new(0),
+            total_errors: AtomicI64::new(0),
+            last_error: RwLock::new(None),
+        }
+    }
+
+    // ========================================================================
+    // HTTP
+    // ========================================================================
+
+    /// Send a request to the remote API and parse the JSON response
+    fn request(&self, method: &str, path: &str, body: Option<&Value>) -> Result<Value> {
+        let config = self.config.read().clone();
+        if !config.is_configured() {
+            return Err(ExtensionError::ExecutionFailed("base_url not configured".into()));
+        }
+
+        let url = config.endpoint_url(path);
+        let mut request = ureq::request(method, &url).timeout(REQUEST_TIMEOUT);
+        if !config.api_token.is_empty() {
+            request = request.set("Authorization", &format!("Bearer {}", config.api_token));
+        }
+
+        self.total_requests.fetch_add(1, Ordering::SeqCst);
+        let started = Instant::now();
+        let response = match body {
+            Some(body) => request.send_json(body.clone()),
+            None => request.call(),
+        };
+        self.last_latency_ms.store(started.elapsed().as_millis() as i64, Ordering::SeqCst);
+
+        let result = response
+            .map_err(|e| ExtensionError::ExecutionFailed(format!("{} {} failed: {}", method, url, e)))
+            .and_then(|response| {
+                // Empty bodies (204 etc.) map to null
+                let text = response
+                    .into_string()
+                    .map_err(|e| ExtensionError::ExecutionFailed(format!("Read response: {}", e)))?;
+                if text.trim().is_empty() {
+                    return Ok(Value::Null);
+                }
+                serde_json::from_str(&text)
+                    .map_err(|e| ExtensionError::ExecutionFailed(format!("Parse response: {}", e)))
+            });
+        match &result {
+            Ok(_) => *self.last_error.write() = None,
+            Err(e) => {
+                self.total_errors.fetch_add(1, Ordering::SeqCst);
+                *self.last_error.write() = Some(e.to_string());
+            }
+        }
+        result
+    }
+
+    /// Poll the status endpoint if the poll interval has elapsed
+    fn poll_status(&self) {
+        let config = self.config.read().clone();
+        let now_ts = Utc::now().timestamp();
+        if !config.is_configured()
+            || !poll_due(now_ts, self.last_poll_ts.load(Ordering::SeqCst), config.poll_interval_secs)
+        {
+            return;
+        }
+        self.last_poll_ts.store(now_ts, Ordering::SeqCst);
+
+        let reachable = match self.request("GET", &config.status_path, None) {
+            Ok(_) => true,
+            Err(e) => {
+                eprintln!("[{{EXTENSION_ID}}] Status poll failed: {}", e);
+                false
+            }
+        };
+        self.reachable.store(reachable as i64, Ordering::SeqCst);
+    }
+
+    fn get_status(&self) -> Value {
+        let config = self.config.read();
+        let reachable = match self.reachable.load(Ordering::SeqCst) {
+            -1 => Value::Null,
+            v => json!(v == 1),
+        };
+        json!({
+            "configured": config.is_configured(),
+            "base_url": config.base_url,
+            "reachable": reachable,
+            "last_latency_ms": self.last_latency_ms.load(Ordering::SeqCst),
+            "total_requests": self.total_requests.load(Ordering::SeqCst),
+            "total_errors": self.total_errors.load(Ordering::SeqCst),
+            "last_error": *self.last_error.read(),
+        })
+    }
+}
+
+impl Default for {{EXTENSION_STRUCT_NAME}} {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn param(name: &str, display_name: &str, description: &str, param_type: MetricDataType, required: bool) -> ParameterDefinition {
+    ParameterDefinition {
+        name: name.to_string(),
+        display_name: display_name.to_string(),
+        description: description.to_string(),
+        param_type,
+        required,
+        default_value: None,
+        min: None,
+        max: None,
+        options: Vec::new(),
+    }
+}
+
+fn metric(name: &str, display_name: &str, data_type: MetricDataType, unit: &str) -> MetricDescriptor {
+    MetricDescriptor {
+        name: name.to_string(),
+        display_name: display_name.to_string(),
+        data_type,
+        unit: unit.to_string(),
+        min: None,
+        max: None,
+        required: false,
+    }
+}
+
+// ============================================================================
+// Extension Implementation
+// ============================================================================
+
+#[async_trait]
+impl Extension for {{EXTENSION_STRUCT_NAME}} {
+    fn metadata(&self) -> &ExtensionMetadata {
+        static META: std::sync::OnceLock<ExtensionMetadata> = std::sync::OnceLock::new();
+        META.get_or_init(|| {
+            ExtensionMetadata::new(
+                "{{EXTENSION_ID}}",
+                "{{EXTENSION_DISPLAY_NAME}}",
+                env!("CARGO_PKG_VERSION"),
+            )
+            .with_description("{{EXTENSION_DESCRIPTION}}")
+            .with_author("{{EXTENSION_AUTHOR}}")
+        })
+    }
+
+    fn metrics(&self) -> Vec<MetricDescriptor> {
+        vec![
+            metric("reachable", "Reachable", MetricDataType::Boolean, ""),
+            metric("last_latency_ms", "Last Latency", MetricDataType::Integer, "ms"),
+            metric("total_requests", "Total Requests", MetricDataType::Integer, "count"),
+            metric("total_errors", "Total Errors", MetricDataType::Integer, "count"),
+        ]
+    }
+
+    fn commands(&self) -> Vec<ExtensionCommand> {
+        vec![
+            ExtensionCommand {
+                name: "configure".to_string(),
+                display_name: "Configure".to_string(),
+                description: "Set the remote API endpoint and credentials".to_string(),
+                payload_template: String::new(),
+                parameters: vec![
+                    param("base_url", "Base URL", "Base URL of the remote API", MetricDataType::String, true),
+                    param("api_token", "API Token", "Bearer token for the remote API", MetricDataType::String, false),
+                    param("status_path", "Status Path", "Path polled for the reachable metric", MetricDataType::String, false),
+                    param("poll_interval_secs", "Poll Interval", "Seconds between status polls", MetricDataType::Integer, false),
+                ],
+                fixed_values: HashMap::new(),
+                samples: vec![json!({"base_url": "https://api.example.com", "poll_interval_secs": 60})],
+                parameter_groups: Vec::new(),
+            },
+            ExtensionCommand {
+                name: "request".to_string(),
+                display_name: "Request".to_string(),
+                description: "Send a request to the remote API and return its JSON response".to_string(),
+                payload_template: String::new(),
+                parameters: vec![
+                    param("method", "Method", "HTTP method (default GET)", MetricDataType::String, false),
+                    param("path", "Path", "API path relative to the base URL", MetricDataType::String, true),
+                    param("body", "Body", "JSON body for POST/PUT", MetricDataType::String, false),
+                ],
+                fixed_values: HashMap::new(),
+                samples: vec![json!({"method": "GET", "path": "/api/v1/devices"})],
+                parameter_groups: Vec::new(),
+            },
+            ExtensionCommand {
+                name: "get_status".to_string(),
+                display_name: "Get Status".to_string(),
+                description: "Get connection status and request statistics".to_string(),
+                payload_template: String::new(),
+                parameters: vec![],
+                fixed_values: HashMap::new(),
+                samples: vec![json!({})],
+                parameter_groups: Vec::new(),
+            },
+        ]
+    }
+
+    async fn execute_command(&self, command: &str, args: &Value) -> Result<Value> {
+        match command {
+            "configure" => {
+                self.config.write().apply(args);
+                // Poll again on the next produce_metrics
+                self.last_poll_ts.store(0, Ordering::SeqCst);
+                self.reachable.store(-1, Ordering::SeqCst);
+                Ok(json!({"success": true}))
+            }
+            "request" => {
+                let path = args.get("path")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| ExtensionError::InvalidArguments("Missing path".to_string()))?;
+                let method = args.get("method")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("GET")
+                    .to_uppercase();
+                let response = self.request(&method, path, args.get("body"))?;
+                Ok(json!({"success": true, "response": response}))
+            }
+            "get_status" => Ok(self.get_status()),
+            _ => Err(ExtensionError::CommandNotFound(command.to_string())),
+        }
+    }
+
+    fn produce_metrics(&self) -> Result<Vec<ExtensionMetricValue>> {
+        self.poll_status();
+
+        let now = Utc::now().timestamp_millis();
+        let integer = |name: &str, counter: &AtomicI64| ExtensionMetricValue {
+            name: name.to_string(),
+            value: ParamMetricValue::Integer(counter.load(Ordering::SeqCst)),
+            timestamp: now,
+        };
+        let mut values = vec![
+            integer("last_latency_ms", &self.last_latency_ms),
+            integer("total_requests", &self.total_requests),
+            integer("total_errors", &self.total_errors),
+        ];
+        // Only reported once a poll has told reachable from unreachable
+        if let reachable @ (0 | 1) = self.reachable.load(Ordering::SeqCst) {
+            values.push(ExtensionMetricValue {
+                name: "reachable".to_string(),
+                value: ParamMetricValue::Boolean(reachable == 1),
+                timestamp: now,
+            });
+        }
+        Ok(values)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+// ============================================================================
+// FFI Export
+// ============================================================================
+
+neomind_extension_sdk::neomind_export!({{EXTENSION_STRUCT_NAME}});
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extension_metadata() {
+        let ext = {{EXTENSION_STRUCT_NAME}}::new();
+        assert_eq!(ext.metadata().id, "{{EXTENSION_ID}}");
+    }
+
+    #[test]
+    fn test_endpoint_url() {
+        let mut config = BridgeConfig::default();
+        config.apply(&json!({"base_url": "https://api.example.com/"}));
+        assert_eq!(config.endpoint_url("/health"), "https://api.example.com/health");
+        assert_eq!(config.endpoint_url("v1/devices"), "https://api.example.com/v1/devices");
+    }
+
+    #[test]
+    fn test_configure_keeps_unset_fields() {
+        let mut config = BridgeConfig::default();
+        config.apply(&json!({"base_url": "https://api.example.com", "poll_interval_secs": 10}));
+        config.apply(&json!({"api_token": "secret"}));
+        assert_eq!(config.base_url, "https://api.example.com");
+        assert_eq!(config.api_token, "secret");
+        assert_eq!(config.poll_interval_secs, 10);
+        assert_eq!(config.status_path, "/health");
+    }
+
+    #[test]
+    fn test_poll_due() {
+        assert!(poll_due(1000, 0, 60));
+        assert!(!poll_due(1030, 1000, 60));
+        assert!(poll_due(1060, 1000, 60));
+    }
+
+    #[test]
+    fn test_unconfigured_bridge_does_not_poll() {
+        let ext = {{EXTENSION_STRUCT_NAME}}::new();
+        let metrics = ext.produce_metrics().unwrap();
+        assert!(metrics.iter().all(|m| m.name != "reachable"));
+        assert!(ext.request("GET", "/health", None).is_err());
+    }
+}
@@ -0,0 +1,453 @@
+//! NeoMind Extension: {{EXTENSION_DISPLAY_NAME}}
+//!
+//! Push-mode streaming, generated by `neomind-ext new --template push-stream`.
+//! The frontend opens a session with `init_session`, then `start_push` spawns a
+//! producer thread that pushes one JPEG frame at a time through `send_push_output`
+//! until `stop_push` or `close_session`.
+//!
+//! Architecture:
+//!   init_session (config) → start_push (thread) → render_frame → JPEG encode → send_push_output → WebSocket
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use neomind_extension_sdk::{
+    send_push_output, Extension, ExtensionCommand, ExtensionError, ExtensionMetadata,
+    ExtensionMetricValue, MetricDescriptor, MetricDataType, ParamMetricValue, Result,
+};
+use neomind_extension_sdk::prelude::{
+    FlowControl, PushOutputMessage, SessionStats, StreamCapability, StreamDataType,
+    StreamDirection, StreamMode, StreamResult, StreamSession,
+};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+// ============================================================================
+// Session Configuration
+// ============================================================================
+
+/// Per-session configuration sent by the frontend in `init_session`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+struct SessionConfig {
+    /// Frames pushed per second
+    target_fps: u32,
+    output_width: u32,
+    output_height: u32,
+    /// JPEG quality (1-100)
+    jpeg_quality: u8,
+    /// Stop after this many frames (0 = until stopped)
+    max_frames: u64,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            target_fps: 10,
+            output_width: 640,
+            output_height: 480,
+            jpeg_quality: 70,
+            max_frames: 0,
+        }
+    }
+}
+
+impl SessionConfig {
+    fn frame_duration(&self) -> Duration {
+        Duration::from_millis(1000 / self.target_fps.clamp(1, 1000) as u64)
+    }
+}
+
+/// Active session state
+struct ActiveSession {
+    config: SessionConfig,
+    running: bool,
+    frame_count: u64,
+    bytes_sent: u64,
+    started_at: Instant,
+    push_task: Option<std::thread::JoinHandle<()>>,
+}
+
+// ============================================================================
+// Session Registry
+// ============================================================================
+
+struct SessionRegistry {
+    sessions: HashMap<String, Arc<Mutex<ActiveSession>>>,
+}
+
+static REGISTRY: std::sync::OnceLock<Mutex<SessionRegistry>> = std::sync::OnceLock::new();
+
+fn get_registry() -> &'static Mutex<SessionRegistry> {
+    REGISTRY.get_or_init(|| {
+        Mutex::new(SessionRegistry {
+            sessions: HashMap::new(),
+        })
+    })
+}
+
+fn get_session(session_id: &str) -> Option<Arc<Mutex<ActiveSession>>> {
+    get_registry().lock().sessions.get(session_id).cloned()
+}
+
+// ============================================================================
+// Frame Production
+// ============================================================================
+
+/// Render frame `sequence` as RGB24.
+///
+/// Replace this with your source (camera, decoder, renderer). The default
+/// draws a moving gradient so the player has something to show.
+fn render_frame(config: &SessionConfig, sequence: u64) -> Vec<u8> {
+    let (width, height) = (config.output_width, config.output_height);
+    let shift = (sequence * 4) as u32;
+    let mut rgb = Vec::with_capacity((width * height * 3) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            rgb.push(((x + shift) * 255 / width.max(1)) as u8);
+            rgb.push((y * 255 / height.max(1)) as u8);
+            rgb.push((shift % 256) as u8);
+        }
+    }
+    rgb
+}
+
+/// Encode RGB24 data to JPEG using the `image` crate.
+fn encode_jpeg(rgb_data: &[u8], width: u32, height: u32, quality: u8) -> Vec<u8> {
+    let img = match image::RgbImage::from_raw(width, height, rgb_data.to_vec()) {
+        Some(img) => img,
+        None => return Vec::new(),
+    };
+    let mut buffer = Vec::with_capacity((width * height) as usize / 4);
+    let mut encoder = image::codecs::jpeg::JpegEncoder::new_with_quality(&mut buffer, quality);
+    let _ = encoder.encode(
+        img.as_raw(),
+        width,
+        height,
+        image::ExtendedColorType::Rgb8,
+    );
+    buffer
+}
+
+fn push_status(session_id: &str, sequence: u64, status: Value) {
+    match PushOutputMessage::json(session_id, sequence, status) {
+        Ok(message) => {
+            if let Err(e) = send_push_output(&message) {
+                tracing::warn!("[{{EXTENSION_STRUCT_NAME}}] Status push failed: {}", e);
+            }
+        }
+        Err(e) => tracing::warn!("[{{EXTENSION_STRUCT_NAME}}] Failed to encode status: {}", e),
+    }
+}
+
+/// Producer loop run on the push thread
+fn run_push_loop(session_id: String, config: SessionConfig) {
+    let frame_duration = config.frame_duration();
+    let mut sequence = 0u64;
+    push_status(&session_id, sequence, json!({
+        "type": "status", "status": "streaming",
+        "width": config.output_width, "height": config.output_height,
+    }));
+
+    loop {
+        let Some(session) = get_session(&session_id) else {
+            break;
+        };
+        if !session.lock().running {
+            break;
+        }
+        if config.max_frames > 0 && sequence >= config.max_frames {
+            push_status(&session_id, sequence, json!({"type": "status", "status": "ended"}));
+            break;
+        }
+
+        let frame_start = Instant::now();
+        let rgb = render_frame(&config, sequence);
+        let jpeg_data = encode_jpeg(&rgb, config.output_width, config.output_height, config.jpeg_quality);
+        if jpeg_data.is_empty() {
+            tracing::warn!("[{{EXTENSION_STRUCT_NAME}}] JPEG encode produced empty output");
+            break;
+        }
+        let size = jpeg_data.len() as u64;
+        if let Err(e) = send_push_output(&PushOutputMessage::image_jpeg(&session_id, sequence, jpeg_data)) {
+            tracing::warn!("[{{EXTENSION_STRUCT_NAME}}] Push failed: {}", e);
+            break;
+        }
+
+        {
+            let mut s = session.lock();
+            s.frame_count += 1;
+            s.bytes_sent += size;
+        }
+        sequence += 1;
+
+        // Frame rate throttling
+        let elapsed = frame_start.elapsed();
+        if elapsed < frame_duration {
+            std::thread::sleep(frame_duration - elapsed);
+        }
+    }
+
+    tracing::info!("[{{EXTENSION_STRUCT_NAME}}] Push task ended: {} ({} frames)", session_id, sequence);
+}
+
+// ============================================================================
+// Extension
+// ============================================================================
+
+pub struct {{EXTENSION_STRUCT_NAME}};
+
+impl {{EXTENSION_STRUCT_NAME}} {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for {{EXTENSION_STRUCT_NAME}} {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Extension for {{EXTENSION_STRUCT_NAME}} {
+    fn metadata(&self) -> &ExtensionMetadata {
+        static META: std::sync::OnceLock<ExtensionMetadata> = std::sync::OnceLock::new();
+        META.get_or_init(|| {
+            ExtensionMetadata::new(
+                "{{EXTENSION_ID}}",
+                "{{EXTENSION_DISPLAY_NAME}}",
+                env!("CARGO_PKG_VERSION"),
+            )
+            .with_description("{{EXTENSION_DESCRIPTION}}")
+            .with_author("{{EXTENSION_AUTHOR}}")
+        })
+    }
+
+    fn metrics(&self) -> Vec<MetricDescriptor> {
+        vec![
+            MetricDescriptor {
+                name: "active_sessions".to_string(),
+                display_name: "Active Sessions".to_string(),
+                data_type: MetricDataType::Integer,
+                unit: "count".to_string(),
+                min: Some(0.0),
+                max: None,
+                required: false,
+            },
+            MetricDescriptor {
+                name: "total_frames".to_string(),
+                display_name: "Total Frames".to_string(),
+                data_type: MetricDataType::Integer,
+                unit: "frames".to_string(),
+                min: Some(0.0),
+                max: None,
+                required: false,
+            },
+        ]
+    }
+
+    fn commands(&self) -> Vec<ExtensionCommand> {
+        vec![
+            ExtensionCommand {
+                name: "get_sessions".to_string(),
+                display_name: "Get Sessions".to_string(),
+                description: "List active push sessions".to_string(),
+                payload_template: String::new(),
+                parameters: vec![],
+                fixed_values: HashMap::new(),
+                samples: vec![json!({})],
+                parameter_groups: vec![],
+            },
+        ]
+    }
+
+    async fn execute_command(&self, command: &str, _args: &Value) -> Result<Value> {
+        match command {
+            "get_sessions" => {
+                let registry = get_registry().lock();
+                let sessions: Vec<_> = registry.sessions.iter().map(|(id, s)| {
+                    let session = s.lock();
+                    json!({
+                        "session_id": id,
+                        "running": session.running,
+                        "frame_count": session.frame_count,
+                        "bytes_sent": session.bytes_sent,
+                        "uptime_secs": session.started_at.elapsed().as_secs(),
+                    })
+                }).collect();
+                Ok(json!({
+                    "active_count": sessions.len(),
+                    "sessions": sessions,
+                }))
+            }
+            _ => Err(ExtensionError::CommandNotFound(command.to_string())),
+        }
+    }
+
+    fn produce_metrics(&self) -> Result<Vec<ExtensionMetricValue>> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let registry = get_registry().lock();
+        let total_frames: u64 = registry.sessions.values().map(|s| s.lock().frame_count).sum();
+
+        Ok(vec![
+            ExtensionMetricValue {
+                name: "active_sessions".to_string(),
+                value: ParamMetricValue::Integer(registry.sessions.len() as i64),
+                timestamp: now,
+            },
+            ExtensionMetricValue {
+                name: "total_frames".to_string(),
+                value: ParamMetricValue::Integer(total_frames as i64),
+                timestamp: now,
+            },
+        ])
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    // ========================================================================
+    // Push Mode Streaming
+    // ========================================================================
+
+    fn stream_capability(&self) -> Option<StreamCapability> {
+        Some(StreamCapability {
+            direction: StreamDirection::Bidirectional,
+            mode: StreamMode::Push,
+            supported_data_types: vec![
+                StreamDataType::Image { format: "jpeg".to_string() },
+            ],
+            max_chunk_size: 524288,
+            preferred_chunk_size: 32768,
+            max_concurrent_sessions: 4,
+            flow_control: FlowControl::default_stream(),
+            config_schema: None,
+        })
+    }
+
+    async fn init_session(&self, session: &StreamSession) -> Result<()> {
+        let config: SessionConfig = serde_json::from_value(session.config.clone())
+            .map_err(|e| ExtensionError::InvalidArguments(format!("Invalid session config: {}", e)))?;
+        if config.target_fps == 0 || config.output_width == 0 || config.output_height == 0 {
+            return Err(ExtensionError::InvalidArguments(
+                "target_fps, output_width and output_height must be positive".to_string(),
+            ));
+        }
+
+        let active = ActiveSession {
+            config,
+            running: true,
+            frame_count: 0,
+            bytes_sent: 0,
+            started_at: Instant::now(),
+            push_task: None,
+        };
+
+        // Replacing an existing session stops its producer
+        let previous = get_registry()
+            .lock()
+            .sessions
+            .insert(session.id.clone(), Arc::new(Mutex::new(active)));
+        if let Some(previous) = previous {
+            previous.lock().running = false;
+        }
+
+        tracing::info!("[{{EXTENSION_STRUCT_NAME}}] Session initialized: {}", session.id);
+        Ok(())
+    }
+
+    fn set_output_sender(&self, _sender: Arc<tokio::sync::mpsc::Sender<PushOutputMessage>>) {
+        // No-op: Push mode uses send_push_output() directly via FFI
+    }
+
+    async fn start_push(&self, session_id: &str) -> Result<()> {
+        let session = get_session(session_id)
+            .ok_or_else(|| ExtensionError::SessionNotFound(session_id.to_string()))?;
+        let mut s = session.lock();
+        if s.push_task.is_some() {
+            return Ok(());
+        }
+
+        let sid = session_id.to_string();
+        let config = s.config.clone();
+        tracing::info!("[{{EXTENSION_STRUCT_NAME}}] Starting push: {}", sid);
+        s.push_task = Some(std::thread::spawn(move || run_push_loop(sid, config)));
+        Ok(())
+    }
+
+    async fn stop_push(&self, session_id: &str) -> Result<()> {
+        if let Some(session) = get_session(session_id) {
+            let mut s = session.lock();
+            s.running = false;
+            // The thread notices `running` on its next frame; no need to join
+            s.push_task.take();
+        }
+        Ok(())
+    }
+
+    async fn process_session_chunk(
+        &self,
+        _session_id: &str,
+        _chunk: neomind_extension_sdk::DataChunk,
+    ) -> Result<StreamResult> {
+        Err(ExtensionError::NotSupported("process_session_chunk not used in push mode".to_string()))
+    }
+
+    async fn close_session(&self, session_id: &str) -> Result<SessionStats> {
+        if let Some(session) = get_registry().lock().sessions.remove(session_id) {
+            session.lock().running = false;
+        }
+        tracing::info!("[{{EXTENSION_STRUCT_NAME}}] Session closed: {}", session_id);
+        Ok(SessionStats::default())
+    }
+}
+
+// FFI export
+neomind_extension_sdk::neomind_export!({{EXTENSION_STRUCT_NAME}});
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extension_metadata() {
+        let ext = {{EXTENSION_STRUCT_NAME}}::new();
+        assert_eq!(ext.metadata().id, "{{EXTENSION_ID}}");
+    }
+
+    #[test]
+    fn test_stream_capability_is_push() {
+        let capability = {{EXTENSION_STRUCT_NAME}}::new().stream_capability().unwrap();
+        assert!(matches!(capability.mode, StreamMode::Push));
+    }
+
+    #[test]
+    fn test_session_config_defaults() {
+        let config: SessionConfig = serde_json::from_value(json!({"max_frames": 5})).unwrap();
+        assert_eq!(config.max_frames, 5);
+        assert_eq!(config.target_fps, 10);
+        assert_eq!(config.frame_duration(), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_frames_encode_as_jpeg() {
+        let config = SessionConfig {
+            output_width: 64,
+            output_height: 48,
+            ..SessionConfig::default()
+        };
+        let rgb = render_frame(&config, 7);
+        assert_eq!(rgb.len(), 64 * 48 * 3);
+        let jpeg = encode_jpeg(&rgb, 64, 48, config.jpeg_quality);
+        assert_eq!(&jpeg[..2], &[0xFF, 0xD8]);
+    }
+}
@@ -0,0 +1,255 @@
+//! NeoMind Extension: {{EXTENSION_DISPLAY_NAME}}
+//!
+//! Sandboxed WASM extension, generated by `neomind-ext new --template wasm`.
+//! Evaluates values against a configurable threshold and counts alerts.
+//!
+//! WASM extensions run without threads, file system or native libraries, so
+//! state lives in atomics and every dependency must build for
+//! `wasm32-unknown-unknown`.
+//!
+//! # Building
+//!
+//! ```bash
+//! # Build for WASM target
+//! cargo build --target wasm32-unknown-unknown --release
+//!
+//! # The output will be at:
+//! # target/wasm32-unknown-unknown/release/{{EXTENSION_PACKAGE_NAME}}.wasm
+//! ```
+
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+
+use neomind_extension_sdk::{
+    async_trait, json, Extension, ExtensionCommand, ExtensionError, ExtensionMetadata,
+    ExtensionMetricValue, MetricDataType, MetricDescriptor, ParamMetricValue,
+    ParameterDefinition, Result,
+};
+
+// ============================================================================
+// Extension Implementation
+// ============================================================================
+
+pub struct {{EXTENSION_STRUCT_NAME}} {
+    /// Alert threshold, stored as f64 bits
+    threshold: AtomicU64,
+    /// Number of evaluated values
+    evaluations: AtomicI64,
+    /// Number of values above the threshold
+    alerts: AtomicI64,
+}
+
+impl {{EXTENSION_STRUCT_NAME}} {
+    pub fn new() -> Self {
+        Self {
+            threshold: AtomicU64::new(50.0f64.to_bits()),
+            evaluations: AtomicI64::new(0),
+            alerts: AtomicI64::new(0),
+        }
+    }
+
+    fn threshold(&self) -> f64 {
+        f64::from_bits(self.threshold.load(Ordering::SeqCst))
+    }
+
+    /// Evaluate one value; returns whether it raised an alert
+    pub fn evaluate(&self, value: f64) -> bool {
+        self.evaluations.fetch_add(1, Ordering::SeqCst);
+        let alert = value > self.threshold();
+        if alert {
+            self.alerts.fetch_add(1, Ordering::SeqCst);
+        }
+        alert
+    }
+}
+
+impl Default for {{EXTENSION_STRUCT_NAME}} {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn number_param(name: &str, display_name: &str, description: &str) -> ParameterDefinition {
+    ParameterDefinition {
+        name: name.to_string(),
+        display_name: display_name.to_string(),
+        description: description.to_string(),
+        param_type: MetricDataType::Float,
+        required: true,
+        default_value: None,
+        min: None,
+        max: None,
+        options: Vec::new(),
+    }
+}
+
+fn require_f64(args: &serde_json::Value, name: &str) -> Result<f64> {
+    args.get(name)
+        .and_then(|v| v.as_f64())
+        .ok_or_else(|| ExtensionError::InvalidArguments(format!("Missing {}", name)))
+}
+
+#[async_trait]
+impl Extension for {{EXTENSION_STRUCT_NAME}} {
+    fn metadata(&self) -> &ExtensionMetadata {
+        static META: std::sync::OnceLock<ExtensionMetadata> = std::sync::OnceLock::new();
+        META.get_or_init(|| {
+            ExtensionMetadata::new(
+                "{{EXTENSION_ID}}",
+                "{{EXTENSION_DISPLAY_NAME}}",
+                env!("CARGO_PKG_VERSION"),
+            )
+            .with_description("{{EXTENSION_DESCRIPTION}}")
+            .with_author("{{EXTENSION_AUTHOR}}")
+        })
+    }
+
+    fn metrics(&self) -> Vec<MetricDescriptor> {
+        vec![
+            MetricDescriptor {
+                name: "evaluations".to_string(),
+                display_name: "Evaluations".to_string(),
+                data_type: MetricDataType::Integer,
+                unit: "count".to_string(),
+                min: Some(0.0),
+                max: None,
+                required: false,
+            },
+            MetricDescriptor {
+                name: "alerts".to_string(),
+                display_name: "Alerts".to_string(),
+                data_type: MetricDataType::Integer,
+                unit: "count".to_string(),
+                min: Some(0.0),
+                max: None,
+                required: false,
+            },
+        ]
+    }
+
+    fn commands(&self) -> Vec<ExtensionCommand> {
+        vec![
+            ExtensionCommand {
+                name: "evaluate".to_string(),
+                display_name: "Evaluate".to_string(),
+                description: "Check a value against the alert threshold".to_string(),
+                payload_template: String::new(),
+                parameters: vec![number_param("value", "Value", "Value to evaluate")],
+                fixed_values: std::collections::HashMap::new(),
+                samples: vec![json!({ "value": 72.5 })],
+                parameter_groups: Vec::new(),
+            },
+            ExtensionCommand {
+                name: "set_threshold".to_string(),
+                display_name: "Set Threshold".to_string(),
+                description: "Change the alert threshold".to_string(),
+                payload_template: String::new(),
+                parameters: vec![number_param("threshold", "Threshold", "Values above this raise an alert")],
+                fixed_values: std::collections::HashMap::new(),
+                samples: vec![json!({ "threshold": 50.0 })],
+                parameter_groups: Vec::new(),
+            },
+            ExtensionCommand {
+                name: "get_status".to_string(),
+                display_name: "Get Status".to_string(),
+                description: "Get the threshold and counters".to_string(),
+                payload_template: String::new(),
+                parameters: Vec::new(),
+                fixed_values: std::collections::HashMap::new(),
+                samples: vec![json!({})],
+                parameter_groups: Vec::new(),
+            },
+        ]
+    }
+
+    async fn execute_command(&self, command: &str, args: &serde_json::Value) -> Result<serde_json::Value> {
+        match command {
+            "evaluate" => {
+                let value = require_f64(args, "value")?;
+                Ok(json!({
+                    "value": value,
+                    "threshold": self.threshold(),
+                    "alert": self.evaluate(value)
+                }))
+            }
+            "set_threshold" => {
+                let threshold = require_f64(args, "threshold")?;
+                self.threshold.store(threshold.to_bits(), Ordering::SeqCst);
+                Ok(json!({ "threshold": threshold }))
+            }
+            "get_status" => Ok(json!({
+                "threshold": self.threshold(),
+                "evaluations": self.evaluations.load(Ordering::SeqCst),
+                "alerts": self.alerts.load(Ordering::SeqCst)
+            })),
+            _ => Err(ExtensionError::CommandNotFound(command.to_string())),
+        }
+    }
+
+    fn produce_metrics(&self) -> Result<Vec<ExtensionMetricValue>> {
+        // No clock in the sandbox; the host stamps metrics on arrival
+        let now = 0i64;
+        Ok(vec![
+            ExtensionMetricValue {
+                name: "evaluations".to_string(),
+                value: ParamMetricValue::Integer(self.evaluations.load(Ordering::SeqCst)),
+                timestamp: now,
+            },
+            ExtensionMetricValue {
+                name: "alerts".to_string(),
+                value: ParamMetricValue::Integer(self.alerts.load(Ordering::SeqCst)),
+                timestamp: now,
+            },
+        ])
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+// ============================================================================
+// Export FFI Functions
+// ============================================================================
+
+neomind_extension_sdk::neomind_export!({{EXTENSION_STRUCT_NAME}});
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extension_metadata() {
+        let ext = {{EXTENSION_STRUCT_NAME}}::new();
+        assert_eq!(ext.metadata().id, "{{EXTENSION_ID}}");
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_counts_alerts() {
+        let ext = {{EXTENSION_STRUCT_NAME}}::new();
+        let result = ext.execute_command("evaluate", &json!({ "value": 72.5 })).await.unwrap();
+        assert_eq!(result["alert"], true);
+        ext.execute_command("evaluate", &json!({ "value": 10.0 })).await.unwrap();
+
+        let status = ext.execute_command("get_status", &json!({})).await.unwrap();
+        assert_eq!(status["evaluations"], 2);
+        assert_eq!(status["alerts"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_set_threshold() {
+        let ext = {{EXTENSION_STRUCT_NAME}}::new();
+        ext.execute_command("set_threshold", &json!({ "threshold": 100.0 })).await.unwrap();
+        assert!(!ext.evaluate(72.5));
+    }
+
+    #[tokio::test]
+    async fn test_missing_argument_is_rejected() {
+        let ext = {{EXTENSION_STRUCT_NAME}}::new();
+        let err = ext.execute_command("evaluate", &json!({})).await.unwrap_err();
+        assert!(matches!(err, ExtensionError::InvalidArguments(_)));
+    }
+}